@@ -278,3 +278,209 @@ fn perf_struct(b: &mut Bencher) {
         }
     });
 }
+
+fn stream_read_ints<E: Endianness>(mut stream: BitReadStream<E>, size: usize) -> u64 {
+    let mut result: u64 = 0;
+    while stream.bits_left() >= size {
+        result = result.wrapping_add(stream.read_int::<u64>(size).unwrap());
+    }
+    result
+}
+
+fn stream_read_ints_unchecked<E: Endianness>(mut stream: BitReadStream<E>, size: usize) -> u64 {
+    let mut result: u64 = 0;
+    while stream.bits_left() >= size {
+        result = result.wrapping_add(unsafe { stream.read_int_unchecked::<u64>(size) });
+    }
+    result
+}
+
+fn bench_stream_ints<E: Endianness>(b: &mut Bencher, endianness: E, start: usize, checked: bool) {
+    let data = vec![1u8; 1024 * 1024];
+    let buffer = BitReadBuffer::new(data, endianness);
+    b.iter(|| {
+        let mut stream = BitReadStream::new(buffer.clone());
+        stream.skip_bits(start).unwrap();
+        let result = if checked {
+            stream_read_ints(stream, 16)
+        } else {
+            stream_read_ints_unchecked(stream, 16)
+        };
+        test::black_box(result);
+    });
+}
+
+#[bench]
+fn perf_stream_int_checked_aligned_le(b: &mut Bencher) {
+    bench_stream_ints(b, LittleEndian, 0, true);
+}
+
+#[bench]
+fn perf_stream_int_checked_aligned_be(b: &mut Bencher) {
+    bench_stream_ints(b, BigEndian, 0, true);
+}
+
+#[bench]
+fn perf_stream_int_checked_unaligned_le(b: &mut Bencher) {
+    bench_stream_ints(b, LittleEndian, 3, true);
+}
+
+#[bench]
+fn perf_stream_int_checked_unaligned_be(b: &mut Bencher) {
+    bench_stream_ints(b, BigEndian, 3, true);
+}
+
+#[bench]
+fn perf_stream_int_unchecked_aligned_le(b: &mut Bencher) {
+    bench_stream_ints(b, LittleEndian, 0, false);
+}
+
+#[bench]
+fn perf_stream_int_unchecked_aligned_be(b: &mut Bencher) {
+    bench_stream_ints(b, BigEndian, 0, false);
+}
+
+#[bench]
+fn perf_stream_int_unchecked_unaligned_le(b: &mut Bencher) {
+    bench_stream_ints(b, LittleEndian, 3, false);
+}
+
+#[bench]
+fn perf_stream_int_unchecked_unaligned_be(b: &mut Bencher) {
+    bench_stream_ints(b, BigEndian, 3, false);
+}
+
+/// Prefix the data with `shift` zero bits so it can be read unaligned
+fn shift_bits<E: Endianness>(data: &[u8], shift: usize) -> Vec<u8> {
+    let mut previous = 0u8;
+    let mut shifted: Vec<u8> = data
+        .iter()
+        .map(|&byte| {
+            let result = if shift == 0 {
+                byte
+            } else if E::is_le() {
+                byte << shift | previous >> (8 - shift)
+            } else {
+                byte >> shift | previous << (8 - shift)
+            };
+            previous = byte;
+            result
+        })
+        .collect();
+    shifted.push(0);
+    shifted
+}
+
+fn bench_stream_strings<E: Endianness>(b: &mut Bencher, endianness: E, start: usize) {
+    let buffer = BitReadBuffer::new(shift_bits::<E>(&get_string_buffer(), start), endianness);
+    b.iter(|| {
+        let mut stream = BitReadStream::new(buffer.clone());
+        stream.skip_bits(start).unwrap();
+        while stream.bits_left() > 128 * 8 {
+            let result = stream.read_string(None).unwrap();
+            test::black_box(result);
+        }
+    });
+}
+
+#[bench]
+fn perf_stream_string_aligned_le(b: &mut Bencher) {
+    bench_stream_strings(b, LittleEndian, 0);
+}
+
+#[bench]
+fn perf_stream_string_aligned_be(b: &mut Bencher) {
+    bench_stream_strings(b, BigEndian, 0);
+}
+
+#[bench]
+fn perf_stream_string_unaligned_le(b: &mut Bencher) {
+    bench_stream_strings(b, LittleEndian, 3);
+}
+
+#[bench]
+fn perf_stream_string_unaligned_be(b: &mut Bencher) {
+    bench_stream_strings(b, BigEndian, 3);
+}
+
+#[allow(dead_code)]
+#[derive(BitRead)]
+struct NestedStruct {
+    basic: BasicStruct,
+    #[size = 3]
+    kind: u8,
+    #[size = 4]
+    name: Vec<u8>,
+    extra: Option<u16>,
+}
+
+fn bench_struct<E: Endianness, T: BitRead<E>>(b: &mut Bencher, endianness: E, start: usize) {
+    let buffer = BitReadBuffer::new(get_string_buffer(), endianness);
+    b.iter(|| {
+        let mut stream = BitReadStream::new(buffer.clone());
+        stream.skip_bits(start).unwrap();
+        while stream.bits_left() > 128 * 8 {
+            let result = stream.read::<T>().unwrap();
+            test::black_box(result);
+        }
+    });
+}
+
+#[bench]
+fn perf_struct_be(b: &mut Bencher) {
+    bench_struct::<_, BasicStruct>(b, BigEndian, 0);
+}
+
+#[bench]
+fn perf_struct_unaligned_le(b: &mut Bencher) {
+    bench_struct::<_, BasicStruct>(b, LittleEndian, 3);
+}
+
+#[bench]
+fn perf_struct_unaligned_be(b: &mut Bencher) {
+    bench_struct::<_, BasicStruct>(b, BigEndian, 3);
+}
+
+#[bench]
+fn perf_nested_struct_le(b: &mut Bencher) {
+    bench_struct::<_, NestedStruct>(b, LittleEndian, 0);
+}
+
+#[bench]
+fn perf_nested_struct_be(b: &mut Bencher) {
+    bench_struct::<_, NestedStruct>(b, BigEndian, 0);
+}
+
+#[bench]
+fn perf_reversed_view_le(b: &mut Bencher) {
+    let data = vec![1u8; 1024 * 1024];
+    let buffer = BitReadBuffer::new(data, LittleEndian).reverse_bits();
+    b.iter(|| {
+        let result = stream_read_ints(BitReadStream::new(buffer.clone()), 16);
+        test::black_box(result);
+    });
+}
+
+fn bench_u128<E: Endianness>(b: &mut Bencher, endianness: E, start: usize) {
+    let data = vec![1u8; 1024 * 1024];
+    let buffer = BitReadBuffer::new(data, endianness);
+    b.iter(|| {
+        let mut pos = start;
+        let mut result: u128 = 0;
+        while pos + 100 <= buffer.bit_len() {
+            result = result.wrapping_add(buffer.read_int::<u128>(pos, 100).unwrap());
+            pos += 100;
+        }
+        test::black_box(result);
+    });
+}
+
+#[bench]
+fn perf_u128_le(b: &mut Bencher) {
+    bench_u128(b, LittleEndian, 0);
+}
+
+#[bench]
+fn perf_u128_unaligned_be(b: &mut Bencher) {
+    bench_u128(b, BigEndian, 3);
+}
@@ -0,0 +1,292 @@
+//! Criterion benchmarks for the read paths, runs on stable using `cargo bench --bench read`
+
+use std::hint::black_box;
+
+use bitbuffer::{BigEndian, BitRead, BitReadBuffer, BitReadStream, Endianness, LittleEndian};
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+
+const DATA_LEN: usize = 64 * 1024;
+
+/// The start offsets for the aligned and unaligned variants of a benchmark
+const ALIGNMENTS: [(&str, usize); 2] = [("aligned", 0), ("unaligned", 3)];
+
+fn int_data() -> Vec<u8> {
+    (0..DATA_LEN).map(|i| i as u8).collect()
+}
+
+/// Null terminated strings of varying length
+fn string_data() -> Vec<u8> {
+    let inputs = [
+        "foo",
+        "something a bit longer",
+        "",
+        "a string that is long enough to not fit in a single usize read",
+    ];
+    let mut data = Vec::with_capacity(DATA_LEN + 128);
+    for input in inputs.iter().cycle() {
+        if data.len() > DATA_LEN {
+            break;
+        }
+        data.extend_from_slice(input.as_bytes());
+        data.push(0);
+    }
+    data.resize(data.len() + 128, 0);
+    data
+}
+
+/// Prefix the data with `shift` zero bits so it can be read unaligned
+fn shift_bits<E: Endianness>(data: &[u8], shift: usize) -> Vec<u8> {
+    let mut previous = 0u8;
+    let mut shifted: Vec<u8> = data
+        .iter()
+        .map(|&byte| {
+            let result = if shift == 0 {
+                byte
+            } else if E::is_le() {
+                byte << shift | previous >> (8 - shift)
+            } else {
+                byte >> shift | previous << (8 - shift)
+            };
+            previous = byte;
+            result
+        })
+        .collect();
+    shifted.push(0);
+    shifted
+}
+
+fn stream_at<E: Endianness>(buffer: &BitReadBuffer<E>, start: usize) -> BitReadStream<E> {
+    let mut stream = BitReadStream::new(buffer.clone());
+    stream.skip_bits(start).unwrap();
+    stream
+}
+
+fn bench_buffer_ints<E: Endianness>(group: &mut BenchmarkGroup<WallTime>, endianness: fn() -> E) {
+    let buffer = BitReadBuffer::new(int_data(), endianness());
+    for (alignment, start) in ALIGNMENTS {
+        for size in [5, 16, 64] {
+            let name = format!("{}/{}/{}", E::as_string(), alignment, size);
+            group.bench_function(name, |b| {
+                b.iter(|| {
+                    let mut result = 0u64;
+                    let mut pos = start;
+                    while pos + size <= buffer.bit_len() {
+                        result = result.wrapping_add(buffer.read_int::<u64>(pos, size).unwrap());
+                        pos += size;
+                    }
+                    black_box(result)
+                })
+            });
+        }
+    }
+}
+
+fn bench_stream_ints<E: Endianness>(group: &mut BenchmarkGroup<WallTime>, endianness: fn() -> E) {
+    let buffer = BitReadBuffer::new(int_data(), endianness());
+    for (alignment, start) in ALIGNMENTS {
+        let name = format!("{}/{}/checked", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                let mut result = 0u64;
+                while stream.bits_left() >= 16 {
+                    result = result.wrapping_add(stream.read_int::<u64>(16).unwrap());
+                }
+                black_box(result)
+            })
+        });
+        let name = format!("{}/{}/unchecked", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                let mut result = 0u64;
+                while stream.bits_left() >= 16 {
+                    result = result.wrapping_add(unsafe { stream.read_int_unchecked::<u64>(16) });
+                }
+                black_box(result)
+            })
+        });
+    }
+}
+
+fn bench_floats<E: Endianness>(group: &mut BenchmarkGroup<WallTime>, endianness: fn() -> E) {
+    let buffer = BitReadBuffer::new(int_data(), endianness());
+    for (alignment, start) in ALIGNMENTS {
+        let name = format!("{}/{}/f32", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                let mut result = 0.0f32;
+                while stream.bits_left() >= 32 {
+                    result += stream.read_float::<f32>().unwrap();
+                }
+                black_box(result)
+            })
+        });
+        let name = format!("{}/{}/f64", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                let mut result = 0.0f64;
+                while stream.bits_left() >= 64 {
+                    result += stream.read_float::<f64>().unwrap();
+                }
+                black_box(result)
+            })
+        });
+    }
+}
+
+fn bench_strings<E: Endianness>(group: &mut BenchmarkGroup<WallTime>, endianness: fn() -> E) {
+    for (alignment, start) in ALIGNMENTS {
+        let data = shift_bits::<E>(&string_data(), start);
+        let buffer = BitReadBuffer::new(data, endianness());
+        let name = format!("{}/{}/null_terminated", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                while stream.bits_left() > 128 * 8 {
+                    black_box(stream.read_string(None).unwrap());
+                }
+            })
+        });
+        let name = format!("{}/{}/fixed", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                while stream.bits_left() > 128 * 8 {
+                    black_box(stream.read_string(Some(16)).unwrap());
+                }
+            })
+        });
+    }
+}
+
+fn bench_bytes<E: Endianness>(group: &mut BenchmarkGroup<WallTime>, endianness: fn() -> E) {
+    let buffer = BitReadBuffer::new(int_data(), endianness());
+    for (alignment, start) in ALIGNMENTS {
+        let name = format!("{}/{}", E::as_string(), alignment);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                while stream.bits_left() >= 1024 * 8 {
+                    black_box(stream.read_bytes(1024).unwrap());
+                }
+            })
+        });
+    }
+}
+
+#[allow(dead_code)]
+#[derive(BitRead)]
+struct BasicStruct {
+    a: f32,
+    b: bool,
+    #[size = 7]
+    c: u32,
+}
+
+#[allow(dead_code)]
+#[derive(BitRead)]
+struct NestedStruct {
+    basic: BasicStruct,
+    #[size = 3]
+    kind: u8,
+    #[size = 4]
+    name: Vec<u8>,
+    extra: Option<u16>,
+}
+
+#[allow(dead_code)]
+#[derive(BitRead)]
+struct PackedStruct {
+    #[size = 3]
+    version: u8,
+    flag: bool,
+    #[size = 12]
+    length: u16,
+    kind: u8,
+}
+
+fn bench_struct<E: Endianness, T: BitRead<E>>(
+    group: &mut BenchmarkGroup<WallTime>,
+    endianness: fn() -> E,
+    name: &str,
+) {
+    let buffer = BitReadBuffer::new(int_data(), endianness());
+    for (alignment, start) in ALIGNMENTS {
+        let name = format!("{}/{}/{}", E::as_string(), alignment, name);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut stream = stream_at(&buffer, start);
+                while stream.bits_left() > 128 * 8 {
+                    black_box(stream.read::<T>().unwrap());
+                }
+            })
+        });
+    }
+}
+
+fn buffer_ints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer_int");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_buffer_ints(&mut group, || LittleEndian);
+    bench_buffer_ints(&mut group, || BigEndian);
+    group.finish();
+}
+
+fn stream_ints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stream_int");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_stream_ints(&mut group, || LittleEndian);
+    bench_stream_ints(&mut group, || BigEndian);
+    group.finish();
+}
+
+fn floats(c: &mut Criterion) {
+    let mut group = c.benchmark_group("float");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_floats(&mut group, || LittleEndian);
+    bench_floats(&mut group, || BigEndian);
+    group.finish();
+}
+
+fn strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("string");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_strings(&mut group, || LittleEndian);
+    bench_strings(&mut group, || BigEndian);
+    group.finish();
+}
+
+fn bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bytes");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_bytes(&mut group, || LittleEndian);
+    bench_bytes(&mut group, || BigEndian);
+    group.finish();
+}
+
+fn structs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("struct");
+    group.throughput(Throughput::Bytes(DATA_LEN as u64));
+    bench_struct::<_, BasicStruct>(&mut group, || LittleEndian, "basic");
+    bench_struct::<_, BasicStruct>(&mut group, || BigEndian, "basic");
+    bench_struct::<_, NestedStruct>(&mut group, || LittleEndian, "nested");
+    bench_struct::<_, NestedStruct>(&mut group, || BigEndian, "nested");
+    bench_struct::<_, PackedStruct>(&mut group, || LittleEndian, "packed");
+    bench_struct::<_, PackedStruct>(&mut group, || BigEndian, "packed");
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    buffer_ints,
+    stream_ints,
+    floats,
+    strings,
+    bytes,
+    structs
+);
+criterion_main!(benches);
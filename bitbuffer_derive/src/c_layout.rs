@@ -0,0 +1,169 @@
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::{Attribute, Field, Fields, Lit, Type};
+use syn_util::get_attribute_value;
+
+use crate::has_attribute;
+
+/// The rules a C compiler uses to place bitfields in storage units
+#[derive(Clone, Copy)]
+enum Convention {
+    /// gcc and clang on System V targets, a bitfield is placed in the first unit of its type that it fits in
+    /// without crossing a unit boundary, adjacent bitfields of different types can share a unit
+    SysV,
+    /// msvc, adjacent bitfields only share a unit when their types have the same size
+    Msvc,
+}
+
+/// The padding around the fields of a struct with C bitfield packing
+pub struct CLayout {
+    /// The number of padding bits before every field
+    pub padding: Vec<usize>,
+    /// The number of padding bits after the last field
+    pub trailing: usize,
+    /// The total size of the struct in bits
+    pub size: usize,
+    /// The width of every `bool` field, a full byte for regular members and one bit for bitfields, `None` for other fields
+    pub bools: Vec<Option<usize>>,
+}
+
+/// The width of the storage unit of a field type and whether the type is `bool`
+fn unit_bits(ty: &Type) -> Option<(usize, bool)> {
+    let ident = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident()?.to_string(),
+        _ => return None,
+    };
+    Some(match ident.as_str() {
+        "bool" => (8, true),
+        "u8" | "i8" => (8, false),
+        "u16" | "i16" => (16, false),
+        "u32" | "i32" | "f32" => (32, false),
+        "u64" | "i64" | "f64" => (64, false),
+        _ => return None,
+    })
+}
+
+fn align_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// The width of a field in bits and whether the field is a bitfield
+fn field_width(field: &Field, unit: usize, is_bool: bool) -> Result<(usize, bool), TokenStream> {
+    let span = field.span();
+    if has_attribute(&field.attrs, "size_bits")
+        || has_attribute(&field.attrs, "count")
+        || has_attribute(&field.attrs, "or_default")
+    {
+        return Err(quote_spanned! { span =>
+            compile_error!("fields of structs with `c_bitfields` need a constant size")
+        });
+    }
+    match get_attribute_value::<Lit>(&field.attrs, &["size"]) {
+        None => Ok((unit, false)),
+        Some(Lit::Int(size)) if is_bool => match size.base10_parse::<usize>() {
+            Ok(1) => Ok((1, true)),
+            _ => Err(quote_spanned! { span =>
+                compile_error!("the size of a `bool` bitfield needs to be 1")
+            }),
+        },
+        Some(Lit::Int(size)) => match size.base10_parse::<usize>() {
+            Ok(width) if width > 0 && width <= unit => Ok((width, true)),
+            _ => Err(quote_spanned! { span =>
+                compile_error!("the size of a bitfield needs to be between 1 and the number of bits in its type")
+            }),
+        },
+        Some(_) => Err(quote_spanned! { span =>
+            compile_error!("fields of structs with `c_bitfields` need a constant size")
+        }),
+    }
+}
+
+/// Compute the padding of the fields for structs with the `c_bitfields` attribute, `None` for other structs
+pub fn c_layout(attrs: &[Attribute], fields: &Fields) -> Option<Result<CLayout, TokenStream>> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("c_bitfields"))?;
+    let span = attr.span();
+    let convention = match get_attribute_value::<String>(attrs, &["c_bitfields"]).as_deref() {
+        None | Some("sysv") => Convention::SysV,
+        Some("msvc") => Convention::Msvc,
+        Some(_) => {
+            return Some(Err(quote_spanned! { span =>
+                compile_error!("expected `#[c_bitfields]`, `#[c_bitfields = \"sysv\"]` or `#[c_bitfields = \"msvc\"]`")
+            }))
+        }
+    };
+
+    let mut padding = Vec::with_capacity(fields.len());
+    let mut bools = Vec::with_capacity(fields.len());
+    let mut offset = 0;
+    let mut max_align = 8;
+    // start and size of the storage unit of the last bitfield, for msvc
+    let mut current_unit: Option<(usize, usize)> = None;
+    for field in fields {
+        let (unit, is_bool) = match unit_bits(&field.ty) {
+            Some(unit) => unit,
+            None => {
+                let span = field.ty.span();
+                return Some(Err(quote_spanned! { span =>
+                    compile_error!("fields of structs with `c_bitfields` need to be a primitive integer, float or bool")
+                }));
+            }
+        };
+        let (width, is_bitfield) = match field_width(field, unit, is_bool) {
+            Ok(width) => width,
+            Err(error) => return Some(Err(error)),
+        };
+        max_align = max_align.max(unit);
+        bools.push(if is_bool { Some(width) } else { None });
+
+        let start = match convention {
+            Convention::SysV if is_bitfield => {
+                if offset / unit == (offset + width - 1) / unit {
+                    offset
+                } else {
+                    align_up(offset, unit)
+                }
+            }
+            Convention::Msvc if is_bitfield => match current_unit {
+                Some((unit_start, unit_bits))
+                    if unit_bits == unit && offset + width <= unit_start + unit =>
+                {
+                    offset
+                }
+                Some((unit_start, unit_bits)) => {
+                    let start = align_up(unit_start + unit_bits, unit);
+                    current_unit = Some((start, unit));
+                    start
+                }
+                None => {
+                    let start = align_up(offset, unit);
+                    current_unit = Some((start, unit));
+                    start
+                }
+            },
+            _ => {
+                let end = match current_unit.take() {
+                    Some((unit_start, unit_bits)) => unit_start + unit_bits,
+                    None => offset,
+                };
+                align_up(end, unit)
+            }
+        };
+        padding.push(start - offset);
+        offset = start + width;
+    }
+    // with msvc the storage unit of the last bitfield is fully occupied
+    let end = match current_unit {
+        Some((unit_start, unit_bits)) => unit_start + unit_bits,
+        None => offset,
+    };
+    let size = align_up(end, max_align);
+    Some(Ok(CLayout {
+        padding,
+        trailing: size - offset,
+        size,
+        bools,
+    }))
+}
@@ -0,0 +1,124 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, bracketed, parenthesized, Expr, Ident, Result, Token, Type};
+
+/// Input for the `bit_layout!` macro: `stream, { name: Type [size], .. }` or `stream, (Type [size], ..)`
+pub struct BitLayout {
+    stream: Expr,
+    kind: LayoutKind,
+}
+
+enum LayoutKind {
+    Struct(Vec<LayoutField>),
+    Tuple(Vec<LayoutField>),
+}
+
+struct LayoutField {
+    name: Option<Ident>,
+    ty: Type,
+    size: Option<Expr>,
+}
+
+impl LayoutField {
+    fn parse(input: ParseStream, named: bool) -> Result<Self> {
+        let name = if named {
+            let name = input.parse()?;
+            input.parse::<Token![:]>()?;
+            Some(name)
+        } else {
+            None
+        };
+        let ty = input.parse()?;
+        let size = if input.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(LayoutField { name, ty, size })
+    }
+}
+
+impl Parse for BitLayout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let stream = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content;
+        let kind = if input.peek(syn::token::Brace) {
+            braced!(content in input);
+            let fields: Punctuated<_, Token![,]> =
+                content.parse_terminated(|input| LayoutField::parse(input, true))?;
+            LayoutKind::Struct(fields.into_iter().collect())
+        } else {
+            parenthesized!(content in input);
+            let fields: Punctuated<_, Token![,]> =
+                content.parse_terminated(|input| LayoutField::parse(input, false))?;
+            LayoutKind::Tuple(fields.into_iter().collect())
+        };
+        Ok(BitLayout { stream, kind })
+    }
+}
+
+fn read_fields<'a>(
+    fields: impl Iterator<Item = (&'a Ident, &'a LayoutField)> + 'a,
+) -> impl Iterator<Item = TokenStream> + 'a {
+    fields.map(|(name, field)| {
+        let ty = &field.ty;
+        match &field.size {
+            Some(size) => quote! {
+                let #name: #ty = __bitbuffer_stream.read_sized((#size) as usize)?;
+            },
+            None => quote! {
+                let #name: #ty = __bitbuffer_stream.read()?;
+            },
+        }
+    })
+}
+
+impl BitLayout {
+    pub fn expand(self) -> TokenStream {
+        let stream = &self.stream;
+        let body = match &self.kind {
+            LayoutKind::Struct(fields) => {
+                let names: Vec<_> = fields
+                    .iter()
+                    .map(|field| field.name.clone().unwrap())
+                    .collect();
+                let types = fields.iter().map(|field| &field.ty);
+                let reads = read_fields(names.iter().zip(fields));
+                quote! {
+                    #[allow(non_camel_case_types)]
+                    struct __BitLayout {
+                        #(#names: #types,)*
+                    }
+
+                    (|| -> ::bitbuffer::Result<__BitLayout> {
+                        #(#reads)*
+                        Ok(__BitLayout { #(#names,)* })
+                    })()
+                }
+            }
+            LayoutKind::Tuple(fields) => {
+                let names: Vec<_> = (0..fields.len())
+                    .map(|index| format_ident!("__bitbuffer_{}", index))
+                    .collect();
+                let reads = read_fields(names.iter().zip(fields));
+                quote! {
+                    (|| {
+                        #(#reads)*
+                        ::bitbuffer::Result::Ok((#(#names,)*))
+                    })()
+                }
+            }
+        };
+        quote! {
+            {
+                let __bitbuffer_stream = (#stream).__layout_stream();
+                #body
+            }
+        }
+    }
+}
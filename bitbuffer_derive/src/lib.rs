@@ -14,6 +14,16 @@
 //!
 //! When deriving `BitReadSized` the input size can be used in the size attribute as the `input_size` field.
 //!
+//! A constant size that is larger than the number of bits in the integer type of the field, like `#[size = 12]` on a `u8`,
+//! is reported as a compile error.
+//!
+//! Structs that only contain integer and `bool` fields with a constant size, of at most 64 bits in total, read all fields
+//! using a single integer read.
+//!
+//! The `layout_warnings` attribute on the struct or enum enables compile warnings for layouts that miss this fast path,
+//! like 3 or more small fields that are read one at a time because the struct has other fields, or a `size_bits`
+//! value that is wider than 64 bits.
+//!
 //! ## Examples
 //!
 //! ```
@@ -84,7 +94,7 @@
 //!     Foo(i8),
 //!     Bar(bool),
 //!     #[discriminant = 3] // since rust only allows setting the discriminant on field-less enums, you can use an attribute instead
-//!     Asd(u8),
+//!     Asd(#[size = 3] u8), // the size can also be set on the field of the variant
 //! }
 //! ```
 //!
@@ -103,6 +113,361 @@
 //! }
 //! ```
 //!
+//! ## Selected variants
+//!
+//! Instead of reading a discriminant, the variant can be selected by a previously read value using the `selector` attribute.
+//! The attribute is an expression that is evaluated as the discriminant, which is typically `input_size` when deriving `BitReadSized`
+//! or refers to `ctx` when deriving `BitReadWithContext`. This allows reading union-like layouts where an earlier field
+//! determines how the following data is interpreted. When writing, no discriminant is written for these enums.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadSized, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitReadSized, PartialEq, Debug)]
+//! #[selector = "input_size"]
+//! enum Payload {
+//!     #[discriminant = 1]
+//!     Int(u16),
+//!     #[discriminant = 2]
+//!     #[size = 2]
+//!     Text(String),
+//! }
+//!
+//! #[derive(BitRead, PartialEq, Debug)]
+//! struct Message {
+//!     kind: u8,
+//!     #[size = "kind"]
+//!     payload: Payload,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![2, b'h', b'i'], LittleEndian));
+//! let message: Message = stream.read()?;
+//! assert_eq!(message.payload, Payload::Text("hi".into()));
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! ## Magic variants
+//!
+//! For chunk based container formats, the variant can be selected by comparing the next bytes against the `magic`
+//! attribute of every variant instead of reading a discriminant. The magics are compared in order of the variants and
+//! the matched bytes are consumed, so when one magic is a prefix of another the longer one should be listed first.
+//! A single variant without `magic` can be added which is read without consuming any bytes when none of the magics match,
+//! otherwise reading fails with an `UnmatchedMagic` error. When writing, the magic of the variant is written first.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead, PartialEq, Debug)]
+//! enum Chunk {
+//!     #[magic = b"RIFF"]
+//!     Riff(u32),
+//!     #[magic = b"LIST"]
+//!     List(u32),
+//!     Unknown,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(b"LIST\x04\0\0\0".to_vec(), LittleEndian));
+//! assert_eq!(stream.read::<Chunk>()?, Chunk::List(4));
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Unchecked reads
+//!
+//! For types with a fixed size, the `read_unchecked` attribute additionally generates an inherent `read_unchecked` method
+//! that skips the bounds checks, allowing hot loops to do a single `check_read` for multiple reads.
+//!
+//! ```
+//! # use bitbuffer::{bit_size_of, BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! #[read_unchecked]
+//! struct Point {
+//!     x: u16,
+//!     y: u16,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! # let buffer = BitReadBuffer::new(vec![0; 64], LittleEndian);
+//! # let mut stream = BitReadStream::new(buffer);
+//! let size = bit_size_of::<Point>().unwrap();
+//! stream.check_read(size * 16)?;
+//! for _ in 0..16 {
+//!     let point = unsafe { Point::read_unchecked(&mut stream)? };
+//! }
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # In place reads
+//!
+//! For structs containing large fields, the `read_in_place` attribute makes the derived implementation write every field
+//! directly into the uninitialized struct as soon as it is read, instead of collecting all fields before constructing the struct.
+//! If reading a field fails, the fields that were already read are dropped.
+//!
+//! While reading, previously read fields are available by reference, so sizes referring to them need to be dereferenceable.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! #[read_in_place]
+//! struct Packet {
+//!     length: u8,
+//!     #[size = "length"]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![2, 0xaa, 0xbb], LittleEndian));
+//! let packet: Packet = stream.read()?;
+//! assert_eq!(packet.payload, vec![0xaa, 0xbb]);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Context
+//!
+//! `BitReadWithContext` can be derived for types that depend on state from outside the type, such as a negotiated
+//! protocol version. The context type is set using the `context` attribute and the context is available as `ctx`
+//! in the expressions of the `size` and `read_if` attributes. Fields of type `Option<T>` with a `read_if` attribute are only
+//! read if the expression is true, fields or enum variants with a `context` attribute are read using `BitReadWithContext` with the same context.
+//!
+//! When the context implements `VersionedContext`, fields can be limited to a range of versions using the `since_version` and
+//! `until_version` attributes, both inclusive. Fields that are not present in the version of the context are set to their default value.
+//!
+//! The value used for fields that are skipped can be set using the `default` attribute, which can refer to `ctx` and previously read fields.
+//! When a `default` is set, fields with a `read_if` attribute don't need to be an `Option<T>`.
+//!
+//! ```
+//! # use bitbuffer::BitReadWithContext;
+//! #
+//! struct Session {
+//!     version: u8,
+//!     length_bits: usize,
+//! }
+//!
+//! #[derive(BitReadWithContext)]
+//! #[context = "Session"]
+//! struct Message {
+//!     #[size = "ctx.length_bits"]
+//!     length: u16,
+//!     #[read_if = "ctx.version > 2"]
+//!     checksum: Option<u32>,
+//! }
+//! ```
+//!
+//! ```
+//! # use bitbuffer::BitReadWithContext;
+//! #
+//! #[derive(BitReadWithContext)]
+//! #[context = "u32"]
+//! struct Player {
+//!     health: u8,
+//!     #[since_version = 24]
+//!     armor: u8,
+//!     #[until_version = 12]
+//!     legacy_flags: u16,
+//!     #[read_if = "health > 0"]
+//!     #[default = "100"]
+//!     max_health: u8,
+//! }
+//! ```
+//!
+//! # Element counts
+//!
+//! For `Vec<T>` fields the `size` attribute sets the number of elements, every element is read using `BitRead`.
+//! The `count` attribute sets the number of elements explicitly, which allows using the `size` attribute for the elements,
+//! reading every element using `BitReadSized` with the same size.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! struct Names {
+//!     count: u8,
+//!     length: u8,
+//!     #[count = "count"]
+//!     #[size = "length"]
+//!     names: Vec<String>,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(b"\x02\x03foobar".to_vec(), LittleEndian));
+//! let names: Names = stream.read()?;
+//! assert_eq!(names.names, ["foo", "bar"]);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # String modes
+//!
+//! Fixed length strings are read with trailing null bytes removed, the `str_mode` attribute selects a different
+//! handling of null bytes, it takes the name of a variant of `StringMode` and requires the `size` attribute.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! struct Names {
+//!     #[size = 4]
+//!     #[str_mode = "NullTerminated"] // stop at the first null byte
+//!     short: String,
+//!     #[size = 4]
+//!     #[str_mode = "FixedKeepNulls"] // keep embedded and trailing null bytes
+//!     raw: String,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(b"a\0bca\0b\0".to_vec(), LittleEndian));
+//! let names: Names = stream.read()?;
+//! assert_eq!(names.short, "a");
+//! assert_eq!(names.raw, "a\0b\0");
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Lossy strings
+//!
+//! String fields with the `lossy` attribute replace invalid utf8 with `U+FFFD REPLACEMENT CHARACTER` instead of
+//! failing the read, the attribute can be combined with `size` and `str_mode`.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! struct Player {
+//!     #[lossy]
+//!     name: String,
+//!     score: u8,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![b'a', 0xff, 0, 12], LittleEndian));
+//! let player: Player = stream.read()?;
+//! assert_eq!(player.name, "a\u{FFFD}");
+//! assert_eq!(player.score, 12);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Calculated sizes
+//!
+//! When multiple fields use the same computed size, the `calc` attribute can bind the result of an expression
+//! to a name after the field is read. The expressions can refer to the field itself, all previously read fields and
+//! earlier bindings, the bindings can be used in the attributes of all following fields.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead)]
+//! struct Table {
+//!     #[size = 4]
+//!     #[calc(entry_bits = "entry_bytes as usize * 8", total_bits = "entry_bits * 2")]
+//!     entry_bytes: u8,
+//!     #[size = "entry_bits"]
+//!     first: u32,
+//!     #[size = "entry_bits"]
+//!     second: u32,
+//!     #[size = "total_bits"]
+//!     checksum: u64,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0x11; 16], LittleEndian));
+//! let table: Table = stream.read()?;
+//! assert_eq!(table.first, 0x11);
+//! assert_eq!(stream.pos(), 36);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! With `read_in_place` the bindings are references, like the previously read fields.
+//!
+//! # Truncated trailing fields
+//!
+//! Fields at the end of a struct can be marked with the `or_default` attribute to set them to their default value when the stream
+//! ends before the field is complete, instead of failing the whole read. This allows reading older data that lacks fields added later.
+//! Once a field is truncated, the rest of the stream is skipped and all following fields are set to their default value,
+//! so every field after a field with `or_default` needs the attribute as well.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead, Debug, PartialEq)]
+//! struct Trailer {
+//!     length: u16,
+//!     #[or_default]
+//!     checksum: u32,
+//!     #[or_default]
+//!     flags: u8,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0x10, 0x00, 0xaa, 0xbb], LittleEndian));
+//! let trailer: Trailer = stream.read()?;
+//! assert_eq!(trailer, Trailer { length: 0x10, checksum: 0, flags: 0 });
+//! assert_eq!(stream.bits_left(), 0);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # C bitfields
+//!
+//! Structs mirrored from C headers can use the `c_bitfields` attribute to read and write the fields with the padding a C
+//! compiler inserts. Fields with a `size` attribute are bitfields of that width, other fields are regular members that are
+//! aligned to their size. A `bool` member is a full byte that is `true` when it isn't zero, use `#[size = 1]` for a
+//! `bool x : 1` bitfield. The struct is padded to a multiple of its largest field type.
+//!
+//! By default the layout follows gcc and clang on System V targets, where a bitfield is only moved to the next storage unit of
+//! its type when it would cross a unit boundary. With `#[c_bitfields = "msvc"]` the layout follows msvc, where adjacent bitfields
+//! only share a storage unit when their types have the same size.
+//!
+//! The order of the bits within a storage unit follows the endianness of the stream, a `LittleEndian` stream matches the
+//! layout on little endian targets where bitfields are allocated starting at the least significant bit, a `BigEndian` stream
+//! matches big endian targets where bitfields are allocated starting at the most significant bit.
+//!
+//! Only primitive integers, floats and `bool` are supported as fields and the sizes need to be integer literals.
+//!
+//! ```
+//! # use bitbuffer::{bit_size_of, BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! // struct header { uint8_t version : 3; uint16_t length : 10; uint8_t flags : 4; uint32_t id; };
+//! #[derive(BitRead, Debug, PartialEq)]
+//! #[c_bitfields]
+//! struct Header {
+//!     #[size = 3]
+//!     version: u8,
+//!     #[size = 10]
+//!     length: u16,
+//!     #[size = 4]
+//!     flags: u8,
+//!     id: u32,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let bytes = vec![0xe5, 0x15, 0x09, 0x00, 0x78, 0x56, 0x34, 0x12];
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+//! let header: Header = stream.read()?;
+//! assert_eq!(header, Header { version: 5, length: 700, flags: 9, id: 0x12345678 });
+//! assert_eq!(bit_size_of::<Header>(), Some(64));
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
 //! # Endianness
 //!
 //! If the struct that `BitRead` or `BitReadSized` is derived for requires a Endianness type parameter, you need to tell the derive macro the name of the type parameter used
@@ -131,21 +496,141 @@
 //!     stream: BitReadStream<BigEndian>,
 //! }
 //! ```
+//!
+//! Without the attribute the implementation is generic over the endianness. With the `specialize_endianness` attribute
+//! separate non-generic implementations for `LittleEndian` and `BigEndian` are generated instead, so the read code for
+//! both endiannesses is compiled in the crate defining the type with the field widths folded into constants.
+//! A generic implementation can't be generated next to them since the implementations would overlap, code that is
+//! generic over the endianness needs a `BitRead<E>` bound to read the type.
+//!
+//! ```
+//! # use bitbuffer::{BigEndian, BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! #
+//! #[derive(BitRead, Debug, PartialEq)]
+//! #[specialize_endianness]
+//! struct Point {
+//!     #[size = 12]
+//!     x: u16,
+//!     #[size = 12]
+//!     y: u16,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0x01, 0x20, 0x00], LittleEndian));
+//! assert_eq!(stream.read::<Point>()?, Point { x: 1, y: 2 });
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0x00, 0x10, 0x02], BigEndian));
+//! assert_eq!(stream.read::<Point>()?, Point { x: 1, y: 2 });
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Schema
+//!
+//! `BitSchema` can be derived alongside `BitRead` or `BitReadSized` to describe the wire layout of the type as a
+//! `bitbuffer::Schema`, including the names of the fields, their sizes and the discriminants of enum variants.
+//!
+//! ```
+//! # use bitbuffer::{BitRead, BitSchema, Schema};
+//! #
+//! #[derive(BitRead, BitSchema)]
+//! #[discriminant_bits = 2]
+//! enum Message {
+//!     Ping,
+//!     #[size = 12]
+//!     Data(u16),
+//! }
+//!
+//! assert!(matches!(Message::schema(), Schema::Enum { discriminant_bits: 2, .. }));
+//! ```
+//!
+//! # Repr enums
+//!
+//! For fieldless enums that already have a `#[repr(..)]` integer type and a `TryFrom` implementation for that integer,
+//! `BitReadRepr` can be derived instead of `BitRead`. The derived implementation reads the repr integer and converts
+//! it using `TryFrom`, returning an `UnmatchedDiscriminant` error if the conversion fails.
+//!
+//! By default the full width of the repr integer is read, the `discriminant_bits` attribute can be used to read fewer bits.
+//!
+//! ```
+//! # use bitbuffer::{BitReadBuffer, BitReadStream, BitReadRepr, LittleEndian, Result};
+//! # use std::convert::TryFrom;
+//! #
+//! #[derive(BitReadRepr, Debug, PartialEq)]
+//! #[repr(u8)]
+//! #[discriminant_bits = 4]
+//! enum Kind {
+//!     Request = 1,
+//!     Response = 2,
+//! }
+//!
+//! impl TryFrom<u8> for Kind {
+//!     type Error = u8;
+//!
+//!     fn try_from(value: u8) -> std::result::Result<Self, u8> {
+//!         match value {
+//!             1 => Ok(Kind::Request),
+//!             2 => Ok(Kind::Response),
+//!             _ => Err(value),
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0b0011_0010], LittleEndian));
+//! assert_eq!(stream.read::<Kind>()?, Kind::Response);
+//! assert!(stream.read::<Kind>().is_err());
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Tracing
+//!
+//! When the `trace` feature of `bitbuffer` is enabled, the derived implementations emit a `tracing` event at the
+//! trace level for every field of a struct that is read, with the name of the type and field, the bit position
+//! the field was read from and the value that was read.
+//! Fields with a type that doesn't implement `Debug` are logged without their value.
 extern crate proc_macro;
 
+mod c_layout;
+mod layout;
+
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, parse_str, Attribute, Data, DataStruct, DeriveInput, Expr,
-    Field, Fields, Ident, Lit, LitStr, Path, Variant,
+    parse_macro_input, parse_quote, parse_str, Attribute, Data, DataEnum, DataStruct, DeriveInput,
+    Expr, Field, Fields, GenericArgument, Ident, Index, Lit, LitByteStr, LitStr, Meta,
+    MetaNameValue, NestedMeta, Path, PathArguments, Token, Type, Variant,
 };
 use syn_util::get_attribute_value;
 
+use c_layout::{c_layout, CLayout};
+
 /// See the [crate documentation](index.html) for details
 #[proc_macro_derive(
     BitRead,
-    attributes(size, size_bits, discriminant_bits, discriminant, endianness)
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        read_unchecked,
+        read_in_place,
+        specialize_endianness,
+        or_default,
+        calc,
+        count,
+        selector,
+        magic,
+        c_bitfields,
+        str_mode,
+        lossy,
+        layout_warnings
+    )
 )]
 pub fn derive_bitread(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_bitread_trait(input, "BitRead".to_owned(), None)
@@ -155,36 +640,282 @@ pub fn derive_bitread(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 /// See the [crate documentation](index.html) for details
 #[proc_macro_derive(
     BitReadSized,
-    attributes(size, size_bits, discriminant_bits, discriminant, endianness)
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        read_unchecked,
+        read_in_place,
+        specialize_endianness,
+        or_default,
+        calc,
+        count,
+        selector,
+        magic,
+        c_bitfields,
+        str_mode,
+        lossy,
+        layout_warnings
+    )
 )]
 pub fn derive_bitread_sized(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let extra_param = parse_str::<TokenStream>(", input_size: usize").unwrap();
     derive_bitread_trait(input, "BitReadSized".to_owned(), Some(extra_param))
 }
 
+/// See the [crate documentation](index.html) for details
+#[proc_macro_derive(
+    BitReadWithContext,
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        context,
+        read_if,
+        since_version,
+        until_version,
+        default,
+        or_default,
+        calc,
+        count,
+        selector,
+        magic,
+        c_bitfields,
+        str_mode,
+        lossy,
+        layout_warnings
+    )
+)]
+pub fn derive_bitread_with_context(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    if let Some(error) = union_error(&input) {
+        return error;
+    }
+
+    let name = &input.ident;
+    let span = input.span();
+    let context = match get_attribute_value::<String>(&input.attrs, &["context"]) {
+        Some(context) => parse_str::<Type>(&context).unwrap(),
+        None => {
+            return proc_macro::TokenStream::from(quote_spanned! { span =>
+                compile_error!("`BitReadWithContext` requires a `#[context = \"..\"]` attribute with the context type");
+            })
+        }
+    };
+
+    let endianness = get_attribute_value(&input.attrs, &["endianness"]);
+    let mut trait_generics = input.generics.clone();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    if endianness.is_none() {
+        trait_generics
+            .params
+            .push(parse_quote!(_E: ::bitbuffer::Endianness));
+    }
+    let (impl_generics, _, _) = trait_generics.split_for_impl();
+    let endianness_ident = Ident::new(&endianness.unwrap_or_else(|| "_E".to_owned()), span);
+
+    let parsed = parse(input.data.clone(), name, &input.attrs, false, true);
+    let size_errors = size_width_errors(&input.data);
+    let layout_warnings = layout_warnings(&input);
+
+    let expanded = quote! {
+        #[allow(clippy::unnecessary_cast)]
+        impl #impl_generics ::bitbuffer::BitReadWithContext<#endianness_ident, #context> for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn read_with_context(stream: &mut ::bitbuffer::BitReadStream<#endianness_ident>, ctx: &#context) -> ::bitbuffer::Result<Self> {
+                ::bitbuffer::__record_read!(stream, { #parsed })
+            }
+        }
+
+        #size_errors
+
+        #layout_warnings
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
 /// See the [crate documentation](index.html) for details
 #[proc_macro_derive(
     BitWrite,
-    attributes(size, size_bits, discriminant_bits, discriminant, endianness)
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        selector,
+        magic,
+        c_bitfields
+    )
 )]
 pub fn derive_bitwrite(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_bitwrite_trait(input, "BitWrite".to_owned(), None)
 }
 
+/// See the [crate documentation](index.html) for details
+#[proc_macro_derive(
+    BitWriteSized,
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        selector,
+        magic,
+        c_bitfields
+    )
+)]
+pub fn derive_bitwrite_sized(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let extra_param = parse_str::<TokenStream>(", input_size: usize").unwrap();
+    derive_bitwrite_trait(input, "BitWriteSized".to_owned(), Some(extra_param))
+}
+
+/// See the [crate documentation](index.html) for details
+#[proc_macro_derive(
+    BitSchema,
+    attributes(
+        size,
+        size_bits,
+        discriminant_bits,
+        discriminant,
+        endianness,
+        count,
+        selector,
+        magic,
+        c_bitfields
+    )
+)]
+pub fn derive_bitschema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    if let Some(error) = union_error(&input) {
+        return error;
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let schema = schema(input.data.clone(), name, &input.attrs);
+
+    let expanded = quote! {
+        impl #impl_generics ::bitbuffer::BitSchema for #name #ty_generics #where_clause {
+            fn schema() -> ::bitbuffer::Schema {
+                #schema
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// See the [crate documentation](index.html) for details
+#[proc_macro_derive(BitReadRepr, attributes(discriminant_bits))]
+pub fn derive_bitread_repr(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+    let span = input.span();
+    let repr = match repr_type(&input.attrs) {
+        Some(repr) => repr,
+        None => {
+            return proc_macro::TokenStream::from(quote_spanned! { span =>
+                compile_error!("`BitReadRepr` requires a `#[repr(..)]` attribute with an integer type");
+            })
+        }
+    };
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut trait_generics = input.generics.clone();
+    trait_generics
+        .params
+        .push(parse_quote!(_E: ::bitbuffer::Endianness));
+    let (impl_generics, _, _) = trait_generics.split_for_impl();
+
+    let bits = match get_attribute_value::<u64>(&input.attrs, &["discriminant_bits"]) {
+        Some(bits) => quote!(#bits as usize),
+        None => quote!(::std::mem::size_of::<#repr>() * 8),
+    };
+    let enum_name = Lit::Str(LitStr::new(&name.to_string(), name.span()));
+
+    let expanded = quote! {
+        impl #impl_generics ::bitbuffer::BitRead<_E> for #name #ty_generics #where_clause {
+            fn read(stream: &mut ::bitbuffer::BitReadStream<_E>) -> ::bitbuffer::Result<Self> {
+                let discriminant: #repr = stream.read_int(#bits)?;
+                <Self as ::std::convert::TryFrom<#repr>>::try_from(discriminant).map_err(|_| {
+                    ::bitbuffer::BitError::UnmatchedDiscriminant {
+                        discriminant: discriminant as usize,
+                        enum_name: #enum_name.to_string(),
+                    }
+                })
+            }
+
+            fn bit_size() -> Option<usize> {
+                Some(#bits)
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Get the integer type from the `repr` attribute
+fn repr_type(attrs: &[Attribute]) -> Option<Ident> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .find(|ident| {
+            matches!(
+                ident.to_string().as_str(),
+                "u8" | "u16"
+                    | "u32"
+                    | "u64"
+                    | "u128"
+                    | "usize"
+                    | "i8"
+                    | "i16"
+                    | "i32"
+                    | "i64"
+                    | "i128"
+                    | "isize"
+            )
+        })
+}
+
+/// Read an anonymous layout from a stream
+///
+/// See the documentation of `bitbuffer::bit_layout` for details
+#[proc_macro]
+pub fn bit_layout(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let layout = parse_macro_input!(input as layout::BitLayout);
+    proc_macro::TokenStream::from(layout.expand())
+}
+
 fn derive_bitread_trait(
     input: proc_macro::TokenStream,
     trait_name: String,
     extra_param: Option<TokenStream>,
 ) -> proc_macro::TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    if let Some(error) = union_error(&input) {
+        return error;
+    }
 
     let name = &input.ident;
 
-    let endianness = get_attribute_value(&input.attrs, &["endianness"]);
+    let endianness: Option<String> = get_attribute_value(&input.attrs, &["endianness"]);
+    let specialize = has_attribute(&input.attrs, "specialize_endianness");
     let mut trait_generics = input.generics.clone();
     // we need these separate generics to only add out Endianness param to the 'impl'
     let (_, ty_generics, where_clause) = input.generics.split_for_impl();
-    if endianness.is_none() {
+    if endianness.is_none() && !specialize {
         trait_generics
             .params
             .push(parse_quote!(_E: ::bitbuffer::Endianness));
@@ -192,20 +923,57 @@ fn derive_bitread_trait(
     let (impl_generics, _, _) = trait_generics.split_for_impl();
     let span = input.span();
 
+    if specialize && endianness.is_some() {
+        return proc_macro::TokenStream::from(quote_spanned! { span =>
+            compile_error!("`specialize_endianness` can't be combined with the `endianness` attribute");
+        });
+    }
+
+    let size_errors = size_width_errors(&input.data);
+    let layout_warnings = layout_warnings(&input);
+    let unchecked_method = if has_attribute(&input.attrs, "read_unchecked") {
+        Some(read_unchecked_method(
+            &input,
+            endianness.is_none(),
+            extra_param.is_some(),
+        ))
+    } else {
+        None
+    };
+
     let size = size(
         input.data.clone(),
-        &name,
+        name,
         &input.attrs,
         extra_param.is_some(),
     );
-    let parsed = parse(input.data.clone(), &name, &input.attrs, false);
-    let parsed_unchecked = parse(input.data.clone(), &name, &input.attrs, true);
-
-    let endianness_placeholder = endianness.unwrap_or_else(|| "_E".to_owned());
-    let trait_def_str = format!("::bitbuffer::{}<{}>", trait_name, &endianness_placeholder);
-    let trait_def = parse_str::<Path>(&trait_def_str).unwrap();
+    if has_attribute(&input.attrs, "read_in_place") && has_attribute(&input.attrs, "c_bitfields") {
+        return proc_macro::TokenStream::from(quote_spanned! { span =>
+            compile_error!("`read_in_place` can't be combined with `c_bitfields`");
+        });
+    }
+    let (parsed, parsed_unchecked) = if has_attribute(&input.attrs, "read_in_place") {
+        (
+            parse_in_place(input.data.clone(), name, &input.attrs, false),
+            parse_in_place(input.data.clone(), name, &input.attrs, true),
+        )
+    } else {
+        (
+            parse(input.data.clone(), name, &input.attrs, false, false),
+            parse(input.data.clone(), name, &input.attrs, true, false),
+        )
+    };
 
-    let endianness_ident = Ident::new(&endianness_placeholder, span);
+    let endiannesses = if specialize {
+        vec![
+            quote!(::bitbuffer::LittleEndian),
+            quote!(::bitbuffer::BigEndian),
+        ]
+    } else {
+        let endianness_ident = Ident::new(&endianness.unwrap_or_else(|| "_E".to_owned()), span);
+        vec![quote!(#endianness_ident)]
+    };
+    let trait_ident = Ident::new(&trait_name, span);
 
     let size_extra_param = if extra_param.is_some() {
         Some(quote!(input_size: usize))
@@ -225,92 +993,926 @@ fn derive_bitread_trait(
         } else {
             "bit_size"
         },
-        Span::call_site(),
-    );
-    //
-    let expanded = quote! {
-        impl #impl_generics #trait_def for #name #ty_generics #where_clause {
-            fn read(stream: &mut ::bitbuffer::BitReadStream<#endianness_ident>#extra_param) -> ::bitbuffer::Result<Self> {
-                // if the read has a predicable size, we can do the bounds check in one go
-                match <Self as #trait_def>::#size_method_name(#extra_param_call) {
-                    Some(size) => {
-                        stream.check_read(size)?;
-                        unsafe {
-                            <Self as #trait_def>::read_unchecked(stream, #extra_param_call)
-                        }
-                    },
-                    None => {
-                        #parsed
-                    }
+        Span::call_site(),
+    );
+    let impls = endiannesses.iter().map(|endianness| {
+        let trait_def = quote!(::bitbuffer::#trait_ident<#endianness>);
+        quote! {
+        #[allow(clippy::unnecessary_cast)]
+        impl #impl_generics #trait_def for #name #ty_generics #where_clause {
+            fn read(stream: &mut ::bitbuffer::BitReadStream<#endianness>#extra_param) -> ::bitbuffer::Result<Self> {
+                // if the read has a predicable size, we can do the bounds check in one go
+                match <Self as #trait_def>::#size_method_name(#extra_param_call) {
+                    Some(size) => {
+                        stream.check_read(size)?;
+                        unsafe {
+                            <Self as #trait_def>::read_unchecked(stream, #extra_param_call)
+                        }
+                    },
+                    None => ::bitbuffer::__record_read!(stream, { #parsed }),
+                }
+            }
+
+            unsafe fn read_unchecked(stream: &mut ::bitbuffer::BitReadStream<#endianness>#extra_param) -> ::bitbuffer::Result<Self> {
+                ::bitbuffer::__record_read!(stream, { #parsed_unchecked })
+            }
+
+            fn #size_method_name(#size_extra_param) -> Option<usize> {
+                #size
+            }
+        }
+        }
+    });
+
+    let expanded = quote! {
+        #(#impls)*
+
+        #unchecked_method
+
+        #size_errors
+
+        #layout_warnings
+    };
+
+    // panic!("{}", TokenStream::to_string(&expanded));
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+/// Expose the unchecked read as an inherent method so call sites can opt into it
+fn read_unchecked_method(
+    input: &DeriveInput,
+    generic_endianness: bool,
+    has_input_size: bool,
+) -> TokenStream {
+    let name = &input.ident;
+    let span = input.span();
+    if !is_fixed_size(&input.data, has_input_size) {
+        return quote_spanned! { span =>
+            compile_error!("`read_unchecked` can only be used on types with a fixed bit size");
+        };
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let endianness = get_attribute_value::<String>(&input.attrs, &["endianness"])
+        .unwrap_or_else(|| "_E".to_owned());
+    let endianness_ident = Ident::new(&endianness, span);
+    let (trait_name, extra_param, extra_param_call, size_call) = if has_input_size {
+        (
+            quote!(::bitbuffer::BitReadSized),
+            Some(quote!(, input_size: usize)),
+            Some(quote!(, input_size)),
+            quote!(bit_size_of_sized::<Self>(input_size)),
+        )
+    } else {
+        (
+            quote!(::bitbuffer::BitRead),
+            None,
+            None,
+            quote!(bit_size_of::<Self>()),
+        )
+    };
+    let (method_generics, method_where) = if generic_endianness {
+        (
+            Some(quote!(<_E: ::bitbuffer::Endianness>)),
+            Some(quote!(where Self: #trait_name<_E>)),
+        )
+    } else {
+        (None, None)
+    };
+    let size_doc = format!(
+        " The caller is responsible for ensuring that at least `{}` bits are left in the stream,",
+        size_call
+    );
+
+    quote_spanned! { span =>
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Read the type from the stream without checking if enough data is left
+            ///
+            /// # Safety
+            ///
+            #[doc = #size_doc]
+            /// for example by calling `stream.check_read()` beforehand
+            #[inline]
+            #[allow(dead_code)]
+            pub unsafe fn read_unchecked #method_generics(stream: &mut ::bitbuffer::BitReadStream<#endianness_ident>#extra_param) -> ::bitbuffer::Result<Self> #method_where {
+                <Self as #trait_name<#endianness_ident>>::read_unchecked(stream #extra_param_call)
+            }
+        }
+    }
+}
+
+/// Whether the size of the type is known without reading it, ignoring the size of the field types themselves
+fn is_fixed_size(data: &Data, has_input_size: bool) -> bool {
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => fields
+            .iter()
+            .all(|field| is_const_size(&field.attrs, has_input_size)),
+        Data::Enum(data) if is_magic_enum(data) => magic_size(data).is_some(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .all(|variant| matches!(&variant.fields, Fields::Unit)),
+        _ => false,
+    }
+}
+
+/// Unions can't be read safely, point to using an enum with a `selector` instead
+fn union_error(input: &DeriveInput) -> Option<proc_macro::TokenStream> {
+    match &input.data {
+        Data::Union(data) => {
+            let span = data.union_token.span();
+            Some(proc_macro::TokenStream::from(quote_spanned! { span =>
+                compile_error!("unions are not supported, use an enum with a `selector` attribute to select the variant using a previously read value");
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// The number of bits in a primitive integer type
+fn int_type_bits(ty: &Type) -> Option<usize> {
+    let ident = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident()?.to_string(),
+        _ => return None,
+    };
+    Some(match ident.as_str() {
+        "u8" | "i8" => 8,
+        "u16" | "i16" => 16,
+        "u32" | "i32" => 32,
+        "u64" | "i64" => 64,
+        "u128" | "i128" => 128,
+        _ => return None,
+    })
+}
+
+/// Report integer fields and variants with a constant `size` larger than their type
+fn size_width_errors(data: &Data) -> TokenStream {
+    let check = |attrs: &[Attribute], ty: &Type| {
+        let bits = int_type_bits(ty)?;
+        let size = match get_attribute_value::<Lit>(attrs, &["size"])? {
+            Lit::Int(size) => size,
+            _ => return None,
+        };
+        let span = size.span();
+        match size.base10_parse::<usize>() {
+            Ok(value) if value > bits => {
+                let message = format!(
+                    "size of {} bits is larger than the {} bits of `{}`",
+                    value,
+                    bits,
+                    quote!(#ty)
+                );
+                Some(quote_spanned! { span => compile_error!(#message); })
+            }
+            _ => None,
+        }
+    };
+    let errors: Vec<TokenStream> = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields
+            .iter()
+            .filter_map(|field| check(&field.attrs, &field.ty))
+            .collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .filter_map(|variant| match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    check(variant_size_attrs(variant), &fields.unnamed[0].ty)
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    quote!(#(#errors)*)
+}
+
+/// Warn about field layouts that prevent the fast paths when the `layout_warnings` attribute is set
+///
+/// Fields that could be read with a single integer read but are read one by one, because the struct also has other
+/// fields, and `size_bits` values that are wider than the size they are read into are reported.
+fn layout_warnings(input: &DeriveInput) -> TokenStream {
+    if !has_attribute(&input.attrs, "layout_warnings") {
+        return TokenStream::new();
+    }
+    let mut warnings = Vec::new();
+    let mut check_size_bits = |attrs: &[Attribute]| {
+        if let Some(Lit::Int(bits)) = get_attribute_value::<Lit>(attrs, &["size_bits"]) {
+            match bits.base10_parse::<usize>() {
+                Ok(value) if value > 64 => {
+                    let message = format!(
+                        "`size_bits` of {} bits is wider than the 64 bits the size is read into",
+                        value
+                    );
+                    warnings.push(compile_warning(bits.span(), &message));
+                }
+                _ => {}
+            }
+        }
+    };
+    match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            for field in fields {
+                check_size_bits(&field.attrs);
+            }
+            if !has_attribute(&input.attrs, "c_bitfields") {
+                warnings.extend(unmerged_field_warnings(fields));
+            }
+        }
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                check_size_bits(variant_size_attrs(variant));
+            }
+        }
+        _ => {}
+    }
+    quote!(#(#warnings)*)
+}
+
+/// Find runs of at least 3 small integer fields that are read one by one but fit in a single integer read
+fn unmerged_field_warnings(fields: &Fields) -> Vec<TokenStream> {
+    let widths: Vec<_> = fields
+        .iter()
+        .map(|field| packed_width(field).map(|(_, _, width)| width))
+        .collect();
+    if widths.iter().all(Option::is_some) && widths.iter().flatten().sum::<usize>() <= 64 {
+        // the struct is already read using a single integer read
+        return Vec::new();
+    }
+    let fields: Vec<_> = fields.iter().collect();
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut current: Option<(usize, usize, usize)> = None;
+    for (index, width) in widths.iter().enumerate() {
+        current = match (current, width) {
+            (Some((start, end, total)), Some(width)) if total + width <= 64 => {
+                Some((start, end + 1, total + width))
+            }
+            (run, width) => {
+                if let Some((start, end, _)) = run {
+                    runs.push((start, end));
+                }
+                width.map(|width| (index, index + 1, width))
+            }
+        };
+    }
+    if let Some((start, end, _)) = current {
+        runs.push((start, end));
+    }
+    runs.into_iter()
+        .filter(|(start, end)| end - start >= 3)
+        .map(|(start, end)| {
+            let name = |field: &Field, index: usize| {
+                field
+                    .ident
+                    .as_ref()
+                    .map_or_else(|| index.to_string(), Ident::to_string)
+            };
+            let message = format!(
+                "the {} fields `{}` to `{}` are read one at a time, moving them into a separate struct allows reading them using a single integer read",
+                end - start,
+                name(fields[start], start),
+                name(fields[end - 1], end - 1)
+            );
+            compile_warning(fields[start].span(), &message)
+        })
+        .collect()
+}
+
+/// Emit a warning at `span`, using a deprecated item since proc macros can't emit warnings directly
+fn compile_warning(span: Span, message: &str) -> TokenStream {
+    quote_spanned! { span =>
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const layout_warning: () = ();
+            layout_warning
+        };
+    }
+}
+
+fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path.is_ident(name))
+}
+
+/// The attributes holding the size of the payload of an enum variant, either on the variant or on its field
+fn variant_size_attrs(variant: &Variant) -> &[Attribute] {
+    let on_variant =
+        has_attribute(&variant.attrs, "size") || has_attribute(&variant.attrs, "size_bits");
+    match &variant.fields {
+        Fields::Unnamed(fields) if !on_variant && fields.unnamed.len() == 1 => {
+            &fields.unnamed[0].attrs
+        }
+        _ => &variant.attrs,
+    }
+}
+
+/// Generate the statements skipping or writing the padding before every field and after the last field
+fn layout_padding(
+    layout: &CLayout,
+    pad: impl Fn(usize) -> TokenStream,
+) -> (Vec<Option<TokenStream>>, Option<TokenStream>) {
+    let pad = |bits: usize| if bits > 0 { Some(pad(bits)) } else { None };
+    (
+        layout.padding.iter().map(|bits| pad(*bits)).collect(),
+        pad(layout.trailing),
+    )
+}
+
+/// Get the bindings from the `calc` attributes of a field, with `borrowed` the values are bound as references
+fn calc_bindings(attrs: &[Attribute], borrowed: bool) -> TokenStream {
+    let bindings = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("calc"))
+        .flat_map(|attr| {
+            let span = attr.span();
+            let nested = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested,
+                _ => {
+                    return vec![quote_spanned! { span =>
+                        compile_error!("expected `#[calc(name = \"expression\")]`");
+                    }]
+                }
+            };
+            nested
+                .into_iter()
+                .map(|meta| match meta {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(expr),
+                        ..
+                    })) if path.get_ident().is_some() => {
+                        let name = path.get_ident().unwrap();
+                        let expr = parse_str::<Expr>(&expr.value()).unwrap();
+                        let value = if borrowed {
+                            quote_spanned! { span => &(#expr) }
+                        } else {
+                            quote_spanned! { span => #expr }
+                        };
+                        quote_spanned! { span =>
+                            #[allow(unused_variables)]
+                            let #name = #value;
+                        }
+                    }
+                    _ => quote_spanned! { span =>
+                        compile_error!("expected `#[calc(name = \"expression\")]`");
+                    },
+                })
+                .collect()
+        });
+    quote!(#(#bindings)*)
+}
+
+/// Generate the expressions reading every field of a struct
+///
+/// With `borrowed`, previously read fields are available as references when evaluating the size of a field,
+/// with `context` the `context` and `read_if` attributes are used to read fields depending on the `ctx`
+fn read_field_values(
+    fields: &Fields,
+    unchecked: bool,
+    borrowed: bool,
+    context: bool,
+) -> Vec<TokenStream> {
+    let mut trailing = false;
+    fields
+        .iter()
+        .map(|f| {
+            let value = read_field_value(f, unchecked, borrowed, context, &mut trailing);
+            if has_attribute(&f.attrs, "lossy") {
+                read_lossy(f, value)
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+/// The type, signedness and width of an integer or boolean field with a constant size and no other attributes
+fn packed_width(field: &Field) -> Option<(&Ident, bool, usize)> {
+    let ty = match &field.ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident()?,
+        _ => return None,
+    };
+    let (bits, signed) = match ty.to_string().as_str() {
+        "bool" => (1, false),
+        "u8" => (8, false),
+        "u16" => (16, false),
+        "u32" => (32, false),
+        "u64" => (64, false),
+        "i8" => (8, true),
+        "i16" => (16, true),
+        "i32" => (32, true),
+        "i64" => (64, true),
+        _ => return None,
+    };
+    if field
+        .attrs
+        .iter()
+        .any(|attr| !attr.path.is_ident("size") && !attr.path.is_ident("doc"))
+    {
+        return None;
+    }
+    let width = match get_attribute_value::<Lit>(&field.attrs, &["size"]) {
+        None => bits,
+        Some(Lit::Int(size)) if bits > 1 => match size.base10_parse::<usize>() {
+            Ok(width) if width > 0 && width <= bits => width,
+            _ => return None,
+        },
+        Some(_) => return None,
+    };
+    Some((ty, signed, width))
+}
+
+/// Read the fields of structs that only contain integer and boolean fields of at most 64 bits in total using a
+/// single integer read, generates the read and the expressions extracting every field from the read integer
+///
+/// Returns `None` when the struct has other fields or field attributes other than a constant `size`
+fn packed_fields(
+    attrs: &[Attribute],
+    fields: &Fields,
+    unchecked: bool,
+    context: bool,
+) -> Option<(TokenStream, Vec<TokenStream>)> {
+    if context || fields.len() < 2 || has_attribute(attrs, "c_bitfields") {
+        return None;
+    }
+    let mut layout = Vec::with_capacity(fields.len());
+    let mut total = 0;
+    for field in fields {
+        let (ty, signed, width) = packed_width(field)?;
+        layout.push((field, ty, signed, total, width));
+        total += width;
+    }
+    if total > 64 {
+        return None;
+    }
+
+    let values = layout
+        .into_iter()
+        .map(|(field, ty, signed, offset, width)| {
+            let span = field.span();
+            let raw = quote_spanned! { span =>
+                stream.__packed_field(__bitbuffer_packed, #total, #offset, #width)
+            };
+            if ty == "bool" {
+                quote_spanned! { span => #raw != 0 }
+            } else if signed && width < 64 {
+                let shift = 64 - width;
+                quote_spanned! { span => ((#raw << #shift) as i64 >> #shift) as #ty }
+            } else {
+                quote_spanned! { span => #raw as #ty }
+            }
+        })
+        .collect();
+    let read = if unchecked {
+        quote! {
+            let __bitbuffer_packed = stream.__read_packed(#total);
+        }
+    } else {
+        quote! {
+            stream.check_read(#total)?;
+            // safety: the bounds have been checked for all fields at once
+            let __bitbuffer_packed = unsafe { stream.__read_packed(#total) };
+        }
+    };
+    Some((read, values))
+}
+
+/// Generate the expression reading a single field, `trailing` is set once a field with `or_default` is read
+fn read_field_value(
+    f: &Field,
+    unchecked: bool,
+    borrowed: bool,
+    context: bool,
+    trailing: &mut bool,
+) -> TokenStream {
+    // Get attributes `#[..]` on each field
+    let size = if borrowed {
+        get_borrowed_field_size(&f.attrs, f.span())
+    } else {
+        get_field_size(&f.attrs, f.span(), true)
+    };
+    let span = f.span();
+    let or_default = has_attribute(&f.attrs, "or_default");
+    if *trailing && !or_default {
+        return quote_spanned! { span =>
+            compile_error!("fields following a field with the `or_default` attribute also need the `or_default` attribute")
+        };
+    }
+    *trailing |= or_default;
+    let count = get_field_count(&f.attrs, span, borrowed);
+    if context {
+        let value = read_context_field_value(f, size, count);
+        let default = get_attribute_value::<String>(&f.attrs, &["default"])
+            .map(|default| parse_str::<Expr>(&default).unwrap());
+        return match or_default {
+            true => read_or_default(span, value, default),
+            false => value,
+        };
+    }
+    let field_type = &f.ty;
+    if has_attribute(&f.attrs, "str_mode") {
+        let value = read_string_mode_value(f, size, count.is_some());
+        return match or_default {
+            true => read_or_default(span, value, None),
+            false => value,
+        };
+    }
+    if let Some(count) = count {
+        let value = read_count_value(span, count, size);
+        return match or_default {
+            true => read_or_default(span, value, None),
+            false => value,
+        };
+    }
+    if or_default {
+        let value = match size {
+            Some(size) => quote_spanned! { span =>
+                {
+                    let _size: usize = #size;
+                    stream.read_sized::<#field_type>(_size)?
+                }
+            },
+            None => quote_spanned! { span =>
+                stream.read::<#field_type>()?
+            },
+        };
+        read_or_default(span, value, None)
+    } else if unchecked {
+        match size {
+            Some(size) => {
+                quote_spanned! { span =>
+                    {
+                        let _size: usize = #size;
+                        stream.read_sized_unchecked::<#field_type>(_size)?
+                    }
+                }
+            }
+            None => {
+                quote_spanned! { span =>
+                    stream.read_unchecked::<#field_type>()?
+                }
+            }
+        }
+    } else {
+        match size {
+            Some(size) => {
+                quote_spanned! { span =>
+                    {
+                        let _size: usize = #size;
+                        stream.read_sized::<#field_type>(_size)?
+                    }
+                }
+            }
+            None => {
+                quote_spanned! { span =>
+                    stream.read::<#field_type>()?
+                }
+            }
+        }
+    }
+}
+
+/// Wrap the expression reading a string field to replace invalid utf8 instead of returning an error
+fn read_lossy(f: &Field, value: TokenStream) -> TokenStream {
+    let span = f.span();
+    if has_attribute(&f.attrs, "count") {
+        return quote_spanned! { span =>
+            compile_error!("the `lossy` attribute can't be combined with `count`")
+        };
+    }
+    quote_spanned! { span =>
+        {
+            #[allow(clippy::needless_question_mark)]
+            let __bitbuffer_result = (|| -> ::bitbuffer::Result<_> { Ok(#value) })();
+            match __bitbuffer_result {
+                Ok(value) => value,
+                Err(::bitbuffer::BitError::Utf8Error(err)) => {
+                    ::std::string::String::from_utf8_lossy(err.as_bytes()).into_owned()
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Generate the expression reading `count` elements into a `Vec`, using `size` as the size of every element
+fn read_count_value(span: Span, count: TokenStream, size: Option<TokenStream>) -> TokenStream {
+    match size {
+        Some(size) => quote_spanned! { span =>
+            {
+                let _count: usize = #count;
+                let _size: usize = #size;
+                stream.read_sized_vec(_count, _size)?
+            }
+        },
+        None => quote_spanned! { span =>
+            {
+                let _count: usize = #count;
+                stream.read_sized(_count)?
+            }
+        },
+    }
+}
+
+/// Generate the expression reading a fixed length string field with the `str_mode` attribute
+fn read_string_mode_value(f: &Field, size: Option<TokenStream>, has_count: bool) -> TokenStream {
+    let span = f.span();
+    let mode = get_attribute_value::<String>(&f.attrs, &["str_mode"]);
+    let mode = match mode.as_deref() {
+        Some(mode @ ("NullTerminated" | "FixedKeepNulls" | "FixedTrimTrailingNulls")) => {
+            Ident::new(mode, span)
+        }
+        _ => {
+            return quote_spanned! { span =>
+                compile_error!("expected `#[str_mode = \"NullTerminated\"]`, `#[str_mode = \"FixedKeepNulls\"]` or `#[str_mode = \"FixedTrimTrailingNulls\"]`")
+            }
+        }
+    };
+    match size {
+        Some(size) if !has_count => quote_spanned! { span =>
+            {
+                let _size: usize = #size;
+                stream.read_string_mode(_size, ::bitbuffer::StringMode::#mode)?
+            }
+        },
+        _ => quote_spanned! { span =>
+            compile_error!("the `str_mode` attribute requires the `size` attribute and can't be combined with `count`")
+        },
+    }
+}
+
+/// Wrap the expression reading a field to use the default value when the stream ends before the field
+///
+/// The rest of the stream is skipped, so any following fields are also set to their default
+fn read_or_default(span: Span, value: TokenStream, default: Option<Expr>) -> TokenStream {
+    let default = default
+        .map(|default| quote_spanned! { span => #default })
+        .unwrap_or_else(|| quote_spanned! { span => ::std::default::Default::default() });
+    quote_spanned! { span =>
+        {
+            #[allow(clippy::needless_question_mark)]
+            let __bitbuffer_result = (|| -> ::bitbuffer::Result<_> { Ok(#value) })();
+            match __bitbuffer_result {
+                Ok(value) => value,
+                Err(::bitbuffer::BitError::NotEnoughData { .. }) => {
+                    stream.set_pos(stream.bit_len())?;
+                    #default
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Generate the expression reading a field of a struct deriving `BitReadWithContext`
+fn read_context_field_value(
+    f: &Field,
+    size: Option<TokenStream>,
+    count: Option<TokenStream>,
+) -> TokenStream {
+    let span = f.span();
+    let condition = get_attribute_value::<String>(&f.attrs, &["read_if"])
+        .map(|condition| parse_str::<Expr>(&condition).unwrap());
+    let default = get_attribute_value::<String>(&f.attrs, &["default"])
+        .map(|default| parse_str::<Expr>(&default).unwrap());
+    // without a default, fields with a `read_if` condition are read as `Option<T>`
+    let optional = condition.is_some() && default.is_none();
+    let field_type = match (optional, option_type(&f.ty)) {
+        (false, _) => &f.ty,
+        (true, Some(inner)) => inner,
+        (true, None) => {
+            return quote_spanned! { span =>
+                compile_error!("`read_if` can only be used on fields of type `Option<T>` unless a `default` is set")
+            }
+        }
+    };
+    let mut conditions = [("since_version", quote!(>=)), ("until_version", quote!(<=))]
+        .iter()
+        .filter_map(|(attr, op)| {
+            get_attribute_value::<u64>(&f.attrs, &[attr]).map(|version| {
+                quote_spanned! { span =>
+                    ::bitbuffer::VersionedContext::version(ctx) #op #version
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let value = match (size, count) {
+        _ if has_attribute(&f.attrs, "context") => quote_spanned! { span =>
+            stream.read_with_context::<#field_type, _>(ctx)?
+        },
+        (size, count) if has_attribute(&f.attrs, "str_mode") => {
+            read_string_mode_value(f, size, count.is_some())
+        }
+        (size, Some(count)) => read_count_value(span, count, size),
+        (Some(size), None) => quote_spanned! { span =>
+            {
+                let _size: usize = #size;
+                stream.read_sized::<#field_type>(_size)?
+            }
+        },
+        (None, None) => quote_spanned! { span =>
+            stream.read::<#field_type>()?
+        },
+    };
+    let value = match condition {
+        Some(condition) if optional => quote_spanned! { span =>
+            if #condition {
+                Some(#value)
+            } else {
+                None
+            }
+        },
+        Some(condition) if conditions.is_empty() => {
+            conditions.push(quote_spanned! { span => #condition });
+            value
+        }
+        Some(condition) => {
+            conditions.push(quote_spanned! { span => (#condition) });
+            value
+        }
+        None => value,
+    };
+    match (conditions.is_empty(), default) {
+        (true, None) => value,
+        (true, Some(_)) if has_attribute(&f.attrs, "or_default") => value,
+        (true, Some(_)) => quote_spanned! { span =>
+            compile_error!("`default` can only be used on fields with a `read_if`, `since_version`, `until_version` or `or_default` attribute")
+        },
+        (false, default) => {
+            let default = default
+                .map(|default| quote_spanned! { span => #default })
+                .unwrap_or_else(|| quote_spanned! { span => ::std::default::Default::default() });
+            quote_spanned! { span =>
+                if #(#conditions)&&* {
+                    #value
+                } else {
+                    #default
                 }
             }
+        }
+    }
+}
 
-            unsafe fn read_unchecked(stream: &mut ::bitbuffer::BitReadStream<#endianness_ident>#extra_param) -> ::bitbuffer::Result<Self> {
-                #parsed_unchecked
+/// Get `T` for a type written as `Option<T>`
+fn option_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            match args.args.first()? {
+                GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+                _ => None,
             }
+        }
+        _ => None,
+    }
+}
 
-            fn #size_method_name(#size_extra_param) -> Option<usize> {
-                #size
+/// Read a struct field by field directly into a `MaybeUninit<Self>`
+fn parse_in_place(
+    data: Data,
+    struct_name: &Ident,
+    attrs: &[Attribute],
+    unchecked: bool,
+) -> TokenStream {
+    let span = struct_name.span();
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) if !fields.is_empty() => fields,
+        _ => {
+            return quote_spanned! { span =>
+                compile_error!("`read_in_place` can only be used on structs with fields")
             }
         }
     };
+    if attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("repr") && attr.tokens.to_string().contains("packed"))
+    {
+        return quote_spanned! { span =>
+            compile_error!("`read_in_place` can't be used on packed structs")
+        };
+    }
 
-    // panic!("{}", TokenStream::to_string(&expanded));
+    let type_name = struct_name.to_string();
+    let values = read_field_values(&fields, unchecked, true, false);
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, f)| match &f.ident {
+            Some(ident) => (quote!(#ident), ident.clone(), ident.to_string()),
+            None => {
+                let index_member = Index::from(index);
+                (
+                    quote!(#index_member),
+                    Ident::new(&format!("_{}", index), span),
+                    index.to_string(),
+                )
+            }
+        })
+        .collect();
 
-    proc_macro::TokenStream::from(expanded)
+    let definitions = fields
+        .iter()
+        .zip(values)
+        .zip(members.iter())
+        .map(|((f, value), (member, name, field_name))| {
+            let bindings = calc_bindings(&f.attrs, true);
+            quote_spanned! { f.span() =>
+                let __bitbuffer_pos = stream.__begin_field();
+                let __bitbuffer_value = #value;
+                ::bitbuffer::__trace_field!(#type_name, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                ::bitbuffer::__record_field!(stream, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                // safety: the pointer points to the uninitialized field, which is written exactly once
+                unsafe { ::std::ptr::addr_of_mut!((*__bitbuffer_ptr).#member).write(__bitbuffer_value) };
+                __bitbuffer_initialized += 1;
+                // safety: the field has just been initialized and is not written again
+                #[allow(unused_variables)]
+                let #name = unsafe { &*::std::ptr::addr_of!((*__bitbuffer_ptr).#member) };
+                #bindings
+            }
+        });
+    let drops = members.iter().enumerate().map(|(index, (member, _, _))| {
+        quote! {
+            if __bitbuffer_initialized > #index {
+                ::std::ptr::drop_in_place(::std::ptr::addr_of_mut!((*__bitbuffer_ptr).#member));
+            }
+        }
+    });
+
+    quote_spanned! { span =>
+        let mut __bitbuffer_uninit = ::std::mem::MaybeUninit::<Self>::uninit();
+        let __bitbuffer_ptr = __bitbuffer_uninit.as_mut_ptr();
+        let mut __bitbuffer_initialized = 0usize;
+        let __bitbuffer_result = (|| -> ::bitbuffer::Result<()> {
+            #(#definitions)*
+            Ok(())
+        })();
+        match __bitbuffer_result {
+            // safety: every field has been initialized
+            Ok(()) => Ok(unsafe { __bitbuffer_uninit.assume_init() }),
+            Err(e) => {
+                // safety: only the fields that have been initialized are dropped, the struct itself is never dropped
+                unsafe {
+                    #(#drops)*
+                }
+                Err(e)
+            }
+        }
+    }
 }
 
-fn parse(data: Data, struct_name: &Ident, attrs: &[Attribute], unchecked: bool) -> TokenStream {
+fn parse(
+    data: Data,
+    struct_name: &Ident,
+    attrs: &[Attribute],
+    unchecked: bool,
+    context: bool,
+) -> TokenStream {
     let span = struct_name.span();
 
     match data {
         Data::Struct(DataStruct { fields, .. }) => {
-            let values = fields.iter().map(|f| {
-                // Get attributes `#[..]` on each field
-                let size = get_field_size(&f.attrs, f.span(), true);
-                let field_type = &f.ty;
-                let span = f.span();
-                if unchecked {
-                    match size {
-                        Some(size) => {
-                            quote_spanned! { span =>
-                                {
-                                    let _size: usize = #size;
-                                    stream.read_sized_unchecked::<#field_type>(_size)?
-                                }
-                            }
-                        }
-                        None => {
-                            quote_spanned! { span =>
-                                stream.read_unchecked::<#field_type>()?
-                            }
-                        }
-                    }
-                } else {
-                    match size {
-                        Some(size) => {
-                            quote_spanned! { span =>
-                                {
-                                    let _size: usize = #size;
-                                    stream.read_sized::<#field_type>(_size)?
-                                }
-                            }
-                        }
-                        None => {
-                            quote_spanned! { span =>
-                                stream.read::<#field_type>()?
-                            }
-                        }
-                    }
+            let (packed_read, values) = match packed_fields(attrs, &fields, unchecked, context) {
+                Some((read, values)) => (Some(read), values),
+                None => (None, read_field_values(&fields, unchecked, false, context)),
+            };
+            let (padding, trailing, values) = match c_layout(attrs, &fields) {
+                Some(Ok(layout)) => {
+                    let (padding, trailing) =
+                        layout_padding(&layout, |bits| quote!(stream.skip_bits(#bits)?;));
+                    let values = values
+                        .into_iter()
+                        .zip(&layout.bools)
+                        .map(|(value, width)| match width {
+                            Some(1) => quote!(stream.read::<bool>()?),
+                            Some(_) => quote!(stream.read_int::<u8>(8)? != 0),
+                            None => value,
+                        })
+                        .collect();
+                    (padding, trailing, values)
                 }
-            });
+                Some(Err(error)) => return error,
+                None => (vec![None; fields.len()], None, values),
+            };
 
+            let type_name = struct_name.to_string();
             match &fields {
                 Fields::Named(fields) => {
-                    let definitions = fields.named.iter().zip(values).map(|(f, value)| {
+                    let definitions = fields.named.iter().zip(values).zip(padding).map(|((f, value), padding)| {
                         let name = &f.ident;
+                        let field_name = name.as_ref().map(Ident::to_string);
+                        let bindings = calc_bindings(&f.attrs, false);
                         quote_spanned! { f.span() =>
-                            let #name = #value;
+                            #padding
+                            let __bitbuffer_pos = stream.__begin_field();
+                            let __bitbuffer_value = #value;
+                            ::bitbuffer::__trace_field!(#type_name, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                            ::bitbuffer::__record_field!(stream, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                            let #name = __bitbuffer_value;
+                            #bindings
                         }
                     });
                     let struct_definition = fields.named.iter().map(|f| {
@@ -320,68 +1922,147 @@ fn parse(data: Data, struct_name: &Ident, attrs: &[Attribute], unchecked: bool)
                         }
                     });
                     quote_spanned! { span =>
+                        #packed_read
                         #(#definitions)*
+                        #trailing
 
                         Ok(#struct_name {
                             #(#struct_definition)*
                         })
                     }
                 }
-                Fields::Unnamed(_) => quote_spanned! { span =>
-                    Ok(#struct_name(
-                        #(#values ,)*
-                    ))
-                },
+                Fields::Unnamed(fields) => {
+                    let names: Vec<_> = (0..fields.unnamed.len())
+                        .map(|index| Ident::new(&format!("_{}", index), span))
+                        .collect();
+                    let definitions = fields.unnamed.iter().zip(values).zip(padding).enumerate().map(
+                        |(index, ((f, value), padding))| {
+                            let name = &names[index];
+                            let field_name = index.to_string();
+                            let bindings = calc_bindings(&f.attrs, false);
+                            quote_spanned! { f.span() =>
+                                #padding
+                                let __bitbuffer_pos = stream.__begin_field();
+                                let __bitbuffer_value = #value;
+                                ::bitbuffer::__trace_field!(#type_name, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                                ::bitbuffer::__record_field!(stream, #field_name, __bitbuffer_pos, __bitbuffer_value);
+                                let #name = __bitbuffer_value;
+                                #bindings
+                            }
+                        },
+                    );
+                    quote_spanned! { span =>
+                        #packed_read
+                        #(#definitions)*
+                        #trailing
+
+                        Ok(#struct_name(
+                            #(#names ,)*
+                        ))
+                    }
+                }
                 Fields::Unit => quote_spanned! {span=>
                     Ok(#struct_name)
                 },
             }
         }
         Data::Enum(data) => {
-            let discriminant_bits: u64 = get_attribute_value(attrs, &["discriminant_bits"]).expect(
-                "'discriminant_bits' attribute is required when deriving `BinRead` for enums",
-            );
+            let enum_name = Lit::Str(LitStr::new(&struct_name.to_string(), struct_name.span()));
+            let magic_indices = magic_indices(&data);
+            let read_discriminant = match get_attribute_value::<String>(attrs, &["selector"]) {
+                Some(selector) => {
+                    let selector = parse_str::<Expr>(&selector).unwrap();
+                    quote! { let discriminant: usize = (#selector) as usize; }
+                }
+                None if is_magic_enum(&data) => {
+                    let magics = data.variants.iter().filter_map(|variant| {
+                        let magic = variant_magic(variant)?;
+                        Some(quote_spanned! { variant.span() => #magic as &[u8] })
+                    });
+                    let fallback = match data
+                        .variants
+                        .iter()
+                        .position(|variant| variant_magic(variant).is_none())
+                    {
+                        Some(index) => {
+                            let fallback = magic_indices[index];
+                            quote! { #fallback }
+                        }
+                        None => quote! {
+                            return Err(::bitbuffer::BitError::UnmatchedMagic { pos: stream.pos(), enum_name: #enum_name.to_string() })
+                        },
+                    };
+                    quote! {
+                        let discriminant: usize = match stream.read_magic(&[#(#magics),*])? {
+                            Some(index) => index,
+                            None => #fallback,
+                        };
+                    }
+                }
+                None => {
+                    let discriminant_bits = match discriminant_bits(attrs, &data, "BitRead") {
+                        Ok(bits) => bits,
+                        Err(error) => return error,
+                    };
+                    quote! { let discriminant: usize = stream.read_int(#discriminant_bits)?; }
+                }
+            };
 
             let mut last_discriminant = -1;
-            let match_arms = data.variants.iter().map(|variant| {
-                let span = variant.span();
-                let variant_name = &variant.ident;
-                let read_fields = match &variant.fields {
-                    Fields::Unit => quote_spanned! {span=>
-                        #struct_name::#variant_name
-                    },
-                    Fields::Unnamed(f) => {
-                        let size = get_field_size(&variant.attrs, f.span(), true);
-                        match size {
-                            Some(size) => {
+            let match_arms =
+                data.variants
+                    .iter()
+                    .zip(&magic_indices)
+                    .map(|(variant, magic_index)| {
+                        let span = variant.span();
+                        let variant_name = &variant.ident;
+                        let read_fields = match &variant.fields {
+                            Fields::Unit => quote_spanned! {span=>
+                                #struct_name::#variant_name
+                            },
+                            Fields::Unnamed(_)
+                                if context && has_attribute(&variant.attrs, "context") =>
+                            {
                                 quote_spanned! { span =>
-                                    #struct_name::#variant_name({
-                                        let _size:usize = #size;
-                                        stream.read_sized(_size)?
-                                    })
+                                    #struct_name::#variant_name(stream.read_with_context(ctx)?)
                                 }
                             }
-                            None => {
-                                quote_spanned! { span =>
-                                    #struct_name::#variant_name(stream.read()?)
+                            Fields::Unnamed(f) => {
+                                let size =
+                                    get_field_size(variant_size_attrs(variant), f.span(), true);
+                                match size {
+                                    Some(size) => {
+                                        quote_spanned! { span =>
+                                            #struct_name::#variant_name({
+                                                let _size:usize = #size;
+                                                stream.read_sized(_size)?
+                                            })
+                                        }
+                                    }
+                                    None => {
+                                        quote_spanned! { span =>
+                                            #struct_name::#variant_name(stream.read()?)
+                                        }
+                                    }
                                 }
                             }
-                        }
-                    }
-                    _ => unimplemented!(),
-                };
+                            _ => unimplemented!(),
+                        };
 
-                let discriminant_token = get_discriminant_token(variant, &mut last_discriminant);
-                quote_spanned! {span=>
-                    #discriminant_token => #read_fields,
-                }
-            });
+                        let discriminant_token = if is_magic_enum(&data) {
+                            quote_spanned! { span => #magic_index }
+                        } else {
+                            get_discriminant_token(variant, &mut last_discriminant)
+                        };
+                        quote_spanned! {span=>
+                            #discriminant_token => #read_fields,
+                        }
+                    });
 
             let span = data.enum_token.span();
 
-            let enum_name = Lit::Str(LitStr::new(&struct_name.to_string(), struct_name.span()));
             quote_spanned! {span=>
-                let discriminant:usize = stream.read_int(#discriminant_bits as usize)?;
+                #read_discriminant
                 Ok(match discriminant {
                     #(#match_arms)*
                     _ => {
@@ -398,6 +2079,12 @@ fn size(data: Data, struct_name: &Ident, attrs: &[Attribute], has_input_size: bo
     let span = struct_name.span();
 
     match data {
+        Data::Struct(DataStruct { fields, .. }) if has_attribute(attrs, "c_bitfields") => {
+            match c_layout(attrs, &fields) {
+                Some(Ok(CLayout { size, .. })) => quote_spanned! { span => Some(#size) },
+                _ => quote_spanned! { span => None },
+            }
+        }
         Data::Struct(DataStruct { fields, .. }) => {
             let sizes = fields.iter().map(|f| {
                 // Get attributes `#[..]` on each field
@@ -426,26 +2113,34 @@ fn size(data: Data, struct_name: &Ident, attrs: &[Attribute], has_input_size: bo
 
             match &fields {
                 Fields::Named(_) => quote_spanned! { span =>
-                    Some(0usize)#(.and_then(|sum: usize| #sizes.map(|size: usize| sum + size)))*
+                    Some(0usize)#(.and_then(|sum: usize| #sizes.and_then(|size: usize| sum.checked_add(size))))*
                 },
                 Fields::Unnamed(_) => quote_spanned! { span =>
-                    Some(0usize)#(.and_then(|sum: usize| #sizes.map(|size: usize| sum + size)))*
+                    Some(0usize)#(.and_then(|sum: usize| #sizes.and_then(|size: usize| sum.checked_add(size))))*
                 },
                 Fields::Unit => quote_spanned! {span=>
                     Some(0usize)
                 },
             }
         }
+        Data::Enum(data) if is_magic_enum(&data) => match magic_size(&data) {
+            Some(size) => quote_spanned! {span=>
+                Some(#size)
+            },
+            None => quote_spanned! {span=>
+                None
+            },
+        },
         Data::Enum(data) => {
-            let discriminant_bits = get_attribute_value::<u64>(attrs, &["discriminant_bits"])
-                .expect(
-                    "'discriminant_bits' attribute is required when deriving `BinRead` for enums",
-                ) as usize;
-
-            let is_unit = data.variants.iter().all(|variant| match &variant.fields {
-                Fields::Unit => true,
-                _ => false,
-            });
+            let discriminant_bits = match discriminant_bits(attrs, &data, "BitRead") {
+                Ok(bits) => bits,
+                Err(error) => return error,
+            };
+
+            let is_unit = data
+                .variants
+                .iter()
+                .all(|variant| matches!(&variant.fields, Fields::Unit));
 
             if is_unit {
                 quote_spanned! {span=>
@@ -461,6 +2156,91 @@ fn size(data: Data, struct_name: &Ident, attrs: &[Attribute], has_input_size: bo
     }
 }
 
+/// Get the number of bits of the discriminant of an enum, `0` when the discriminant is set by the `selector` attribute
+/// or the variants are selected by their `magic` attribute
+fn discriminant_bits(
+    attrs: &[Attribute],
+    data: &DataEnum,
+    trait_name: &str,
+) -> Result<usize, TokenStream> {
+    let span = data.enum_token.span();
+    if has_attribute(attrs, "selector") {
+        if is_magic_enum(data) {
+            return Err(quote_spanned! { span =>
+                compile_error!("the `selector` and `magic` attributes can't be combined")
+            });
+        }
+        return Ok(0);
+    }
+    if is_magic_enum(data) {
+        return Ok(0);
+    }
+    match get_attribute_value::<u64>(attrs, &["discriminant_bits"]) {
+        Some(bits) => Ok(bits as usize),
+        None => {
+            let message = format!(
+                "'discriminant_bits' or 'selector' attribute is required when deriving `{}` for enums",
+                trait_name
+            );
+            Err(quote_spanned! { span => compile_error!(#message) })
+        }
+    }
+}
+
+/// Whether the variants of the enum are selected by the bytes of their `magic` attribute instead of a discriminant
+fn is_magic_enum(data: &DataEnum) -> bool {
+    data.variants
+        .iter()
+        .any(|variant| has_attribute(&variant.attrs, "magic"))
+}
+
+/// Get the bytes of the `magic` attribute of a variant
+fn variant_magic(variant: &Variant) -> Option<LitByteStr> {
+    match get_attribute_value::<Lit>(&variant.attrs, &["magic"])? {
+        Lit::ByteStr(magic) => Some(magic),
+        Lit::Str(magic) => Some(LitByteStr::new(magic.value().as_bytes(), magic.span())),
+        _ => panic!("magic is required to be a byte string or string literal"),
+    }
+}
+
+/// Get the index used as discriminant for every variant of an enum with `magic` attributes
+///
+/// Variants with a magic are numbered in order, the single variant without magic is used when none of the magics match
+fn magic_indices(data: &DataEnum) -> Vec<usize> {
+    let magic_count = data
+        .variants
+        .iter()
+        .filter(|variant| variant_magic(variant).is_some())
+        .count();
+    if is_magic_enum(data) && data.variants.len() > magic_count + 1 {
+        panic!("at most one variant without `magic` attribute is allowed, to be read when none of the magics match");
+    }
+    let mut next = 0;
+    data.variants
+        .iter()
+        .map(|variant| match variant_magic(variant) {
+            Some(_) => {
+                next += 1;
+                next - 1
+            }
+            None => magic_count,
+        })
+        .collect()
+}
+
+/// The size of an enum with `magic` attributes, if all variants are unit variants with magics of the same length
+fn magic_size(data: &DataEnum) -> Option<usize> {
+    let mut lengths =
+        data.variants
+            .iter()
+            .map(|variant| match (&variant.fields, variant_magic(variant)) {
+                (Fields::Unit, Some(magic)) => Some(magic.value().len() * 8),
+                _ => None,
+            });
+    let first = lengths.next()??;
+    lengths.all(|length| length == Some(first)).then_some(first)
+}
+
 fn get_discriminant_token(variant: &Variant, last_discriminant: &mut isize) -> TokenStream {
     let span = variant.span();
     match Discriminant::from(variant) {
@@ -483,6 +2263,9 @@ fn derive_bitwrite_trait(
     extra_param: Option<TokenStream>,
 ) -> proc_macro::TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    if let Some(error) = union_error(&input) {
+        return error;
+    }
 
     let name = &input.ident;
 
@@ -498,14 +2281,8 @@ fn derive_bitwrite_trait(
     let (impl_generics, _, _) = trait_generics.split_for_impl();
     let span = input.span();
 
-    let size = size(
-        input.data.clone(),
-        &name,
-        &input.attrs,
-        extra_param.is_some(),
-    );
-    let write = write(input.data.clone(), &name, &input.attrs);
-    let parsed_unchecked = parse(input.data.clone(), &name, &input.attrs, true);
+    let write = write(input.data.clone(), name, &input.attrs);
+    let size_errors = size_width_errors(&input.data);
 
     let endianness_placeholder = endianness.unwrap_or_else(|| "_E".to_owned());
     let trait_def_str = format!("::bitbuffer::{}<{}>", trait_name, &endianness_placeholder);
@@ -513,25 +2290,16 @@ fn derive_bitwrite_trait(
 
     let endianness_ident = Ident::new(&endianness_placeholder, span);
 
-    let size_extra_param = if extra_param.is_some() {
-        Some(quote!(input_size: usize))
-    } else {
-        None
-    };
-
-    let extra_param_call = if extra_param.is_some() {
-        Some(quote!(input_size))
-    } else {
-        None
-    };
-
     //
     let expanded = quote! {
+        #[allow(clippy::unnecessary_cast)]
         impl #impl_generics #trait_def for #name #ty_generics #where_clause {
             fn write(&self, stream: &mut ::bitbuffer::BitWriteStream<#endianness_ident>#extra_param) -> ::bitbuffer::Result<()> {
                 #write
             }
         }
+
+        #size_errors
     };
 
     //    panic!("{}", TokenStream::to_string(&expanded));
@@ -547,20 +2315,44 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
             let destructure = fields.iter().map(|field| {
                 let span = field.span();
                 if let Some(name) = &field.ident {
-                    quote_spanned! { span => let #name = &self.#name; }
+                    quote_spanned! { span =>
+                        #[allow(unused_variables)]
+                        let #name = &self.#name;
+                    }
                 } else {
                     quote! {}
                 }
             });
-            let write_field = |index: usize, field: &Field| {
+            let layout = match c_layout(attrs, &fields) {
+                Some(Ok(layout)) => Some(layout),
+                Some(Err(error)) => return error,
+                None => None,
+            };
+            let write_field = |(index, field): (usize, &Field)| {
                 let span = field.span();
                 let size = get_field_size(&field.attrs, span, false);
                 let field_type = &field.ty;
-                let name = field
-                    .ident
-                    .as_ref()
-                    .map(|name| quote_spanned! { span => #name})
-                    .unwrap_or(quote_spanned! { span => 0});
+                let name = match &field.ident {
+                    Some(name) => quote_spanned! { span => #name },
+                    None => {
+                        let index = Index::from(index);
+                        quote_spanned! { span => #index }
+                    }
+                };
+                // `bool` members of structs with `c_bitfields` are a single bit or a full byte
+                match layout.as_ref().and_then(|layout| layout.bools[index]) {
+                    Some(1) => {
+                        return quote_spanned! { span =>
+                            stream.write_bool(self.#name)?;
+                        }
+                    }
+                    Some(_) => {
+                        return quote_spanned! { span =>
+                            stream.write_int(self.#name as u8, 8)?;
+                        }
+                    }
+                    None => {}
+                }
                 match size {
                     Some(size) => {
                         quote_spanned! { span =>
@@ -578,21 +2370,29 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
                 }
             };
 
-            let writes = fields
-                .iter()
-                .enumerate()
-                .map(|(index, field)| write_field(index, field));
+            let (padding, trailing) = match &layout {
+                Some(layout) => {
+                    layout_padding(layout, |bits| quote!(stream.write_int(0u64, #bits)?;))
+                }
+                None => (vec![None; fields.len()], None),
+            };
+            let writes = fields.iter().enumerate().map(write_field);
 
             quote_spanned! { span =>
                 #(#destructure)*
-                #(#writes)*
+                #(#padding #writes)*
+                #trailing
                 Ok(())
             }
         }
         Data::Enum(data) => {
-            let discriminant_bits: u64 = get_attribute_value(attrs, &["discriminant_bits"]).expect(
-                "'discriminant_bits' attribute is required when deriving `BinWrite` for enums",
-            );
+            // with a selector the discriminant is written as part of the containing type
+            let has_selector = has_attribute(attrs, "selector");
+            let is_magic = is_magic_enum(&data);
+            let discriminant_bits = match discriminant_bits(attrs, &data, "BitWrite") {
+                Ok(bits) => bits,
+                Err(error) => return error,
+            };
 
             let mut last_discriminant = -1;
             let match_arms = data.variants.iter().map(|variant| {
@@ -600,17 +2400,34 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
 
                 let span = variant.span();
                 let variant_name = &variant.ident;
+                // the variant without magic is expected to write its own identifying bytes
+                let write_discriminant = if is_magic {
+                    variant_magic(variant).map(|magic| {
+                        quote_spanned! { span =>
+                            stream.write_bytes(#magic)?;
+                        }
+                    })
+                } else if has_selector {
+                    None
+                } else {
+                    Some(quote_spanned! { span =>
+                        stream.write_int(#discriminant_token, #discriminant_bits)?;
+                    })
+                };
                 match &variant.fields {
                     Fields::Unit => quote_spanned! {span=>
-                        #struct_name::#variant_name => stream.write_int(#discriminant_token, #discriminant_bits as usize)
+                        #struct_name::#variant_name => {
+                            #write_discriminant
+                            Ok(())
+                        }
                     },
                     Fields::Unnamed(f) => {
-                        let size = get_field_size(&variant.attrs, f.span(), false);
+                        let size = get_field_size(variant_size_attrs(variant), f.span(), false);
                         match size {
                             Some(size) => {
                                 quote_spanned! { span =>
                                      #struct_name::#variant_name(inner) => {
-                                        stream.write_int(#discriminant_token, #discriminant_bits as usize)?;
+                                        #write_discriminant
                                         stream.write_sized(inner, #size)
                                     }
                                 }
@@ -618,7 +2435,7 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
                             None => {
                                 quote_spanned! { span =>
                                     #struct_name::#variant_name(inner) => {
-                                        stream.write_int(#discriminant_token, #discriminant_bits as usize)?;
+                                        #write_discriminant
                                         stream.write(inner)
                                     }
                                 }
@@ -631,7 +2448,6 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
 
             let span = data.enum_token.span();
 
-            let enum_name = Lit::Str(LitStr::new(&struct_name.to_string(), struct_name.span()));
             quote_spanned! {span=>
                 match self {
                     #(#match_arms),*
@@ -643,7 +2459,10 @@ fn write(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
 }
 
 fn is_const_size(attrs: &[Attribute], has_input_size: bool) -> bool {
-    if get_attribute_value::<Lit>(attrs, &["size_bits"]).is_some() {
+    if get_attribute_value::<Lit>(attrs, &["size_bits"]).is_some()
+        || has_attribute(attrs, "or_default")
+        || has_attribute(attrs, "count")
+    {
         return false;
     }
     get_attribute_value(attrs, &["size"])
@@ -665,7 +2484,7 @@ fn get_field_size(attrs: &[Attribute], span: Span, is_read: bool) -> Option<Toke
             }
             Lit::Str(size_field) => {
                 let size = parse_str::<Expr>(&size_field.value()).unwrap();
-                if !is_read {
+                if !is_read && size_field.value() != "input_size" {
                     // we borrow the field so we need to deref
                     quote_spanned! {span =>
                         *(#size) as usize
@@ -691,6 +2510,165 @@ fn get_field_size(attrs: &[Attribute], span: Span, is_read: bool) -> Option<Toke
         })
 }
 
+/// Get the number of elements to read for a field with the `count` attribute
+fn get_field_count(attrs: &[Attribute], span: Span, borrowed: bool) -> Option<TokenStream> {
+    get_attribute_value::<Lit>(attrs, &["count"]).map(|count_lit| match count_lit {
+        Lit::Int(count) => quote_spanned! { span => #count },
+        Lit::Str(count_field) => {
+            let count = parse_str::<Expr>(&count_field.value()).unwrap();
+            if borrowed && count_field.value() != "input_size" {
+                quote_spanned! { span => *(#count) as usize }
+            } else {
+                quote_spanned! { span => (#count) as usize }
+            }
+        }
+        _ => panic!("Unsupported value for count attribute"),
+    })
+}
+
+/// Get the size of a field while the previously read fields are borrowed
+fn get_borrowed_field_size(attrs: &[Attribute], span: Span) -> Option<TokenStream> {
+    match get_attribute_value::<Lit>(attrs, &["size"]) {
+        Some(Lit::Str(size_field)) if size_field.value() != "input_size" => {
+            let size = parse_str::<Expr>(&size_field.value()).unwrap();
+            Some(quote_spanned! {span =>
+                *(#size) as usize
+            })
+        }
+        _ => get_field_size(attrs, span, true),
+    }
+}
+
+fn schema(data: Data, struct_name: &Ident, attrs: &[Attribute]) -> TokenStream {
+    let span = struct_name.span();
+
+    match data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            let field_schemas = fields.iter().enumerate().map(|(index, f)| {
+                let span = f.span();
+                let name = f
+                    .ident
+                    .as_ref()
+                    .map(Ident::to_string)
+                    .unwrap_or_else(|| index.to_string());
+                let size = size_schema(&f.attrs);
+                let field_type = &f.ty;
+                quote_spanned! { span =>
+                    ::bitbuffer::FieldSchema {
+                        name: #name,
+                        size: #size,
+                        schema: <#field_type as ::bitbuffer::BitSchema>::schema(),
+                    }
+                }
+            });
+            quote_spanned! { span =>
+                ::bitbuffer::Schema::Struct {
+                    type_name: ::std::any::type_name::<Self>(),
+                    fields: vec![#(#field_schemas),*],
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let discriminant_bits = match discriminant_bits(attrs, &data, "BitSchema") {
+                Ok(bits) => bits,
+                Err(error) => return error,
+            };
+            let is_magic = is_magic_enum(&data);
+
+            let mut last_discriminant = -1;
+            let variant_schemas = data.variants.iter().map(|variant| {
+                let span = variant.span();
+                let name = variant.ident.to_string();
+                let magic = match variant_magic(variant) {
+                    Some(magic) => quote_spanned! { span => Some(#magic as &'static [u8]) },
+                    None => quote_spanned! { span => None },
+                };
+                let discriminant = match Discriminant::from(variant) {
+                    _ if is_magic => quote_spanned! { span => None },
+                    Discriminant::Int(discriminant) => {
+                        last_discriminant = discriminant as isize;
+                        quote_spanned! { span => Some(#discriminant) }
+                    }
+                    Discriminant::Wildcard => quote_spanned! { span => None },
+                    Discriminant::Default => {
+                        last_discriminant += 1;
+                        let discriminant = last_discriminant as usize;
+                        quote_spanned! { span => Some(#discriminant) }
+                    }
+                };
+                let field = match &variant.fields {
+                    Fields::Unit => quote_spanned! { span => None },
+                    Fields::Unnamed(f) => {
+                        let field_type = &f.unnamed[0].ty;
+                        quote_spanned! { span =>
+                            Some(<#field_type as ::bitbuffer::BitSchema>::schema())
+                        }
+                    }
+                    Fields::Named(_) => quote_spanned! { span =>
+                        compile_error!("variants with named fields are not supported, use a variant with a single unnamed field")
+                    },
+                };
+                let size = size_schema(variant_size_attrs(variant));
+                quote_spanned! { span =>
+                    ::bitbuffer::VariantSchema {
+                        name: #name,
+                        discriminant: #discriminant,
+                        magic: #magic,
+                        size: #size,
+                        field: #field,
+                    }
+                }
+            });
+
+            quote_spanned! { span =>
+                ::bitbuffer::Schema::Enum {
+                    type_name: ::std::any::type_name::<Self>(),
+                    discriminant_bits: #discriminant_bits,
+                    variants: vec![#(#variant_schemas),*],
+                }
+            }
+        }
+        Data::Union(data) => {
+            let span = data.union_token.span();
+            quote_spanned! { span =>
+                compile_error!("unions are not supported")
+            }
+        }
+    }
+}
+
+fn size_schema(attrs: &[Attribute]) -> TokenStream {
+    // with a `count` the size applies to the elements, the size of the field is the number of elements
+    let size = get_attribute_value::<Lit>(attrs, &["count"])
+        .or_else(|| get_attribute_value::<Lit>(attrs, &["size"]));
+    match size {
+        Some(Lit::Int(size)) => match size.base10_parse::<usize>() {
+            Ok(size) => quote!(::bitbuffer::SizeSchema::Fixed(#size)),
+            Err(error) => error.to_compile_error(),
+        },
+        Some(Lit::Str(size_field)) => quote!(::bitbuffer::SizeSchema::Expression(#size_field)),
+        Some(size) => {
+            let span = size.span();
+            quote_spanned! { span =>
+                compile_error!("Unsupported value for size attribute")
+            }
+        }
+        None => match get_attribute_value::<Lit>(attrs, &["size_bits"]) {
+            Some(Lit::Int(size_bits)) => match size_bits.base10_parse::<usize>() {
+                Ok(size_bits) => quote!(::bitbuffer::SizeSchema::Prefixed(#size_bits)),
+                Err(error) => error.to_compile_error(),
+            },
+            Some(size_bits) => {
+                let span = size_bits.span();
+                quote_spanned! { span =>
+                    compile_error!("Unsupported value for size_bits attribute")
+                }
+            }
+            None => quote!(::bitbuffer::SizeSchema::None),
+        },
+    }
+}
+
 enum Discriminant {
     Int(usize),
     Default,
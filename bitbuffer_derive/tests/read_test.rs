@@ -1,11 +1,17 @@
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::char_lit_as_u8,
+    clippy::disallowed_names,
+    clippy::unusual_byte_groupings
+)]
 #![allow(dead_code)]
 #![allow(unreachable_patterns)]
 
 use bitbuffer::{
-    bit_size_of, bit_size_of_sized, BigEndian, BitReadBuffer, BitReadStream, Endianness,
-    LittleEndian,
+    bit_size_of, bit_size_of_sized, BigEndian, BitError, BitReadBuffer, BitReadStream,
+    BitWriteStream, Endianness, LittleEndian, VersionedContext,
 };
-use bitbuffer_derive::{BitRead, BitReadSized};
+use bitbuffer_derive::{BitRead, BitReadSized, BitReadWithContext, BitWrite, BitWriteSized};
 
 #[derive(BitRead, PartialEq, Debug)]
 struct TestStruct {
@@ -335,3 +341,1443 @@ fn test_bit_size_sized() {
         Some(8 + 8 * 16 + 1)
     );
 }
+
+#[derive(BitRead, PartialEq, Debug)]
+struct TreeNode {
+    #[size = 4]
+    value: Box<u8>,
+    left: Option<Box<TreeNode>>,
+    right: Option<Box<TreeNode>>,
+}
+
+#[test]
+fn test_read_recursive() {
+    // 0001 1 0010 0 0 0 ...
+    let bytes = vec![0b0001_1001, 0b0000_0000];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert_eq!(
+        TreeNode {
+            value: Box::new(1),
+            left: Some(Box::new(TreeNode {
+                value: Box::new(2),
+                left: None,
+                right: None,
+            })),
+            right: None,
+        },
+        stream.read().unwrap()
+    );
+    assert_eq!(12, stream.pos());
+}
+
+#[test]
+fn test_read_depth_limit() {
+    let bytes = vec![0b1111_1111, 0b1111_1111, 0b1111_1111, 0b1111_1111];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_max_depth(4);
+    assert!(matches!(
+        stream.read::<TreeNode>(),
+        Err(BitError::DepthLimitReached { max_depth: 4 })
+    ));
+
+    let bytes = vec![0b0001_0000, 0b0000_0000];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_max_depth(4);
+    assert!(stream.read::<TreeNode>().is_ok());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+#[read_unchecked]
+struct UncheckedStruct {
+    foo: u8,
+    #[size = 4]
+    bar: u8,
+    baz: bool,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[endianness = "BigEndian"]
+#[read_unchecked]
+struct UncheckedStructSized {
+    foo: u8,
+    #[size = "input_size"]
+    bar: u8,
+}
+
+#[test]
+fn test_read_unchecked_method() {
+    let bytes = vec![0b1010_1010, 0b1100_1100, 0, 0];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream
+        .check_read(bit_size_of::<UncheckedStruct>().unwrap())
+        .unwrap();
+    assert_eq!(
+        UncheckedStruct {
+            foo: 0b1010_1010,
+            bar: 0b1100,
+            baz: true,
+        },
+        unsafe { UncheckedStruct::read_unchecked(&mut stream) }.unwrap()
+    );
+    assert_eq!(13, stream.pos());
+
+    stream.set_pos(0).unwrap();
+    assert_eq!(
+        UncheckedStructSized {
+            foo: 0b1010_1010,
+            bar: 0b110,
+        },
+        unsafe { UncheckedStructSized::read_unchecked(&mut stream, 3) }.unwrap()
+    );
+    assert_eq!(11, stream.pos());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+#[specialize_endianness]
+#[read_unchecked]
+struct SpecializedStruct {
+    #[size = 4]
+    foo: u8,
+    bar: u16,
+    #[size = 10]
+    baz: u32,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[specialize_endianness]
+struct SpecializedSized {
+    foo: bool,
+    #[size = "input_size"]
+    bar: u8,
+}
+
+#[test]
+fn test_specialize_endianness() {
+    let bytes = vec![0b1010_1010, 0b1100_1100, 0x12, 0x34, 0x56];
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes.clone(), LittleEndian));
+    assert_eq!(
+        SpecializedStruct {
+            foo: 0b1010,
+            bar: 0b0010_1100_1100_1010,
+            baz: 0b11_0100_0001,
+        },
+        stream.read().unwrap()
+    );
+    assert_eq!(30, stream.pos());
+    stream.set_pos(0).unwrap();
+    assert_eq!(
+        SpecializedSized {
+            foo: false,
+            bar: 0b10101
+        },
+        stream.read_sized(5).unwrap()
+    );
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, BigEndian));
+    assert_eq!(
+        SpecializedStruct {
+            foo: 0b1010,
+            bar: 0b1010_1100_1100_0001,
+            baz: 0b0010_0011_01,
+        },
+        stream.read().unwrap()
+    );
+    stream.set_pos(0).unwrap();
+    assert_eq!(
+        SpecializedStruct {
+            foo: 0b1010,
+            bar: 0b1010_1100_1100_0001,
+            baz: 0b0010_0011_01,
+        },
+        unsafe { SpecializedStruct::read_unchecked(&mut stream) }.unwrap()
+    );
+    stream.set_pos(0).unwrap();
+    assert_eq!(
+        SpecializedSized {
+            foo: true,
+            bar: 0b01010
+        },
+        stream.read_sized(5).unwrap()
+    );
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0xff], BigEndian));
+    assert!(stream.read::<SpecializedStruct>().is_err());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct UsizeStruct {
+    #[size = 4]
+    count: usize,
+    offset: isize,
+}
+
+#[test]
+fn test_read_usize() {
+    let bytes = vec![0b0011_1111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xf0];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!(
+        UsizeStruct {
+            count: 3,
+            offset: -1,
+        },
+        stream.read().unwrap()
+    );
+    assert_eq!(Some(4 + 64), bit_size_of::<UsizeStruct>());
+}
+
+#[derive(BitRead, bitbuffer_derive::BitSchema)]
+#[discriminant_bits = 2]
+enum SchemaEnum {
+    Empty,
+    #[size = 5]
+    Small(u8),
+    #[discriminant = 3]
+    Flag(bool),
+    #[discriminant = "_"]
+    Other,
+}
+
+#[derive(BitRead, bitbuffer_derive::BitSchema)]
+struct SchemaStruct {
+    #[size = 4]
+    version: u8,
+    kind: SchemaEnum,
+    #[size_bits = 3]
+    name: String,
+    #[size = "version"]
+    values: Vec<u16>,
+    extra: Option<bitbuffer::UInt<6>>,
+}
+
+#[derive(BitRead, bitbuffer_derive::BitSchema)]
+struct SchemaTuple(bool, #[size = 7] u8);
+
+#[test]
+fn test_schema() {
+    use bitbuffer::{BitSchema, FieldSchema, Schema, SizeSchema, VariantSchema};
+
+    fn value(type_name: &'static str, bit_size: Option<usize>) -> Schema {
+        Schema::Value {
+            type_name,
+            bit_size,
+        }
+    }
+
+    let enum_schema = Schema::Enum {
+        type_name: std::any::type_name::<SchemaEnum>(),
+        discriminant_bits: 2,
+        variants: vec![
+            VariantSchema {
+                name: "Empty",
+                discriminant: Some(0),
+                magic: None,
+                size: SizeSchema::None,
+                field: None,
+            },
+            VariantSchema {
+                name: "Small",
+                discriminant: Some(1),
+                magic: None,
+                size: SizeSchema::Fixed(5),
+                field: Some(value("u8", Some(8))),
+            },
+            VariantSchema {
+                name: "Flag",
+                discriminant: Some(3),
+                magic: None,
+                size: SizeSchema::None,
+                field: Some(value("bool", Some(1))),
+            },
+            VariantSchema {
+                name: "Other",
+                discriminant: None,
+                magic: None,
+                size: SizeSchema::None,
+                field: None,
+            },
+        ],
+    };
+    assert_eq!(enum_schema, SchemaEnum::schema());
+
+    assert_eq!(
+        Schema::Struct {
+            type_name: std::any::type_name::<SchemaStruct>(),
+            fields: vec![
+                FieldSchema {
+                    name: "version",
+                    size: SizeSchema::Fixed(4),
+                    schema: value("u8", Some(8)),
+                },
+                FieldSchema {
+                    name: "kind",
+                    size: SizeSchema::None,
+                    schema: enum_schema,
+                },
+                FieldSchema {
+                    name: "name",
+                    size: SizeSchema::Prefixed(3),
+                    schema: value(std::any::type_name::<String>(), None),
+                },
+                FieldSchema {
+                    name: "values",
+                    size: SizeSchema::Expression("version"),
+                    schema: Schema::List(Box::new(value("u16", Some(16)))),
+                },
+                FieldSchema {
+                    name: "extra",
+                    size: SizeSchema::None,
+                    schema: Schema::Optional(Box::new(value(
+                        std::any::type_name::<bitbuffer::UInt<6>>(),
+                        Some(6)
+                    ))),
+                },
+            ],
+        },
+        SchemaStruct::schema()
+    );
+
+    match SchemaTuple::schema() {
+        Schema::Struct { fields, .. } => {
+            assert_eq!(
+                vec!["0", "1"],
+                fields.iter().map(|f| f.name).collect::<Vec<_>>()
+            );
+            assert_eq!(SizeSchema::Fixed(7), fields[1].size);
+        }
+        schema => panic!("unexpected schema {:?}", schema),
+    }
+}
+
+#[derive(bitbuffer_derive::BitReadRepr, PartialEq, Debug)]
+#[repr(u16)]
+enum ReprEnum {
+    Zero = 0,
+    Big = 0x1234,
+}
+
+impl std::convert::TryFrom<u16> for ReprEnum {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Self, ()> {
+        match value {
+            0 => Ok(ReprEnum::Zero),
+            0x1234 => Ok(ReprEnum::Big),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(bitbuffer_derive::BitReadRepr, PartialEq, Debug)]
+#[repr(i8)]
+#[discriminant_bits = 3]
+enum SmallReprEnum {
+    Minus = -1,
+    One = 1,
+}
+
+impl std::convert::TryFrom<i8> for SmallReprEnum {
+    type Error = i8;
+
+    fn try_from(value: i8) -> Result<Self, i8> {
+        match value {
+            -1 => Ok(SmallReprEnum::Minus),
+            1 => Ok(SmallReprEnum::One),
+            _ => Err(value),
+        }
+    }
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct ReprStruct {
+    kind: ReprEnum,
+    small: SmallReprEnum,
+}
+
+#[test]
+fn test_read_repr() {
+    assert_eq!(Some(16), bit_size_of::<ReprEnum>());
+    assert_eq!(Some(3), bit_size_of::<SmallReprEnum>());
+    assert_eq!(Some(19), bit_size_of::<ReprStruct>());
+
+    let bytes = vec![0x12, 0x34, 0b1110_0000, 0, 0x01, 0b0010_0000];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert_eq!(
+        ReprStruct {
+            kind: ReprEnum::Big,
+            small: SmallReprEnum::Minus,
+        },
+        stream.read().unwrap()
+    );
+
+    stream.set_pos(24).unwrap();
+    assert!(matches!(
+        stream.read::<ReprEnum>(),
+        Err(BitError::UnmatchedDiscriminant {
+            discriminant: 1,
+            ..
+        })
+    ));
+    stream.set_pos(40).unwrap();
+    assert_eq!(SmallReprEnum::One, stream.read().unwrap());
+}
+
+thread_local! {
+    static LIVE_TRACKED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[derive(Debug)]
+struct Tracked(u8);
+
+impl<E: Endianness> bitbuffer::BitRead<E> for Tracked {
+    fn read(stream: &mut BitReadStream<E>) -> bitbuffer::Result<Self> {
+        let value = stream.read()?;
+        LIVE_TRACKED.with(|live| live.set(live.get() + 1));
+        Ok(Tracked(value))
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        LIVE_TRACKED.with(|live| live.set(live.get() - 1));
+    }
+}
+
+#[derive(BitRead, Debug)]
+#[read_in_place]
+struct InPlaceStruct {
+    tracked: Tracked,
+    length: u8,
+    #[size = "length"]
+    payload: Vec<u8>,
+    #[size_bits = 4]
+    name: String,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[read_in_place]
+struct InPlaceTuple(#[size = 4] u8, #[size = "input_size"] u8);
+
+#[test]
+fn test_read_in_place() {
+    let bytes = vec![1, 2, 0xaa, 0xbb, 0x16, 0x80];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: InPlaceStruct = stream.read().unwrap();
+    assert_eq!(1, value.tracked.0);
+    assert_eq!(2, value.length);
+    assert_eq!(vec![0xaa, 0xbb], value.payload);
+    assert_eq!("h", value.name);
+    assert_eq!(1, LIVE_TRACKED.with(|live| live.get()));
+    drop(value);
+    assert_eq!(0, LIVE_TRACKED.with(|live| live.get()));
+
+    // the tracked field is dropped when a later field fails to read
+    let buffer = BitReadBuffer::new(vec![1, 10, 0xaa], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert!(matches!(
+        stream.read::<InPlaceStruct>(),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(0, LIVE_TRACKED.with(|live| live.get()));
+
+    let buffer = BitReadBuffer::new(vec![0b1010_0110], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert_eq!(Some(7), bit_size_of_sized::<InPlaceTuple>(3));
+    assert_eq!(InPlaceTuple(0b1010, 0b011), stream.read_sized(3).unwrap());
+}
+
+struct Session {
+    version: u8,
+    id_bits: usize,
+}
+
+impl VersionedContext for Session {
+    fn version(&self) -> u64 {
+        self.version.into()
+    }
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "Session"]
+struct ContextHeader {
+    #[size = "ctx.id_bits"]
+    id: u32,
+    #[read_if = "ctx.version >= 2"]
+    flags: Option<u8>,
+    #[context]
+    body: ContextBody,
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "Session"]
+#[discriminant_bits = 1]
+enum ContextBody {
+    #[size = "ctx.version"]
+    Short(u8),
+    #[context]
+    Nested(ContextPayload),
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "Session"]
+struct ContextPayload(#[read_if = "ctx.version == 1"] Option<bool>, u8);
+
+#[test]
+fn test_read_with_context() {
+    let bytes = vec![0x12, 0x34, 0x56, 0x78];
+    let buffer = BitReadBuffer::new(bytes.clone(), BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let session = Session {
+        version: 2,
+        id_bits: 12,
+    };
+    let header: ContextHeader = stream.read_with_context(&session).unwrap();
+    assert_eq!(
+        ContextHeader {
+            id: 0x123,
+            flags: Some(0x45),
+            body: ContextBody::Short(0b11),
+        },
+        header
+    );
+    assert_eq!(23, stream.pos());
+
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let session = Session {
+        version: 1,
+        id_bits: 3,
+    };
+    let header: ContextHeader = stream.read_with_context(&session).unwrap();
+    assert_eq!(
+        ContextHeader {
+            id: 0,
+            flags: None,
+            body: ContextBody::Nested(ContextPayload(Some(false), 0b0100_0110)),
+        },
+        header
+    );
+    assert_eq!(13, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0x12], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert!(matches!(
+        stream.read_with_context::<ContextHeader, _>(&session),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "u32"]
+struct VersionedPlayer {
+    health: u8,
+    #[since_version = 24]
+    armor: u8,
+    #[until_version = 12]
+    #[size = 4]
+    legacy_flags: u8,
+    #[since_version = 10]
+    #[until_version = 20]
+    #[read_if = "*ctx != 15"]
+    team: Option<u8>,
+}
+
+#[test]
+fn test_read_versioned() {
+    let bytes = vec![100, 50, 0x12, 0x34];
+    let read = |version: u32| {
+        let buffer = BitReadBuffer::new(bytes.clone(), BigEndian);
+        let mut stream = BitReadStream::from(buffer);
+        let player: VersionedPlayer = stream.read_with_context(&version).unwrap();
+        (player, stream.pos())
+    };
+
+    let player = |armor, legacy_flags, team| VersionedPlayer {
+        health: 100,
+        armor,
+        legacy_flags,
+        team,
+    };
+    assert_eq!((player(0, 3, None), 12), read(9));
+    assert_eq!((player(0, 3, Some(0x21)), 20), read(10));
+    assert_eq!((player(0, 3, Some(0x21)), 20), read(12));
+    assert_eq!((player(0, 0, Some(50)), 16), read(13));
+    assert_eq!((player(0, 0, None), 8), read(15));
+    assert_eq!((player(0, 0, Some(50)), 16), read(20));
+    assert_eq!((player(50, 0, None), 16), read(24));
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "Session"]
+struct ContextDefaults {
+    #[size = 4]
+    kind: u8,
+    #[read_if = "kind == 1 || ctx.version > 3"]
+    #[default = "ctx.id_bits as u8"]
+    #[size = 4]
+    extra: u8,
+    #[since_version = 2]
+    #[default = "kind + 1"]
+    #[size = 4]
+    revision: u8,
+}
+
+#[test]
+fn test_read_defaults() {
+    let read = |version: u8, bytes: Vec<u8>| {
+        let buffer = BitReadBuffer::new(bytes, BigEndian);
+        let mut stream = BitReadStream::from(buffer);
+        let session = Session {
+            version,
+            id_bits: 9,
+        };
+        let value: ContextDefaults = stream.read_with_context(&session).unwrap();
+        (value, stream.pos())
+    };
+
+    let defaults = |kind, extra, revision| ContextDefaults {
+        kind,
+        extra,
+        revision,
+    };
+    assert_eq!((defaults(2, 9, 3), 4), read(1, vec![0x2f]));
+    assert_eq!((defaults(1, 0xf, 2), 8), read(1, vec![0x1f]));
+    assert_eq!((defaults(2, 9, 0xf), 8), read(2, vec![0x2f]));
+    assert_eq!((defaults(2, 0xf, 0x3), 12), read(4, vec![0x2f, 0x30]));
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct TruncatedTrailer {
+    length: u8,
+    #[size = "length"]
+    payload: Vec<u8>,
+    #[or_default]
+    #[size = 12]
+    checksum: u16,
+    #[or_default]
+    flag: bool,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[read_in_place]
+struct TruncatedInPlace {
+    #[size = "input_size"]
+    value: u8,
+    #[or_default]
+    #[size_bits = 4]
+    name: String,
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "Session"]
+struct TruncatedContext {
+    #[size = "ctx.id_bits"]
+    id: u16,
+    #[or_default]
+    #[default = "ctx.version"]
+    version: u8,
+}
+
+#[test]
+fn test_read_or_default() {
+    assert_eq!(None, bit_size_of::<TruncatedTrailer>());
+
+    let read = |bytes: Vec<u8>| {
+        let buffer = BitReadBuffer::new(bytes, BigEndian);
+        let mut stream = BitReadStream::from(buffer);
+        let trailer: TruncatedTrailer = stream.read().unwrap();
+        (trailer, stream.pos())
+    };
+    let trailer = |checksum, flag| TruncatedTrailer {
+        length: 1,
+        payload: vec![0xaa],
+        checksum,
+        flag,
+    };
+    assert_eq!((trailer(0x123, true), 29), read(vec![1, 0xaa, 0x12, 0x3f]));
+    assert_eq!((trailer(0x123, false), 29), read(vec![1, 0xaa, 0x12, 0x30]));
+    assert_eq!((trailer(0, false), 24), read(vec![1, 0xaa, 0x12]));
+    assert_eq!((trailer(0, false), 16), read(vec![1, 0xaa]));
+
+    // fields without `or_default` still need to be complete
+    let buffer = BitReadBuffer::new(vec![2, 0xaa], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert!(matches!(
+        stream.read::<TruncatedTrailer>(),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    let buffer = BitReadBuffer::new(vec![0xff, 0x1f], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: TruncatedInPlace = stream.read_sized(8).unwrap();
+    assert_eq!(
+        TruncatedInPlace {
+            value: 0xff,
+            name: String::new()
+        },
+        value
+    );
+    assert_eq!(16, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0xff, 0x1f], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let session = Session {
+        version: 3,
+        id_bits: 12,
+    };
+    let value: TruncatedContext = stream.read_with_context(&session).unwrap();
+    assert_eq!(
+        TruncatedContext {
+            id: 0xff1,
+            version: 3
+        },
+        value
+    );
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct CalcStruct {
+    #[calc(entry_bits = "count as usize * 4")]
+    #[size = 3]
+    count: u8,
+    #[size = "entry_bits"]
+    first: u32,
+    #[calc(rest_bits = "entry_bits - first as usize")]
+    #[size = "entry_bits"]
+    second: u32,
+    #[size = "rest_bits"]
+    rest: u16,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[read_in_place]
+struct CalcInPlace {
+    #[size = "input_size"]
+    #[calc(double = "*length as usize * 2")]
+    length: u8,
+    #[size = "double"]
+    value: u16,
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "u8"]
+struct CalcContext {
+    #[size = 4]
+    #[calc(bits = "(*ctx + flags) as usize")]
+    flags: u8,
+    #[size = "bits"]
+    value: u32,
+}
+
+#[test]
+fn test_read_calc() {
+    let buffer = BitReadBuffer::new(vec![0x60, 0x0a, 0x1f, 0xff, 0x40], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    // entry_bits = 12, rest_bits = 12 - 5 = 7
+    let value: CalcStruct = stream.read().unwrap();
+    assert_eq!(
+        CalcStruct {
+            count: 3,
+            first: 5,
+            second: 0x0ff,
+            rest: 0x7d,
+        },
+        value
+    );
+    assert_eq!(34, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0b0011_1111, 0b1000_0000], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: CalcInPlace = stream.read_sized(4).unwrap();
+    assert_eq!(
+        CalcInPlace {
+            length: 3,
+            value: 0b11_1110
+        },
+        value
+    );
+    assert_eq!(10, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0b0010_1010, 0b1011_0000], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: CalcContext = stream.read_with_context(&3).unwrap();
+    assert_eq!(
+        CalcContext {
+            flags: 2,
+            value: 0b1010_1
+        },
+        value
+    );
+    assert_eq!(9, stream.pos());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct CountEntry {
+    #[size = 3]
+    length: u8,
+    #[size = "length"]
+    bits: Vec<bool>,
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct CountStruct {
+    #[size = 3]
+    count: u8,
+    #[count = "count"]
+    entries: Vec<CountEntry>,
+    #[count = 2]
+    #[size = 5]
+    pairs: Vec<u8>,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[read_in_place]
+struct CountInPlace {
+    #[size = 2]
+    count: u8,
+    #[count = "count"]
+    #[size = "input_size"]
+    values: Vec<u16>,
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "usize"]
+struct CountContext {
+    #[count = "*ctx"]
+    #[size_bits = 2]
+    values: Vec<u8>,
+}
+
+#[test]
+fn test_read_count() {
+    // count 2, entry of 1 bit, entry of 2 bits, pairs of 5 bits
+    let buffer = BitReadBuffer::new(vec![0b0100_0001, 0b1010_1101, 0b1000_0100], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: CountStruct = stream.read().unwrap();
+    assert_eq!(
+        CountStruct {
+            count: 2,
+            entries: vec![
+                CountEntry {
+                    length: 0,
+                    bits: vec![]
+                },
+                CountEntry {
+                    length: 3,
+                    bits: vec![false, true, false]
+                }
+            ],
+            pairs: vec![0b11011, 0b00001],
+        },
+        value
+    );
+    assert_eq!(22, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0b1011_0110, 0b1100_0000], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: CountInPlace = stream.read_sized(3).unwrap();
+    assert_eq!(
+        CountInPlace {
+            count: 2,
+            values: vec![0b110, 0b110]
+        },
+        value
+    );
+    assert_eq!(8, stream.pos());
+
+    // the size prefix is read once for all elements
+    let buffer = BitReadBuffer::new(vec![0b0111_1101], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    let value: CountContext = stream.read_with_context(&2).unwrap();
+    assert_eq!(CountContext { values: vec![1, 1] }, value);
+    assert_eq!(4, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0b1011_0110], BigEndian);
+    let mut stream = BitReadStream::from(buffer);
+    assert!(stream.read_sized::<CountInPlace>(16).is_err());
+}
+
+#[derive(BitReadSized, BitWriteSized, PartialEq, Debug)]
+#[selector = "input_size"]
+enum SelectedPayload {
+    Empty,
+    #[size = 4]
+    Small(u8),
+    #[discriminant = 5]
+    Large(u16),
+}
+
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+struct SelectedMessage {
+    #[size = 3]
+    kind: u8,
+    #[size = "kind"]
+    payload: SelectedPayload,
+}
+
+#[derive(BitReadWithContext, PartialEq, Debug)]
+#[context = "u8"]
+#[selector = "*ctx"]
+enum SelectedContext {
+    #[discriminant = 1]
+    Flag(bool),
+    #[discriminant = 2]
+    Value(u8),
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+#[selector = "input_size"]
+enum SelectedUnit {
+    First,
+    Second,
+}
+
+#[test]
+fn test_read_selector() {
+    fn read_message(bytes: Vec<u8>) -> bitbuffer::Result<(SelectedMessage, usize)> {
+        let mut stream = BitReadStream::from(BitReadBuffer::new(bytes, BigEndian));
+        let message = stream.read()?;
+        Ok((message, stream.pos()))
+    }
+
+    assert_eq!(
+        (
+            SelectedMessage {
+                kind: 0,
+                payload: SelectedPayload::Empty
+            },
+            3
+        ),
+        read_message(vec![0b0000_0000]).unwrap()
+    );
+    assert_eq!(
+        (
+            SelectedMessage {
+                kind: 1,
+                payload: SelectedPayload::Small(0b1010)
+            },
+            7
+        ),
+        read_message(vec![0b0011_0100]).unwrap()
+    );
+    assert_eq!(
+        (
+            SelectedMessage {
+                kind: 5,
+                payload: SelectedPayload::Large(0xffff)
+            },
+            19
+        ),
+        read_message(vec![0b1011_1111, 0xff, 0xff]).unwrap()
+    );
+    assert!(matches!(
+        read_message(vec![0b0100_0000]),
+        Err(BitError::UnmatchedDiscriminant {
+            discriminant: 2,
+            ..
+        })
+    ));
+
+    let mut stream = BitWriteStream::new(BigEndian);
+    stream
+        .write(&SelectedMessage {
+            kind: 1,
+            payload: SelectedPayload::Small(0b1010),
+        })
+        .unwrap();
+    assert_eq!(7, stream.bit_len());
+    assert_eq!(vec![0b0011_0100], stream.finish());
+
+    let mut stream = BitReadStream::from(BitReadBuffer::new(vec![0b1000_0000], BigEndian));
+    let value: SelectedContext = stream.read_with_context(&1).unwrap();
+    assert_eq!(SelectedContext::Flag(true), value);
+    assert_eq!(1, stream.pos());
+
+    assert_eq!(Some(0), bit_size_of_sized::<SelectedUnit>(1));
+    let mut stream = BitReadStream::from(BitReadBuffer::new(vec![], BigEndian));
+    assert_eq!(SelectedUnit::Second, stream.read_sized(1).unwrap());
+}
+
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[read_unchecked]
+struct TypedWidths {
+    kind: bitbuffer::UInt<3>,
+    delta: bitbuffer::Int<5>,
+    flags: (bitbuffer::UInt<2>, bitbuffer::UInt<2>, bitbuffer::UInt<2>),
+    extra: bitbuffer::UBits<4>,
+    offset: bitbuffer::IBits<3>,
+}
+
+#[test]
+fn test_read_typed_widths() {
+    use bitbuffer::{Int, UInt};
+
+    // the widths of the field types are part of the fixed size, no size attributes needed
+    assert_eq!(Some(3 + 5 + 3 * 2 + 4 + 3), bit_size_of::<TypedWidths>());
+
+    let value = TypedWidths {
+        kind: UInt::new(5).unwrap(),
+        delta: Int::new(-9).unwrap(),
+        flags: (UInt::new(1).unwrap(), UInt::new(3).unwrap(), UInt::MIN),
+        extra: UInt::new(11).unwrap(),
+        offset: Int::new(-3).unwrap(),
+    };
+    let mut write = BitWriteStream::new(LittleEndian);
+    write.write(&value).unwrap();
+    assert_eq!(21, write.bit_len());
+
+    let mut stream = BitReadStream::from(BitReadBuffer::new(write.finish(), LittleEndian));
+    stream.check_read(21).unwrap();
+    assert_eq!(
+        value,
+        unsafe { TypedWidths::read_unchecked(&mut stream) }.unwrap()
+    );
+    assert_eq!(21, stream.pos());
+}
+
+#[derive(BitRead, BitWrite, bitbuffer_derive::BitSchema, PartialEq, Debug)]
+#[endianness = "LittleEndian"]
+enum MagicChunk {
+    #[magic = b"RIFF"]
+    Riff(u32),
+    #[magic = b"LISTX"]
+    ListExtended(u8),
+    #[magic = "LIST"]
+    List(u16),
+    Unknown,
+}
+
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+enum MagicTag {
+    #[magic = b"\x89PNG"]
+    Png,
+    #[magic = b"GIF8"]
+    Gif,
+}
+
+#[test]
+fn test_read_magic() {
+    use bitbuffer::{BitSchema, Schema};
+
+    fn read_chunk(bytes: &[u8]) -> bitbuffer::Result<(MagicChunk, usize)> {
+        let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.to_vec(), LittleEndian));
+        let chunk = stream.read()?;
+        Ok((chunk, stream.pos()))
+    }
+
+    assert_eq!(
+        (MagicChunk::Riff(1), 64),
+        read_chunk(b"RIFF\x01\0\0\0").unwrap()
+    );
+    assert_eq!(
+        (MagicChunk::ListExtended(2), 48),
+        read_chunk(b"LISTX\x02").unwrap()
+    );
+    assert_eq!(
+        (MagicChunk::List(3), 48),
+        read_chunk(b"LIST\x03\0").unwrap()
+    );
+    // nothing is consumed by the variant without magic
+    assert_eq!((MagicChunk::Unknown, 0), read_chunk(b"JUNK").unwrap());
+    assert_eq!((MagicChunk::Unknown, 0), read_chunk(b"RI").unwrap());
+    assert!(matches!(
+        read_chunk(b"RIFF\x01"),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    for chunk in [
+        MagicChunk::Riff(5),
+        MagicChunk::ListExtended(6),
+        MagicChunk::List(7),
+    ] {
+        let mut write = BitWriteStream::new(LittleEndian);
+        write.write(&chunk).unwrap();
+        let bytes = write.finish();
+        assert_eq!(chunk, read_chunk(&bytes).unwrap().0);
+    }
+
+    assert_eq!(None, bit_size_of::<MagicChunk>());
+    assert_eq!(Some(32), bit_size_of::<MagicTag>());
+
+    let mut stream =
+        BitReadStream::from(BitReadBuffer::new(b"GIF8\x89PNGJPEG".to_vec(), BigEndian));
+    assert_eq!(MagicTag::Gif, stream.read().unwrap());
+    assert_eq!(MagicTag::Png, stream.read().unwrap());
+    assert!(matches!(
+        stream.read::<MagicTag>(),
+        Err(BitError::UnmatchedMagic { pos: 64, .. })
+    ));
+    assert_eq!(64, stream.pos());
+
+    match MagicChunk::schema() {
+        Schema::Enum {
+            discriminant_bits,
+            variants,
+            ..
+        } => {
+            assert_eq!(0, discriminant_bits);
+            let magics: Vec<_> = variants.iter().map(|variant| variant.magic).collect();
+            assert_eq!(
+                vec![
+                    Some(&b"RIFF"[..]),
+                    Some(&b"LISTX"[..]),
+                    Some(&b"LIST"[..]),
+                    None
+                ],
+                magics
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+// struct Header { uint8_t a : 3; uint16_t b : 10; uint8_t c : 4; uint32_t d; };
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[c_bitfields]
+struct SysVHeader {
+    #[size = 3]
+    a: u8,
+    #[size = 10]
+    b: u16,
+    #[size = 4]
+    c: u8,
+    d: u32,
+}
+
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[c_bitfields = "msvc"]
+struct MsvcHeader {
+    #[size = 3]
+    a: u8,
+    #[size = 10]
+    b: u16,
+    #[size = 4]
+    c: u8,
+    d: u32,
+}
+
+// struct Flags { uint32_t kind : 4; bool enabled : 1; int32_t delta : 6; uint8_t tail; };
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[c_bitfields]
+struct SignedFlags(#[size = 4] u32, #[size = 1] bool, #[size = 6] i32, u8);
+
+// struct Entry { bool valid; uint16_t value; bool last; };
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[c_bitfields]
+struct Entry {
+    valid: bool,
+    value: u16,
+    last: bool,
+}
+
+#[test]
+fn test_c_bitfields_sysv() {
+    // as laid out by gcc on x86_64
+    let bytes = vec![0xe5, 0x15, 0x09, 0x00, 0x78, 0x56, 0x34, 0x12];
+    let expected = SysVHeader {
+        a: 5,
+        b: 700,
+        c: 9,
+        d: 0x1234_5678,
+    };
+    assert_eq!(Some(64), bit_size_of::<SysVHeader>());
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.clone(), LittleEndian));
+    assert_eq!(expected, stream.read().unwrap());
+    assert_eq!(64, stream.pos());
+
+    let mut write = BitWriteStream::new(LittleEndian);
+    write.write(&expected).unwrap();
+    assert_eq!(bytes, write.finish());
+}
+
+fn c_bitfields<E: Endianness>(endianness: fn() -> E) {
+    let header = MsvcHeader {
+        a: 5,
+        b: 700,
+        c: 9,
+        d: 0x1234_5678,
+    };
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(5u8, 3).unwrap();
+    write.write_int(0u16, 13).unwrap();
+    write.write_int(700u16, 10).unwrap();
+    write.write_int(0u8, 6).unwrap();
+    write.write_int(9u8, 4).unwrap();
+    write.write_int(0u32, 28).unwrap();
+    write.write_int(0x1234_5678u32, 32).unwrap();
+    let bytes = write.finish();
+    assert_eq!(Some(96), bit_size_of::<MsvcHeader>());
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.clone(), endianness()));
+    assert_eq!(header, stream.read().unwrap());
+    let mut write = BitWriteStream::new(endianness());
+    write.write(&header).unwrap();
+    assert_eq!(bytes, write.finish());
+
+    // kind at 0, enabled at 4, delta at 5, tail aligned to the next byte, padded to 32 bits
+    let flags = SignedFlags(11, true, -20, 200);
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(11u8, 4).unwrap();
+    write.write_bool(true).unwrap();
+    write.write_int(-20i8, 6).unwrap();
+    write.write_int(0u8, 5).unwrap();
+    write.write_int(200u8, 8).unwrap();
+    write.write_int(0u8, 8).unwrap();
+    let bytes = write.finish();
+    assert_eq!(Some(32), bit_size_of::<SignedFlags>());
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.clone(), endianness()));
+    assert_eq!(flags, stream.read().unwrap());
+    assert_eq!(32, stream.pos());
+    let mut write = BitWriteStream::new(endianness());
+    write.write(&flags).unwrap();
+    assert_eq!(bytes, write.finish());
+
+    // the bools are full bytes, value aligned to 16 bits, padded to 48 bits
+    let entry = Entry {
+        valid: true,
+        value: 0x1234,
+        last: false,
+    };
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(1u8, 8).unwrap();
+    write.write_int(0u8, 8).unwrap();
+    write.write_int(0x1234u16, 16).unwrap();
+    write.write_int(0u16, 16).unwrap();
+    let bytes = write.finish();
+    assert_eq!(Some(48), bit_size_of::<Entry>());
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.clone(), endianness()));
+    assert_eq!(entry, stream.read().unwrap());
+    assert_eq!(48, stream.pos());
+    let mut write = BitWriteStream::new(endianness());
+    write.write(&entry).unwrap();
+    assert_eq!(bytes, write.finish());
+
+    // any non zero byte is true
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(0x80u8, 8).unwrap();
+    write.write_int(0u8, 8).unwrap();
+    write.write_int(0x1234u16, 16).unwrap();
+    write.write_int(2u8, 8).unwrap();
+    write.write_int(0u8, 8).unwrap();
+    let mut stream = BitReadStream::from(BitReadBuffer::new(write.finish(), endianness()));
+    let read: Entry = stream.read().unwrap();
+    assert!(read.valid && read.last);
+}
+
+#[test]
+fn test_c_bitfields_le() {
+    c_bitfields(|| LittleEndian);
+}
+
+#[test]
+fn test_c_bitfields_be() {
+    c_bitfields(|| BigEndian);
+}
+
+#[derive(BitRead, BitWrite, PartialEq, Debug)]
+#[discriminant_bits = 2]
+enum FieldSizeEnum {
+    Small(#[size = 3] u8),
+    Signed(#[size = 5] i8),
+    #[size = 4]
+    Variant(u8),
+    Full(u8),
+}
+
+fn field_size_enum<E: Endianness>(endianness: fn() -> E) {
+    let values = vec![
+        FieldSizeEnum::Small(5),
+        FieldSizeEnum::Signed(-7),
+        FieldSizeEnum::Variant(9),
+        FieldSizeEnum::Full(200),
+    ];
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(0u8, 2).unwrap();
+    write.write_int(5u8, 3).unwrap();
+    write.write_int(1u8, 2).unwrap();
+    write.write_int(-7i8, 5).unwrap();
+    write.write_int(2u8, 2).unwrap();
+    write.write_int(9u8, 4).unwrap();
+    write.write_int(3u8, 2).unwrap();
+    write.write_int(200u8, 8).unwrap();
+    let bytes = write.finish();
+
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes.clone(), endianness()));
+    for value in &values {
+        assert_eq!(*value, stream.read().unwrap());
+    }
+    assert_eq!(28, stream.pos());
+
+    let mut write = BitWriteStream::new(endianness());
+    for value in &values {
+        write.write(value).unwrap();
+    }
+    assert_eq!(bytes, write.finish());
+}
+
+#[test]
+fn test_field_size_enum_le() {
+    field_size_enum(|| LittleEndian);
+}
+
+#[test]
+fn test_field_size_enum_be() {
+    field_size_enum(|| BigEndian);
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct StringModes {
+    #[size = 4]
+    #[str_mode = "NullTerminated"]
+    terminated: String,
+    #[size = 4]
+    #[str_mode = "FixedKeepNulls"]
+    kept: String,
+    #[size = 4]
+    #[str_mode = "FixedTrimTrailingNulls"]
+    trimmed: String,
+    len: u8,
+    #[size = "len"]
+    #[str_mode = "FixedKeepNulls"]
+    dynamic: String,
+}
+
+#[test]
+fn test_str_mode() {
+    let bytes = b"a\0bca\0b\0a\0b\0\x02\0\0".to_vec();
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes, LittleEndian));
+    assert_eq!(
+        StringModes {
+            terminated: "a".to_string(),
+            kept: "a\0b\0".to_string(),
+            trimmed: "a\0b".to_string(),
+            len: 2,
+            dynamic: "\0\0".to_string(),
+        },
+        stream.read().unwrap()
+    );
+    assert_eq!(15 * 8, stream.pos());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct LossyStrings {
+    #[lossy]
+    name: String,
+    #[size = 3]
+    #[lossy]
+    fixed: String,
+    #[size = 3]
+    #[str_mode = "NullTerminated"]
+    #[lossy]
+    terminated: String,
+    score: u8,
+}
+
+#[test]
+fn test_lossy() {
+    let bytes = vec![b'a', 0xff, 0, 0xfe, b'b', 0, b'c', 0, 0xff, 12];
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes, LittleEndian));
+    assert_eq!(
+        LossyStrings {
+            name: "a\u{FFFD}".to_string(),
+            fixed: "\u{FFFD}b".to_string(),
+            terminated: "c".to_string(),
+            score: 12,
+        },
+        stream.read().unwrap()
+    );
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct PackedHeader {
+    #[size = 3]
+    kind: u8,
+    active: bool,
+    #[size = 7]
+    delta: i8,
+    #[size = 20]
+    id: u32,
+    signed: i16,
+    #[size = 17]
+    tail: u64,
+}
+
+#[derive(BitReadSized, PartialEq, Debug)]
+struct PackedPair(#[size = 5] i32, u8);
+
+fn packed_fields<E: Endianness>(endianness: fn() -> E) {
+    let header = PackedHeader {
+        kind: 5,
+        active: true,
+        delta: -37,
+        id: 0xabcde,
+        signed: -1234,
+        tail: 0x1f0f0,
+    };
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(1u8, 3).unwrap();
+    write.write_int(header.kind, 3).unwrap();
+    write.write_bool(header.active).unwrap();
+    write.write_int(header.delta, 7).unwrap();
+    write.write_int(header.id, 20).unwrap();
+    write.write_int(header.signed, 16).unwrap();
+    write.write_int(header.tail, 17).unwrap();
+    write.write_int(-3i32, 5).unwrap();
+    write.write_int(200u8, 8).unwrap();
+    let bytes = write.finish();
+
+    let mut stream = BitReadStream::from(BitReadBuffer::new(bytes, endianness()));
+    stream.skip_bits(3).unwrap();
+    assert_eq!(header, stream.read().unwrap());
+    assert_eq!(3 + 64, stream.pos());
+    assert_eq!(PackedPair(-3, 200), stream.read_sized(0).unwrap());
+    assert_eq!(3 + 64 + 13, stream.pos());
+    assert!(matches!(
+        stream.read::<PackedHeader>(),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(3 + 64 + 13, stream.pos());
+
+    stream.set_pos(3).unwrap();
+    let (read, tree) = stream.read_instrumented::<PackedHeader>().unwrap();
+    assert_eq!(header, read);
+    assert_eq!(3..67, tree.range);
+    assert_eq!("delta", tree.children[2].name);
+    assert_eq!(7..14, tree.children[2].range);
+    assert_eq!("-37", tree.children[2].value);
+}
+
+#[test]
+fn test_packed_fields_le() {
+    packed_fields(|| LittleEndian);
+}
+
+#[test]
+fn test_packed_fields_be() {
+    packed_fields(|| BigEndian);
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+#[layout_warnings]
+struct WarningFreeLayout {
+    header: PackedHeader,
+    #[size_bits = 8]
+    name: String,
+}
+
+#[test]
+fn test_layout_warnings() {
+    let mut write = BitWriteStream::new(LittleEndian);
+    write.write_int(0u64, 64).unwrap();
+    write.write_int(2u8, 8).unwrap();
+    write.write_bytes(b"hi").unwrap();
+    let mut stream = BitReadStream::from(BitReadBuffer::new(write.finish(), LittleEndian));
+    let layout: WarningFreeLayout = stream.read().unwrap();
+    assert_eq!("hi", layout.name);
+}
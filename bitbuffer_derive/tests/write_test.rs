@@ -1,10 +1,13 @@
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::char_lit_as_u8,
+    clippy::disallowed_names,
+    clippy::unusual_byte_groupings
+)]
 #![allow(dead_code)]
 #![allow(unreachable_patterns)]
 
-use bitbuffer::{
-    bit_size_of, bit_size_of_sized, BigEndian, BitReadBuffer, BitReadStream, BitWrite, Endianness,
-    LittleEndian,
-};
+use bitbuffer::BitWrite;
 
 #[derive(BitWrite)]
 struct TestStruct {
@@ -0,0 +1,45 @@
+#![no_main]
+//! Read a set of derived types from arbitrary buffers
+
+use bitbuffer::{BigEndian, BitRead, BitReadBuffer, BitReadSized, BitReadStream, LittleEndian};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(BitRead, Debug)]
+struct Header {
+    version: u8,
+    #[size = 3]
+    kind: u8,
+    flag: bool,
+    length: u16,
+    #[size = "length"]
+    name: String,
+    values: Option<Box<Header>>,
+}
+
+#[derive(BitRead, Debug)]
+#[discriminant_bits = 2]
+enum Packet {
+    Empty,
+    Header(Header),
+    #[size = 12]
+    Short(i16),
+    Text(String),
+}
+
+#[derive(BitReadSized, Debug)]
+struct Sized {
+    #[size = "input_size"]
+    items: Vec<u16>,
+    tail: f64,
+}
+
+fuzz_target!(|input: (BitReadBuffer<LittleEndian>, BitReadBuffer<BigEndian>, usize)| {
+    let (le, be, size) = input;
+    let mut stream = BitReadStream::new(le);
+    stream.set_max_depth(64);
+    while stream.read::<Packet>().is_ok() {}
+
+    let mut stream = BitReadStream::new(be);
+    stream.set_max_depth(64);
+    let _ = stream.read_sized::<Sized>(size);
+});
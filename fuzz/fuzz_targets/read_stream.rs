@@ -0,0 +1,65 @@
+#![no_main]
+//! Run a sequence of reads, controlled by the start of the input, over the rest of the input
+
+use bitbuffer::{
+    BigEndian, BitReadBuffer, BitReadStream, Endianness, HammingCode, LittleEndian,
+    ManchesterEncoding, NrziEncoding, Parity,
+};
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashMap;
+
+fn run<E: Endianness>(ops: &[u8], bytes: &[u8], endianness: E) {
+    let buffer = BitReadBuffer::new(bytes.to_vec(), endianness);
+    let buffer = match ops.first() {
+        Some(1) => buffer.reverse_bits(),
+        Some(2) => buffer.reverse_bytes(),
+        _ => buffer,
+    };
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_max_depth(64);
+
+    for op in ops.chunks_exact(2) {
+        let param = op[1] as usize;
+        let _ = match op[0] % 20 {
+            0 => stream.read_bool().map(drop),
+            1 => stream.read_int::<u64>(param % 65).map(drop),
+            2 => stream.read_int::<i32>(param % 33).map(drop),
+            3 => stream.read_float::<f32>().map(drop),
+            4 => stream.read_float::<f64>().map(drop),
+            5 => stream.read_bytes(param).map(drop),
+            6 => stream.read_string(None).map(drop),
+            7 => stream.read_string(Some(param)).map(drop),
+            8 => stream
+                .read_bits(param)
+                .and_then(|mut bits| bits.read::<Option<String>>())
+                .map(drop),
+            9 => stream.skip_bits(param),
+            10 => stream.set_pos(param),
+            11 => stream.read_unstuffed(param).map(drop),
+            12 => stream
+                .read_manchester(param, ManchesterEncoding::Ieee)
+                .map(drop),
+            13 => stream
+                .read_nrzi(param, NrziEncoding::Space, param & 1 == 1)
+                .map(drop),
+            14 => stream.read_interleaved(param & 7, param >> 3).map(drop),
+            15 => stream
+                .read_hamming(param & 3, HammingCode::Secded)
+                .map(drop),
+            16 => stream.read_parity(param, Parity::Even).map(drop),
+            17 => stream.read_sized::<Vec<u16>>(param).map(drop),
+            18 => stream.read_sized::<HashMap<u8, String>>(param).map(drop),
+            _ => stream.read_sized::<String>(param).map(drop),
+        };
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.is_empty() {
+        return;
+    }
+    let split = (data[0] as usize).min(data.len() - 1);
+    let (ops, bytes) = data[1..].split_at(split);
+    run(ops, bytes, LittleEndian);
+    run(ops, bytes, BigEndian);
+});
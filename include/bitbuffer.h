@@ -0,0 +1,142 @@
+#ifndef BITBUFFER_H
+#define BITBUFFER_H
+
+/* Warning, this file is autogenerated by cbindgen. Don't modify this manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The endianness of a stream
+ */
+typedef enum BitbufferEndianness {
+  /**
+   * Little endian
+   */
+  BITBUFFER_ENDIANNESS_LITTLE_ENDIAN = 0,
+  /**
+   * Big endian
+   */
+  BITBUFFER_ENDIANNESS_BIG_ENDIAN = 1,
+} BitbufferEndianness;
+
+/**
+ * The result of a call
+ */
+typedef enum BitbufferStatus {
+  /**
+   * The call succeeded
+   */
+  BITBUFFER_STATUS_OK = 0,
+  /**
+   * A null pointer or otherwise invalid argument was provided
+   */
+  BITBUFFER_STATUS_INVALID_ARGUMENT = 1,
+  /**
+   * Not enough bits available in the stream
+   */
+  BITBUFFER_STATUS_NOT_ENOUGH_DATA = 2,
+  /**
+   * More bits were requested than fit in the output type
+   */
+  BITBUFFER_STATUS_TOO_MANY_BITS = 3,
+  /**
+   * The requested position is outside the stream
+   */
+  BITBUFFER_STATUS_INDEX_OUT_OF_BOUNDS = 4,
+  /**
+   * Any other error
+   */
+  BITBUFFER_STATUS_OTHER = 5,
+} BitbufferStatus;
+
+/**
+ * A stream of either endianness
+ */
+typedef struct BitbufferStream BitbufferStream;
+
+/**
+ * Create a stream from `len` bytes at `data`, the bytes are copied into the stream
+ *
+ * Returns null if `data` is null while `len` is not 0.
+ *
+ * # Safety
+ *
+ * `data` needs to point to at least `len` readable bytes.
+ */
+struct BitbufferStream *bitbuffer_stream_new(const uint8_t *data,
+                                             uintptr_t len,
+                                             enum BitbufferEndianness endianness);
+
+/**
+ * Free a stream created by `bitbuffer_stream_new`
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a stream created by `bitbuffer_stream_new` that hasn't been freed yet.
+ */
+void bitbuffer_stream_free(struct BitbufferStream *stream);
+
+/**
+ * Get the position of the stream in bits, returns 0 for a null stream
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream.
+ */
+uintptr_t bitbuffer_stream_pos(const struct BitbufferStream *stream);
+
+/**
+ * Get the number of bits left in the stream, returns 0 for a null stream
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream.
+ */
+uintptr_t bitbuffer_stream_bits_left(const struct BitbufferStream *stream);
+
+/**
+ * Set the position of the stream in bits
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream.
+ */
+enum BitbufferStatus bitbuffer_stream_set_pos(struct BitbufferStream *stream, uintptr_t pos);
+
+/**
+ * Read an unsigned integer of `count` bits, at most 64
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream, `out` needs to be null or valid for writes.
+ */
+enum BitbufferStatus bitbuffer_stream_read_int(struct BitbufferStream *stream,
+                                               uintptr_t count,
+                                               uint64_t *out);
+
+/**
+ * Read a signed integer of `count` bits, at most 64
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream, `out` needs to be null or valid for writes.
+ */
+enum BitbufferStatus bitbuffer_stream_read_signed(struct BitbufferStream *stream,
+                                                  uintptr_t count,
+                                                  int64_t *out);
+
+/**
+ * Read `len` bytes into `out`
+ *
+ * # Safety
+ *
+ * `stream` needs to be null or a valid stream, `out` needs to be null or valid for writing `len` bytes.
+ */
+enum BitbufferStatus bitbuffer_stream_read_bytes(struct BitbufferStream *stream,
+                                                 uint8_t *out,
+                                                 uintptr_t len);
+
+#endif /* BITBUFFER_H */
@@ -0,0 +1,32 @@
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{BitReadBuffer, Endianness};
+
+/// Generates arbitrary bytes, with a bit length that doesn't have to be a multiple of 8
+impl<'a, E: Endianness> Arbitrary<'a> for BitReadBuffer<E> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let unused_bits = u.int_in_range(0..=7)?;
+        let bytes = Vec::<u8>::arbitrary(u)?;
+        Self::from_arbitrary_bytes(bytes, unused_bits)
+    }
+
+    fn arbitrary_take_rest(mut u: Unstructured<'a>) -> Result<Self> {
+        let unused_bits = u.int_in_range(0..=7)?;
+        let bytes = u.take_rest().to_vec();
+        Self::from_arbitrary_bytes(bytes, unused_bits)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (min, _) = Vec::<u8>::size_hint(depth);
+        (min + 1, None)
+    }
+}
+
+impl<E: Endianness> BitReadBuffer<E> {
+    fn from_arbitrary_bytes(bytes: Vec<u8>, unused_bits: usize) -> Result<Self> {
+        let bit_len = (bytes.len() * 8).saturating_sub(unused_bits);
+        BitReadBuffer::try_from_storage(bytes)
+            .and_then(|buffer| buffer.get_sub_buffer(bit_len))
+            .map_err(|_| ::arbitrary::Error::IncorrectFormat)
+    }
+}
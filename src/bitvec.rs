@@ -0,0 +1,39 @@
+use crate::{BitReadSized, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+use ::bitvec::order::BitOrder;
+use ::bitvec::store::BitStore;
+use ::bitvec::vec::BitVec;
+
+/// Reads `size` bits, the first bit read from the stream is stored at index `0`
+impl<E: Endianness, T: BitStore, O: BitOrder> BitReadSized<E> for BitVec<T, O> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        stream.check_read(size)?;
+        let mut bits = BitVec::with_capacity(size);
+        stream.read_bits_with(size, |bit| bits.push(bit))?;
+        Ok(bits)
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        Some(size)
+    }
+}
+
+/// Writes every bit, starting with the bit at index `0`
+impl<E: Endianness, T: BitStore, O: BitOrder> BitWrite<E> for BitVec<T, O> {
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        for chunk in self.chunks(64) {
+            let word = chunk
+                .iter()
+                .by_vals()
+                .enumerate()
+                .filter(|(_, bit)| *bit)
+                .fold(0u64, |word, (i, _)| {
+                    let shift = if E::is_le() { i } else { chunk.len() - 1 - i };
+                    word | (1 << shift)
+                });
+            stream.write_int(word, chunk.len())?;
+        }
+        Ok(())
+    }
+}
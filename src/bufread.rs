@@ -0,0 +1,310 @@
+use std::io::{self, BufRead};
+
+use crate::{BitError, BitRead, BitReadBuffer, BitReadSized, BitReadStream, Endianness, Result};
+
+/// Stream that reads bits from a [`BufRead`] while only keeping a bounded lookahead window in memory
+///
+/// Values are read from the window using the normal [`BitRead`] implementations, the window is refilled from the
+/// reader when it runs low. Every value that is read or peeked at needs to fit in the window, reading larger values
+/// fails with [`ReadError::WindowExceeded`], which allows parsing arbitrarily large inputs in constant memory.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitRead, BufReadStream, LittleEndian, Result};
+/// use std::io::Cursor;
+///
+/// #[derive(BitRead, Debug, PartialEq)]
+/// struct Record {
+///     #[size = 4]
+///     kind: u8,
+///     #[size = 12]
+///     value: u16,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let reader = Cursor::new(vec![0x21, 0x43, 0x65, 0x87]);
+/// let mut stream = BufReadStream::new(reader, 16, LittleEndian);
+/// let kind: u8 = stream.peek_int(4)?;
+/// assert_eq!(kind, 1);
+/// let record: Record = stream.read()?;
+/// assert_eq!(record, Record { kind: 1, value: 0x432 });
+/// let record: Record = stream.read()?;
+/// assert_eq!(record, Record { kind: 5, value: 0x876 });
+/// assert!(stream.at_end()?);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+/// [`BitRead`]: trait.BitRead.html
+/// [`ReadError::WindowExceeded`]: enum.ReadError.html#variant.WindowExceeded
+pub struct BufReadStream<R, E>
+where
+    R: BufRead,
+    E: Endianness,
+{
+    reader: R,
+    window_bytes: usize,
+    /// the bytes of the window
+    window: BitReadBuffer<E>,
+    /// position in the window
+    offset: usize,
+    /// number of bits that have been dropped from the start of the window
+    dropped: usize,
+    eof: bool,
+    /// error from the reader that is reported once the window runs out of data
+    error: Option<io::Error>,
+}
+
+impl<R, E> BufReadStream<R, E>
+where
+    R: BufRead,
+    E: Endianness,
+{
+    /// Create a new stream reading from `reader` with a lookahead window of `window_bytes` bytes
+    ///
+    /// The window is at least one byte.
+    pub fn new(reader: R, window_bytes: usize, _endianness: E) -> Self {
+        BufReadStream {
+            reader,
+            window_bytes: window_bytes.clamp(1, BitReadBuffer::<E>::MAX_BYTE_LEN),
+            window: BitReadBuffer::from(Vec::new()),
+            offset: 0,
+            dropped: 0,
+            eof: false,
+            error: None,
+        }
+    }
+
+    /// The number of bits read from the stream
+    pub fn pos(&self) -> usize {
+        self.dropped + self.offset
+    }
+
+    /// The size of the lookahead window in bytes
+    pub fn window_bytes(&self) -> usize {
+        self.window_bytes
+    }
+
+    /// Get the underlying reader, any data in the lookahead window is lost
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Drop the read bytes from the window and fill it from the reader until it contains `window_bytes` bytes
+    ///
+    /// An error from the reader is kept until the bytes read before it are used up, it is only returned when `needed`
+    /// is set and no new bytes could be added to the window.
+    fn refill(&mut self, needed: bool) -> Result<()> {
+        let start = self.offset / 8;
+        let window_len = self.window.bit_len() / 8;
+        let mut bytes = self.window.read_bytes(start * 8, window_len - start)?;
+        let kept = bytes.len();
+        while bytes.len() < self.window_bytes && !self.eof && self.error.is_none() {
+            let available = match self.reader.fill_buf() {
+                Ok(available) => available,
+                Err(e) => {
+                    self.error = Some(e);
+                    break;
+                }
+            };
+            if available.is_empty() {
+                self.eof = true;
+                break;
+            }
+            let count = available.len().min(self.window_bytes - bytes.len());
+            bytes.extend_from_slice(&available[..count]);
+            self.reader.consume(count);
+        }
+        let added = bytes.len() > kept;
+        self.window = BitReadBuffer::try_from_storage(bytes)?;
+        self.dropped += start * 8;
+        self.offset -= start * 8;
+        match self.error.take() {
+            Some(error) if needed && !added => Err(error.into()),
+            error => {
+                self.error = error;
+                Ok(())
+            }
+        }
+    }
+
+    /// Run `read` on the window, advancing the stream with the bits read if `advance` is set
+    fn read_window<T, F>(&mut self, advance: bool, read: F) -> Result<T>
+    where
+        F: Fn(&mut BitReadStream<E>) -> Result<T>,
+    {
+        // refill early so reads don't have to be retried when the window is still mostly full
+        if !self.eof && self.window.bit_len() - self.offset < self.window_bytes * 4 {
+            self.refill(false)?;
+        }
+        let mut refilled = false;
+        loop {
+            let mut stream = BitReadStream::new(self.window.clone());
+            stream.set_pos(self.offset)?;
+            match read(&mut stream) {
+                Ok(value) => {
+                    if advance {
+                        self.offset = stream.pos();
+                    }
+                    return Ok(value);
+                }
+                Err(BitError::NotEnoughData { .. }) if !self.eof && !refilled => {
+                    self.refill(true)?;
+                    refilled = true;
+                }
+                Err(BitError::NotEnoughData { .. }) if !self.eof => {
+                    // the window couldn't be filled far enough because of an error from the reader
+                    if let Some(error) = self.error.take() {
+                        return Err(error.into());
+                    }
+                    return Err(BitError::WindowExceeded {
+                        pos: self.pos(),
+                        window_bytes: self.window_bytes,
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Read a value based on the provided type
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the reader
+    /// - [`ReadError::WindowExceeded`]: the value doesn't fit in the lookahead window
+    /// - [`ReadError::Io`]: reading from the reader failed
+    /// - any error from reading the value
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::WindowExceeded`]: enum.ReadError.html#variant.WindowExceeded
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    pub fn read<T: BitRead<E>>(&mut self) -> Result<T> {
+        self.read_window(true, |stream| stream.read())
+    }
+
+    /// Read a value based on the provided type and size
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn read_sized<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
+        self.read_window(true, |stream| stream.read_sized(size))
+    }
+
+    /// Read a value without advancing the stream
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn peek<T: BitRead<E>>(&mut self) -> Result<T> {
+        self.read_window(false, |stream| stream.read())
+    }
+
+    /// Read a value based on the provided type and size without advancing the stream
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn peek_sized<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
+        self.read_window(false, |stream| stream.read_sized(size))
+    }
+
+    /// Read a single bit as boolean
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn read_bool(&mut self) -> Result<bool> {
+        self.read()
+    }
+
+    /// Read `count` bits as an integer
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn read_int<T>(&mut self, count: usize) -> Result<T>
+    where
+        T: BitReadSized<E>,
+    {
+        self.read_sized(count)
+    }
+
+    /// Read `count` bits as an integer without advancing the stream
+    ///
+    /// See [`read`] for the possible errors.
+    ///
+    /// [`read`]: #method.read
+    pub fn peek_int<T>(&mut self, count: usize) -> Result<T>
+    where
+        T: BitReadSized<E>,
+    {
+        self.peek_sized(count)
+    }
+
+    /// Skip `count` bits
+    ///
+    /// Unlike reads, skips are not limited by the size of the lookahead window.
+    /// If the reader ends before all bits are skipped, the stream is left at the end of the data.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the reader
+    /// - [`ReadError::Io`]: reading from the reader failed
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    pub fn skip_bits(&mut self, mut count: usize) -> Result<()> {
+        let requested = count;
+        loop {
+            let available = self.window.bit_len() - self.offset;
+            if count <= available {
+                self.offset += count;
+                return Ok(());
+            }
+            self.offset += available;
+            if self.eof {
+                return Err(BitError::NotEnoughData {
+                    requested,
+                    bits_left: requested - count + available,
+                });
+            }
+            count -= available;
+            self.refill(true)?;
+        }
+    }
+
+    /// Check if all data from the reader has been read
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::Io`]: reading from the reader failed
+    ///
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    pub fn at_end(&mut self) -> Result<bool> {
+        if self.offset == self.window.bit_len() && !self.eof {
+            self.refill(true)?;
+        }
+        Ok(self.eof && self.offset == self.window.bit_len())
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Create a stream reading from a [`BufRead`] with a lookahead window of `window_bytes` bytes
+    ///
+    /// See [`BufReadStream`] for details.
+    ///
+    /// [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+    /// [`BufReadStream`]: struct.BufReadStream.html
+    pub fn from_bufread<R: BufRead>(
+        reader: R,
+        window_bytes: usize,
+        endianness: E,
+    ) -> BufReadStream<R, E> {
+        BufReadStream::new(reader, window_bytes, endianness)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{BitReadBuffer, BitReadStream, BitWriteStream, Endianness, Result};
+
+/// Build a new buffer by concatenating buffers and streams at arbitrary bit boundaries
+///
+/// The parts are appended one after the other without any padding between them, even when the
+/// parts don't end on a byte boundary. This can be used to reassemble messages that are split
+/// into fragments of arbitrary bit length.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitBufferBuilder, BitReadBuffer, BitReadStream, LittleEndian, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// let first = BitReadBuffer::new(vec![0b0000_0101], LittleEndian);
+/// let mut second = BitReadStream::new(BitReadBuffer::new(vec![0b1111_0011], LittleEndian));
+///
+/// let mut builder = BitBufferBuilder::new(LittleEndian);
+/// builder.push_buffer(&first.sub_buffer(0..3)?);
+/// builder.push_bits(&mut second, 5)?;
+/// let buffer = builder.finish();
+///
+/// assert_eq!(buffer.bit_len(), 8);
+/// assert_eq!(buffer.read_int::<u8>(0, 8)?, 0b1001_1101);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub struct BitBufferBuilder<E: Endianness> {
+    stream: BitWriteStream<E>,
+}
+
+impl<E: Endianness> BitBufferBuilder<E> {
+    /// Create a new empty builder
+    pub fn new(endianness: E) -> Self {
+        BitBufferBuilder {
+            stream: BitWriteStream::new(endianness),
+        }
+    }
+
+    /// The number of bits added to the builder
+    pub fn bit_len(&self) -> usize {
+        self.stream.bit_len()
+    }
+
+    /// Append all bits of a buffer
+    pub fn push_buffer(&mut self, buffer: &BitReadBuffer<E>) {
+        self.stream.write_buffer_bits(buffer, 0, buffer.bit_len());
+    }
+
+    /// Append the bits left in a stream, without changing the position of the stream
+    pub fn push_stream(&mut self, stream: &BitReadStream<E>) {
+        let (buffer, pos) = stream.buffer_pos();
+        self.stream
+            .write_buffer_bits(buffer, pos, stream.bits_left());
+    }
+
+    /// Read `count` bits from a stream and append them
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn push_bits(&mut self, stream: &mut BitReadStream<E>, count: usize) -> Result<()> {
+        let bits = stream.read_bits(count)?;
+        self.push_stream(&bits);
+        Ok(())
+    }
+
+    /// Create a buffer containing all appended bits
+    pub fn finish(self) -> BitReadBuffer<E> {
+        let bit_len = self.stream.bit_len();
+        let mut buffer = BitReadBuffer::from(self.stream.finish());
+        buffer.truncate(bit_len);
+        buffer
+    }
+}
@@ -0,0 +1,225 @@
+//! C api for reading streams, requires the `capi` feature
+//!
+//! The functions operate on an opaque `BitbufferStream` created by [`bitbuffer_stream_new`] which needs to be
+//! freed with [`bitbuffer_stream_free`]. A C header for the api is available at `include/bitbuffer.h`,
+//! generated using cbindgen with the `cbindgen.toml` in the repository.
+//!
+//! Reading functions return a [`BitbufferStatus`] and write the read value into the provided output pointer,
+//! on error the position of the stream is left unchanged.
+//!
+//! A static library for linking into C or C++ projects can be built using
+//! `cargo rustc --release --features capi --crate-type staticlib`.
+//!
+//! [`bitbuffer_stream_new`]: fn.bitbuffer_stream_new.html
+//! [`bitbuffer_stream_free`]: fn.bitbuffer_stream_free.html
+//! [`BitbufferStatus`]: enum.BitbufferStatus.html
+
+use crate::{BigEndian, BitError, BitReadBuffer, BitReadStream, LittleEndian, Result};
+use std::slice;
+
+/// The endianness of a stream
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitbufferEndianness {
+    /// Little endian
+    LittleEndian = 0,
+    /// Big endian
+    BigEndian = 1,
+}
+
+/// The result of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitbufferStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A null pointer or otherwise invalid argument was provided
+    InvalidArgument = 1,
+    /// Not enough bits available in the stream
+    NotEnoughData = 2,
+    /// More bits were requested than fit in the output type
+    TooManyBits = 3,
+    /// The requested position is outside the stream
+    IndexOutOfBounds = 4,
+    /// Any other error
+    Other = 5,
+}
+
+impl From<Result<()>> for BitbufferStatus {
+    fn from(result: Result<()>) -> Self {
+        match result {
+            Ok(()) => BitbufferStatus::Ok,
+            Err(BitError::NotEnoughData { .. }) => BitbufferStatus::NotEnoughData,
+            Err(BitError::TooManyBits { .. }) => BitbufferStatus::TooManyBits,
+            Err(BitError::IndexOutOfBounds { .. }) => BitbufferStatus::IndexOutOfBounds,
+            Err(_) => BitbufferStatus::Other,
+        }
+    }
+}
+
+/// A stream of either endianness
+pub enum BitbufferStream {
+    /// A little endian stream
+    LittleEndian(BitReadStream<LittleEndian>),
+    /// A big endian stream
+    BigEndian(BitReadStream<BigEndian>),
+}
+
+macro_rules! with_stream {
+    ($stream:expr, |$inner:ident| $body:expr) => {
+        match $stream {
+            BitbufferStream::LittleEndian($inner) => $body,
+            BitbufferStream::BigEndian($inner) => $body,
+        }
+    };
+}
+
+/// Create a stream from `len` bytes at `data`, the bytes are copied into the stream
+///
+/// Returns null if `data` is null while `len` is not 0, or if `len` is larger than the maximum buffer length.
+///
+/// # Safety
+///
+/// `data` needs to point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_new(
+    data: *const u8,
+    len: usize,
+    endianness: BitbufferEndianness,
+) -> *mut BitbufferStream {
+    let bytes = match (data.is_null(), len) {
+        (_, 0) => Vec::new(),
+        (true, _) => return std::ptr::null_mut(),
+        (false, len) => slice::from_raw_parts(data, len).to_vec(),
+    };
+    let stream = match endianness {
+        BitbufferEndianness::LittleEndian => match BitReadBuffer::try_new(bytes, LittleEndian) {
+            Ok(buffer) => BitbufferStream::LittleEndian(BitReadStream::new(buffer)),
+            Err(_) => return std::ptr::null_mut(),
+        },
+        BitbufferEndianness::BigEndian => match BitReadBuffer::try_new(bytes, BigEndian) {
+            Ok(buffer) => BitbufferStream::BigEndian(BitReadStream::new(buffer)),
+            Err(_) => return std::ptr::null_mut(),
+        },
+    };
+    Box::into_raw(Box::new(stream))
+}
+
+/// Free a stream created by `bitbuffer_stream_new`
+///
+/// # Safety
+///
+/// `stream` needs to be null or a stream created by `bitbuffer_stream_new` that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_free(stream: *mut BitbufferStream) {
+    if !stream.is_null() {
+        drop(Box::from_raw(stream));
+    }
+}
+
+/// Get the position of the stream in bits, returns 0 for a null stream
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_pos(stream: *const BitbufferStream) -> usize {
+    match stream.as_ref() {
+        Some(stream) => with_stream!(stream, |inner| inner.pos()),
+        None => 0,
+    }
+}
+
+/// Get the number of bits left in the stream, returns 0 for a null stream
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_bits_left(stream: *const BitbufferStream) -> usize {
+    match stream.as_ref() {
+        Some(stream) => with_stream!(stream, |inner| inner.bits_left()),
+        None => 0,
+    }
+}
+
+/// Set the position of the stream in bits
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_set_pos(
+    stream: *mut BitbufferStream,
+    pos: usize,
+) -> BitbufferStatus {
+    match stream.as_mut() {
+        Some(stream) => with_stream!(stream, |inner| inner.set_pos(pos)).into(),
+        None => BitbufferStatus::InvalidArgument,
+    }
+}
+
+/// Read an unsigned integer of `count` bits, at most 64
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream, `out` needs to be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_read_int(
+    stream: *mut BitbufferStream,
+    count: usize,
+    out: *mut u64,
+) -> BitbufferStatus {
+    match (stream.as_mut(), out.as_mut()) {
+        (Some(stream), Some(out)) => with_stream!(stream, |inner| inner
+            .read_int(count)
+            .map(|value| *out = value))
+        .into(),
+        _ => BitbufferStatus::InvalidArgument,
+    }
+}
+
+/// Read a signed integer of `count` bits, at most 64
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream, `out` needs to be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_read_signed(
+    stream: *mut BitbufferStream,
+    count: usize,
+    out: *mut i64,
+) -> BitbufferStatus {
+    match (stream.as_mut(), out.as_mut()) {
+        (Some(stream), Some(out)) => with_stream!(stream, |inner| inner
+            .read_int(count)
+            .map(|value| *out = value))
+        .into(),
+        _ => BitbufferStatus::InvalidArgument,
+    }
+}
+
+/// Read `len` bytes into `out`
+///
+/// # Safety
+///
+/// `stream` needs to be null or a valid stream, `out` needs to be null or valid for writing `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitbuffer_stream_read_bytes(
+    stream: *mut BitbufferStream,
+    out: *mut u8,
+    len: usize,
+) -> BitbufferStatus {
+    if out.is_null() && len > 0 {
+        return BitbufferStatus::InvalidArgument;
+    }
+    match stream.as_mut() {
+        Some(stream) => with_stream!(stream, |inner| inner.read_bytes(len).map(|bytes| {
+            if len > 0 {
+                slice::from_raw_parts_mut(out, len).copy_from_slice(&bytes)
+            }
+        }))
+        .into(),
+        None => BitbufferStatus::InvalidArgument,
+    }
+}
@@ -0,0 +1,130 @@
+//! Support for the `gzip` and `zstd` features, reading compressed data
+
+use std::io::{BufReader, Read};
+
+use crate::{BitReadBuffer, BitReadStream, BufReadStream, Endianness, Result};
+
+#[cfg(feature = "gzip")]
+use flate2::read::MultiGzDecoder;
+
+/// Read all bytes from a decompressing reader into a buffer
+fn read_to_buffer<R: Read, E: Endianness>(mut reader: R) -> Result<BitReadBuffer<E>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    BitReadBuffer::try_from_storage(bytes)
+}
+
+impl<E: Endianness> BitReadBuffer<E> {
+    /// Create a new buffer from gzip compressed data, requires the `gzip` feature
+    ///
+    /// All data is decompressed up front, use [`BitReadStream::from_gzip`] to decompress while reading.
+    /// Files consisting of multiple concatenated gzip members are decompressed as a whole.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::Io`]: reading from the reader or decompressing the data failed
+    /// - [`ReadError::BufferTooLarge`]: the decompressed data is larger than [`MAX_BYTE_LEN`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::from_gzip(File::open("demo.dem.gz")?, LittleEndian)?;
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let header: String = stream.read_string(Some(8))?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitReadStream::from_gzip`]: struct.BitReadStream.html#method.from_gzip
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    /// [`ReadError::BufferTooLarge`]: enum.ReadError.html#variant.BufferTooLarge
+    /// [`MAX_BYTE_LEN`]: #associatedconstant.MAX_BYTE_LEN
+    #[cfg(feature = "gzip")]
+    pub fn from_gzip<R: Read>(reader: R, _endianness: E) -> Result<Self> {
+        read_to_buffer(MultiGzDecoder::new(reader))
+    }
+
+    /// Create a new buffer from zstd compressed data, requires the `zstd` feature
+    ///
+    /// All data is decompressed up front, use [`BitReadStream::from_zstd`] to decompress while reading.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::Io`]: reading from the reader or decompressing the data failed
+    /// - [`ReadError::BufferTooLarge`]: the decompressed data is larger than [`MAX_BYTE_LEN`]
+    ///
+    /// [`BitReadStream::from_zstd`]: struct.BitReadStream.html#method.from_zstd
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    /// [`ReadError::BufferTooLarge`]: enum.ReadError.html#variant.BufferTooLarge
+    /// [`MAX_BYTE_LEN`]: #associatedconstant.MAX_BYTE_LEN
+    #[cfg(feature = "zstd")]
+    pub fn from_zstd<R: Read>(reader: R, _endianness: E) -> Result<Self> {
+        read_to_buffer(zstd::stream::read::Decoder::new(reader)?)
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Create a stream that decompresses gzip compressed data while reading, requires the `gzip` feature
+    ///
+    /// Only a lookahead window of `window_bytes` bytes of decompressed data is kept in memory,
+    /// see [`BufReadStream`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bitbuffer::{BitReadStream, LittleEndian, Result};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitReadStream::from_gzip(File::open("demo.dem.gz")?, 4096, LittleEndian);
+    /// let header: String = stream.read_sized(8)?;
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BufReadStream`]: struct.BufReadStream.html
+    #[cfg(feature = "gzip")]
+    pub fn from_gzip<R: Read>(
+        reader: R,
+        window_bytes: usize,
+        endianness: E,
+    ) -> BufReadStream<BufReader<MultiGzDecoder<R>>, E> {
+        BufReadStream::new(
+            BufReader::new(MultiGzDecoder::new(reader)),
+            window_bytes,
+            endianness,
+        )
+    }
+
+    /// Create a stream that decompresses zstd compressed data while reading, requires the `zstd` feature
+    ///
+    /// Only a lookahead window of `window_bytes` bytes of decompressed data is kept in memory,
+    /// see [`BufReadStream`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::Io`]: setting up the decompression failed
+    ///
+    /// [`BufReadStream`]: struct.BufReadStream.html
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    #[cfg(feature = "zstd")]
+    #[allow(clippy::type_complexity)]
+    pub fn from_zstd<R: Read>(
+        reader: R,
+        window_bytes: usize,
+        endianness: E,
+    ) -> Result<BufReadStream<BufReader<zstd::stream::read::Decoder<'static, BufReader<R>>>, E>>
+    {
+        Ok(BufReadStream::new(
+            BufReader::new(zstd::stream::read::Decoder::new(reader)?),
+            window_bytes,
+            endianness,
+        ))
+    }
+}
@@ -0,0 +1,102 @@
+//! Reading from byte slices in `const` contexts
+//!
+//! The functions in this module read bits the same way as [`BitReadBuffer`] does, but operate on plain
+//! byte slices and can be used in `const` items, allowing fixed test vectors and lookup tables to be decoded
+//! at compile time.
+//!
+//! Unlike the methods on [`BitReadBuffer`], these functions panic when reading out of bounds, which results
+//! in a compile error when used in a `const` item.
+//!
+//! ```
+//! use bitbuffer::const_read::{read_bool_be, read_int_be, read_int_le};
+//!
+//! const BYTES: &[u8] = &[0b1011_0101, 0b0110_1010];
+//! const FLAG: bool = read_bool_be(BYTES, 0);
+//! const LOW: u64 = read_int_le(BYTES, 0, 3);
+//! const MIDDLE: u64 = read_int_be(BYTES, 4, 8);
+//!
+//! assert_eq!(FLAG, true);
+//! assert_eq!(LOW, 0b101);
+//! assert_eq!(MIDDLE, 0b0101_0110);
+//! ```
+//!
+//! [`BitReadBuffer`]: ../struct.BitReadBuffer.html
+
+/// Read a single bit from a little endian byte slice
+///
+/// # Panics
+///
+/// Panics if `pos` is outside the slice.
+pub const fn read_bool_le(bytes: &[u8], pos: usize) -> bool {
+    (bytes[pos / 8] >> (pos % 8)) & 1 == 1
+}
+
+/// Read a single bit from a big endian byte slice
+///
+/// # Panics
+///
+/// Panics if `pos` is outside the slice.
+pub const fn read_bool_be(bytes: &[u8], pos: usize) -> bool {
+    (bytes[pos / 8] >> (7 - pos % 8)) & 1 == 1
+}
+
+/// Read an unsigned integer of `count` bits from a little endian byte slice
+///
+/// # Panics
+///
+/// Panics if `count` is larger than 64 or the read bits are outside the slice.
+pub const fn read_int_le(bytes: &[u8], pos: usize, count: usize) -> u64 {
+    assert!(count <= 64, "can't read more than 64 bits into an u64");
+    let mut value = 0;
+    let mut i = 0;
+    while i < count {
+        if read_bool_le(bytes, pos + i) {
+            value |= 1 << i;
+        }
+        i += 1;
+    }
+    value
+}
+
+/// Read an unsigned integer of `count` bits from a big endian byte slice
+///
+/// # Panics
+///
+/// Panics if `count` is larger than 64 or the read bits are outside the slice.
+pub const fn read_int_be(bytes: &[u8], pos: usize, count: usize) -> u64 {
+    assert!(count <= 64, "can't read more than 64 bits into an u64");
+    let mut value = 0;
+    let mut i = 0;
+    while i < count {
+        value = (value << 1) | read_bool_be(bytes, pos + i) as u64;
+        i += 1;
+    }
+    value
+}
+
+/// Read a signed integer of `count` bits from a little endian byte slice
+///
+/// # Panics
+///
+/// Panics if `count` is larger than 64 or the read bits are outside the slice.
+pub const fn read_signed_le(bytes: &[u8], pos: usize, count: usize) -> i64 {
+    sign_extend(read_int_le(bytes, pos, count), count)
+}
+
+/// Read a signed integer of `count` bits from a big endian byte slice
+///
+/// # Panics
+///
+/// Panics if `count` is larger than 64 or the read bits are outside the slice.
+pub const fn read_signed_be(bytes: &[u8], pos: usize, count: usize) -> i64 {
+    sign_extend(read_int_be(bytes, pos, count), count)
+}
+
+const fn sign_extend(value: u64, count: usize) -> i64 {
+    if count == 0 {
+        0
+    } else {
+        let shift = 64 - count;
+        ((value << shift) as i64) >> shift
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{BitRead, BitReadBuffer, BitReadStream, Endianness, Result};
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
+
+/// A node in the tree describing how a value was decoded
+///
+/// Created by [`read_instrumented`], every field read by a derived [`BitRead`] or [`BitReadSized`]
+/// implementation results in a node, fields of nested types are children of the node for the outer field.
+///
+/// [`read_instrumented`]: struct.BitReadStream.html#method.read_instrumented
+/// [`BitRead`]: trait.BitRead.html
+/// [`BitReadSized`]: trait.BitReadSized.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeNode {
+    /// The name of the field, or the name of the type for the root node
+    pub name: String,
+    /// The range of bits the value was read from, relative to the start of the instrumented stream
+    pub range: Range<usize>,
+    /// The `Debug` representation of the value
+    pub value: String,
+    /// Nodes for the fields of the value
+    pub children: Vec<DecodeNode>,
+}
+
+impl DecodeNode {
+    /// Find the deepest node that contains the bit at `pos`
+    pub fn find(&self, pos: usize) -> Option<&DecodeNode> {
+        if !self.range.contains(&pos) {
+            return None;
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find(pos))
+            .or(Some(self))
+    }
+
+    /// Compare the tree with the tree of another read of the same type and list the fields with different values
+    ///
+    /// Fields are compared by name, when the fields of a value differ between both trees, for example because
+    /// different enum variants were read, the value is reported as a whole.
+    pub fn diff(&self, other: &DecodeNode) -> Vec<FieldDiff> {
+        let mut diffs = Vec::new();
+        self.diff_into(other, None, &mut diffs);
+        diffs
+    }
+
+    /// `path` is `None` for the root node, which isn't included in the paths of its fields
+    fn diff_into(&self, other: &DecodeNode, path: Option<&str>, diffs: &mut Vec<FieldDiff>) {
+        let same_fields = !self.children.is_empty()
+            && self.children.len() == other.children.len()
+            && self
+                .children
+                .iter()
+                .zip(other.children.iter())
+                .all(|(left, right)| left.name == right.name);
+        if same_fields {
+            for (left, right) in self.children.iter().zip(other.children.iter()) {
+                let path = match path {
+                    Some(path) => format!("{}.{}", path, left.name),
+                    None => left.name.clone(),
+                };
+                left.diff_into(right, Some(&path), diffs);
+            }
+        } else if self.value != other.value {
+            diffs.push(FieldDiff {
+                path: path.unwrap_or(&self.name).to_string(),
+                left_range: self.range.clone(),
+                left_value: self.value.clone(),
+                right_range: other.range.clone(),
+                right_value: other.value.clone(),
+            });
+        }
+    }
+}
+
+/// A field with a different value in two reads of the same type
+///
+/// Created by [`diff`] or [`DecodeNode::diff`], the `Display` implementation formats the field
+/// as `path: left at bits a..b != right at bits c..d`.
+///
+/// [`diff`]: fn.diff.html
+/// [`DecodeNode::diff`]: struct.DecodeNode.html#method.diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The names of the field and the fields containing it, separated by `.`
+    pub path: String,
+    /// The range of bits the field was read from in the left buffer
+    pub left_range: Range<usize>,
+    /// The `Debug` representation of the value in the left buffer
+    pub left_value: String,
+    /// The range of bits the field was read from in the right buffer
+    pub right_range: Range<usize>,
+    /// The `Debug` representation of the value in the right buffer
+    pub right_value: String,
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} at bits {}..{} != {} at bits {}..{}",
+            self.path,
+            self.left_value,
+            self.left_range.start,
+            self.left_range.end,
+            self.right_value,
+            self.right_range.start,
+            self.right_range.end
+        )
+    }
+}
+
+/// Read a value from both buffers and list the fields with different values
+///
+/// The fields are recorded using [`read_instrumented`], see [`DecodeNode::diff`] for how the reads are compared.
+/// This is useful for comparing the output of a [`BitWrite`] implementation against the original data.
+///
+/// # Errors
+///
+/// Any error that occurred while reading the value from either buffer
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{diff, BitRead, BitReadBuffer, LittleEndian};
+/// # use bitbuffer::Result;
+///
+/// #[derive(BitRead, Debug)]
+/// struct Header {
+///     #[size = 3]
+///     version: u8,
+///     flag: bool,
+///     #[size = 4]
+///     length: u8,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let original = BitReadBuffer::new(vec![0b0101_1101], LittleEndian);
+/// let written = BitReadBuffer::new(vec![0b0111_0101], LittleEndian);
+/// let diffs = diff::<Header, _>(&original, &written)?;
+/// assert_eq!(diffs.len(), 2);
+/// assert_eq!(diffs[0].to_string(), "flag: true at bits 3..4 != false at bits 3..4");
+/// assert_eq!(diffs[1].path, "length");
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`read_instrumented`]: struct.BitReadStream.html#method.read_instrumented
+/// [`DecodeNode::diff`]: struct.DecodeNode.html#method.diff
+/// [`BitWrite`]: trait.BitWrite.html
+pub fn diff<T, E>(left: &BitReadBuffer<E>, right: &BitReadBuffer<E>) -> Result<Vec<FieldDiff>>
+where
+    T: BitRead<E> + Debug,
+    E: Endianness,
+{
+    let (_, left) = BitReadStream::new(left.clone()).read_instrumented::<T>()?;
+    let (_, right) = BitReadStream::new(right.clone()).read_instrumented::<T>()?;
+    Ok(left.diff(&right))
+}
+
+/// Collects the nodes while reading
+#[derive(Debug)]
+pub(crate) struct DecodeRecorder {
+    /// absolute position of the start of the instrumented stream
+    base: usize,
+    /// children of the fields that are currently being read
+    stack: Vec<Vec<DecodeNode>>,
+}
+
+impl DecodeRecorder {
+    pub fn new(base: usize) -> Self {
+        DecodeRecorder {
+            base,
+            stack: vec![Vec::new()],
+        }
+    }
+
+    pub fn begin_field(&mut self) {
+        self.stack.push(Vec::new());
+    }
+
+    /// Finish a field that was read from the absolute range `start..end`
+    pub fn end_field(&mut self, name: &str, start: usize, end: usize, value: &dyn Debug) {
+        let children = self.stack.pop().unwrap_or_default();
+        let node = DecodeNode {
+            name: name.to_string(),
+            range: start.saturating_sub(self.base)..end.saturating_sub(self.base),
+            value: format!("{:?}", value),
+            children,
+        };
+        match self.stack.last_mut() {
+            Some(parent) => parent.push(node),
+            None => self.stack.push(vec![node]),
+        }
+    }
+
+    /// Get the root node for a value read from the absolute range `start..end`
+    pub fn finish(mut self, name: &str, start: usize, end: usize, value: &dyn Debug) -> DecodeNode {
+        // fields that failed to read can leave unfinished levels on the stack
+        self.stack.truncate(1);
+        self.end_field(name, start, end, value);
+        self.stack.pop().unwrap_or_default().pop().unwrap()
+    }
+}
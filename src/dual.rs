@@ -0,0 +1,139 @@
+use crate::{BitReadBuffer, BitReadStream, BitReadStreamRev, Endianness, Result};
+
+/// Two cursors over the same buffer, one reading forwards from the start and one reading backwards from the end
+///
+/// Some compression formats store two bitstreams in a single block, one written from the start and one from the
+/// end, and decode them by alternating between the two. Both cursors share the underlying buffer without copying,
+/// and neither cursor can read past the other: every read only sees the bits between the two cursors.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadBuffer, DualReadStream, LittleEndian, Result};
+///
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![1, 2, 3, 4, 5, 6], LittleEndian);
+/// let mut stream = DualReadStream::new(buffer);
+///
+/// let pairs = stream.alternate(
+///     3,
+///     |forward| forward.read::<u8>(),
+///     |backward| backward.read_int::<u8>(8),
+/// )?;
+/// assert_eq!(pairs, vec![(1, 6), (2, 5), (3, 4)]);
+/// assert_eq!(stream.bits_left(), 0);
+/// assert!(stream.read_forward(|forward| forward.read::<u8>()).is_err());
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DualReadStream<E: Endianness> {
+    forward: BitReadStream<E>,
+    backward: BitReadStreamRev<E>,
+}
+
+impl<E: Endianness> DualReadStream<E> {
+    /// Create a forward cursor at the start and a backward cursor at the end of the buffer
+    pub fn new(buffer: BitReadBuffer<E>) -> Self {
+        DualReadStream {
+            forward: BitReadStream::new(buffer.clone()),
+            backward: BitReadStreamRev::new(buffer),
+        }
+    }
+
+    /// Read from the forward cursor
+    ///
+    /// The stream passed to `read` ends at the position of the backward cursor, the forward cursor is moved by
+    /// the number of bits read from it.
+    ///
+    /// # Errors
+    ///
+    /// - any error returned by `read`, reading past the backward cursor results in [`ReadError::NotEnoughData`]
+    ///
+    /// On error the position of the forward cursor is unchanged.
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_forward<T, F>(&mut self, read: F) -> Result<T>
+    where
+        F: FnOnce(&mut BitReadStream<E>) -> Result<T>,
+    {
+        let mut view = self.forward.clone().read_bits(self.bits_left())?;
+        let result = read(&mut view)?;
+        // the bits read by the view already count towards the read limit
+        self.forward.set_pos(self.forward.pos() + view.pos())?;
+        self.backward.set_start(self.forward.pos());
+        Ok(result)
+    }
+
+    /// Read from the backward cursor
+    ///
+    /// Reads from the stream passed to `read` can't move past the position of the forward cursor.
+    ///
+    /// # Errors
+    ///
+    /// - any error returned by `read`, reading past the forward cursor results in [`ReadError::NotEnoughData`]
+    ///
+    /// On error the position of the backward cursor is unchanged.
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_backward<T, F>(&mut self, read: F) -> Result<T>
+    where
+        F: FnOnce(&mut BitReadStreamRev<E>) -> Result<T>,
+    {
+        let start = self.backward.pos();
+        let result = read(&mut self.backward);
+        if result.is_err() {
+            self.backward.set_pos(start)?;
+        }
+        result
+    }
+
+    /// Alternate between reading from the forward and the backward cursor `count` times
+    ///
+    /// # Errors
+    ///
+    /// - any error returned by `forward` or `backward`
+    pub fn alternate<A, B, F, G>(
+        &mut self,
+        count: usize,
+        mut forward: F,
+        mut backward: G,
+    ) -> Result<Vec<(A, B)>>
+    where
+        F: FnMut(&mut BitReadStream<E>) -> Result<A>,
+        G: FnMut(&mut BitReadStreamRev<E>) -> Result<B>,
+    {
+        let mut values = Vec::with_capacity(count.min(self.bits_left()));
+        for _ in 0..count {
+            let a = self.read_forward(&mut forward)?;
+            let b = self.read_backward(&mut backward)?;
+            values.push((a, b));
+        }
+        Ok(values)
+    }
+
+    /// The position of the forward cursor
+    pub fn forward_pos(&self) -> usize {
+        self.forward.pos()
+    }
+
+    /// The position of the backward cursor
+    pub fn backward_pos(&self) -> usize {
+        self.backward.pos()
+    }
+
+    /// The number of bits between the two cursors
+    pub fn bits_left(&self) -> usize {
+        self.backward.pos() - self.forward.pos()
+    }
+
+    /// Split into the forward and backward cursors
+    ///
+    /// The returned cursors are independent, they are no longer prevented from reading past each other.
+    pub fn into_parts(self) -> (BitReadStream<E>, BitReadStreamRev<E>) {
+        let mut backward = self.backward;
+        backward.set_start(0);
+        (self.forward, backward)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    BitRead, BitReadSized, BitReadStream, BitWrite, BitWriteSized, BitWriteStream, Endianness,
+    Result,
+};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+macro_rules! impl_duration_unit {
+    ($name:ident, $unit:literal, $from:path) => {
+        #[doc = concat!("A duration or timestamp stored as an integer number of ", $unit)]
+        ///
+        /// The wrapper reads and writes the same way as the wrapped integer, so the width can be
+        /// set using the `size` attribute when deriving.
+        ///
+        /// When used as a timestamp, the value is the time since the unix epoch.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name<T>(pub T);
+
+        impl<T: Copy + Into<u64>> $name<T> {
+            /// Get the value as duration
+            #[inline]
+            pub fn duration(&self) -> Duration {
+                $from(self.0.into())
+            }
+
+            /// Get the value as timestamp relative to the unix epoch
+            ///
+            /// Returns `None` if the timestamp can't be represented as `SystemTime`
+            #[inline]
+            pub fn since_unix_epoch(&self) -> Option<SystemTime> {
+                UNIX_EPOCH.checked_add(self.duration())
+            }
+
+            /// Get the value as timestamp relative to the unix epoch
+            ///
+            /// Returns `None` if the timestamp can't be represented as `DateTime`, requires the `chrono` feature.
+            #[cfg(feature = "chrono")]
+            pub fn to_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+                let duration = self.duration();
+                chrono::DateTime::from_timestamp(
+                    i64::try_from(duration.as_secs()).ok()?,
+                    duration.subsec_nanos(),
+                )
+            }
+
+            /// Get the value as timestamp relative to the unix epoch
+            ///
+            /// Returns `None` if the timestamp can't be represented as `OffsetDateTime`, requires the `time` feature.
+            #[cfg(feature = "time")]
+            pub fn to_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+                let duration = time::Duration::try_from(self.duration()).ok()?;
+                time::OffsetDateTime::UNIX_EPOCH.checked_add(duration)
+            }
+        }
+
+        impl<T: Copy + Into<u64>> From<$name<T>> for Duration {
+            #[inline]
+            fn from(value: $name<T>) -> Self {
+                value.duration()
+            }
+        }
+
+        impl<E: Endianness, T: BitRead<E>> BitRead<E> for $name<T> {
+            #[inline]
+            fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+                Ok($name(T::read(stream)?))
+            }
+
+            #[inline]
+            unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+                Ok($name(T::read_unchecked(stream)?))
+            }
+
+            #[inline]
+            fn bit_size() -> Option<usize> {
+                T::bit_size()
+            }
+        }
+
+        impl<E: Endianness, T: BitReadSized<E>> BitReadSized<E> for $name<T> {
+            #[inline]
+            fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+                Ok($name(T::read(stream, size)?))
+            }
+
+            #[inline]
+            unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+                Ok($name(T::read_unchecked(stream, size)?))
+            }
+
+            #[inline]
+            fn bit_size_sized(size: usize) -> Option<usize> {
+                T::bit_size_sized(size)
+            }
+        }
+
+        impl<E: Endianness, T: BitWrite<E>> BitWrite<E> for $name<T> {
+            #[inline]
+            fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+                self.0.write(stream)
+            }
+        }
+
+        impl<E: Endianness, T: BitWriteSized<E>> BitWriteSized<E> for $name<T> {
+            #[inline]
+            fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+                self.0.write(stream, size)
+            }
+        }
+    };
+}
+
+impl_duration_unit!(Seconds, "seconds", Duration::from_secs);
+impl_duration_unit!(Millis, "milliseconds", Duration::from_millis);
+impl_duration_unit!(Micros, "microseconds", Duration::from_micros);
+impl_duration_unit!(Nanos, "nanoseconds", Duration::from_nanos);
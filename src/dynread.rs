@@ -0,0 +1,160 @@
+use std::io::BufRead;
+
+use crate::{BitReadStream, BufReadStream, Endianness, Result};
+
+/// Object safe interface for reading from a stream
+///
+/// The endianness and data source of the stream are erased, so streams of different types can be stored together
+/// or passed through interfaces that can't be generic, like plugin boundaries.
+/// Implemented by [`BitReadStream`] and [`BufReadStream`].
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BigEndian, BitReadBuffer, BitReadStream, DynBitRead, LittleEndian, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut readers: Vec<Box<dyn DynBitRead>> = vec![
+///     Box::new(BitReadStream::new(BitReadBuffer::new(vec![0x34, 0x12], LittleEndian))),
+///     Box::new(BitReadStream::new(BitReadBuffer::new(vec![0x12, 0x34], BigEndian))),
+/// ];
+/// for reader in readers.iter_mut() {
+///     assert_eq!(reader.read_uint(16)?, 0x1234);
+///     assert!(reader.at_end()?);
+/// }
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitReadStream`]: struct.BitReadStream.html
+/// [`BufReadStream`]: struct.BufReadStream.html
+pub trait DynBitRead {
+    /// Whether the stream reads little endian data
+    fn is_le(&self) -> bool;
+
+    /// The current position in the stream in bits
+    fn pos(&self) -> usize;
+
+    /// Check if the end of the stream has been reached
+    fn at_end(&mut self) -> Result<bool>;
+
+    /// Read a single bit as boolean
+    fn read_bool(&mut self) -> Result<bool>;
+
+    /// Read `count` bits as an unsigned integer, `count` can be at most 64
+    fn read_uint(&mut self, count: usize) -> Result<u64>;
+
+    /// Read `count` bits as a signed integer, `count` can be at most 64
+    fn read_sint(&mut self, count: usize) -> Result<i64>;
+
+    /// Read a 32 bit float
+    fn read_f32(&mut self) -> Result<f32>;
+
+    /// Read a 64 bit float
+    fn read_f64(&mut self) -> Result<f64>;
+
+    /// Read `byte_count` bytes
+    fn read_bytes(&mut self, byte_count: usize) -> Result<Vec<u8>>;
+
+    /// Read a string of `byte_len` bytes, or up to the next null byte if no length is given
+    fn read_string(&mut self, byte_len: Option<usize>) -> Result<String>;
+
+    /// Skip `count` bits
+    fn skip_bits(&mut self, count: usize) -> Result<()>;
+}
+
+impl<E: Endianness> DynBitRead for BitReadStream<E> {
+    fn is_le(&self) -> bool {
+        E::is_le()
+    }
+
+    fn pos(&self) -> usize {
+        BitReadStream::pos(self)
+    }
+
+    fn at_end(&mut self) -> Result<bool> {
+        Ok(self.bits_left() == 0)
+    }
+
+    fn read_bool(&mut self) -> Result<bool> {
+        BitReadStream::read_bool(self)
+    }
+
+    fn read_uint(&mut self, count: usize) -> Result<u64> {
+        self.read_int(count)
+    }
+
+    fn read_sint(&mut self, count: usize) -> Result<i64> {
+        self.read_int(count)
+    }
+
+    fn read_f32(&mut self) -> Result<f32> {
+        self.read_float()
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        self.read_float()
+    }
+
+    fn read_bytes(&mut self, byte_count: usize) -> Result<Vec<u8>> {
+        BitReadStream::read_bytes(self, byte_count)
+    }
+
+    fn read_string(&mut self, byte_len: Option<usize>) -> Result<String> {
+        BitReadStream::read_string(self, byte_len)
+    }
+
+    fn skip_bits(&mut self, count: usize) -> Result<()> {
+        BitReadStream::skip_bits(self, count)
+    }
+}
+
+impl<R: BufRead, E: Endianness> DynBitRead for BufReadStream<R, E> {
+    fn is_le(&self) -> bool {
+        E::is_le()
+    }
+
+    fn pos(&self) -> usize {
+        BufReadStream::pos(self)
+    }
+
+    fn at_end(&mut self) -> Result<bool> {
+        BufReadStream::at_end(self)
+    }
+
+    fn read_bool(&mut self) -> Result<bool> {
+        BufReadStream::read_bool(self)
+    }
+
+    fn read_uint(&mut self, count: usize) -> Result<u64> {
+        self.read_int(count)
+    }
+
+    fn read_sint(&mut self, count: usize) -> Result<i64> {
+        self.read_int(count)
+    }
+
+    fn read_f32(&mut self) -> Result<f32> {
+        self.read()
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        self.read()
+    }
+
+    fn read_bytes(&mut self, byte_count: usize) -> Result<Vec<u8>> {
+        self.read_sized(byte_count)
+    }
+
+    fn read_string(&mut self, byte_len: Option<usize>) -> Result<String> {
+        match byte_len {
+            Some(byte_len) => self.read_sized(byte_len),
+            None => self.read(),
+        }
+    }
+
+    fn skip_bits(&mut self, count: usize) -> Result<()> {
+        BufReadStream::skip_bits(self, count)
+    }
+}
@@ -0,0 +1,159 @@
+use crate::golomb::write_unary;
+use crate::{BitError, BitRead, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+use num_traits::{PrimInt, Unsigned};
+use std::mem::size_of;
+
+/// An unsigned integer stored in unary
+///
+/// The value is stored as a number of `0` bits terminated by a `1` bit.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result, Unary};
+/// #
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b0001_1000], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let first: Unary<u8> = stream.read()?;
+/// let second: Unary<u8> = stream.read()?;
+/// assert_eq!(first.0, 3);
+/// assert_eq!(second.0, 0);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Unary<T>(pub T);
+
+/// A positive integer stored using Elias gamma coding
+///
+/// A value with `n + 1` significant bits is stored as `n` `0` bits, followed by the value without
+/// its most significant bit in `n` bits, read the same way as other integers.
+///
+/// Elias gamma coding can't store `0`, writing it results in an [`IntegerOutOfRange`] error.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, EliasGamma, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b0010_1100], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let first: EliasGamma<u8> = stream.read()?;
+/// let second: EliasGamma<u8> = stream.read()?;
+/// assert_eq!(first.0, 5);
+/// assert_eq!(second.0, 1);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EliasGamma<T>(pub T);
+
+/// A positive integer stored using Elias delta coding
+///
+/// The number of significant bits of the value is stored using Elias gamma coding, followed by
+/// the value without its most significant bit.
+///
+/// Elias delta coding can't store `0`, writing it results in an [`IntegerOutOfRange`] error.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, EliasDelta, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// // 4 significant bits, followed by 0b001
+/// let buffer = BitReadBuffer::new(vec![0b0010_0001], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let value: EliasDelta<u8> = stream.read()?;
+/// assert_eq!(value.0, 9);
+/// assert_eq!(stream.pos(), 8);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EliasDelta<T>(pub T);
+
+/// Get the number of significant bits of a non zero value
+fn significant_bits<T: PrimInt + Unsigned>(value: T) -> Result<usize> {
+    if value.is_zero() {
+        return Err(BitError::IntegerOutOfRange {
+            value: 0,
+            bits: size_of::<T>() * 8,
+        });
+    }
+    Ok(size_of::<T>() * 8 - value.leading_zeros() as usize)
+}
+
+/// Write the value without its most significant bit, preceded by the length prefix
+fn write_elias_gamma<E: Endianness>(
+    stream: &mut BitWriteStream<E>,
+    value: u128,
+    bits: usize,
+) -> Result<()> {
+    write_unary(stream, (bits - 1) as u128)?;
+    if bits > 1 {
+        stream.write_int(value, bits - 1)?;
+    }
+    Ok(())
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitRead<E> for Unary<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        let value = stream.read_unary()?;
+        T::from(value)
+            .map(Unary)
+            .ok_or(BitError::IntegerOutOfRange {
+                value: value as i128,
+                bits: size_of::<T>() * 8,
+            })
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitWrite<E> for Unary<T> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        write_unary(stream, self.0.to_u128().unwrap())
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitRead<E> for EliasGamma<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        stream.read_elias_gamma().map(EliasGamma)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitWrite<E> for EliasGamma<T> {
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        let bits = significant_bits(self.0)?;
+        write_elias_gamma(stream, self.0.to_u128().unwrap(), bits)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitRead<E> for EliasDelta<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        stream.read_elias_delta().map(EliasDelta)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitWrite<E> for EliasDelta<T> {
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        let bits = significant_bits(self.0)?;
+        write_elias_gamma(stream, bits as u128, significant_bits(bits)?)?;
+        if bits > 1 {
+            stream.write_int(self.0.to_u128().unwrap(), bits - 1)?;
+        }
+        Ok(())
+    }
+}
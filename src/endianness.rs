@@ -1,4 +1,9 @@
 /// Trait for specifying endianness of bit buffer
+///
+/// This trait is sealed, only [`BigEndian`] and [`LittleEndian`] implement it.
+///
+/// [`BigEndian`]: struct.BigEndian.html
+/// [`LittleEndian`]: struct.LittleEndian.html
 pub trait Endianness: private::Sealed {
     /// Get the endianness as string, either LittleEndian or BigEndian
     fn as_string() -> &'static str {
@@ -0,0 +1,84 @@
+/// The kind of parity used to protect a range of bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// The total number of set bits, including the parity bit, is even
+    Even,
+    /// The total number of set bits, including the parity bit, is odd
+    Odd,
+}
+
+impl Parity {
+    /// Check if the number of set bits matches the parity
+    #[inline]
+    pub(crate) fn matches(self, ones: u32) -> bool {
+        match self {
+            Parity::Even => ones & 1 == 0,
+            Parity::Odd => ones & 1 == 1,
+        }
+    }
+}
+
+/// Hamming code variants that can be decoded
+///
+/// Code words are read in the classic Hamming order `p1 p2 d1 p3 d2 d3 d4`,
+/// with the data bits being returned in the order `d1 d2 d3 d4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HammingCode {
+    /// Hamming(7,4), 7 bit code words that can correct a single bit error
+    Hamming74,
+    /// Hamming(7,4) followed by an even parity bit over the code word,
+    /// 8 bit code words that can correct a single and detect a double bit error
+    Secded,
+}
+
+/// The result of decoding a single forward error correction block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FecStatus {
+    /// No error was detected
+    Ok,
+    /// A single bit error was detected and corrected
+    Corrected,
+    /// An error was detected that couldn't be corrected, the data bits are returned as read
+    Uncorrectable,
+}
+
+impl HammingCode {
+    /// The number of bits in a single code word
+    #[inline]
+    pub fn block_len(self) -> usize {
+        match self {
+            HammingCode::Hamming74 => 7,
+            HammingCode::Secded => 8,
+        }
+    }
+
+    /// Decode a single code word into its data bits
+    ///
+    /// Only the first [`block_len`](#method.block_len) bits of `word` are used
+    pub(crate) fn decode(self, mut word: [bool; 8]) -> ([bool; 4], FecStatus) {
+        let syndrome = (0..7)
+            .filter(|&i| word[i])
+            .fold(0, |syndrome, i| syndrome ^ (i + 1));
+        let status = match self {
+            HammingCode::Hamming74 if syndrome == 0 => FecStatus::Ok,
+            HammingCode::Hamming74 => {
+                word[syndrome - 1] = !word[syndrome - 1];
+                FecStatus::Corrected
+            }
+            HammingCode::Secded => {
+                let parity_error = word.iter().filter(|bit| **bit).count() % 2 == 1;
+                match (syndrome, parity_error) {
+                    (0, false) => FecStatus::Ok,
+                    // only the overall parity bit is wrong
+                    (0, true) => FecStatus::Corrected,
+                    (_, true) => {
+                        word[syndrome - 1] = !word[syndrome - 1];
+                        FecStatus::Corrected
+                    }
+                    (_, false) => FecStatus::Uncorrectable,
+                }
+            }
+        };
+        ([word[2], word[4], word[5], word[6]], status)
+    }
+}
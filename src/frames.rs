@@ -0,0 +1,76 @@
+use std::marker::PhantomData;
+
+use crate::{BitRead, BitReadStream, Endianness, Result};
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Iterate over frames that start with a length prefix of type `P`, until the end of the stream
+    ///
+    /// Every frame consists of a `P` holding the length of the frame data in bits, followed by the data itself,
+    /// the iterator yields a sub-stream for the data of each frame.
+    ///
+    /// If the prefix or data of a frame is cut short by the end of the stream, the error is yielded once with
+    /// the stream positioned at the start of that frame and the iterator stops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![16, 0x12, 0x34, 8, 0x56];
+    /// let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+    /// let frames = stream.frames_sized::<u8>().collect::<Result<Vec<_>>>()?;
+    /// assert_eq!(frames.len(), 2);
+    /// assert_eq!(frames[0].bit_len(), 16);
+    /// assert_eq!(frames[1].clone().read::<u8>()?, 0x56);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream for the prefix or data of a frame
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn frames_sized<P: BitRead<E> + Into<usize>>(&mut self) -> Frames<'_, E, P> {
+        Frames {
+            stream: self,
+            done: false,
+            prefix: PhantomData,
+        }
+    }
+
+    fn read_frame<P: BitRead<E> + Into<usize>>(&mut self) -> Result<BitReadStream<E>> {
+        let start = self.pos();
+        let result = self
+            .read::<P>()
+            .and_then(|length| self.read_bits(length.into()));
+        if result.is_err() {
+            self.set_pos(start)?;
+        }
+        result
+    }
+}
+
+/// Iterator over length prefixed frames in a stream, created by [`BitReadStream::frames_sized`]
+///
+/// [`BitReadStream::frames_sized`]: struct.BitReadStream.html#method.frames_sized
+pub struct Frames<'a, E: Endianness, P> {
+    stream: &'a mut BitReadStream<E>,
+    done: bool,
+    prefix: PhantomData<P>,
+}
+
+impl<E: Endianness, P: BitRead<E> + Into<usize>> Iterator for Frames<'_, E, P> {
+    type Item = Result<BitReadStream<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.stream.bits_left() == 0 {
+            return None;
+        }
+        let result = self.stream.read_frame::<P>();
+        self.done = result.is_err();
+        Some(result)
+    }
+}
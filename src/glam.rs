@@ -0,0 +1,92 @@
+use crate::{
+    BitRead, BitReadSized, BitReadStream, BitWrite, BitWriteSized, BitWriteStream, Endianness,
+    Result,
+};
+use ::glam::{DQuat, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Quat, UVec2, UVec3, UVec4, Vec2};
+use ::glam::{Vec3, Vec3A, Vec4};
+
+macro_rules! impl_vector {
+    ($type:ty, $component:ty, $len:expr, [$($field:ident),+]) => {
+        /// The components are read in order, as if reading an array of the components
+        impl<E: Endianness> BitRead<E> for $type {
+            #[inline]
+            fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+                $(let $field = stream.read::<$component>()?;)+
+                Ok(<$type>::from_array([$($field),+]))
+            }
+
+            #[inline]
+            unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+                $(let $field = stream.read_unchecked::<$component>()?;)+
+                Ok(<$type>::from_array([$($field),+]))
+            }
+
+            #[inline]
+            fn bit_size() -> Option<usize> {
+                <$component as BitRead<E>>::bit_size().map(|size| size * $len)
+            }
+        }
+
+        /// The components are written in order, as if writing an array of the components
+        impl<E: Endianness> BitWrite<E> for $type {
+            #[inline]
+            fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+                self.to_array()
+                    .iter()
+                    .try_for_each(|component| stream.write(component))
+            }
+        }
+    };
+}
+
+macro_rules! impl_vector_sized {
+    ($type:ty, $component:ty, $len:expr, [$($field:ident),+]) => {
+        impl_vector!($type, $component, $len, [$($field),+]);
+
+        /// Every component is read as a `size` bit integer
+        impl<E: Endianness> BitReadSized<E> for $type {
+            #[inline]
+            fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+                $(let $field = stream.read_sized::<$component>(size)?;)+
+                Ok(<$type>::from_array([$($field),+]))
+            }
+
+            #[inline]
+            unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+                $(let $field = stream.read_sized_unchecked::<$component>(size)?;)+
+                Ok(<$type>::from_array([$($field),+]))
+            }
+
+            #[inline]
+            fn bit_size_sized(size: usize) -> Option<usize> {
+                size.checked_mul($len)
+            }
+        }
+
+        /// Every component is written as a `size` bit integer
+        impl<E: Endianness> BitWriteSized<E> for $type {
+            #[inline]
+            fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+                self.to_array()
+                    .iter()
+                    .try_for_each(|component| stream.write_sized(component, size))
+            }
+        }
+    };
+}
+
+impl_vector!(Vec2, f32, 2, [x, y]);
+impl_vector!(Vec3, f32, 3, [x, y, z]);
+impl_vector!(Vec3A, f32, 3, [x, y, z]);
+impl_vector!(Vec4, f32, 4, [x, y, z, w]);
+impl_vector!(Quat, f32, 4, [x, y, z, w]);
+impl_vector!(DVec2, f64, 2, [x, y]);
+impl_vector!(DVec3, f64, 3, [x, y, z]);
+impl_vector!(DVec4, f64, 4, [x, y, z, w]);
+impl_vector!(DQuat, f64, 4, [x, y, z, w]);
+impl_vector_sized!(IVec2, i32, 2, [x, y]);
+impl_vector_sized!(IVec3, i32, 3, [x, y, z]);
+impl_vector_sized!(IVec4, i32, 4, [x, y, z, w]);
+impl_vector_sized!(UVec2, u32, 2, [x, y]);
+impl_vector_sized!(UVec3, u32, 3, [x, y, z]);
+impl_vector_sized!(UVec4, u32, 4, [x, y, z, w]);
@@ -0,0 +1,147 @@
+use crate::{
+    BitError, BitReadSized, BitReadStream, BitWriteSized, BitWriteStream, Endianness, Result,
+};
+use num_traits::{PrimInt, Unsigned};
+use std::cmp::min;
+use std::convert::TryFrom;
+use std::mem::size_of;
+
+/// An unsigned integer stored using Rice coding
+///
+/// The value is split into a quotient and a remainder of `k` bits, the quotient is stored in unary
+/// as a number of `0` bits terminated by a `1` bit, followed by the remainder, as used by FLAC.
+///
+/// The wrapper can only be read and written with size, the size sets the parameter `k`.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result, Rice};
+/// #
+/// # fn main() -> Result<()> {
+/// // quotient 2, remainder 0b01
+/// let buffer = BitReadBuffer::new(vec![0b0010_1000], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let value: Rice<u32> = stream.read_sized(2)?;
+/// assert_eq!(value.0, 9);
+/// assert_eq!(stream.pos(), 5);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Rice<T>(pub T);
+
+/// An unsigned integer stored using Golomb coding
+///
+/// The value is split into a quotient and a remainder by dividing it by `m`, the quotient is stored
+/// in unary as a number of `0` bits terminated by a `1` bit, followed by the remainder in truncated binary.
+///
+/// The wrapper can only be read and written with size, the size sets the parameter `m`.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Golomb, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// // quotient 1, remainder 0b11 - 1
+/// let buffer = BitReadBuffer::new(vec![0b0111_0000], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let value: Golomb<u32> = stream.read_sized(3)?;
+/// assert_eq!(value.0, 5);
+/// assert_eq!(stream.pos(), 4);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Golomb<T>(pub T);
+
+/// The number of bits needed to store the remainder of a golomb code with parameter `m`
+///
+/// Remainders below the returned cutoff are stored using one bit less.
+pub(crate) fn remainder_bits(m: usize) -> (usize, u128) {
+    let bits = (usize::BITS - (m - 1).leading_zeros()) as usize;
+    (bits, (1u128 << bits) - m as u128)
+}
+
+/// Convert a decoded value into `T`
+pub(crate) fn to_int<T: PrimInt + Unsigned>(value: Option<u128>) -> Result<T> {
+    value.and_then(T::from).ok_or(BitError::IntegerOutOfRange {
+        value: value.map_or(i128::MAX, |value| {
+            i128::try_from(value).unwrap_or(i128::MAX)
+        }),
+        bits: size_of::<T>() * 8,
+    })
+}
+
+/// Write `quotient` in unary as a number of `0` bits terminated by a `1` bit
+pub(crate) fn write_unary<E: Endianness>(
+    stream: &mut BitWriteStream<E>,
+    quotient: u128,
+) -> Result<()> {
+    let mut zeros = usize::try_from(quotient).map_err(|_| BitError::IntegerOutOfRange {
+        value: i128::try_from(quotient).unwrap_or(i128::MAX),
+        bits: usize::BITS as usize,
+    })?;
+    while zeros > 0 {
+        let count = min(zeros, 64);
+        stream.write_int(0u64, count)?;
+        zeros -= count;
+    }
+    stream.write_bool(true)
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitReadSized<E> for Rice<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        stream.read_rice(size).map(Rice)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitWriteSized<E> for Rice<T> {
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        if size > size_of::<T>() * 8 {
+            return Err(BitError::TooManyBits {
+                requested: size,
+                max: size_of::<T>() * 8,
+            });
+        }
+        let value = self.0.to_u128().unwrap();
+        write_unary(stream, value.checked_shr(size as u32).unwrap_or(0))?;
+        if size > 0 {
+            stream.write_int(value, size)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitReadSized<E> for Golomb<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        stream.read_golomb(size).map(Golomb)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Unsigned> BitWriteSized<E> for Golomb<T> {
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        if size == 0 {
+            return Err(BitError::InvalidGolombParameter);
+        }
+        let value = self.0.to_u128().unwrap();
+        write_unary(stream, value / size as u128)?;
+
+        let remainder = value % size as u128;
+        let (bits, cutoff) = remainder_bits(size);
+        if remainder < cutoff {
+            stream.write_int(remainder, bits - 1)
+        } else if bits > 0 {
+            let remainder = remainder + cutoff;
+            stream.write_int(remainder >> 1, bits - 1)?;
+            stream.write_bool(remainder & 1 == 1)
+        } else {
+            Ok(())
+        }
+    }
+}
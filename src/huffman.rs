@@ -0,0 +1,227 @@
+use crate::{BitError, BitReadBuffer, Endianness, Result};
+use std::cmp::min;
+use std::collections::HashMap;
+
+/// Maximum number of bits used to index the lookup table
+const LOOKUP_BITS: usize = 10;
+
+/// Maximum length of a single code
+const MAX_CODE_LENGTH: usize = 32;
+
+/// A table of prefix codes used to decode huffman encoded symbols
+///
+/// Codes are matched against the stream one bit at a time, the first bit read from the stream is
+/// the most significant bit of the code, regardless of the endianness of the stream.
+///
+/// Codes up to 10 bits long are decoded with a single lookup, longer codes fall back to decoding
+/// bit by bit. The table doesn't need to be complete, reading a sequence of bits that doesn't match
+/// any code results in an error.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, HuffmanTable, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// // 'b' = 0, 'a' = 10, 'c' = 110, 'd' = 111
+/// let table = HuffmanTable::canonical(vec![(2, 'a'), (1, 'b'), (3, 'c'), (3, 'd')])?;
+///
+/// let bytes = vec![0b0101_1011, 0b1000_0000];
+/// let buffer = BitReadBuffer::new(bytes, BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// assert_eq!(stream.read_huffman(&table)?, 'b');
+/// assert_eq!(stream.read_huffman(&table)?, 'a');
+/// assert_eq!(stream.read_huffman(&table)?, 'c');
+/// assert_eq!(stream.read_huffman(&table)?, 'd');
+/// assert_eq!(stream.pos(), 9);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HuffmanTable<T> {
+    symbols: Vec<T>,
+    codes: HashMap<(usize, u32), usize>,
+    lookup: Vec<Option<(usize, usize)>>,
+    lookup_bits: usize,
+    max_length: usize,
+}
+
+impl<T> HuffmanTable<T> {
+    /// Create a table from a list of `(code, length, symbol)` entries
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::InvalidHuffmanTable`]: a code is empty, longer than 32 bits, doesn't fit in
+    ///   its length or is a prefix of another code
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{HuffmanTable, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let table = HuffmanTable::new(vec![(0b0, 1, 'a'), (0b10, 2, 'b'), (0b11, 2, 'c')])?;
+    /// assert_eq!(table.len(), 3);
+    /// assert_eq!(table.max_length(), 2);
+    ///
+    /// // 0b1 is a prefix of 0b10
+    /// assert!(HuffmanTable::new(vec![(0b1, 1, 'a'), (0b10, 2, 'b')]).is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::InvalidHuffmanTable`]: enum.ReadError.html#variant.InvalidHuffmanTable
+    pub fn new<I: IntoIterator<Item = (u32, usize, T)>>(entries: I) -> Result<Self> {
+        let mut symbols = Vec::new();
+        let mut entries: Vec<(u32, usize, usize)> = entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, (code, length, symbol))| {
+                symbols.push(symbol);
+                (code, length, index)
+            })
+            .collect();
+
+        for &(code, length, _) in &entries {
+            if length == 0 || length > MAX_CODE_LENGTH || u64::from(code) >> length != 0 {
+                return Err(BitError::InvalidHuffmanTable { code, length });
+            }
+        }
+
+        // with the codes sorted as left aligned bit strings, any code that is a prefix of another
+        // code ends up directly before a code it conflicts with
+        entries.sort_by_key(|&(code, length, _)| {
+            (u64::from(code) << (MAX_CODE_LENGTH - length), length)
+        });
+        for pair in entries.windows(2) {
+            let (prefix, prefix_length, _) = pair[0];
+            let (code, length, _) = pair[1];
+            if length >= prefix_length && code >> (length - prefix_length) == prefix {
+                return Err(BitError::InvalidHuffmanTable { code, length });
+            }
+        }
+
+        let max_length = entries
+            .iter()
+            .map(|&(_, length, _)| length)
+            .max()
+            .unwrap_or(0);
+        let lookup_bits = min(max_length, LOOKUP_BITS);
+        let mut lookup = vec![None; 1 << lookup_bits];
+        let mut codes = HashMap::with_capacity(entries.len());
+        for &(code, length, index) in &entries {
+            if length <= lookup_bits {
+                let start = (code as usize) << (lookup_bits - length);
+                let end = start + (1 << (lookup_bits - length));
+                for entry in &mut lookup[start..end] {
+                    *entry = Some((length, index));
+                }
+            }
+            codes.insert((length, code), index);
+        }
+
+        Ok(HuffmanTable {
+            symbols,
+            codes,
+            lookup,
+            lookup_bits,
+            max_length,
+        })
+    }
+
+    /// Create a table from a list of `(length, symbol)` entries, assigning canonical codes
+    ///
+    /// Codes are assigned in order of increasing length, symbols with the same length get
+    /// consecutive codes in the order they are listed, as done by DEFLATE.
+    /// Symbols with a length of 0 are not part of the table.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::InvalidHuffmanTable`]: a length is longer than 32 bits or there are more
+    ///   symbols than can be assigned a code of their length
+    ///
+    /// [`ReadError::InvalidHuffmanTable`]: enum.ReadError.html#variant.InvalidHuffmanTable
+    pub fn canonical<I: IntoIterator<Item = (usize, T)>>(lengths: I) -> Result<Self> {
+        let mut lengths: Vec<(usize, T)> = lengths
+            .into_iter()
+            .filter(|(length, _)| *length > 0)
+            .collect();
+        lengths.sort_by_key(|(length, _)| *length);
+
+        let mut entries = Vec::with_capacity(lengths.len());
+        let mut next_code: u64 = 0;
+        let mut previous_length = 0;
+        for (length, symbol) in lengths {
+            if length > MAX_CODE_LENGTH {
+                return Err(BitError::InvalidHuffmanTable { code: 0, length });
+            }
+            next_code <<= length - previous_length;
+            if next_code >> length != 0 {
+                return Err(BitError::InvalidHuffmanTable {
+                    code: next_code as u32,
+                    length,
+                });
+            }
+            entries.push((next_code as u32, length, symbol));
+            next_code += 1;
+            previous_length = length;
+        }
+
+        HuffmanTable::new(entries)
+    }
+
+    /// The number of symbols in the table
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Check if the table contains no symbols
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// The length of the longest code in the table
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Match the bits at `pos` against the table, returns the length of the matched code and the symbol
+    pub(crate) fn decode<E: Endianness>(
+        &self,
+        buffer: &BitReadBuffer<E>,
+        pos: usize,
+        bits_left: usize,
+    ) -> Result<Option<(usize, &T)>> {
+        let available = min(self.max_length, bits_left);
+        let peek_bits = min(self.lookup_bits, available);
+        let mut code = if peek_bits > 0 {
+            let bits = buffer.read_int::<u32>(pos, peek_bits)?;
+            if E::is_le() {
+                bits.reverse_bits() >> (32 - peek_bits)
+            } else {
+                bits
+            }
+        } else {
+            0
+        };
+
+        if let Some((length, index)) =
+            self.lookup[(code as usize) << (self.lookup_bits - peek_bits)]
+        {
+            if length <= peek_bits {
+                return Ok(Some((length, &self.symbols[index])));
+            }
+        }
+
+        // codes up to the peeked length are all in the lookup table
+        for length in peek_bits + 1..=available {
+            code = (code << 1) | u32::from(buffer.read_bool(pos + length - 1)?);
+            if let Some(&index) = self.codes.get(&(length, code)) {
+                return Ok(Some((length, &self.symbols[index])));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{BitError, BitRead, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not, Sub};
+
+/// An unsigned integer of exactly `BITS` bits
+///
+/// Reading or writing a `UInt<BITS>` always uses `BITS` bits, which allows the width of a field
+/// to be part of its type instead of being set using the `size` attribute.
+///
+/// `BITS` has to be between 1 and 64, arithmetic wraps around at `2^BITS`.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result, UInt};
+/// #
+/// #[derive(BitRead)]
+/// struct Header {
+///     version: UInt<3>,
+///     length: UInt<13>,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b1010_1101, 0b0000_0001], LittleEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let header: Header = stream.read()?;
+/// assert_eq!(header.version.get(), 0b101);
+/// assert_eq!(header.length, UInt::new(0b11_0101).unwrap());
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UInt<const BITS: usize>(u64);
+
+/// A signed two's complement integer of exactly `BITS` bits
+///
+/// See [`UInt`] for details, arithmetic wraps around within the range of a `BITS` bit signed integer.
+///
+/// [`UInt`]: struct.UInt.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Int<const BITS: usize>(i64);
+
+/// An unsigned integer of exactly `N` bits, alias for [`UInt`]
+///
+/// [`UInt`]: struct.UInt.html
+pub type UBits<const N: usize> = UInt<N>;
+
+/// A signed integer of exactly `N` bits, alias for [`Int`]
+///
+/// [`Int`]: struct.Int.html
+pub type IBits<const N: usize> = Int<N>;
+
+impl<const BITS: usize> UInt<BITS> {
+    const VALID: () = assert!(BITS > 0 && BITS <= 64, "BITS has to be between 1 and 64");
+
+    /// The number of bits in the integer
+    pub const BITS: usize = BITS;
+    /// The smallest value that can be represented
+    pub const MIN: Self = UInt(0);
+    /// The largest value that can be represented
+    pub const MAX: Self = UInt(u64::MAX >> (64 - BITS));
+
+    /// Create a new integer, returns `None` if the value doesn't fit in `BITS` bits
+    #[inline]
+    pub fn new(value: u64) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        if value <= Self::MAX.0 {
+            Some(UInt(value))
+        } else {
+            None
+        }
+    }
+
+    /// Create a new integer from the lowest `BITS` bits of the value
+    #[inline]
+    pub fn new_wrapping(value: u64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        UInt(value & Self::MAX.0)
+    }
+
+    /// Get the value of the integer
+    #[inline]
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Checked addition, returns `None` if the result doesn't fit in `BITS` bits
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).and_then(Self::new)
+    }
+
+    /// Checked subtraction, returns `None` if the result would be negative
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(UInt)
+    }
+
+    /// Checked multiplication, returns `None` if the result doesn't fit in `BITS` bits
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).and_then(Self::new)
+    }
+}
+
+impl<const BITS: usize> Int<BITS> {
+    const VALID: () = assert!(BITS > 0 && BITS <= 64, "BITS has to be between 1 and 64");
+
+    /// The number of bits in the integer
+    pub const BITS: usize = BITS;
+    /// The smallest value that can be represented
+    pub const MIN: Self = Int(i64::MIN >> (64 - BITS));
+    /// The largest value that can be represented
+    pub const MAX: Self = Int(i64::MAX >> (64 - BITS));
+
+    /// Create a new integer, returns `None` if the value doesn't fit in `BITS` bits
+    #[inline]
+    pub fn new(value: i64) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        if value >= Self::MIN.0 && value <= Self::MAX.0 {
+            Some(Int(value))
+        } else {
+            None
+        }
+    }
+
+    /// Create a new integer from the lowest `BITS` bits of the value
+    #[inline]
+    pub fn new_wrapping(value: i64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        let shift = 64 - BITS as u32;
+        Int(value.wrapping_shl(shift).wrapping_shr(shift))
+    }
+
+    /// Get the value of the integer
+    #[inline]
+    pub fn get(self) -> i64 {
+        self.0
+    }
+
+    /// Checked addition, returns `None` if the result doesn't fit in `BITS` bits
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).and_then(Self::new)
+    }
+
+    /// Checked subtraction, returns `None` if the result doesn't fit in `BITS` bits
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).and_then(Self::new)
+    }
+
+    /// Checked multiplication, returns `None` if the result doesn't fit in `BITS` bits
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).and_then(Self::new)
+    }
+}
+
+macro_rules! impl_int_ops {
+    ($type:ident) => {
+        impl<const BITS: usize> Add for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self::new_wrapping(self.0.wrapping_add(rhs.0))
+            }
+        }
+
+        impl<const BITS: usize> Sub for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new_wrapping(self.0.wrapping_sub(rhs.0))
+            }
+        }
+
+        impl<const BITS: usize> Mul for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                Self::new_wrapping(self.0.wrapping_mul(rhs.0))
+            }
+        }
+
+        impl<const BITS: usize> BitAnd for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, rhs: Self) -> Self {
+                $type(self.0 & rhs.0)
+            }
+        }
+
+        impl<const BITS: usize> BitOr for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                $type(self.0 | rhs.0)
+            }
+        }
+
+        impl<const BITS: usize> BitXor for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn bitxor(self, rhs: Self) -> Self {
+                $type(self.0 ^ rhs.0)
+            }
+        }
+
+        impl<const BITS: usize> Not for $type<BITS> {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                Self::new_wrapping(!self.0)
+            }
+        }
+
+        impl<const BITS: usize> fmt::Display for $type<BITS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+impl_int_ops!(UInt);
+impl_int_ops!(Int);
+
+impl<const BITS: usize> From<UInt<BITS>> for u64 {
+    #[inline]
+    fn from(value: UInt<BITS>) -> Self {
+        value.0
+    }
+}
+
+impl<const BITS: usize> From<Int<BITS>> for i64 {
+    #[inline]
+    fn from(value: Int<BITS>) -> Self {
+        value.0
+    }
+}
+
+impl<const BITS: usize> TryFrom<u64> for UInt<BITS> {
+    type Error = BitError;
+
+    #[inline]
+    fn try_from(value: u64) -> Result<Self> {
+        UInt::new(value).ok_or(BitError::IntegerOutOfRange {
+            value: value as i128,
+            bits: BITS,
+        })
+    }
+}
+
+impl<const BITS: usize> TryFrom<i64> for Int<BITS> {
+    type Error = BitError;
+
+    #[inline]
+    fn try_from(value: i64) -> Result<Self> {
+        Int::new(value).ok_or(BitError::IntegerOutOfRange {
+            value: value as i128,
+            bits: BITS,
+        })
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitRead<E> for UInt<BITS> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        Ok(UInt::new_wrapping(stream.read_int(BITS)?))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        Ok(UInt::new_wrapping(stream.read_int_unchecked(BITS)))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(BITS)
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitRead<E> for Int<BITS> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        Ok(Int::new_wrapping(stream.read_int(BITS)?))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        Ok(Int::new_wrapping(stream.read_int_unchecked(BITS)))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(BITS)
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitWrite<E> for UInt<BITS> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(self.0, BITS)
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitWrite<E> for Int<BITS> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(self.0, BITS)
+    }
+}
@@ -40,6 +40,25 @@
 //! # }
 //! ```
 //!
+//! The [`prelude`] module re-exports the commonly used traits and types.
+//!
+//! # Untrusted input
+//!
+//! Reading from a [`BitReadBuffer`] or [`BitReadStream`] never panics, regardless of the data being read,
+//! malformed or truncated data always results in an error being returned.
+//! This also holds for types using the derived [`BitRead`] and [`BitReadSized`] implementations,
+//! as long as the implementations of the field types don't panic.
+//! Panics are reserved for programming errors, such as creating a [`LazyBitRead`] for a type without a fixed size
+//! or reading more bits from a [`ReadGuard`] than were checked.
+//!
+//! To protect against inputs designed to exhaust resources, the nesting depth and the total number of bits read
+//! can be limited using [`set_max_depth`] and [`set_read_limit`].
+//!
+//! The `fuzz` directory contains [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) targets that verify these guarantees,
+//! and can be used as a starting point for fuzzing parsers build using this crate.
+//! With the `arbitrary` feature, [`BitReadBuffer`] implements `Arbitrary`, so it can be used directly as the input
+//! of a fuzz target.
+//!
 //! [`BitReadBuffer`]: struct.BitReadBuffer.html
 //! [`BitReadStream`]: struct.BitReadStream.html
 //! [`read_bool`]: struct.BitStream.html#method.read_bool
@@ -51,27 +70,167 @@
 //! [`read_sized`]: struct.BitStream.html#method.read_sized
 //! [`BitRead`]: trait.BitRead.html
 //! [`BitReadSized`]: trait.BitReadSized.html
+//! [`LazyBitRead`]: struct.LazyBitRead.html
+//! [`ReadGuard`]: unchecked/struct.ReadGuard.html
+//! [`set_max_depth`]: struct.BitReadStream.html#method.set_max_depth
+//! [`set_read_limit`]: struct.BitReadStream.html#method.set_read_limit
+//! [`prelude`]: prelude/index.html
 
 #![warn(missing_docs)]
+// err-derive generates its impls inside an anonymous const
+#![allow(non_local_definitions)]
 
 use err_derive::Error;
 pub use std::string::FromUtf8Error;
 
-pub use bitbuffer_derive::{BitRead, BitReadSized, BitWrite};
+/// Read an anonymous layout from a stream
+///
+/// The layout is either a list of named fields in braces, which returns a generated struct,
+/// or a list of types in parentheses, which returns a tuple.
+/// Every field can be followed by a size in brackets to read the field using [`read_sized`],
+/// the size can refer to previously read fields.
+///
+/// The macro evaluates to a `Result` with the read layout.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{bit_layout, BitReadBuffer, BitReadStream, LittleEndian, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// let bytes = vec![0b0010_0101, b'h', b'i', 0xff];
+/// let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+/// let header = bit_layout!(stream, {
+///     version: u8 [4],
+///     length: u8 [4],
+///     name: String [length],
+/// })?;
+/// assert_eq!(header.version, 5);
+/// assert_eq!(header.name, "hi");
+///
+/// let (flag, rest) = bit_layout!(stream, (bool, u8 [7]))?;
+/// assert_eq!((flag, rest), (true, 0x7f));
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// Since the generated struct is defined inside the macro expansion, the field types can't refer
+/// to generic parameters of the surrounding function, use the tuple form in that case.
+///
+/// [`read_sized`]: struct.BitReadStream.html#method.read_sized
+pub use bitbuffer_derive::bit_layout;
+pub use bitbuffer_derive::{
+    BitRead, BitReadRepr, BitReadSized, BitReadWithContext, BitSchema, BitWrite, BitWriteSized,
+};
+pub use bufread::BufReadStream;
+pub use builder::BitBufferBuilder;
+pub use decode::{diff, DecodeNode, FieldDiff};
+pub use dual::DualReadStream;
+pub use duration::{Micros, Millis, Nanos, Seconds};
+pub use dynread::DynBitRead;
+pub use elias::{EliasDelta, EliasGamma, Unary};
 pub use endianness::*;
-pub use read::{BitRead, BitReadSized, LazyBitRead, LazyBitReadSized};
-pub use readbuffer::BitReadBuffer;
+pub use fec::{FecStatus, HammingCode, Parity};
+pub use frames::Frames;
+pub use golomb::{Golomb, Rice};
+pub use huffman::HuffmanTable;
+pub use int::{IBits, Int, UBits, UInt};
+pub use linecode::{ManchesterEncoding, NrziEncoding};
+pub use output::ByteOutput;
+#[cfg(feature = "prefetch")]
+pub use prefetch::PrefetchReader;
+pub use quantized::{BitAngle, Quantized};
+pub use read::{
+    BitRead, BitReadSized, BitReadWithContext, LazyBitRead, LazyBitReadSized, VersionedContext,
+};
+pub use read_into::{BitReadInto, BitReadIntoSized};
+pub use readbuffer::{BitReadBuffer, StringMode};
 pub use readstream::BitReadStream;
-pub use write::BitWrite;
+pub use readstream_rev::BitReadStreamRev;
+pub use registry::ParserRegistry;
+pub use schema::{BitSchema, FieldSchema, Schema, SizeSchema, VariantSchema};
+#[cfg(feature = "stats")]
+pub use stats::{ReadStats, TypeStats};
+pub use storage::Storage;
+pub use write::{BitWrite, BitWriteSized};
+pub use writebuffer::BitWriteBuffer;
 pub use writestream::BitWriteStream;
+pub use zigzag::ZigZag;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bitvec")]
+mod bitvec;
+mod bufread;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compress;
+pub mod const_read;
+mod decode;
+mod dual;
+mod duration;
+mod dynread;
+mod elias;
 mod endianness;
-mod num_traits;
+mod fec;
+mod frames;
+#[cfg(feature = "glam")]
+mod glam;
+mod golomb;
+mod huffman;
+mod int;
+mod linecode;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "net")]
+mod net;
+pub mod num_traits;
+mod output;
+#[cfg(any(feature = "json", feature = "cbor"))]
+mod payload;
+mod pos;
+#[cfg(feature = "prefetch")]
+mod prefetch;
+pub mod prelude;
+pub mod protobuf;
+#[cfg(feature = "python")]
+pub mod python;
+mod quantized;
+pub mod range_coder;
 mod read;
+mod read_all;
+mod read_into;
 mod readbuffer;
 mod readstream;
+mod readstream_rev;
+mod registry;
+mod schema;
+#[cfg(feature = "source")]
+pub mod source;
+mod stats;
+mod storage;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod tlv;
+mod trace;
+pub mod unchecked;
+#[cfg(feature = "wasm")]
+mod wasm;
 mod write;
+mod writebuffer;
 mod writestream;
+mod zigzag;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::read_all::{read_all_bit_size, read_all_bit_size_sized};
+    pub use crate::trace::{TraceDebug, TraceFallback, TraceValue};
+    #[cfg(feature = "trace")]
+    pub use tracing;
+}
 
 /// Errors that can be returned when trying to read from a buffer
 #[derive(Debug, Error)]
@@ -127,6 +286,206 @@ pub enum BitError {
     /// The read slice of bytes are not valid utf8
     #[error(display = "The read slice of bytes are not valid utf8: {}", _0)]
     Utf8Error(#[error(source)] FromUtf8Error),
+    /// The read value is not a valid unicode scalar value
+    #[error(display = "The value {:#x} is not a valid unicode scalar value", value)]
+    InvalidChar {
+        /// The read value
+        value: u32,
+    },
+    /// Six consecutive `1` bits were found while removing bit stuffing
+    #[error(
+        display = "Six consecutive 1 bits found at position {} while removing bit stuffing",
+        pos
+    )]
+    InvalidBitStuffing {
+        /// The position of the sixth `1` bit
+        pos: usize,
+    },
+    /// A line code symbol that can't be decoded was found
+    #[error(display = "Invalid line code symbol found at position {}", pos)]
+    InvalidLineCode {
+        /// The position of the invalid symbol
+        pos: usize,
+    },
+    /// A parity or error correction check failed for the read bits
+    #[error(display = "Parity check failed for the bits read at position {}", pos)]
+    ParityError {
+        /// The position of the first bit covered by the failed check
+        pos: usize,
+    },
+    /// A self-describing payload couldn't be serialized or deserialized
+    #[error(
+        display = "Invalid self-describing payload at position {}: {}",
+        pos,
+        message
+    )]
+    InvalidPayload {
+        /// The position of the start of the payload
+        pos: usize,
+        /// The error reported by the serialization format
+        message: String,
+    },
+    /// A protobuf varint longer than 10 bytes or overflowing 64 bits was found
+    #[error(display = "Invalid protobuf varint found at position {}", pos)]
+    InvalidVarint {
+        /// The position of the start of the varint
+        pos: usize,
+    },
+    /// A protobuf tag with an unknown or unexpected wire type was found
+    #[error(
+        display = "Invalid protobuf wire type {} found at position {}",
+        wire_type,
+        pos
+    )]
+    InvalidWireType {
+        /// The wire type found in the tag
+        wire_type: u8,
+        /// The position of the tag
+        pos: usize,
+    },
+    /// The requested sync pattern doesn't occur in the remaining bits of the stream
+    #[error(
+        display = "The sync pattern {:#x} of {} bits was not found in the remaining bits",
+        pattern,
+        bits
+    )]
+    PatternNotFound {
+        /// The pattern that was searched for
+        pattern: u64,
+        /// The number of bits in the pattern
+        bits: usize,
+    },
+    /// The buffer is too large for all bit positions to fit in a `usize`
+    #[error(
+        display = "The buffer of {} bytes is too large, buffers can contain at most {} bytes",
+        byte_len,
+        max
+    )]
+    BufferTooLarge {
+        /// The length of the buffer in bytes
+        byte_len: usize,
+        /// The maximum length of a buffer in bytes
+        max: usize,
+    },
+    /// Reading a value from a `BufReadStream` requires more data than fits in the lookahead window
+    #[error(
+        display = "Reading at position {} requires more than the {} bytes of lookahead available",
+        pos,
+        window_bytes
+    )]
+    WindowExceeded {
+        /// The position of the read
+        pos: usize,
+        /// The size of the lookahead window in bytes
+        window_bytes: usize,
+    },
+    /// None of the magic byte strings of the variants match while trying to read an enum
+    #[error(
+        display = "No magic of enum '{}' matches the bytes at position {}",
+        enum_name,
+        pos
+    )]
+    UnmatchedMagic {
+        /// The position of the unmatched bytes
+        pos: usize,
+        /// The name of the enum that is trying to be read
+        enum_name: String,
+    },
+    /// No parser is registered for the requested id in a `ParserRegistry`
+    #[error(display = "No parser registered for id {}", id)]
+    UnknownParser {
+        /// The requested id
+        id: u64,
+    },
+    /// The terminator byte was not found within the maximum number of bytes
+    #[error(
+        display = "The terminator byte {:#x} was not found within {} bytes",
+        terminator,
+        max
+    )]
+    TerminatorNotFound {
+        /// The terminator that was searched for
+        terminator: u8,
+        /// The maximum number of bytes before the terminator
+        max: usize,
+    },
+    /// A nibble that isn't a decimal digit was found while reading a binary coded decimal
+    #[error(
+        display = "Invalid binary coded decimal digit {:#x} found at position {}",
+        digit,
+        pos
+    )]
+    InvalidBcdDigit {
+        /// The invalid digit
+        digit: u8,
+        /// The position of the invalid digit
+        pos: usize,
+    },
+    /// A sequence of bits that doesn't match any code in the huffman table was found
+    #[error(display = "Invalid huffman code found at position {}", pos)]
+    InvalidHuffmanCode {
+        /// The position of the invalid code
+        pos: usize,
+    },
+    /// A huffman table was created with an invalid code
+    #[error(
+        display = "Invalid huffman code {:#b} with length {}, codes need to be between 1 and 32 bits and can't be a prefix of another code",
+        code,
+        length
+    )]
+    InvalidHuffmanTable {
+        /// The invalid code
+        code: u32,
+        /// The length of the invalid code
+        length: usize,
+    },
+    /// A golomb code with a parameter of 0 was read or written
+    #[error(display = "The parameter of a golomb code can't be 0")]
+    InvalidGolombParameter,
+    /// The maximum nesting depth was reached while reading a value
+    #[error(
+        display = "The maximum nesting depth of {} was reached while reading",
+        max_depth
+    )]
+    DepthLimitReached {
+        /// The configured maximum nesting depth
+        max_depth: usize,
+    },
+    /// The read limit set for the stream was reached
+    #[error(
+        display = "Read limit reached, {} bits were requested but only {} bits remain in the read budget",
+        requested,
+        remaining
+    )]
+    ReadLimitReached {
+        /// The number of bits requested to read
+        requested: usize,
+        /// The number of bits left in the read budget
+        remaining: usize,
+    },
+    /// A read was attempted that would read past the marker set on the stream
+    #[error(
+        display = "Read of {} bits at position {} would read past the marker at position {}",
+        requested,
+        pos,
+        marker
+    )]
+    ReadPastMarker {
+        /// The position of the marker
+        marker: usize,
+        /// The position of the stream when the read was attempted
+        pos: usize,
+        /// The number of bits requested to read
+        requested: usize,
+    },
+    /// The value doesn't fit in an integer of the requested number of bits
+    #[error(display = "The value {} doesn't fit in a {} bit integer", value, bits)]
+    IntegerOutOfRange {
+        /// The value that was attempted to be converted
+        value: i128,
+        /// The number of bits in the target integer
+        bits: usize,
+    },
     /// The string that was requested to be written does not fit in the specified fixed length
     #[error(
         display = "The string that was requested to be written does not fit in the specified fixed length, string is {} bytes long, while a size of {} has been specified",
@@ -139,6 +498,12 @@ pub enum BitError {
         /// The requested fixed size to encode the string into
         requested_length: usize,
     },
+    /// Reading from the input or writing the read data to the output failed
+    #[error(
+        display = "Reading from the input or writing to the output failed: {}",
+        _0
+    )]
+    Io(#[error(source)] std::io::Error),
 }
 
 /// Either the read bits in the requested format or a [`ReadError`](enum.ReadError.html)
@@ -155,3 +520,54 @@ pub fn bit_size_of<T: BitRead<LittleEndian>>() -> Option<usize> {
 pub fn bit_size_of_sized<T: BitReadSized<LittleEndian>>(size: usize) -> Option<usize> {
     T::bit_size_sized(size)
 }
+
+/// Read a type from a byte slice, returning the value and the number of bits read
+///
+/// This is a shorthand for creating a [`BitReadBuffer`] and [`BitReadStream`] when a type
+/// only needs to be read once.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{read_from, BitRead, LittleEndian, Result};
+/// #
+/// #[derive(BitRead)]
+/// struct Header {
+///     #[size = 3]
+///     version: u8,
+///     flag: bool,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let (header, bits) = read_from::<Header, _>(&[0b1101], LittleEndian)?;
+/// assert_eq!(header.version, 0b101);
+/// assert_eq!(header.flag, true);
+/// assert_eq!(bits, 4);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitReadBuffer`]: struct.BitReadBuffer.html
+/// [`BitReadStream`]: struct.BitReadStream.html
+pub fn read_from<T: BitRead<E>, E: Endianness>(bytes: &[u8], endianness: E) -> Result<(T, usize)> {
+    let mut stream = BitReadStream::new(BitReadBuffer::try_new(bytes.to_vec(), endianness)?);
+    let value = stream.read()?;
+    Ok((value, stream.pos()))
+}
+
+/// Read a type from a little endian byte slice, returning the value and the number of bits read
+///
+/// See [`read_from`](fn.read_from.html) for details.
+#[inline]
+pub fn read_le<T: BitRead<LittleEndian>>(bytes: &[u8]) -> Result<(T, usize)> {
+    read_from(bytes, LittleEndian)
+}
+
+/// Read a type from a big endian byte slice, returning the value and the number of bits read
+///
+/// See [`read_from`](fn.read_from.html) for details.
+#[inline]
+pub fn read_be<T: BitRead<BigEndian>>(bytes: &[u8]) -> Result<(T, usize)> {
+    read_from(bytes, BigEndian)
+}
@@ -0,0 +1,41 @@
+/// The convention used to map Manchester encoded symbols to logical bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManchesterEncoding {
+    /// IEEE 802.3 convention, a `0` is encoded as `10` and a `1` as `01`
+    Ieee,
+    /// G.E. Thomas convention, a `0` is encoded as `01` and a `1` as `10`
+    Thomas,
+}
+
+impl ManchesterEncoding {
+    /// Decode a pair of line bits, returns `None` for pairs without a transition
+    #[inline]
+    pub(crate) fn decode(self, first: bool, second: bool) -> Option<bool> {
+        match (self, first, second) {
+            (_, true, true) | (_, false, false) => None,
+            (ManchesterEncoding::Ieee, _, second) => Some(second),
+            (ManchesterEncoding::Thomas, first, _) => Some(first),
+        }
+    }
+}
+
+/// The convention used to map NRZI encoded line levels to logical bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NrziEncoding {
+    /// NRZ-S, a `0` is encoded as a transition and a `1` as no transition (USB, HDLC)
+    Space,
+    /// NRZ-M, a `1` is encoded as a transition and a `0` as no transition
+    Mark,
+}
+
+impl NrziEncoding {
+    /// Decode a line level given the previous level
+    #[inline]
+    pub(crate) fn decode(self, previous: bool, level: bool) -> bool {
+        let transition = previous != level;
+        match self {
+            NrziEncoding::Space => !transition,
+            NrziEncoding::Mark => transition,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    BitRead, BitReadSized, BitReadStream, BitWrite, BitWriteSized, BitWriteStream, Endianness,
+    Result,
+};
+use ::nalgebra::{SVector, Scalar};
+
+/// The components are read in order, as if reading an array of the components
+impl<E: Endianness, T: Scalar + BitRead<E>, const D: usize> BitRead<E> for SVector<T, D> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        let components = (0..D).map(|_| stream.read()).collect::<Result<Vec<T>>>()?;
+        Ok(SVector::from_iterator(components))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        let components = (0..D)
+            .map(|_| stream.read_unchecked())
+            .collect::<Result<Vec<T>>>()?;
+        Ok(SVector::from_iterator(components))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        T::bit_size().and_then(|size| size.checked_mul(D))
+    }
+}
+
+/// Every component is read using `size`
+impl<E: Endianness, T: Scalar + BitReadSized<E>, const D: usize> BitReadSized<E> for SVector<T, D> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        let components = (0..D)
+            .map(|_| stream.read_sized(size))
+            .collect::<Result<Vec<T>>>()?;
+        Ok(SVector::from_iterator(components))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        let components = (0..D)
+            .map(|_| stream.read_sized_unchecked(size))
+            .collect::<Result<Vec<T>>>()?;
+        Ok(SVector::from_iterator(components))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        T::bit_size_sized(size).and_then(|size| size.checked_mul(D))
+    }
+}
+
+/// The components are written in order, as if writing an array of the components
+impl<E: Endianness, T: Scalar + BitWrite<E>, const D: usize> BitWrite<E> for SVector<T, D> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        self.iter()
+            .try_for_each(|component| stream.write(component))
+    }
+}
+
+/// Every component is written using `size`
+impl<E: Endianness, T: Scalar + BitWriteSized<E>, const D: usize> BitWriteSized<E>
+    for SVector<T, D>
+{
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        self.iter()
+            .try_for_each(|component| stream.write_sized(component, size))
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{BitRead, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+use std::convert::TryInto;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+
+/// The octets of an `Ipv4Addr` are read in order, independent of the endianness of the stream
+impl<E: Endianness> BitRead<E> for Ipv4Addr {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        let octets: [u8; 4] = stream.read_bytes(4)?.as_slice().try_into().unwrap();
+        Ok(Ipv4Addr::from(octets))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(32)
+    }
+}
+
+/// The octets of an `Ipv6Addr` are read in order, independent of the endianness of the stream
+impl<E: Endianness> BitRead<E> for Ipv6Addr {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        let octets: [u8; 16] = stream.read_bytes(16)?.as_slice().try_into().unwrap();
+        Ok(Ipv6Addr::from(octets))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(128)
+    }
+}
+
+/// A `SocketAddrV4` is read as the address followed by a 16 bit port
+///
+/// The port is read using the endianness of the stream, network byte order requires a [`BigEndian`] stream.
+///
+/// [`BigEndian`]: struct.BigEndian.html
+impl<E: Endianness> BitRead<E> for SocketAddrV4 {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        Ok(SocketAddrV4::new(stream.read()?, stream.read()?))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(48)
+    }
+}
+
+/// The octets of an `Ipv4Addr` are written in order, independent of the endianness of the stream
+impl<E: Endianness> BitWrite<E> for Ipv4Addr {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_bytes(&self.octets())
+    }
+}
+
+/// The octets of an `Ipv6Addr` are written in order, independent of the endianness of the stream
+impl<E: Endianness> BitWrite<E> for Ipv6Addr {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_bytes(&self.octets())
+    }
+}
+
+/// A `SocketAddrV4` is written as the address followed by a 16 bit port
+impl<E: Endianness> BitWrite<E> for SocketAddrV4 {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        self.ip().write(stream)?;
+        self.port().write(stream)
+    }
+}
@@ -1,8 +1,16 @@
-/// some extra number traits
+//! some extra number traits
+//!
+//! These are used as bounds for reading and writing integers and floats, implementing them for
+//! a primitive-like type allows it to be used with [`read_int`](../struct.BitReadStream.html#method.read_int)
+//! and friends.
+
+pub use ::num_traits::{Float, PrimInt};
 
 /// Allow casting floats unchecked
 pub trait UncheckedPrimitiveFloat: Sized {
+    /// Convert from `f32` like an `as` cast
     fn from_f32_unchecked(n: f32) -> Self;
+    /// Convert from `f64` like an `as` cast
     fn from_f64_unchecked(n: f64) -> Self;
 }
 
@@ -30,32 +38,57 @@ impl UncheckedPrimitiveFloat for f64 {
 
 /// Allow casting integers unchecked
 pub trait UncheckedPrimitiveInt: Sized {
+    /// Convert from `u8` like an `as` cast
     fn from_u8_unchecked(n: u8) -> Self;
+    /// Convert from `i8` like an `as` cast
     fn from_i8_unchecked(n: i8) -> Self;
+    /// Convert from `u16` like an `as` cast
     fn from_u16_unchecked(n: u16) -> Self;
+    /// Convert from `i16` like an `as` cast
     fn from_i16_unchecked(n: i16) -> Self;
+    /// Convert from `u32` like an `as` cast
     fn from_u32_unchecked(n: u32) -> Self;
+    /// Convert from `i32` like an `as` cast
     fn from_i32_unchecked(n: i32) -> Self;
+    /// Convert from `u64` like an `as` cast
     fn from_u64_unchecked(n: u64) -> Self;
+    /// Convert from `i64` like an `as` cast
     fn from_i64_unchecked(n: i64) -> Self;
+    /// Convert from `u128` like an `as` cast
     fn from_u128_unchecked(n: u128) -> Self;
+    /// Convert from `i128` like an `as` cast
     fn from_i128_unchecked(n: i128) -> Self;
+    /// Convert from `usize` like an `as` cast
     fn from_usize_unchecked(n: usize) -> Self;
+    /// Convert from `isize` like an `as` cast
     fn from_isize_unchecked(n: isize) -> Self;
 
+    /// Convert into `u8` like an `as` cast
     fn into_u8_unchecked(self) -> u8;
+    /// Convert into `i8` like an `as` cast
     fn into_i8_unchecked(self) -> i8;
+    /// Convert into `u16` like an `as` cast
     fn into_u16_unchecked(self) -> u16;
+    /// Convert into `i16` like an `as` cast
     fn into_i16_unchecked(self) -> i16;
+    /// Convert into `u32` like an `as` cast
     fn into_u32_unchecked(self) -> u32;
+    /// Convert into `i32` like an `as` cast
     fn into_i32_unchecked(self) -> i32;
+    /// Convert into `u64` like an `as` cast
     fn into_u64_unchecked(self) -> u64;
+    /// Convert into `i64` like an `as` cast
     fn into_i64_unchecked(self) -> i64;
+    /// Convert into `u128` like an `as` cast
     fn into_u128_unchecked(self) -> u128;
+    /// Convert into `i128` like an `as` cast
     fn into_i128_unchecked(self) -> i128;
+    /// Convert into `usize` like an `as` cast
     fn into_usize_unchecked(self) -> usize;
+    /// Convert into `isize` like an `as` cast
     fn into_isize_unchecked(self) -> isize;
 
+    /// Convert from any other integer like an `as` cast
     fn from_unchecked<N: UncheckedPrimitiveInt>(n: N) -> Self;
 }
 
@@ -180,7 +213,9 @@ impl_unchecked_int!(i128, into_i128_unchecked);
 impl_unchecked_int!(usize, into_usize_unchecked);
 impl_unchecked_int!(isize, into_isize_unchecked);
 
+/// Whether an integer type is signed
 pub trait IsSigned {
+    /// `true` for signed integer types
     fn is_signed() -> bool;
 }
 
@@ -195,9 +230,12 @@ macro_rules! impl_is_signed {
     };
 }
 
+/// Split an integer into its bytes
 pub trait IntoBytes: Sized {
+    /// Iterator over the bytes
     type Iter: DoubleEndedIterator<Item = u8> + ExactSizeIterator;
 
+    /// The bytes of the integer, in little endian order
     fn into_bytes(self) -> Self::Iter;
 }
 
@@ -205,6 +243,7 @@ macro_rules! impl_into_bytes {
     ($type:ty, $iter:ident) => {
         // once std::array:IntoIter is stabilized we can get rid of this iterator
         // https://github.com/rust-lang/rust/issues/65798
+        /// Iterator over the bytes of an integer
         pub struct $iter {
             data: [u8; std::mem::size_of::<$type>()],
             start: usize,
@@ -212,6 +251,7 @@ macro_rules! impl_into_bytes {
         }
 
         impl $iter {
+            /// Iterate over the bytes of `int`
             pub fn new(int: $type) -> Self {
                 $iter {
                     data: int.to_le_bytes(),
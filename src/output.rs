@@ -0,0 +1,83 @@
+/// Storage that bytes and strings read from a buffer or stream can be appended to
+///
+/// This allows reading owned data into storage other than a `Vec<u8>`, for example to keep the
+/// parsed data in an arena that can be freed in one go.
+///
+/// With the `arena` feature enabled, this is implemented for `bumpalo::collections::Vec<u8>`.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// # let bytes = vec![0x48, 0x65, 0x6c, 0x6c, 0x6f, 0, 0x20, 0x77];
+/// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+/// # let mut stream = BitReadStream::new(buffer);
+/// let mut output = Vec::new();
+/// stream.read_string_into(None, &mut output)?;
+/// stream.read_bytes_into(2, &mut output)?;
+/// assert_eq!(output, b"Hello w");
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub trait ByteOutput {
+    /// Append bytes to the output
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    /// All bytes in the output
+    fn as_slice(&self) -> &[u8];
+
+    /// Shorten the output to `len` bytes
+    fn truncate(&mut self, len: usize);
+
+    /// Reserve space for at least `additional` more bytes
+    #[inline]
+    fn reserve(&mut self, _additional: usize) {}
+}
+
+impl ByteOutput for Vec<u8> {
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len)
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+}
+
+#[cfg(feature = "arena")]
+impl ByteOutput for bumpalo::collections::Vec<'_, u8> {
+    #[inline]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        bumpalo::collections::Vec::extend_from_slice(self, bytes)
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        bumpalo::collections::Vec::truncate(self, len)
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        bumpalo::collections::Vec::reserve(self, additional)
+    }
+}
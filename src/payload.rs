@@ -0,0 +1,159 @@
+use crate::{BitError, BitReadStream, BitWriteStream, Endianness, Result};
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+use std::fmt::Display;
+
+fn invalid_payload(pos: usize, error: impl Display) -> BitError {
+    BitError::InvalidPayload {
+        pos,
+        message: error.to_string(),
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Read `byte_count` bytes from the stream and deserialize them as json, requires the `json` feature
+    ///
+    /// The payload doesn't need to start at a byte boundary in the stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidPayload`]: the bytes are not valid json for `T`
+    ///
+    /// If the payload is invalid the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = br#"{"name":"demo","ticks":[1,2]}"#.to_vec();
+    /// let length = bytes.len();
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let value: serde_json::Value = stream.read_json(length)?;
+    /// assert_eq!(value["name"], "demo");
+    /// assert_eq!(value["ticks"][1], 2);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidPayload`]: enum.ReadError.html#variant.InvalidPayload
+    #[cfg(feature = "json")]
+    pub fn read_json<T: DeserializeOwned>(&mut self, byte_count: usize) -> Result<T> {
+        let pos = self.pos();
+        let bytes = self.read_bytes(byte_count)?;
+        serde_json::from_slice(&bytes).map_err(|e| {
+            self.set_pos(pos)
+                .expect("position was valid before reading");
+            invalid_payload(pos, e)
+        })
+    }
+
+    /// Read `byte_count` bytes from the stream and deserialize them as cbor, requires the `cbor` feature
+    ///
+    /// The payload doesn't need to start at a byte boundary in the stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidPayload`]: the bytes are not valid cbor for `T`
+    ///
+    /// If the payload is invalid the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // a 3 bit header followed by the cbor array `[1, 2]`
+    /// let bytes = vec![0b0001_0101, 0b0000_1100, 0b0001_0000, 0];
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_int::<u8>(3)?, 0b101);
+    /// let value: Vec<u8> = stream.read_cbor(3)?;
+    /// assert_eq!(value, [1, 2]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidPayload`]: enum.ReadError.html#variant.InvalidPayload
+    #[cfg(feature = "cbor")]
+    pub fn read_cbor<T: DeserializeOwned>(&mut self, byte_count: usize) -> Result<T> {
+        let pos = self.pos();
+        let bytes = self.read_bytes(byte_count)?;
+        serde_cbor::from_slice(&bytes).map_err(|e| {
+            self.set_pos(pos)
+                .expect("position was valid before reading");
+            invalid_payload(pos, e)
+        })
+    }
+}
+
+impl<E: Endianness> BitWriteStream<E> {
+    /// Serialize a value as json and write it to the stream, requires the `json` feature
+    ///
+    /// Returns the number of bytes written, which is needed to read the payload back.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::InvalidPayload`]: the value can't be serialized as json
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitWriteStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitWriteStream::new(LittleEndian);
+    /// let length = stream.write_json(&vec!["a", "b"])?;
+    /// assert_eq!(length, 9);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::InvalidPayload`]: enum.ReadError.html#variant.InvalidPayload
+    #[cfg(feature = "json")]
+    pub fn write_json<T: Serialize>(&mut self, value: &T) -> Result<usize> {
+        let bytes = serde_json::to_vec(value).map_err(|e| invalid_payload(self.bit_len(), e))?;
+        self.write_bytes(&bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Serialize a value as cbor and write it to the stream, requires the `cbor` feature
+    ///
+    /// Returns the number of bytes written, which is needed to read the payload back.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::InvalidPayload`]: the value can't be serialized as cbor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitWriteStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitWriteStream::new(LittleEndian);
+    /// let length = stream.write_cbor(&[1u8, 2])?;
+    /// assert_eq!(length, 3);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::InvalidPayload`]: enum.ReadError.html#variant.InvalidPayload
+    #[cfg(feature = "cbor")]
+    pub fn write_cbor<T: Serialize>(&mut self, value: &T) -> Result<usize> {
+        let bytes = serde_cbor::to_vec(value).map_err(|e| invalid_payload(self.bit_len(), e))?;
+        self.write_bytes(&bytes)?;
+        Ok(bytes.len())
+    }
+}
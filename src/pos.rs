@@ -0,0 +1,66 @@
+use std::ops::AddAssign;
+
+use crate::{BitError, Result};
+
+/// A position in bits
+///
+/// All arithmetic on positions is checked, so sizes read from hostile input result in an error instead of
+/// wrapping around to a position that passes the bounds checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct BitPos(usize);
+
+impl BitPos {
+    #[inline]
+    pub fn new(bits: usize) -> Self {
+        BitPos(bits)
+    }
+
+    #[inline]
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// The number of bits between this position and `bit_len`
+    #[inline]
+    pub fn bits_left(self, bit_len: usize) -> usize {
+        bit_len.saturating_sub(self.0)
+    }
+
+    /// The position `count` bits further, or `None` if that can't be represented
+    #[inline]
+    pub fn checked_add(self, count: usize) -> Option<Self> {
+        self.0.checked_add(count).map(BitPos)
+    }
+
+    /// The position after reading `count` bits from this position, in data of `bit_len` bits
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: this position is past `bit_len`
+    /// - [`ReadError::NotEnoughData`]: reading `count` bits would end past `bit_len`
+    #[inline]
+    pub fn advance(self, count: usize, bit_len: usize) -> Result<Self> {
+        if self.0 > bit_len {
+            return Err(BitError::IndexOutOfBounds {
+                pos: self.0,
+                size: bit_len,
+            });
+        }
+        match self.checked_add(count) {
+            Some(end) if end.0 <= bit_len => Ok(end),
+            _ => Err(BitError::NotEnoughData {
+                requested: count,
+                bits_left: self.bits_left(bit_len),
+            }),
+        }
+    }
+}
+
+/// Moving the position forward saturates instead of wrapping, a position that can't be represented ends up past the end
+/// of any data so the next bounds check fails
+impl AddAssign<usize> for BitPos {
+    #[inline]
+    fn add_assign(&mut self, count: usize) {
+        self.0 = self.0.saturating_add(count);
+    }
+}
@@ -0,0 +1,145 @@
+//! Support for the `prefetch` feature, reading ahead of the parser on a background thread
+
+use std::io::{self, BufRead, Read};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread;
+
+use crate::{BitReadStream, BufReadStream, Endianness};
+
+/// The number of chunks the background thread reads ahead by default
+const DEFAULT_DEPTH: usize = 4;
+
+/// Reader that reads ahead of the consumer on a background thread, requires the `prefetch` feature
+///
+/// The background thread reads chunks of `chunk_size` bytes from the inner reader and queues up to `depth` chunks,
+/// so decompression or network latency of the inner reader overlaps with parsing the previous chunks.
+/// Errors from the inner reader are returned once all chunks read before the error have been consumed,
+/// if the inner reader panics an error is returned in the same way instead of ending the data early.
+///
+/// Dropping the reader stops the background thread after its current read.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadStream, LittleEndian, PrefetchReader, Result};
+/// use std::io::Cursor;
+///
+/// # fn main() -> Result<()> {
+/// let reader = PrefetchReader::new(Cursor::new(vec![0x21, 0x43, 0x65, 0x87]), 2, 4);
+/// let mut stream = BitReadStream::from_bufread(reader, 16, LittleEndian);
+/// assert_eq!(stream.read_int::<u16>(16)?, 0x4321);
+/// assert_eq!(stream.read_int::<u16>(16)?, 0x8765);
+/// assert!(stream.at_end()?);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub struct PrefetchReader {
+    receiver: Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    /// position in the current chunk
+    offset: usize,
+    /// the inner reader returned an error or reached its end
+    done: bool,
+}
+
+impl PrefetchReader {
+    /// Start reading ahead from `reader` in chunks of `chunk_size` bytes, queueing at most `depth` chunks
+    ///
+    /// Both the chunk size and depth are at least one.
+    pub fn new<R>(mut reader: R, chunk_size: usize, depth: usize) -> Self
+    where
+        R: Read + Send + 'static,
+    {
+        let chunk_size = chunk_size.max(1);
+        let (sender, receiver) = sync_channel(depth.max(1));
+        thread::spawn(move || loop {
+            let mut chunk = vec![0; chunk_size];
+            let result = match reader.read(&mut chunk) {
+                Ok(0) => {
+                    // an empty chunk marks the end of the reader, so a panic in the reader can be told apart from it
+                    let _ = sender.send(Ok(Vec::new()));
+                    return;
+                }
+                Ok(len) => {
+                    chunk.truncate(len);
+                    Ok(chunk)
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => Err(e),
+            };
+            let failed = result.is_err();
+            // the receiver is gone once the reader is dropped
+            if sender.send(result).is_err() || failed {
+                return;
+            }
+        });
+        PrefetchReader {
+            receiver,
+            chunk: Vec::new(),
+            offset: 0,
+            done: false,
+        }
+    }
+}
+
+impl Read for PrefetchReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for PrefetchReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.offset == self.chunk.len() && !self.done {
+            match self.receiver.recv() {
+                Ok(Ok(chunk)) if chunk.is_empty() => self.done = true,
+                Ok(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.offset = 0;
+                }
+                Ok(Err(e)) => {
+                    self.done = true;
+                    return Err(e);
+                }
+                // the background thread stopped without sending the end marker or an error, the inner reader panicked
+                Err(_) => {
+                    self.done = true;
+                    return Err(io::Error::other(
+                        "the prefetch thread stopped before the end of the reader",
+                    ));
+                }
+            }
+        }
+        Ok(&self.chunk[self.offset..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.offset = (self.offset + amt).min(self.chunk.len());
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Create a stream reading from a [`Read`] that is read ahead on a background thread, requires the `prefetch` feature
+    ///
+    /// The reader is read in chunks of `window_bytes` bytes, see [`PrefetchReader`] and [`BufReadStream`] for details.
+    ///
+    /// [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`PrefetchReader`]: struct.PrefetchReader.html
+    /// [`BufReadStream`]: struct.BufReadStream.html
+    pub fn from_read_prefetch<R>(
+        reader: R,
+        window_bytes: usize,
+        endianness: E,
+    ) -> BufReadStream<PrefetchReader, E>
+    where
+        R: Read + Send + 'static,
+    {
+        let reader = PrefetchReader::new(reader, window_bytes, DEFAULT_DEPTH);
+        BufReadStream::new(reader, window_bytes, endianness)
+    }
+}
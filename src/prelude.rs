@@ -0,0 +1,25 @@
+//! Re-exports of the commonly used traits and types
+//!
+//! ```
+//! use bitbuffer::prelude::*;
+//!
+//! #[derive(BitRead, BitWrite)]
+//! struct Header {
+//!     version: UInt<3>,
+//!     flag: bool,
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0b1101], LittleEndian));
+//! let header: Header = stream.read()?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::{
+    BigEndian, BitAngle, BitError, BitRead, BitReadBuffer, BitReadInto, BitReadIntoSized,
+    BitReadSized, BitReadStream, BitReadWithContext, BitSchema, BitWrite, BitWriteBuffer,
+    BitWriteSized, BitWriteStream, ByteOutput, DecodeNode, Endianness, Int, LazyBitRead,
+    LazyBitReadSized, LittleEndian, Micros, Millis, Nanos, Quantized, Result, Seconds, UInt,
+    VersionedContext, ZigZag,
+};
@@ -0,0 +1,198 @@
+//! Reading the protobuf wire format
+//!
+//! Protobuf messages are byte oriented, these helpers read the basic building blocks of the wire format,
+//! [`read_pb_varint`] and [`read_pb_tag`], and [`skip_pb_field`] skips over fields that the caller doesn't handle.
+//! Messages are usually embedded byte aligned, but the helpers work at any position in the stream.
+//!
+//! ```
+//! use bitbuffer::protobuf::{read_pb_tag, read_pb_varint, skip_pb_field, WireType};
+//! # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+//!
+//! # fn main() -> Result<()> {
+//! // field 1: varint 150, field 2: string "hi"
+//! let bytes = vec![0x08, 0x96, 0x01, 0x12, 0x02, b'h', b'i'];
+//! let buffer = BitReadBuffer::new(bytes, LittleEndian);
+//! let mut stream = BitReadStream::new(buffer);
+//!
+//! let tag = read_pb_tag(&mut stream)?;
+//! assert_eq!((tag.field, tag.wire_type), (1, WireType::Varint));
+//! assert_eq!(read_pb_varint(&mut stream)?, 150);
+//!
+//! let tag = read_pb_tag(&mut stream)?;
+//! assert_eq!((tag.field, tag.wire_type), (2, WireType::LengthDelimited));
+//! skip_pb_field(&mut stream, tag)?;
+//! assert_eq!(stream.bits_left(), 0);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`read_pb_varint`]: fn.read_pb_varint.html
+//! [`read_pb_tag`]: fn.read_pb_tag.html
+//! [`skip_pb_field`]: fn.skip_pb_field.html
+
+use crate::{BitError, BitReadStream, Endianness, Result};
+use std::convert::TryFrom;
+
+/// The maximum number of bytes in a varint
+const MAX_VARINT_BYTES: usize = 10;
+
+/// The encoding of a field value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WireType {
+    /// A varint, used for `int32`, `int64`, `uint32`, `uint64`, `sint32`, `sint64`, `bool` and `enum`
+    Varint = 0,
+    /// 8 bytes, used for `fixed64`, `sfixed64` and `double`
+    Fixed64 = 1,
+    /// A varint length followed by that many bytes, used for strings, bytes, embedded messages and packed fields
+    LengthDelimited = 2,
+    /// The start of a deprecated group
+    StartGroup = 3,
+    /// The end of a deprecated group
+    EndGroup = 4,
+    /// 4 bytes, used for `fixed32`, `sfixed32` and `float`
+    Fixed32 = 5,
+}
+
+impl TryFrom<u8> for WireType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> std::result::Result<Self, u8> {
+        match value {
+            0 => Ok(WireType::Varint),
+            1 => Ok(WireType::Fixed64),
+            2 => Ok(WireType::LengthDelimited),
+            3 => Ok(WireType::StartGroup),
+            4 => Ok(WireType::EndGroup),
+            5 => Ok(WireType::Fixed32),
+            _ => Err(value),
+        }
+    }
+}
+
+/// The key of a field, consisting of the field number and wire type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag {
+    /// The field number
+    pub field: u32,
+    /// The encoding of the field value
+    pub wire_type: WireType,
+}
+
+/// Read a base 128 varint
+///
+/// # Errors
+///
+/// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+/// - [`ReadError::InvalidVarint`]: the varint is longer than 10 bytes or doesn't fit in 64 bits
+///
+/// On error the position of the stream is unchanged.
+///
+/// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+/// [`ReadError::InvalidVarint`]: ../enum.ReadError.html#variant.InvalidVarint
+pub fn read_pb_varint<E: Endianness>(stream: &mut BitReadStream<E>) -> Result<u64> {
+    let start = stream.pos();
+    let mut value = 0u64;
+    for index in 0..MAX_VARINT_BYTES {
+        let byte: u8 = match stream.read_int(8) {
+            Ok(byte) => byte,
+            Err(e) => {
+                stream.set_pos(start)?;
+                return Err(e);
+            }
+        };
+        // only the lowest bit of the last byte fits in 64 bits
+        if index == MAX_VARINT_BYTES - 1 && byte > 1 {
+            break;
+        }
+        value |= u64::from(byte & 0x7f) << (index * 7);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    stream.set_pos(start)?;
+    Err(BitError::InvalidVarint { pos: start })
+}
+
+/// Read the tag of a field
+///
+/// # Errors
+///
+/// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+/// - [`ReadError::InvalidVarint`]: the tag is not a valid varint or the field number doesn't fit in 32 bits
+/// - [`ReadError::InvalidWireType`]: the tag has an unknown wire type
+///
+/// On error the position of the stream is unchanged.
+///
+/// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+/// [`ReadError::InvalidVarint`]: ../enum.ReadError.html#variant.InvalidVarint
+/// [`ReadError::InvalidWireType`]: ../enum.ReadError.html#variant.InvalidWireType
+pub fn read_pb_tag<E: Endianness>(stream: &mut BitReadStream<E>) -> Result<Tag> {
+    let start = stream.pos();
+    let key = read_pb_varint(stream)?;
+    let field = match u32::try_from(key >> 3) {
+        Ok(field) => field,
+        Err(_) => {
+            stream.set_pos(start)?;
+            return Err(BitError::InvalidVarint { pos: start });
+        }
+    };
+    match WireType::try_from((key & 0b111) as u8) {
+        Ok(wire_type) => Ok(Tag { field, wire_type }),
+        Err(wire_type) => {
+            stream.set_pos(start)?;
+            Err(BitError::InvalidWireType {
+                wire_type,
+                pos: start,
+            })
+        }
+    }
+}
+
+/// Skip the value of a field after its tag has been read
+///
+/// Groups are skipped including all nested fields up to the matching end tag.
+///
+/// # Errors
+///
+/// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+/// - [`ReadError::InvalidVarint`]: a length or nested tag is not a valid varint
+/// - [`ReadError::InvalidWireType`]: an end group tag without matching start was found
+/// - [`ReadError::DepthLimitReached`]: groups are nested deeper than the maximum depth of the stream
+///
+/// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+/// [`ReadError::InvalidVarint`]: ../enum.ReadError.html#variant.InvalidVarint
+/// [`ReadError::InvalidWireType`]: ../enum.ReadError.html#variant.InvalidWireType
+/// [`ReadError::DepthLimitReached`]: ../enum.ReadError.html#variant.DepthLimitReached
+pub fn skip_pb_field<E: Endianness>(stream: &mut BitReadStream<E>, tag: Tag) -> Result<()> {
+    match tag.wire_type {
+        WireType::Varint => read_pb_varint(stream).map(|_| ()),
+        WireType::Fixed64 => stream.skip_bits(64),
+        WireType::Fixed32 => stream.skip_bits(32),
+        WireType::LengthDelimited => {
+            let length = read_pb_varint(stream)?;
+            let count = usize::try_from(length)
+                .unwrap_or(usize::MAX)
+                .saturating_mul(8);
+            stream.skip_bits(count)
+        }
+        WireType::StartGroup => stream.nested(|stream| loop {
+            let pos = stream.pos();
+            let inner = read_pb_tag(stream)?;
+            if inner.wire_type == WireType::EndGroup {
+                return if inner.field == tag.field {
+                    Ok(())
+                } else {
+                    Err(BitError::InvalidWireType {
+                        wire_type: WireType::EndGroup as u8,
+                        pos,
+                    })
+                };
+            }
+            skip_pb_field(stream, inner)?;
+        }),
+        WireType::EndGroup => Err(BitError::InvalidWireType {
+            wire_type: WireType::EndGroup as u8,
+            pos: stream.pos(),
+        }),
+    }
+}
@@ -0,0 +1,209 @@
+//! Python bindings for reading buffers and streams, requires the `python` feature
+//!
+//! The bindings expose `BitReadBufferLE`, `BitReadBufferBE`, `BitReadStreamLE` and `BitReadStreamBE` classes
+//! that read bits with the exact same semantics as the rust types. Errors are raised as `ValueError`.
+//!
+//! The classes can be added to an existing python module using [`register`], or used through the
+//! `bitbuffer` module defined by this crate when building it as an extension module.
+//!
+//! ```python
+//! from bitbuffer import BitReadStreamLE
+//!
+//! stream = BitReadStreamLE(open("demo.dem", "rb").read())
+//! magic = stream.read_string(8)
+//! version = stream.read_int(32)
+//! ```
+//!
+//! [`register`]: fn.register.html
+
+use crate::{BigEndian, BitError, BitReadBuffer, BitReadStream, LittleEndian};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+impl From<BitError> for PyErr {
+    fn from(err: BitError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+macro_rules! impl_python_bindings {
+    ($buffer:ident, $buffer_name:literal, $stream:ident, $stream_name:literal, $endianness:ident) => {
+        #[doc = concat!("Python wrapper for a `BitReadBuffer<", stringify!($endianness), ">`")]
+        #[pyclass(name = $buffer_name, unsendable)]
+        pub struct $buffer(BitReadBuffer<$endianness>);
+
+        #[pymethods]
+        impl $buffer {
+            #[new]
+            fn new(bytes: Vec<u8>) -> PyResult<Self> {
+                Ok($buffer(BitReadBuffer::try_new(bytes, $endianness)?))
+            }
+
+            fn bit_len(&self) -> usize {
+                self.0.bit_len()
+            }
+
+            fn byte_len(&self) -> usize {
+                self.0.byte_len()
+            }
+
+            fn read_bool(&self, position: usize) -> PyResult<bool> {
+                Ok(self.0.read_bool(position)?)
+            }
+
+            #[pyo3(signature = (position, count, signed = false))]
+            fn read_int(
+                &self,
+                py: Python<'_>,
+                position: usize,
+                count: usize,
+                signed: bool,
+            ) -> PyResult<PyObject> {
+                Ok(if signed {
+                    self.0.read_int::<i128>(position, count)?.into_py(py)
+                } else {
+                    self.0.read_int::<u128>(position, count)?.into_py(py)
+                })
+            }
+
+            fn read_float32(&self, position: usize) -> PyResult<f32> {
+                Ok(self.0.read_float(position)?)
+            }
+
+            fn read_float64(&self, position: usize) -> PyResult<f64> {
+                Ok(self.0.read_float(position)?)
+            }
+
+            fn read_bytes<'py>(
+                &self,
+                py: Python<'py>,
+                position: usize,
+                byte_count: usize,
+            ) -> PyResult<Bound<'py, PyBytes>> {
+                Ok(PyBytes::new_bound(
+                    py,
+                    &self.0.read_bytes(position, byte_count)?,
+                ))
+            }
+
+            #[pyo3(signature = (position, byte_len = None))]
+            fn read_string(&self, position: usize, byte_len: Option<usize>) -> PyResult<String> {
+                Ok(self.0.read_string(position, byte_len)?)
+            }
+
+            fn stream(&self) -> $stream {
+                $stream(BitReadStream::new(self.0.clone()))
+            }
+        }
+
+        #[doc = concat!("Python wrapper for a `BitReadStream<", stringify!($endianness), ">`")]
+        #[pyclass(name = $stream_name, unsendable)]
+        pub struct $stream(BitReadStream<$endianness>);
+
+        #[pymethods]
+        impl $stream {
+            #[new]
+            fn new(bytes: Vec<u8>) -> PyResult<Self> {
+                Ok($stream(BitReadStream::new(BitReadBuffer::try_new(
+                    bytes,
+                    $endianness,
+                )?)))
+            }
+
+            #[getter]
+            fn pos(&self) -> usize {
+                self.0.pos()
+            }
+
+            fn set_pos(&mut self, pos: usize) -> PyResult<()> {
+                Ok(self.0.set_pos(pos)?)
+            }
+
+            fn bit_len(&self) -> usize {
+                self.0.bit_len()
+            }
+
+            fn bits_left(&self) -> usize {
+                self.0.bits_left()
+            }
+
+            fn skip_bits(&mut self, count: usize) -> PyResult<()> {
+                Ok(self.0.skip_bits(count)?)
+            }
+
+            fn read_bool(&mut self) -> PyResult<bool> {
+                Ok(self.0.read_bool()?)
+            }
+
+            #[pyo3(signature = (count, signed = false))]
+            fn read_int(
+                &mut self,
+                py: Python<'_>,
+                count: usize,
+                signed: bool,
+            ) -> PyResult<PyObject> {
+                Ok(if signed {
+                    self.0.read_int::<i128>(count)?.into_py(py)
+                } else {
+                    self.0.read_int::<u128>(count)?.into_py(py)
+                })
+            }
+
+            fn read_float32(&mut self) -> PyResult<f32> {
+                Ok(self.0.read_float()?)
+            }
+
+            fn read_float64(&mut self) -> PyResult<f64> {
+                Ok(self.0.read_float()?)
+            }
+
+            fn read_bytes<'py>(
+                &mut self,
+                py: Python<'py>,
+                byte_count: usize,
+            ) -> PyResult<Bound<'py, PyBytes>> {
+                Ok(PyBytes::new_bound(py, &self.0.read_bytes(byte_count)?))
+            }
+
+            #[pyo3(signature = (byte_len = None))]
+            fn read_string(&mut self, byte_len: Option<usize>) -> PyResult<String> {
+                Ok(self.0.read_string(byte_len)?)
+            }
+
+            fn read_bits(&mut self, count: usize) -> PyResult<Self> {
+                Ok($stream(self.0.read_bits(count)?))
+            }
+        }
+    };
+}
+
+impl_python_bindings!(
+    PyBitReadBufferLE,
+    "BitReadBufferLE",
+    PyBitReadStreamLE,
+    "BitReadStreamLE",
+    LittleEndian
+);
+impl_python_bindings!(
+    PyBitReadBufferBE,
+    "BitReadBufferBE",
+    PyBitReadStreamBE,
+    "BitReadStreamBE",
+    BigEndian
+);
+
+/// Add the buffer and stream classes to a python module
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyBitReadBufferLE>()?;
+    module.add_class::<PyBitReadBufferBE>()?;
+    module.add_class::<PyBitReadStreamLE>()?;
+    module.add_class::<PyBitReadStreamBE>()?;
+    Ok(())
+}
+
+/// The `bitbuffer` python module
+#[pymodule]
+fn bitbuffer(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    register(module)
+}
@@ -0,0 +1,207 @@
+use crate::{BitRead, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+use std::fmt;
+
+/// A float quantized to a `BITS` bit integer spread evenly between `MIN` and `MAX`
+///
+/// Reading or writing a `Quantized` always uses `BITS` bits, see [`read_quantized_float`] for
+/// details on the encoding. For ranges that don't have integer bounds use [`read_quantized_float`] directly.
+///
+/// `BITS` has to be between 1 and 32.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, BigEndian, Quantized, Result};
+/// #
+/// #[derive(BitRead)]
+/// struct Angles {
+///     pitch: Quantized<8, -90, 90>,
+///     yaw: Quantized<8, 0, 360>,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0, 0b0011_0011], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let angles: Angles = stream.read()?;
+/// assert_eq!(angles.pitch.get(), -90.0);
+/// assert_eq!(angles.yaw.get(), 72.0);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`read_quantized_float`]: struct.BitReadStream.html#method.read_quantized_float
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Quantized<const BITS: usize, const MIN: i32, const MAX: i32>(f32);
+
+impl<const BITS: usize, const MIN: i32, const MAX: i32> Quantized<BITS, MIN, MAX> {
+    const VALID: () = assert!(BITS > 0 && BITS <= 32, "BITS has to be between 1 and 32");
+
+    /// The number of bits used to store the value
+    pub const BITS: usize = BITS;
+
+    /// Create a new quantized float, the value is clamped to the range and rounded once written
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        Quantized(value.clamp(MIN as f32, MAX as f32))
+    }
+
+    /// Get the value of the float
+    #[inline]
+    pub fn get(self) -> f32 {
+        self.0
+    }
+}
+
+impl<const BITS: usize, const MIN: i32, const MAX: i32> fmt::Display for Quantized<BITS, MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<const BITS: usize, const MIN: i32, const MAX: i32> From<Quantized<BITS, MIN, MAX>> for f32 {
+    #[inline]
+    fn from(value: Quantized<BITS, MIN, MAX>) -> Self {
+        value.0
+    }
+}
+
+impl<E: Endianness, const BITS: usize, const MIN: i32, const MAX: i32> BitRead<E>
+    for Quantized<BITS, MIN, MAX>
+{
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        stream
+            .read_quantized_float(BITS, MIN as f32, MAX as f32)
+            .map(Quantized)
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(BITS)
+    }
+}
+
+impl<E: Endianness, const BITS: usize, const MIN: i32, const MAX: i32> BitWrite<E>
+    for Quantized<BITS, MIN, MAX>
+{
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_quantized_float(self.0, BITS, MIN as f32, MAX as f32)
+    }
+}
+
+/// An angle stored as a `BITS` bit binary angular measurement
+///
+/// The full circle is divided into `2^BITS` steps, reading or writing a `BitAngle` always uses `BITS` bits,
+/// see [`read_bit_angle`] for details on the encoding.
+///
+/// `BITS` has to be between 1 and 32.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitAngle, BitRead, BitReadBuffer, BitReadStream, BigEndian, Result};
+/// #
+/// #[derive(BitRead)]
+/// struct View {
+///     pitch: BitAngle<8>,
+///     yaw: BitAngle<16>,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b1100_0000, 0b0100_0000, 0], BigEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let view: View = stream.read()?;
+/// assert_eq!(view.pitch.degrees(), 270.0);
+/// assert_eq!(view.pitch.normalized(), -90.0);
+/// assert_eq!(view.yaw.radians(), std::f32::consts::FRAC_PI_2);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`read_bit_angle`]: struct.BitReadStream.html#method.read_bit_angle
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct BitAngle<const BITS: usize>(f32);
+
+impl<const BITS: usize> BitAngle<BITS> {
+    const VALID: () = assert!(BITS > 0 && BITS <= 32, "BITS has to be between 1 and 32");
+
+    /// The number of bits used to store the angle
+    pub const BITS: usize = BITS;
+
+    /// Create a new angle from degrees, the angle is wrapped to `0..360` and rounded once written
+    #[inline]
+    pub fn new(degrees: f32) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        BitAngle(degrees.rem_euclid(360.0))
+    }
+
+    /// Create a new angle from radians
+    #[inline]
+    pub fn from_radians(radians: f32) -> Self {
+        Self::new(radians.to_degrees())
+    }
+
+    /// The angle in degrees, between `0` and `360`
+    #[inline]
+    pub fn degrees(self) -> f32 {
+        self.0
+    }
+
+    /// The angle in radians, between `0` and `2π`
+    #[inline]
+    pub fn radians(self) -> f32 {
+        self.0.to_radians()
+    }
+
+    /// The angle in degrees normalized to `-180..180`
+    #[inline]
+    pub fn normalized(self) -> f32 {
+        if self.0 >= 180.0 {
+            self.0 - 360.0
+        } else {
+            self.0
+        }
+    }
+}
+
+impl<const BITS: usize> fmt::Display for BitAngle<BITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<const BITS: usize> From<BitAngle<BITS>> for f32 {
+    #[inline]
+    fn from(value: BitAngle<BITS>) -> Self {
+        value.0
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitRead<E> for BitAngle<BITS> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        stream.read_bit_angle(BITS).map(BitAngle)
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(BITS)
+    }
+}
+
+impl<E: Endianness, const BITS: usize> BitWrite<E> for BitAngle<BITS> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_bit_angle(self.0, BITS)
+    }
+}
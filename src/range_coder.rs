@@ -0,0 +1,192 @@
+//! Binary range decoder, as used by LZMA and similar compression formats
+//!
+//! The [`RangeDecoder`] decodes bits using probabilities supplied by the caller through a [`BitModel`],
+//! the context modeling, which model is used for which bit, is left to the format being decoded.
+//!
+//! ```
+//! use bitbuffer::range_coder::{Probability, RangeDecoder};
+//! # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+//!
+//! # fn main() -> Result<()> {
+//! let bytes = vec![0x00, 0x59, 0xa4, 0xbb, 0xe0, 0x00];
+//! let buffer = BitReadBuffer::new(bytes, BigEndian);
+//! let mut stream = BitReadStream::new(buffer);
+//! let mut decoder = RangeDecoder::new(&mut stream)?;
+//!
+//! let mut flag = Probability::default();
+//! assert_eq!(decoder.decode_bit(&mut flag)?, false);
+//! assert_eq!(decoder.decode_bit(&mut flag)?, true);
+//!
+//! let mut literal = [Probability::default(); 0x100];
+//! assert_eq!(decoder.decode_tree(&mut literal, 8)?, b'a' as u32);
+//! assert_eq!(decoder.decode_direct(4)?, 0b1010);
+//! assert!(decoder.is_finished());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RangeDecoder`]: struct.RangeDecoder.html
+//! [`BitModel`]: trait.BitModel.html
+
+use crate::{BitReadStream, Endianness, Result};
+
+/// The number of bits used for probabilities, a probability of `1 << PROBABILITY_BITS` means certain
+pub const PROBABILITY_BITS: u32 = 11;
+
+const TOP_VALUE: u32 = 1 << 24;
+
+/// A model providing the probability of the next decoded bit
+pub trait BitModel {
+    /// The probability of the next bit being `0`, scaled to [`PROBABILITY_BITS`]
+    ///
+    /// [`PROBABILITY_BITS`]: constant.PROBABILITY_BITS.html
+    fn probability(&self) -> u32;
+
+    /// Update the model after a bit has been decoded
+    fn update(&mut self, bit: bool);
+}
+
+/// An adaptive probability as used by LZMA
+///
+/// The probability starts at one half and moves `1/32` of the way towards the decoded bit on every update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Probability(pub u16);
+
+impl Probability {
+    const SHIFT: u32 = 5;
+}
+
+impl Default for Probability {
+    fn default() -> Self {
+        Probability(1 << (PROBABILITY_BITS - 1))
+    }
+}
+
+impl BitModel for Probability {
+    #[inline]
+    fn probability(&self) -> u32 {
+        u32::from(self.0)
+    }
+
+    #[inline]
+    fn update(&mut self, bit: bool) {
+        if bit {
+            self.0 -= self.0 >> Self::SHIFT;
+        } else {
+            self.0 += ((1 << PROBABILITY_BITS) - self.0) >> Self::SHIFT;
+        }
+    }
+}
+
+/// A binary range decoder reading its input from a stream
+///
+/// The encoded data is read from the stream one byte at a time, when the decoder is created 5 bytes
+/// are read, the first of which is always `0` for data encoded by LZMA.
+#[derive(Debug)]
+pub struct RangeDecoder<'a, E: Endianness> {
+    stream: &'a mut BitReadStream<E>,
+    range: u32,
+    code: u32,
+}
+
+impl<'a, E: Endianness> RangeDecoder<'a, E> {
+    /// Create a decoder reading from the current position of the stream
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+    pub fn new(stream: &'a mut BitReadStream<E>) -> Result<Self> {
+        let mut code = 0;
+        for _ in 0..5 {
+            code = (code << 8) | u32::from(stream.read_int::<u8>(8)?);
+        }
+        Ok(RangeDecoder {
+            stream,
+            range: u32::MAX,
+            code,
+        })
+    }
+
+    #[inline]
+    fn normalize(&mut self) -> Result<()> {
+        if self.range < TOP_VALUE {
+            self.range <<= 8;
+            self.code = (self.code << 8) | u32::from(self.stream.read_int::<u8>(8)?);
+        }
+        Ok(())
+    }
+
+    /// Decode a single bit using the provided model, the model is updated with the decoded bit
+    #[inline]
+    pub fn decode_bit<M: BitModel>(&mut self, model: &mut M) -> Result<bool> {
+        let bound = (self.range >> PROBABILITY_BITS) * model.probability();
+        let bit = if self.code < bound {
+            self.range = bound;
+            false
+        } else {
+            self.range -= bound;
+            self.code -= bound;
+            true
+        };
+        model.update(bit);
+        self.normalize()?;
+        Ok(bit)
+    }
+
+    /// Decode `count` bits with a fixed probability of one half, most significant bit first
+    ///
+    /// `count` can be at most 32.
+    pub fn decode_direct(&mut self, count: usize) -> Result<u32> {
+        let mut result = 0;
+        for _ in 0..count {
+            self.range >>= 1;
+            let bit = self.code >= self.range;
+            if bit {
+                self.code -= self.range;
+            }
+            result = (result << 1) | u32::from(bit);
+            self.normalize()?;
+        }
+        Ok(result)
+    }
+
+    /// Decode a `bits` wide value using a binary tree of models, most significant bit first
+    ///
+    /// The model used for every bit is selected by the bits decoded before it, `models` needs to contain
+    /// at least `1 << bits` models.
+    pub fn decode_tree<M: BitModel>(&mut self, models: &mut [M], bits: usize) -> Result<u32> {
+        let mut index = 1;
+        for _ in 0..bits {
+            index = (index << 1) | self.decode_bit(&mut models[index])? as usize;
+        }
+        Ok((index - (1 << bits)) as u32)
+    }
+
+    /// Decode a `bits` wide value using a binary tree of models, least significant bit first
+    ///
+    /// The model used for every bit is selected by the bits decoded before it, `models` needs to contain
+    /// at least `1 << bits` models.
+    pub fn decode_tree_reverse<M: BitModel>(
+        &mut self,
+        models: &mut [M],
+        bits: usize,
+    ) -> Result<u32> {
+        let mut index = 1;
+        let mut result = 0;
+        for i in 0..bits {
+            let bit = self.decode_bit(&mut models[index])?;
+            index = (index << 1) | bit as usize;
+            result |= u32::from(bit) << i;
+        }
+        Ok(result)
+    }
+
+    /// Check if the decoder reached the end of the encoded data
+    ///
+    /// Data encoded by LZMA always ends with the decoder in this state.
+    pub fn is_finished(&self) -> bool {
+        self.code == 0
+    }
+}
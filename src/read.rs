@@ -1,8 +1,9 @@
 use crate::endianness::{BigEndian, LittleEndian};
-use crate::{BitReadStream, Endianness, Result};
+use crate::{BitError, BitReadStream, Endianness, Result};
 use std::cell::RefCell;
 use std::cmp::min;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::mem::size_of;
@@ -93,10 +94,18 @@ pub trait BitRead<E: Endianness>: Sized {
     /// Read the type from stream
     fn read(stream: &mut BitReadStream<E>) -> Result<Self>;
 
-    /// Note: only the bounds are unchecked
+    /// Read the type from stream without checking if enough data is left
     ///
-    /// any other validations (e.g. checking for valid utf8) still needs to be done
-    #[doc(hidden)]
+    /// Note: only the bounds are unchecked, any other validations (e.g. checking for valid utf8) still need to be done.
+    /// Types with a fixed size can override this to skip the bounds checks of the individual reads.
+    ///
+    /// # Safety
+    ///
+    /// The type needs to have a fixed size and the stream needs to have at least [`bit_size`] bits left.
+    /// See the [`unchecked`] module for details.
+    ///
+    /// [`bit_size`]: #method.bit_size
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
         Self::read(stream)
@@ -119,6 +128,29 @@ pub trait BitRead<E: Endianness>: Sized {
     fn bit_size() -> Option<usize> {
         None
     }
+
+    /// The minimum number of bits that will be read
+    ///
+    /// This is used to reject length prefixes that can't possibly fit in the stream
+    /// before allocating space for the elements.
+    #[inline]
+    fn min_bit_size() -> usize {
+        Self::bit_size().unwrap_or(0)
+    }
+
+    /// Read `count` values, used by the [`BitReadSized`] implementation of `Vec<T>`
+    ///
+    /// Types can override this to read multiple values at once, the caller has already checked
+    /// that `count` values of [`min_bit_size`](#method.min_bit_size) bits fit in the stream.
+    #[doc(hidden)]
+    #[inline]
+    fn read_vec(stream: &mut BitReadStream<E>, count: usize) -> Result<Vec<Self>> {
+        let mut vec = Vec::with_capacity(min(count, 128));
+        for _ in 0..count {
+            vec.push(stream.read()?)
+        }
+        Ok(vec)
+    }
 }
 
 macro_rules! impl_read_int {
@@ -201,6 +233,85 @@ impl_read_int_nonzero!(std::num::NonZeroU32);
 impl_read_int_nonzero!(std::num::NonZeroU64);
 impl_read_int_nonzero!(std::num::NonZeroU128);
 
+/// `char` is read as a 32 bit unicode scalar value
+impl<E: Endianness> BitRead<E> for char {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        char_from_u32(stream.read_int(32)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        char_from_u32(stream.read_int_unchecked(32))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(32)
+    }
+}
+
+#[inline]
+fn char_from_u32(value: u32) -> Result<char> {
+    std::char::from_u32(value).ok_or(BitError::InvalidChar { value })
+}
+
+/// `usize` is always read as 64 bits, independent of the platform
+///
+/// Reading a value that doesn't fit in a `usize` on the current platform results in an error.
+impl<E: Endianness> BitRead<E> for usize {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        usize_from_u64(stream.read_int(64)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        usize_from_u64(stream.read_int_unchecked(64))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(64)
+    }
+}
+
+/// `isize` is always read as 64 bits, independent of the platform
+///
+/// Reading a value that doesn't fit in a `isize` on the current platform results in an error.
+impl<E: Endianness> BitRead<E> for isize {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        isize_from_i64(stream.read_int(64)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>) -> Result<Self> {
+        isize_from_i64(stream.read_int_unchecked(64))
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(64)
+    }
+}
+
+#[inline]
+fn usize_from_u64(value: u64) -> Result<usize> {
+    usize::try_from(value).map_err(|_| BitError::IntegerOutOfRange {
+        value: value as i128,
+        bits: usize::BITS as usize,
+    })
+}
+
+#[inline]
+fn isize_from_i64(value: i64) -> Result<isize> {
+    isize::try_from(value).map_err(|_| BitError::IntegerOutOfRange {
+        value: value as i128,
+        bits: isize::BITS as usize,
+    })
+}
+
 impl<E: Endianness> BitRead<E> for f32 {
     #[inline]
     fn read(stream: &mut BitReadStream<E>) -> Result<f32> {
@@ -250,6 +361,14 @@ impl<E: Endianness> BitRead<E> for bool {
     fn bit_size() -> Option<usize> {
         Some(1)
     }
+
+    /// Reads the bits a word at a time
+    #[inline]
+    fn read_vec(stream: &mut BitReadStream<E>, count: usize) -> Result<Vec<bool>> {
+        let mut vec = Vec::with_capacity(count);
+        stream.read_bits_with(count, |bit| vec.push(bit))?;
+        Ok(vec)
+    }
 }
 
 impl<E: Endianness> BitRead<E> for String {
@@ -257,6 +376,12 @@ impl<E: Endianness> BitRead<E> for String {
     fn read(stream: &mut BitReadStream<E>) -> Result<String> {
         stream.read_string(None)
     }
+
+    #[inline]
+    fn min_bit_size() -> usize {
+        // the null terminator
+        8
+    }
 }
 
 impl<E: Endianness, T: BitRead<E>> BitRead<E> for Rc<T> {
@@ -274,6 +399,11 @@ impl<E: Endianness, T: BitRead<E>> BitRead<E> for Rc<T> {
     fn bit_size() -> Option<usize> {
         T::bit_size()
     }
+
+    #[inline]
+    fn min_bit_size() -> usize {
+        T::min_bit_size()
+    }
 }
 
 impl<E: Endianness, T: BitRead<E>> BitRead<E> for Arc<T> {
@@ -291,6 +421,11 @@ impl<E: Endianness, T: BitRead<E>> BitRead<E> for Arc<T> {
     fn bit_size() -> Option<usize> {
         T::bit_size()
     }
+
+    #[inline]
+    fn min_bit_size() -> usize {
+        T::min_bit_size()
+    }
 }
 
 impl<E: Endianness, T: BitRead<E>> BitRead<E> for Box<T> {
@@ -308,6 +443,11 @@ impl<E: Endianness, T: BitRead<E>> BitRead<E> for Box<T> {
     fn bit_size() -> Option<usize> {
         T::bit_size()
     }
+
+    #[inline]
+    fn min_bit_size() -> usize {
+        T::min_bit_size()
+    }
 }
 
 macro_rules! impl_read_tuple {
@@ -325,7 +465,12 @@ macro_rules! impl_read_tuple {
 
             #[inline]
             fn bit_size() -> Option<usize> {
-                Some(0)$(.and_then(|sum| <$type>::bit_size().map(|size| sum + size)))*
+                Some(0usize)$(.and_then(|sum| <$type>::bit_size().and_then(|size| sum.checked_add(size))))*
+            }
+
+            #[inline]
+            fn min_bit_size() -> usize {
+                0usize$(.saturating_add(<$type>::min_bit_size()))*
             }
         }
     };
@@ -403,7 +548,17 @@ pub trait BitReadSized<E: Endianness>: Sized {
     /// Read the type from stream
     fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self>;
 
-    #[doc(hidden)]
+    /// Read the type from stream without checking if enough data is left
+    ///
+    /// Note: only the bounds are unchecked, any other validations (e.g. checking for valid utf8) still need to be done.
+    ///
+    /// # Safety
+    ///
+    /// The type needs to have a fixed size for `size` and the stream needs to have at least [`bit_size`] bits left.
+    /// See the [`unchecked`] module for details.
+    ///
+    /// [`bit_size`]: #method.bit_size
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
         Self::read(stream, size)
@@ -460,6 +615,60 @@ impl_read_int_sized!(i32);
 impl_read_int_sized!(i64);
 impl_read_int_sized!(i128);
 
+/// Reads a unicode scalar value of `size` bits, e.g. 7 for ascii or 21 for any `char`
+impl<E: Endianness> BitReadSized<E> for char {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        char_from_u32(stream.read_int(size)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        char_from_u32(stream.read_int_unchecked(size))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        Some(size)
+    }
+}
+
+/// Reads `size` bits, up to 64, independent of the platform
+impl<E: Endianness> BitReadSized<E> for usize {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        usize_from_u64(stream.read_int(size)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        usize_from_u64(stream.read_int_unchecked(size))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        Some(size)
+    }
+}
+
+/// Reads `size` bits, up to 64, independent of the platform
+impl<E: Endianness> BitReadSized<E> for isize {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        isize_from_i64(stream.read_int(size)?)
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        isize_from_i64(stream.read_int_unchecked(size))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        Some(size)
+    }
+}
+
 impl<E: Endianness> BitReadSized<E> for String {
     #[inline]
     fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<String> {
@@ -472,6 +681,57 @@ impl<E: Endianness> BitReadSized<E> for String {
     }
 }
 
+impl<E: Endianness, T: BitReadSized<E>> BitReadSized<E> for Rc<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Rc::new(T::read(stream, size)?))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Rc::new(T::read_unchecked(stream, size)?))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        T::bit_size_sized(size)
+    }
+}
+
+impl<E: Endianness, T: BitReadSized<E>> BitReadSized<E> for Arc<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Arc::new(T::read(stream, size)?))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Arc::new(T::read_unchecked(stream, size)?))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        T::bit_size_sized(size)
+    }
+}
+
+impl<E: Endianness, T: BitReadSized<E>> BitReadSized<E> for Box<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Box::new(T::read(stream, size)?))
+    }
+
+    #[inline]
+    unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        Ok(Box::new(T::read_unchecked(stream, size)?))
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        T::bit_size_sized(size)
+    }
+}
+
 /// Read a boolean, if true, read `T`, else return `None`
 impl<E: Endianness, T: BitRead<E>> BitRead<E> for Option<T> {
     fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
@@ -481,6 +741,11 @@ impl<E: Endianness, T: BitRead<E>> BitRead<E> for Option<T> {
             Ok(None)
         }
     }
+
+    #[inline]
+    fn min_bit_size() -> usize {
+        1
+    }
 }
 
 impl<E: Endianness, T: BitReadSized<E>> BitReadSized<E> for Option<T> {
@@ -505,18 +770,37 @@ impl<E: Endianness> BitReadSized<E> for BitReadStream<E> {
     }
 }
 
+/// Check that `count` elements of at least `min_bit_size` bits can fit in the stream
+///
+/// This prevents hostile length prefixes from causing large allocations or long loops
+#[inline]
+pub(crate) fn check_element_count<E: Endianness>(
+    stream: &BitReadStream<E>,
+    count: usize,
+    min_bit_size: usize,
+) -> Result<()> {
+    if min_bit_size > 0 && count > stream.bits_left() / min_bit_size {
+        Err(BitError::NotEnoughData {
+            requested: count.saturating_mul(min_bit_size),
+            bits_left: stream.bits_left(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
 /// Read `T` `size` times and return as `Vec<T>`
+///
+/// A `Vec<bool>` reads `size` individual bits.
 impl<E: Endianness, T: BitRead<E>> BitReadSized<E> for Vec<T> {
     fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
-        let mut vec = Vec::with_capacity(min(size, 128));
-        for _ in 0..size {
-            vec.push(stream.read()?)
-        }
-        Ok(vec)
+        check_element_count(stream, size, T::min_bit_size())?;
+        T::read_vec(stream, size)
     }
 
     #[inline]
     unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        check_element_count(stream, size, T::min_bit_size())?;
         let mut vec = Vec::with_capacity(min(size, 128));
         for _ in 0..size {
             vec.push(stream.read_unchecked()?)
@@ -526,7 +810,7 @@ impl<E: Endianness, T: BitRead<E>> BitReadSized<E> for Vec<T> {
 
     #[inline]
     fn bit_size_sized(size: usize) -> Option<usize> {
-        T::bit_size().map(|element_size| size * element_size)
+        T::bit_size().and_then(|element_size| size.checked_mul(element_size))
     }
 }
 
@@ -542,6 +826,11 @@ impl<E: Endianness, T: BitRead<E>> BitReadSized<E> for Vec<T> {
 #[allow(clippy::implicit_hasher)]
 impl<E: Endianness, K: BitRead<E> + Eq + Hash, T: BitRead<E>> BitReadSized<E> for HashMap<K, T> {
     fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        check_element_count(
+            stream,
+            size,
+            K::min_bit_size().saturating_add(T::min_bit_size()),
+        )?;
         let mut map = HashMap::with_capacity(min(size, 128));
         for _ in 0..size {
             let key = stream.read()?;
@@ -553,6 +842,11 @@ impl<E: Endianness, K: BitRead<E> + Eq + Hash, T: BitRead<E>> BitReadSized<E> fo
 
     #[inline]
     unsafe fn read_unchecked(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        check_element_count(
+            stream,
+            size,
+            K::min_bit_size().saturating_add(T::min_bit_size()),
+        )?;
         let mut map = HashMap::with_capacity(min(size, 128));
         for _ in 0..size {
             let key = stream.read_unchecked()?;
@@ -565,13 +859,121 @@ impl<E: Endianness, K: BitRead<E> + Eq + Hash, T: BitRead<E>> BitReadSized<E> fo
     #[inline]
     fn bit_size_sized(size: usize) -> Option<usize> {
         if let (Some(key_size), Some(value_size)) = (K::bit_size(), T::bit_size()) {
-            Some(size * (key_size + value_size))
+            size.checked_mul(key_size + value_size)
         } else {
             None
         }
     }
 }
 
+/// Trait for types that need some state from outside the type itself to be read from a stream
+///
+/// The context is passed by reference to every read, typical examples are the negotiated protocol version
+/// or a set of enabled extensions that decide which fields are present and how large they are.
+///
+/// The `BitReadWithContext` trait can be used with `#[derive]` on structs and enums, the context type is set
+/// with the `context` attribute and is available as `ctx` in the expressions used for `size` and `read_if` attributes.
+///
+/// - `#[read_if = "expr"]` on a field of type `Option<T>` reads `T` only if the expression is true
+/// - `#[context]` on a field reads the field using `BitReadWithContext`, with the same context
+/// - `#[default = "expr"]` sets the value for fields skipped by `read_if` or a version attribute, a field with
+///   a `default` and `read_if` attribute is read as `T` instead of `Option<T>`
+///
+/// ## Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BitReadWithContext, LittleEndian, Result};
+/// #
+/// struct Session {
+///     version: u8,
+///     id_bits: usize,
+/// }
+///
+/// #[derive(BitReadWithContext, Debug, PartialEq)]
+/// #[context = "Session"]
+/// struct Header {
+///     #[size = "ctx.id_bits"]
+///     id: u32,
+///     #[read_if = "ctx.version >= 2"]
+///     flags: Option<u8>,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let session = Session { version: 2, id_bits: 12 };
+/// let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0x34, 0x12, 0xff], LittleEndian));
+/// let header: Header = stream.read_with_context(&session)?;
+/// assert_eq!(header, Header { id: 0x234, flags: Some(0xf1) });
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub trait BitReadWithContext<E: Endianness, Ctx: ?Sized>: Sized {
+    /// Read the type from stream using the provided context
+    fn read_with_context(stream: &mut BitReadStream<E>, ctx: &Ctx) -> Result<Self>;
+}
+
+/// Context types that carry a format or protocol version
+///
+/// Fields of types deriving [`BitReadWithContext`] can use the `since_version` and `until_version` attributes
+/// to only be read for a range of versions, both bounds are inclusive. Fields that are not read are set to their default value.
+///
+/// ## Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, BitReadWithContext, LittleEndian, Result, VersionedContext};
+/// #
+/// struct Demo {
+///     protocol: u32,
+/// }
+///
+/// impl VersionedContext for Demo {
+///     fn version(&self) -> u64 {
+///         self.protocol.into()
+///     }
+/// }
+///
+/// #[derive(BitReadWithContext, Debug, PartialEq)]
+/// #[context = "Demo"]
+/// struct Player {
+///     health: u8,
+///     #[since_version = 24]
+///     armor: u8,
+///     #[until_version = 12]
+///     legacy_flags: u16,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let mut stream = BitReadStream::new(BitReadBuffer::new(vec![100, 50, 0xff], LittleEndian));
+/// let player: Player = stream.read_with_context(&Demo { protocol: 24 })?;
+/// assert_eq!(player, Player { health: 100, armor: 50, legacy_flags: 0 });
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitReadWithContext`]: trait.BitReadWithContext.html
+pub trait VersionedContext {
+    /// The version of the data being read
+    fn version(&self) -> u64;
+}
+
+macro_rules! impl_versioned_context_int {
+    ($type:ty) => {
+        impl VersionedContext for $type {
+            #[inline]
+            fn version(&self) -> u64 {
+                *self as u64
+            }
+        }
+    };
+}
+
+impl_versioned_context_int!(u8);
+impl_versioned_context_int!(u16);
+impl_versioned_context_int!(u32);
+impl_versioned_context_int!(u64);
+impl_versioned_context_int!(usize);
+
 #[derive(Clone, Debug)]
 /// Struct that lazily reads it's contents from the stream
 pub struct LazyBitRead<T: BitRead<E>, E: Endianness> {
@@ -0,0 +1,97 @@
+use crate::{BitRead, BitReadSized, BitReadStream, Endianness};
+
+/// Read multiple values from a stream with a single bounds check
+///
+/// Every value is given a name and a type, values implementing [`BitReadSized`] can be read by
+/// adding a `#[size = ..]` attribute before the type. The values are returned as a tuple
+/// in a [`Result`].
+///
+/// When all types have a fixed size, the bounds and read limits are checked once for all values
+/// combined before reading them without further checks, which reduces the per value overhead
+/// when reading many small values. Otherwise the values are read one by one as if read with
+/// [`read`] and [`read_sized`].
+///
+/// The size expressions are evaluated once, before any value is read.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{read_all, BitReadBuffer, BitReadStream, LittleEndian, Result};
+/// #
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b1011_0101, 0b0110_1010], LittleEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let (kind, flags, last) = read_all!(stream => kind: u8, flags: #[size = 3] u8, last: bool)?;
+/// assert_eq!(kind, 0b1011_0101);
+/// assert_eq!(flags, 0b010);
+/// assert_eq!(last, true);
+/// assert_eq!(stream.pos(), 12);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitReadSized`]: trait.BitReadSized.html
+/// [`Result`]: type.Result.html
+/// [`read`]: struct.BitReadStream.html#method.read
+/// [`read_sized`]: struct.BitReadStream.html#method.read_sized
+#[macro_export]
+macro_rules! read_all {
+    (@size $stream:ident, $type:ty) => {
+        (0usize, $crate::__private::read_all_bit_size::<_, $type>($stream))
+    };
+    (@size $stream:ident, $type:ty, $size:expr) => {{
+        let size: usize = $size;
+        (size, $crate::__private::read_all_bit_size_sized::<_, $type>($stream, size))
+    }};
+    (@read_unchecked $stream:ident, $type:ty, $input:expr) => {
+        $stream.read_unchecked::<$type>()
+    };
+    (@read_unchecked $stream:ident, $type:ty, $input:expr, $size:expr) => {
+        $stream.read_sized_unchecked::<$type>($input)
+    };
+    (@read $stream:ident, $type:ty, $input:expr) => {
+        $stream.read::<$type>()
+    };
+    (@read $stream:ident, $type:ty, $input:expr, $size:expr) => {
+        $stream.read_sized::<$type>($input)
+    };
+    ($stream:expr => $($name:ident : $(#[size = $size:expr])? $type:ty),+ $(,)?) => {
+        (|stream: &mut $crate::BitReadStream<_>| -> $crate::Result<_> {
+            $(let $name = $crate::read_all!(@size stream, $type $(, $size)?);)+
+            let total = Some(0usize)$(.and_then(|total| total.checked_add($name.1?)))+;
+            match total {
+                Some(total) => {
+                    stream.check_read(total)?;
+                    $(let $name: $type = unsafe {
+                        $crate::read_all!(@read_unchecked stream, $type, $name.0 $(, $size)?)?
+                    };)+
+                    Ok(($($name,)+))
+                }
+                None => {
+                    $(let $name: $type = $crate::read_all!(@read stream, $type, $name.0 $(, $size)?)?;)+
+                    Ok(($($name,)+))
+                }
+            }
+        })(($stream).__layout_stream())
+    };
+}
+
+/// The fixed size of `T` when read from `stream`
+#[doc(hidden)]
+#[inline(always)]
+pub fn read_all_bit_size<E: Endianness, T: BitRead<E>>(
+    _stream: &BitReadStream<E>,
+) -> Option<usize> {
+    T::bit_size()
+}
+
+/// The fixed size of `T` when read from `stream` with `size`
+#[doc(hidden)]
+#[inline(always)]
+pub fn read_all_bit_size_sized<E: Endianness, T: BitReadSized<E>>(
+    _stream: &BitReadStream<E>,
+    size: usize,
+) -> Option<usize> {
+    T::bit_size_sized(size)
+}
@@ -0,0 +1,135 @@
+use std::cmp::min;
+use std::mem::take;
+
+use crate::read::check_element_count;
+use crate::{BitRead, BitReadStream, Endianness, Result};
+
+/// Trait for types that can be read into an existing value, reusing the allocations of the value
+///
+/// When reading many values of the same type in a loop, reading into the value of the previous iteration avoids
+/// allocating new strings and vectors for every value.
+///
+/// On error the value is left in a valid but unspecified state.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadBuffer, BitReadInto, BitReadStream, Endianness, LittleEndian, Result};
+///
+/// #[derive(Default)]
+/// struct Packet {
+///     name: String,
+///     values: Vec<u8>,
+/// }
+///
+/// impl<E: Endianness> BitReadInto<E> for Packet {
+///     fn read_into(&mut self, stream: &mut BitReadStream<E>) -> Result<()> {
+///         stream.read_into(&mut self.name)?;
+///         let count: u8 = stream.read()?;
+///         stream.read_into_sized(&mut self.values, count as usize)
+///     }
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let bytes = b"a\0\x02\x01\x02bc\0\x01\x03".to_vec();
+/// let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+/// let mut packet = Packet::default();
+/// stream.read_into(&mut packet)?;
+/// assert_eq!(packet.name, "a");
+/// assert_eq!(packet.values, [1, 2]);
+/// stream.read_into(&mut packet)?;
+/// assert_eq!(packet.name, "bc");
+/// assert_eq!(packet.values, [3]);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub trait BitReadInto<E: Endianness> {
+    /// Read the type from the stream into `self`
+    fn read_into(&mut self, stream: &mut BitReadStream<E>) -> Result<()>;
+}
+
+/// Trait for types that can be read into an existing value when the size is known, reusing the allocations of the value
+///
+/// See [`BitReadInto`] for details.
+///
+/// [`BitReadInto`]: trait.BitReadInto.html
+pub trait BitReadIntoSized<E: Endianness> {
+    /// Read the type from the stream into `self`, using `size` the same way as [`BitReadSized`]
+    ///
+    /// [`BitReadSized`]: trait.BitReadSized.html
+    fn read_into_sized(&mut self, stream: &mut BitReadStream<E>, size: usize) -> Result<()>;
+}
+
+macro_rules! impl_read_into_value {
+    ($($type:ty),*) => {
+        $(
+            impl<E: Endianness> BitReadInto<E> for $type {
+                #[inline]
+                fn read_into(&mut self, stream: &mut BitReadStream<E>) -> Result<()> {
+                    *self = stream.read()?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_read_into_value!(
+    u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, bool, char, f32, f64
+);
+
+/// Read a string into the allocation of `string`, leaving it empty on error
+fn read_string_into_existing<E: Endianness>(
+    string: &mut String,
+    stream: &mut BitReadStream<E>,
+    byte_len: Option<usize>,
+) -> Result<()> {
+    let mut bytes = take(string).into_bytes();
+    bytes.clear();
+    let result = stream.read_string_into(byte_len, &mut bytes);
+    if result.is_err() {
+        bytes.clear();
+    }
+    // safety: on success read_string_into only writes valid utf8
+    *string = unsafe { String::from_utf8_unchecked(bytes) };
+    result
+}
+
+/// Read a null-terminated string
+impl<E: Endianness> BitReadInto<E> for String {
+    fn read_into(&mut self, stream: &mut BitReadStream<E>) -> Result<()> {
+        read_string_into_existing(self, stream, None)
+    }
+}
+
+/// Read a string of `size` bytes
+impl<E: Endianness> BitReadIntoSized<E> for String {
+    fn read_into_sized(&mut self, stream: &mut BitReadStream<E>, size: usize) -> Result<()> {
+        read_string_into_existing(self, stream, Some(size))
+    }
+}
+
+/// Read `size` elements, the existing elements are read into and new elements are only created when the vector is
+/// shorter than `size`
+impl<E: Endianness, T: BitRead<E> + BitReadInto<E>> BitReadIntoSized<E> for Vec<T> {
+    fn read_into_sized(&mut self, stream: &mut BitReadStream<E>, size: usize) -> Result<()> {
+        check_element_count(stream, size, T::min_bit_size())?;
+        self.truncate(size);
+        for element in self.iter_mut() {
+            stream.read_into(element)?;
+        }
+        self.reserve(min(size - self.len(), 128));
+        while self.len() < size {
+            self.push(stream.read()?);
+        }
+        Ok(())
+    }
+}
+
+impl<E: Endianness, T: BitReadInto<E>> BitReadInto<E> for Box<T> {
+    #[inline]
+    fn read_into(&mut self, stream: &mut BitReadStream<E>) -> Result<()> {
+        T::read_into(self, stream)
+    }
+}
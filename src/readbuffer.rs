@@ -1,20 +1,44 @@
 use std::cmp::min;
 use std::fmt;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
 use std::marker::PhantomData;
 use std::mem::size_of;
-use std::ops::{BitOrAssign, BitXor};
+use std::ops::{BitOrAssign, BitXor, Range};
 use std::rc::Rc;
 
 use num_traits::{Float, PrimInt};
 
 use crate::endianness::Endianness;
 use crate::num_traits::{IsSigned, UncheckedPrimitiveFloat, UncheckedPrimitiveInt};
-use crate::{BitError, Result};
+use crate::pos::BitPos;
+use crate::storage::Storage;
+use crate::{BitError, ByteOutput, Result};
 use std::convert::TryInto;
 
 const USIZE_SIZE: usize = size_of::<usize>();
 
+/// How null bytes in a fixed length string are handled
+///
+/// Used by [`read_string_mode`] and the `str_mode` derive attribute.
+///
+/// [`read_string_mode`]: struct.BitReadStream.html#method.read_string_mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StringMode {
+    /// The string ends at the first null byte, the remaining bytes are skipped without being validated
+    NullTerminated,
+    /// All bytes are part of the string, including any null bytes
+    FixedKeepNulls,
+    /// Null bytes at the end of the string are removed, null bytes in the middle of the string are kept
+    ///
+    /// This is the behavior of [`read_string`] when a length is given.
+    ///
+    /// [`read_string`]: struct.BitReadStream.html#method.read_string
+    #[default]
+    FixedTrimTrailingNulls,
+}
+
 /// Buffer that allows reading integers of arbitrary bit length and non byte-aligned integers
 ///
 /// # Examples
@@ -38,16 +62,91 @@ pub struct BitReadBuffer<E>
 where
     E: Endianness,
 {
-    bytes: Rc<Vec<u8>>,
+    bytes: Rc<dyn Storage>,
     bit_len: usize,
     endianness: PhantomData<E>,
+    view: Option<ByteView>,
+    /// Lazily transformed bytes, the view is applied on top of the transformed bytes
+    transform: Option<ByteTransform>,
+}
+
+/// The bytes of a buffer, transformed while reading
+type ByteTransform = Rc<dyn Fn(usize) -> u8>;
+
+/// Mapping from the bytes as seen trough a reversed view to the underlying bytes
+#[derive(Debug, Clone, Copy)]
+struct ByteView {
+    /// underlying index of the first byte in the view
+    start: usize,
+    /// whether the underlying bytes are iterated in reverse
+    descending: bool,
+    /// whether the bits in every byte are reversed
+    reverse_bits: bool,
+    /// number of bytes in the view, bytes past the end read as 0
+    byte_len: usize,
+}
+
+impl ByteView {
+    #[inline]
+    fn get(&self, index: usize, underlying: impl FnOnce(usize) -> u8) -> u8 {
+        if index >= self.byte_len {
+            return 0;
+        }
+        let byte = if self.descending {
+            underlying(self.start - index)
+        } else {
+            underlying(self.start + index)
+        };
+        if self.reverse_bits {
+            byte.reverse_bits()
+        } else {
+            byte
+        }
+    }
+
+    /// Skip the first `count` bytes of this view
+    fn skip(self, count: usize) -> Self {
+        ByteView {
+            start: if self.descending {
+                self.start - count
+            } else {
+                self.start + count
+            },
+            byte_len: self.byte_len - count,
+            ..self
+        }
+    }
+
+    /// Reverse the order of the first `byte_len` bytes of this view
+    fn reversed(self, byte_len: usize, reverse_bits: bool) -> Self {
+        let last = byte_len.saturating_sub(1);
+        ByteView {
+            start: if self.descending {
+                self.start - last
+            } else {
+                self.start + last
+            },
+            descending: !self.descending,
+            reverse_bits: self.reverse_bits ^ reverse_bits,
+            byte_len,
+        }
+    }
 }
 
 impl<E> BitReadBuffer<E>
 where
     E: Endianness,
 {
-    /// Create a new BitBuffer from a byte vector
+    /// The largest number of bytes a buffer can contain, ensuring that every bit position fits in a `usize`
+    pub const MAX_BYTE_LEN: usize = usize::MAX / 8 - USIZE_SIZE;
+
+    /// Create a new BitBuffer from a byte vector or any other [`Storage`]
+    ///
+    /// The storage is shared by all clones and sub buffers of the buffer, it is never copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is larger than [`MAX_BYTE_LEN`], use [`try_new`] to handle this case.
     ///
     /// # Examples
     ///
@@ -59,17 +158,64 @@ where
     ///     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
     /// ];
     /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    ///
+    /// let shared: std::sync::Arc<[u8]> = std::sync::Arc::from(&[1, 2, 3][..]);
+    /// let buffer = BitReadBuffer::new(shared, LittleEndian);
     /// ```
-    pub fn new(mut bytes: Vec<u8>, _endianness: E) -> Self {
-        let byte_len = bytes.len();
+    ///
+    /// [`Storage`]: trait.Storage.html
+    /// [`MAX_BYTE_LEN`]: #associatedconstant.MAX_BYTE_LEN
+    /// [`try_new`]: #method.try_new
+    pub fn new<S: Storage>(bytes: S, _endianness: E) -> Self {
+        match Self::try_from_storage(bytes) {
+            Ok(buffer) => buffer,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
-        // pad with usize worth of bytes to ensure we can always read a full usize
-        bytes.extend_from_slice(&0usize.to_le_bytes());
-        BitReadBuffer {
+    /// Create a new BitBuffer from a byte vector or any other [`Storage`], without panicking for pathological sizes
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::BufferTooLarge`]: the buffer is larger than [`MAX_BYTE_LEN`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitbuffer::{BitReadBuffer, LittleEndian};
+    /// # use bitbuffer::Result;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::try_new(vec![0b1011_0101, 0b0110_1010], LittleEndian)?;
+    /// assert_eq!(buffer.bit_len(), 16);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Storage`]: trait.Storage.html
+    /// [`ReadError::BufferTooLarge`]: enum.ReadError.html#variant.BufferTooLarge
+    /// [`MAX_BYTE_LEN`]: #associatedconstant.MAX_BYTE_LEN
+    pub fn try_new<S: Storage>(bytes: S, _endianness: E) -> Result<Self> {
+        Self::try_from_storage(bytes)
+    }
+
+    pub(crate) fn try_from_storage<S: Storage>(bytes: S) -> Result<Self> {
+        let byte_len = bytes.as_ref().len();
+        if byte_len > Self::MAX_BYTE_LEN {
+            return Err(BitError::BufferTooLarge {
+                byte_len,
+                max: Self::MAX_BYTE_LEN,
+            });
+        }
+
+        Ok(BitReadBuffer {
             bytes: Rc::new(bytes),
             bit_len: byte_len * 8,
             endianness: PhantomData,
-        }
+            view: None,
+            transform: None,
+        })
     }
 }
 
@@ -78,14 +224,14 @@ pub(crate) fn get_bits_from_usize<E: Endianness>(
     bit_offset: usize,
     count: usize,
 ) -> usize {
-    let usize_bit_size = size_of::<usize>() * 8;
+    let usize_bit_size = usize::BITS as usize;
 
     let shifted = if E::is_le() {
         val >> bit_offset
     } else {
         val >> (usize_bit_size - bit_offset - count)
     };
-    let mask = !(std::usize::MAX << count);
+    let mask = !(usize::MAX << count);
     shifted & mask
 }
 
@@ -98,27 +244,65 @@ where
         self.bit_len
     }
 
-    /// The available number of bytes in the buffer
+    /// The available number of bytes in the storage of the buffer
     pub fn byte_len(&self) -> usize {
-        self.bytes.len()
+        self.data().len()
+    }
+
+    /// The bytes of the storage
+    #[inline]
+    fn data(&self) -> &[u8] {
+        (*self.bytes).as_ref()
+    }
+
+    /// A byte of the data below the view
+    #[inline]
+    fn underlying_byte(&self, byte_index: usize) -> u8 {
+        match &self.transform {
+            Some(transform) => transform(byte_index),
+            None => self.data()[byte_index],
+        }
+    }
+
+    #[inline]
+    fn byte(&self, byte_index: usize) -> u8 {
+        match &self.view {
+            Some(view) => view.get(byte_index, |index| self.underlying_byte(index)),
+            None => self.data()[byte_index],
+        }
     }
 
     unsafe fn read_usize_bytes(&self, byte_index: usize) -> [u8; USIZE_SIZE] {
-        debug_assert!(byte_index + USIZE_SIZE <= self.bytes.len());
-        // this is safe because all calling paths check that byte_index is less than the unpadded
-        // length (because they check based on bit_len), so with padding byte_index + USIZE_SIZE is
-        // always within bounds
-        self.bytes
-            .get_unchecked(byte_index..byte_index + USIZE_SIZE)
-            .try_into()
-            .unwrap()
-    }
-
-    /// note that only the bottom USIZE - 1 bytes are usable
-    unsafe fn read_shifted_usize(&self, byte_index: usize, shift: usize) -> usize {
+        if let Some(view) = &self.view {
+            let mut bytes = [0; USIZE_SIZE];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = view.get(byte_index + i, |index| self.underlying_byte(index));
+            }
+            return bytes;
+        }
+        let data = self.data();
+        match data.get(byte_index..byte_index + USIZE_SIZE) {
+            Some(bytes) => bytes.try_into().unwrap(),
+            None => {
+                // less than a usize worth of data left, the bytes past the end read as 0
+                let mut bytes = [0; USIZE_SIZE];
+                let tail = data.get(byte_index..).unwrap_or_default();
+                bytes[0..tail.len()].copy_from_slice(tail);
+                bytes
+            }
+        }
+    }
+
+    /// Read the bytes starting `shift` bits after the start of `byte_index`
+    ///
+    /// note that only the first USIZE - 1 bytes are usable
+    unsafe fn read_shifted_bytes(&self, byte_index: usize, shift: usize) -> [u8; USIZE_SIZE] {
         let raw_bytes: [u8; USIZE_SIZE] = self.read_usize_bytes(byte_index);
-        let raw_usize: usize = usize::from_le_bytes(raw_bytes);
-        raw_usize >> shift
+        if E::is_le() {
+            (usize::from_le_bytes(raw_bytes) >> shift).to_le_bytes()
+        } else {
+            (usize::from_be_bytes(raw_bytes) << shift).to_be_bytes()
+        }
     }
 
     unsafe fn read_usize(&self, position: usize, count: usize) -> usize {
@@ -167,7 +351,7 @@ where
         let bit_offset = position & 7;
 
         if position < self.bit_len() {
-            let byte = self.bytes[byte_index];
+            let byte = self.byte(byte_index);
             if E::is_le() {
                 let shifted = byte >> bit_offset as u8;
                 Ok(shifted & 1u8 == 1)
@@ -178,24 +362,39 @@ where
         } else {
             Err(BitError::NotEnoughData {
                 requested: 1,
-                bits_left: self.bit_len().saturating_sub(position),
+                bits_left: BitPos::new(position).bits_left(self.bit_len()),
             })
         }
     }
 
-    #[doc(hidden)]
+    /// Read a single bit from the buffer as boolean without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// `position` needs to be less than the length of the buffer. See the [`unchecked`] module for details.
+    ///
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     pub unsafe fn read_bool_unchecked(&self, position: usize) -> bool {
         let byte_index = position / 8;
         let bit_offset = position & 7;
 
-        let byte = self.bytes.get_unchecked(byte_index);
-        let shifted = byte >> bit_offset;
-        shifted & 1u8 == 1
+        let byte = match &self.view {
+            Some(view) => view.get(byte_index, |index| self.underlying_byte(index)),
+            // indexed with bounds checks, the storage is only trusted to return the same bytes for every read
+            None => self.data()[byte_index],
+        };
+        if E::is_le() {
+            (byte >> bit_offset) & 1u8 == 1
+        } else {
+            (byte << bit_offset) & 0b1000_0000u8 == 0b1000_0000u8
+        }
     }
 
     /// Read a sequence of bits from the buffer as integer
     ///
+    /// Any number of bits up to the size of the integer type can be read, including up to 128 bits for `u128` and `i128`.
+    ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the buffer
@@ -235,37 +434,39 @@ where
             });
         }
 
-        if position + count > self.bit_len() {
-            return if position > self.bit_len() {
-                Err(BitError::IndexOutOfBounds {
-                    pos: position,
-                    size: self.bit_len(),
-                })
-            } else {
-                Err(BitError::NotEnoughData {
-                    requested: count,
-                    bits_left: self.bit_len() - position,
-                })
-            };
-        }
+        BitPos::new(position).advance(count, self.bit_len())?;
 
         Ok(unsafe { self.read_int_unchecked(position, count) })
     }
 
-    #[doc(hidden)]
+    /// Read a sequence of bits from the buffer as integer without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// `position + count` needs to be at most the length of the buffer. `count` should be at most the size of the
+    /// integer type, larger counts don't result in undefined behavior but give meaningless results.
+    /// See the [`unchecked`] module for details.
+    ///
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     pub unsafe fn read_int_unchecked<T>(&self, position: usize, count: usize) -> T
     where
         T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt + BitXor,
     {
         let type_bit_size = size_of::<T>() * 8;
-        let usize_bit_size = size_of::<usize>() * 8;
+        let usize_bit_size = usize::BITS as usize;
+
+        if count == 0 {
+            return T::zero();
+        }
 
         let bit_offset = position & 7;
 
         let fit_usize = count + bit_offset < usize_bit_size;
         let value = if fit_usize {
             self.read_fit_usize(position, count)
+        } else if USIZE_SIZE == 8 && count + bit_offset <= 128 {
+            self.read_two_words(position, count)
         } else {
             self.read_no_fit_usize(position, count)
         };
@@ -286,6 +487,31 @@ where
         T::from_unchecked(raw)
     }
 
+    /// Read up to 128 bits using two usize reads
+    ///
+    /// Only valid on 64 bit platforms and when the read doesn't fit in a single usize,
+    /// any bytes past the end of the storage read as 0.
+    #[inline]
+    unsafe fn read_two_words<T>(&self, position: usize, count: usize) -> T
+    where
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
+    {
+        let byte_index = position / 8;
+        let bit_offset = position & 7;
+
+        let mut bytes = [0; 16];
+        bytes[0..8].copy_from_slice(&self.read_usize_bytes(byte_index)[0..8]);
+        bytes[8..16].copy_from_slice(&self.read_usize_bytes(byte_index + 8)[0..8]);
+
+        let shifted = if E::is_le() {
+            u128::from_le_bytes(bytes) >> bit_offset
+        } else {
+            u128::from_be_bytes(bytes) >> (128 - bit_offset - count)
+        };
+        let mask = u128::MAX >> (128 - count);
+        T::from_unchecked(shifted & mask)
+    }
+
     unsafe fn read_no_fit_usize<T>(&self, position: usize, count: usize) -> T
     where
         T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
@@ -322,7 +548,7 @@ where
         } else if T::is_signed() {
             let sign_bit = value >> (count - 1) & T::one();
             if sign_bit == T::one() {
-                value | (T::zero() - T::one()) ^ ((T::one() << count) - T::one())
+                value | (!T::zero() << count)
             } else {
                 value
             }
@@ -361,38 +587,89 @@ where
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
     #[inline]
     pub fn read_bytes(&self, position: usize, byte_count: usize) -> Result<Vec<u8>> {
-        if position + byte_count * 8 > self.bit_len() {
-            if position > self.bit_len() {
-                return Err(BitError::IndexOutOfBounds {
-                    pos: position,
-                    size: self.bit_len(),
-                });
-            } else {
-                return Err(BitError::NotEnoughData {
-                    requested: byte_count * 8,
-                    bits_left: self.bit_len() - position,
-                });
-            }
-        }
+        let mut data = Vec::new();
+        self.read_bytes_into(position, byte_count, &mut data)?;
+        Ok(data)
+    }
 
-        Ok(unsafe { self.read_bytes_unchecked(position, byte_count) })
+    /// Read a series of bytes from the buffer, appending them to `output`
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut output = vec![1];
+    /// buffer.read_bytes_into(5, 3, &mut output)?;
+    /// assert_eq!(output, &[1, 0b0_1010_101, 0b0_1100_011, 0b1_1001_101]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_bytes_into<O: ByteOutput>(
+        &self,
+        position: usize,
+        byte_count: usize,
+        output: &mut O,
+    ) -> Result<()> {
+        BitPos::new(position).advance(byte_count.saturating_mul(8), self.bit_len())?;
+
+        unsafe { self.read_bytes_into_unchecked(position, byte_count, output) };
+        Ok(())
     }
 
-    #[doc(hidden)]
+    /// Read a series of bytes from the buffer without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// `position + byte_count * 8` needs to be at most the length of the buffer. See the [`unchecked`] module for details.
+    ///
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     pub unsafe fn read_bytes_unchecked(&self, position: usize, byte_count: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        self.read_bytes_into_unchecked(position, byte_count, &mut data);
+        data
+    }
+
+    pub(crate) unsafe fn read_bytes_into_unchecked<O: ByteOutput>(
+        &self,
+        position: usize,
+        byte_count: usize,
+        data: &mut O,
+    ) {
         let shift = position & 7;
+        data.reserve(byte_count);
 
         if shift == 0 {
             let byte_pos = position / 8;
-            return self.bytes[byte_pos..byte_pos + byte_count].to_vec();
+            if self.view.is_some() {
+                for index in byte_pos..byte_pos + byte_count {
+                    data.extend_from_slice(&[self.byte(index)]);
+                }
+            } else {
+                data.extend_from_slice(&self.data()[byte_pos..byte_pos + byte_count]);
+            }
+            return;
         }
 
-        let mut data = Vec::with_capacity(byte_count);
         let mut byte_left = byte_count;
         let mut read_pos = position / 8;
         while byte_left > USIZE_SIZE - 1 {
-            let bytes = self.read_shifted_usize(read_pos, shift).to_le_bytes();
+            let bytes = self.read_shifted_bytes(read_pos, shift);
             let read_bytes = USIZE_SIZE - 1;
             let usable_bytes = &bytes[0..read_bytes];
             data.extend_from_slice(usable_bytes);
@@ -401,11 +678,42 @@ where
             byte_left -= read_bytes;
         }
 
-        let bytes = self.read_shifted_usize(read_pos, shift).to_le_bytes();
+        let bytes = self.read_shifted_bytes(read_pos, shift);
         let usable_bytes = &bytes[0..byte_left];
         data.extend_from_slice(usable_bytes);
+    }
 
-        data
+    /// Write `byte_count` bytes starting at `position` to `writer`, without bounds checks
+    pub(crate) unsafe fn copy_bytes_to<W: Write>(
+        &self,
+        position: usize,
+        byte_count: usize,
+        writer: &mut W,
+    ) -> io::Result<()> {
+        let shift = position & 7;
+        if shift == 0 && self.view.is_none() {
+            let byte_pos = position / 8;
+            return writer.write_all(&self.data()[byte_pos..byte_pos + byte_count]);
+        }
+
+        // copy in chunks of whole usizes worth of shifted bytes
+        const CHUNK_READS: usize = 64;
+        let read_bytes = USIZE_SIZE - 1;
+        let mut chunk = [0; CHUNK_READS * (USIZE_SIZE - 1)];
+        let mut byte_left = byte_count;
+        let mut read_pos = position / 8;
+        while byte_left > 0 {
+            let chunk_len = min(byte_left, chunk.len());
+            for offset in (0..chunk_len).step_by(read_bytes) {
+                let bytes = self.read_shifted_bytes(read_pos + offset, shift);
+                let len = min(read_bytes, chunk_len - offset);
+                chunk[offset..offset + len].copy_from_slice(&bytes[0..len]);
+            }
+            writer.write_all(&chunk[0..chunk_len])?;
+            read_pos += chunk_len;
+            byte_left -= chunk_len;
+        }
+        Ok(())
     }
 
     /// Read a series of bytes from the buffer as string
@@ -447,32 +755,106 @@ where
     pub fn read_string(&self, position: usize, byte_len: Option<usize>) -> Result<String> {
         match byte_len {
             Some(byte_len) => {
-                let bytes = self.read_bytes(position, byte_len)?;
-                let raw_string = String::from_utf8(bytes)?;
-                Ok(raw_string.trim_end_matches(char::from(0)).to_owned())
+                self.read_string_mode(position, byte_len, StringMode::FixedTrimTrailingNulls)
             }
             None => {
+                if position > self.bit_len() {
+                    return Err(BitError::IndexOutOfBounds {
+                        pos: position,
+                        size: self.bit_len(),
+                    });
+                }
                 let bytes = self.read_string_bytes(position)?;
                 String::from_utf8(bytes).map_err(BitError::from)
             }
         }
     }
 
+    /// Read `byte_len` bytes from the buffer as string, handling null bytes according to `mode`
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the buffer
+    /// - [`ReadError::Utf8Error`]: the bytes that are part of the string are not valid utf8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, LittleEndian, Result, StringMode};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(b"ab\0cd\0\0".to_vec(), LittleEndian);
+    /// assert_eq!(buffer.read_string_mode(0, 7, StringMode::NullTerminated)?, "ab");
+    /// assert_eq!(buffer.read_string_mode(0, 7, StringMode::FixedKeepNulls)?, "ab\0cd\0\0");
+    /// assert_eq!(buffer.read_string_mode(0, 7, StringMode::FixedTrimTrailingNulls)?, "ab\0cd");
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Utf8Error`]: enum.ReadError.html#variant.Utf8Error
+    pub fn read_string_mode(
+        &self,
+        position: usize,
+        byte_len: usize,
+        mode: StringMode,
+    ) -> Result<String> {
+        let mut bytes = self.read_bytes(position, byte_len)?;
+        match mode {
+            StringMode::NullTerminated => {
+                if let Some(end) = memchr::memchr(0, &bytes) {
+                    bytes.truncate(end);
+                }
+            }
+            StringMode::FixedKeepNulls => {}
+            StringMode::FixedTrimTrailingNulls => {
+                let end = bytes
+                    .iter()
+                    .rposition(|byte| *byte != 0)
+                    .map_or(0, |i| i + 1);
+                bytes.truncate(end);
+            }
+        }
+        Ok(String::from_utf8(bytes)?)
+    }
+
     #[inline]
     fn find_null_byte(&self, byte_index: usize) -> usize {
-        memchr::memchr(0, &self.bytes[byte_index..])
+        if self.view.is_some() {
+            // bytes past the end of the view read as 0
+            return (byte_index..).find(|index| self.byte(*index) == 0).unwrap();
+        }
+        let data = self.data();
+        memchr::memchr(0, &data[byte_index..])
             .map(|index| index + byte_index)
-            .unwrap() // due to padding we always have 0 bytes at the end
+            .unwrap_or(data.len())
     }
 
     #[inline]
     fn read_string_bytes(&self, position: usize) -> Result<Vec<u8>> {
+        let mut acc = Vec::with_capacity(32);
+        self.read_string_bytes_into(position, &mut acc);
+        Ok(acc)
+    }
+
+    /// Append the bytes up to the next null byte to `acc`
+    ///
+    /// The caller is responsible for checking that `position` is within the buffer
+    #[inline]
+    pub(crate) fn read_string_bytes_into<O: ByteOutput>(&self, position: usize, acc: &mut O) {
         let shift = position & 7;
         if shift == 0 {
             let byte_index = position / 8;
-            Ok(self.bytes[byte_index..self.find_null_byte(byte_index)].to_vec())
+            let end = self.find_null_byte(byte_index);
+            if self.view.is_some() {
+                for index in byte_index..end {
+                    acc.extend_from_slice(&[self.byte(index)]);
+                }
+            } else {
+                acc.extend_from_slice(&self.data()[byte_index..end]);
+            }
         } else {
-            let mut acc = Vec::with_capacity(32);
             let mut byte_index = position / 8;
             loop {
                 // note: if less then a usize worth of data is left in the buffer, read_usize_bytes
@@ -481,17 +863,16 @@ where
                 //
                 // This is safe because the final usize is filled with 0's, thus triggering the exit clause
                 // before reading any out of bounds
-                let shifted = unsafe { self.read_shifted_usize(byte_index, shift) };
+                let bytes = unsafe { self.read_shifted_bytes(byte_index, shift) };
 
-                let has_null = contains_zero_byte_non_top(shifted);
-                let bytes: [u8; USIZE_SIZE] = shifted.to_le_bytes();
+                let has_null = contains_zero_byte_non_top(usize::from_le_bytes(bytes));
                 let usable_bytes = &bytes[0..USIZE_SIZE - 1];
 
                 if has_null {
                     for i in 0..USIZE_SIZE - 1 {
                         if usable_bytes[i] == 0 {
                             acc.extend_from_slice(&usable_bytes[0..i]);
-                            return Ok(acc);
+                            return;
                         }
                     }
                 }
@@ -533,24 +914,19 @@ where
         T: Float + UncheckedPrimitiveFloat,
     {
         let type_bit_size = size_of::<T>() * 8;
-        if position + type_bit_size > self.bit_len() {
-            if position > self.bit_len() {
-                return Err(BitError::IndexOutOfBounds {
-                    pos: position,
-                    size: self.bit_len(),
-                });
-            } else {
-                return Err(BitError::NotEnoughData {
-                    requested: size_of::<T>() * 8,
-                    bits_left: self.bit_len() - position,
-                });
-            }
-        }
+        BitPos::new(position).advance(type_bit_size, self.bit_len())?;
 
         Ok(unsafe { self.read_float_unchecked(position) })
     }
 
-    #[doc(hidden)]
+    /// Read a sequence of bits from the buffer as float without bounds checks
+    ///
+    /// # Safety
+    ///
+    /// `position` plus the size of the float needs to be at most the length of the buffer.
+    /// See the [`unchecked`] module for details.
+    ///
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     pub unsafe fn read_float_unchecked<T>(&self, position: usize) -> T
     where
@@ -569,6 +945,12 @@ where
         }
     }
 
+    /// Shorten the buffer in place, `bit_len` needs to be at most the current length
+    pub(crate) fn truncate(&mut self, bit_len: usize) {
+        debug_assert!(bit_len <= self.bit_len);
+        self.bit_len = bit_len;
+    }
+
     pub(crate) fn get_sub_buffer(&self, bit_len: usize) -> Result<Self> {
         if bit_len > self.bit_len() {
             return Err(BitError::NotEnoughData {
@@ -581,17 +963,258 @@ where
             bytes: Rc::clone(&self.bytes),
             bit_len,
             endianness: PhantomData,
+            view: self.view,
+            transform: self.transform.clone(),
         })
     }
-}
 
-impl<E: Endianness> From<Vec<u8>> for BitReadBuffer<E> {
-    fn from(bytes: Vec<u8>) -> Self {
-        let byte_len = bytes.len();
+    /// Create a buffer containing a range of bits from this buffer
+    ///
+    /// If the range starts at a byte boundary the new buffer shares the data with this buffer,
+    /// otherwise the bits in the range are copied.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: the range ends past the end of the buffer
+    /// - [`ReadError::IndexOutOfBounds`]: the range starts after it ends
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![0b1011_0101, 0b0110_1010], LittleEndian);
+    /// let sub = buffer.sub_buffer(4..12)?;
+    /// assert_eq!(sub.bit_len(), 8);
+    /// assert_eq!(sub.read_int::<u8>(0, 8)?, 0b1010_1011);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn sub_buffer(&self, range: Range<usize>) -> Result<Self> {
+        let truncated = self.get_sub_buffer(range.end)?;
+        if range.start > range.end {
+            return Err(BitError::IndexOutOfBounds {
+                pos: range.start,
+                size: range.end,
+            });
+        }
+
+        let bit_len = range.end - range.start;
+        if range.start == 0 {
+            Ok(truncated)
+        } else if range.start & 7 == 0 {
+            let view = truncated.view.unwrap_or(ByteView {
+                start: 0,
+                descending: false,
+                reverse_bits: false,
+                byte_len: range.end.div_ceil(8),
+            });
+            Ok(BitReadBuffer {
+                bit_len,
+                view: Some(view.skip(range.start / 8)),
+                ..truncated
+            })
+        } else {
+            let mut bytes = self.read_bytes(range.start, bit_len / 8)?;
+            let rest = bit_len & 7;
+            if rest > 0 {
+                let last: u8 = self.read_int(range.end - rest, rest)?;
+                bytes.push(if E::is_le() { last } else { last << (8 - rest) });
+            }
+            BitReadBuffer::from(bytes).get_sub_buffer(bit_len)
+        }
+    }
+
+    /// Get the bytes of the buffer
+    ///
+    /// Returns `None` if the buffer doesn't contain a whole number of bytes or if the buffer is a
+    /// view that doesn't map directly to the underlying bytes, such as the buffers created by
+    /// [`reverse_bytes`] or by [`sub_buffer`] with a non zero start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![1, 2, 3], LittleEndian);
+    /// assert_eq!(buffer.as_bytes(), Some(&[1, 2, 3][..]));
+    /// assert_eq!(buffer.sub_buffer(0..12)?.as_bytes(), None);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`reverse_bytes`]: #method.reverse_bytes
+    /// [`sub_buffer`]: #method.sub_buffer
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match (self.view, self.bit_len & 7) {
+            (None, 0) => Some(&self.data()[0..self.bit_len / 8]),
+            _ => None,
+        }
+    }
+
+    /// The trailing bits that don't form a full byte, as integer
+    fn trailing_bits(&self) -> u8 {
+        let rest = self.bit_len & 7;
+        unsafe { self.read_int_unchecked(self.bit_len - rest, rest) }
+    }
+
+    fn reversed(&self, reverse_bits: bool) -> Self {
+        let byte_len = self.bit_len / 8;
+        let view = self.view.unwrap_or(ByteView {
+            start: 0,
+            descending: false,
+            reverse_bits: false,
+            byte_len,
+        });
         BitReadBuffer {
-            bytes: Rc::new(bytes),
+            bytes: Rc::clone(&self.bytes),
             bit_len: byte_len * 8,
             endianness: PhantomData,
+            view: Some(view.reversed(byte_len, reverse_bits)),
+            transform: self.transform.clone(),
+        }
+    }
+
+    /// Create a view of the buffer with the order of all bits reversed
+    ///
+    /// The reversal is applied while reading, no data is copied. The view only contains
+    /// whole bytes, any trailing bits that don't form a full byte are not part of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1100_0000, 0b0000_0101];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let reversed = buffer.reverse_bits();
+    /// assert_eq!(reversed.read_int::<u16>(0, 16)?, 0b1010_0000_0000_0011);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn reverse_bits(&self) -> Self {
+        self.reversed(true)
+    }
+
+    /// Create a view of the buffer with the order of the bytes reversed
+    ///
+    /// The reversal is applied while reading, no data is copied. The view only contains
+    /// whole bytes, any trailing bits that don't form a full byte are not part of the view.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![1, 2, 3];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let reversed = buffer.reverse_bytes();
+    /// assert_eq!(reversed.read_bytes(0, 3)?, vec![3, 2, 1]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn reverse_bytes(&self) -> Self {
+        self.reversed(false)
+    }
+
+    /// Create a view of the buffer with a transform applied to every byte
+    ///
+    /// The transform is called with the index and value of every byte when it's read, no data is copied
+    /// up front. This can be used to descramble data that is XORed with a rolling key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let key = [0x55, 0xaa];
+    /// let scrambled = vec![b'h' ^ 0x55, b'i' ^ 0xaa, 0x55];
+    /// let buffer = BitReadBuffer::new(scrambled, LittleEndian);
+    /// let descrambled = buffer.map_bytes(move |index, byte| byte ^ key[index % key.len()]);
+    ///
+    /// let mut stream = BitReadStream::new(descrambled);
+    /// assert_eq!(stream.read_string(None)?, "hi");
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn map_bytes<F>(&self, transform: F) -> Self
+    where
+        F: Fn(usize, u8) -> u8 + 'static,
+        E: 'static,
+    {
+        let source = self.clone();
+        BitReadBuffer {
+            bytes: Rc::clone(&self.bytes),
+            bit_len: self.bit_len,
+            endianness: PhantomData,
+            // the view keeps reads past the end from reaching the transform
+            view: Some(ByteView {
+                start: 0,
+                descending: false,
+                reverse_bits: false,
+                byte_len: self.bit_len.div_ceil(8),
+            }),
+            transform: Some(Rc::new(move |index| transform(index, source.byte(index)))),
+        }
+    }
+}
+
+/// Collects individual bits into a new buffer
+pub(crate) struct BitCollector<E: Endianness> {
+    bytes: Vec<u8>,
+    bit_len: usize,
+    endianness: PhantomData<E>,
+}
+
+impl<E: Endianness> BitCollector<E> {
+    pub fn with_capacity(bit_len: usize) -> Self {
+        BitCollector {
+            bytes: Vec::with_capacity(bit_len.div_ceil(8)),
+            bit_len: 0,
+            endianness: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, bit: bool) {
+        let bit_offset = self.bit_len & 7;
+        if bit_offset == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            let shift = if E::is_le() {
+                bit_offset
+            } else {
+                7 - bit_offset
+            };
+            *self.bytes.last_mut().unwrap() |= 1 << shift;
+        }
+        self.bit_len += 1;
+    }
+
+    pub fn finish(self) -> Result<BitReadBuffer<E>> {
+        BitReadBuffer::from(self.bytes).get_sub_buffer(self.bit_len)
+    }
+}
+
+impl<E: Endianness> From<Vec<u8>> for BitReadBuffer<E> {
+    fn from(bytes: Vec<u8>) -> Self {
+        match BitReadBuffer::try_from_storage(bytes) {
+            Ok(buffer) => buffer,
+            Err(e) => panic!("{}", e),
         }
     }
 }
@@ -602,7 +1225,36 @@ impl<E: Endianness> Clone for BitReadBuffer<E> {
             bytes: Rc::clone(&self.bytes),
             bit_len: self.bit_len(),
             endianness: PhantomData,
+            view: self.view,
+            transform: self.transform.clone(),
+        }
+    }
+}
+
+/// Buffers are equal if they contain the same bits, regardless of how the data is stored
+impl<E: Endianness> PartialEq for BitReadBuffer<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bit_len == other.bit_len
+            && (0..self.bit_len / 8).all(|index| self.byte(index) == other.byte(index))
+            && self.trailing_bits() == other.trailing_bits()
+    }
+}
+
+impl<E: Endianness> Eq for BitReadBuffer<E> {}
+
+impl<E: Endianness> Hash for BitReadBuffer<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bit_len.hash(state);
+        match self.as_bytes() {
+            Some(bytes) => state.write(bytes),
+            None => {
+                let bytes: Vec<u8> = (0..self.bit_len / 8)
+                    .map(|index| self.byte(index))
+                    .collect();
+                state.write(&bytes)
+            }
         }
+        self.trailing_bits().hash(state);
     }
 }
 
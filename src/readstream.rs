@@ -1,13 +1,35 @@
 use std::mem::size_of;
 use std::ops::BitOrAssign;
 
-use num_traits::{Float, PrimInt};
+use num_traits::{Float, PrimInt, Signed, Unsigned};
 
+use crate::decode::{DecodeNode, DecodeRecorder};
 use crate::endianness::Endianness;
+use crate::fec::{FecStatus, HammingCode, Parity};
+use crate::golomb;
+use crate::huffman::HuffmanTable;
+use crate::linecode::{ManchesterEncoding, NrziEncoding};
 use crate::num_traits::{IsSigned, UncheckedPrimitiveFloat, UncheckedPrimitiveInt};
+use crate::pos::BitPos;
+use crate::read::check_element_count;
+use crate::readbuffer::{BitCollector, StringMode};
+use crate::zigzag;
 use crate::BitReadBuffer;
-use crate::{BitError, BitRead, BitReadSized, Result};
+#[cfg(feature = "stats")]
+use crate::ReadStats;
+use crate::{
+    BitError, BitRead, BitReadInto, BitReadIntoSized, BitReadSized, BitReadWithContext, ByteOutput,
+    Result,
+};
+use std::cell::{Cell, RefCell};
 use std::cmp::min;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Write;
+use std::io;
+use std::ops::Range;
+use std::rc::Rc;
 
 /// Stream that provides an easy way to iterate trough a [`BitBuffer`]
 ///
@@ -25,14 +47,24 @@ use std::cmp::min;
 /// ```
 ///
 /// [`BitBuffer`]: struct.BitBuffer.html
-#[derive(Debug)]
 pub struct BitReadStream<E>
 where
     E: Endianness,
 {
+    /// The buffer, ending at the end of the stream
     buffer: BitReadBuffer<E>,
+    /// Start of the stream in the buffer, all positions exposed by the stream are relative to this
     start_pos: usize,
-    pos: usize,
+    /// Current position in the buffer, between `start_pos` and the end of the buffer
+    pos: BitPos,
+    depth: usize,
+    max_depth: usize,
+    budget: Option<Rc<Cell<usize>>>,
+    marker: Option<usize>,
+    saved_positions: Vec<usize>,
+    recorder: Option<Rc<RefCell<DecodeRecorder>>>,
+    #[cfg(feature = "stats")]
+    stats: Option<Rc<RefCell<ReadStats>>>,
 }
 
 impl<E> BitReadStream<E>
@@ -58,8 +90,16 @@ where
     pub fn new(buffer: BitReadBuffer<E>) -> Self {
         BitReadStream {
             start_pos: 0,
-            pos: 0,
+            pos: BitPos::new(0),
             buffer,
+            depth: 0,
+            max_depth: usize::MAX,
+            budget: None,
+            marker: None,
+            saved_positions: Vec::new(),
+            recorder: None,
+            #[cfg(feature = "stats")]
+            stats: None,
         }
     }
 
@@ -92,78 +132,85 @@ where
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
     #[inline]
     pub fn read_bool(&mut self) -> Result<bool> {
-        let result = self.buffer.read_bool(self.pos);
+        self.check_limits(1)?;
+        let result = self.buffer.read_bool(self.pos.get());
         if result.is_ok() {
             self.pos += 1;
+            self.spend(1);
         }
         result
     }
 
-    #[doc(hidden)]
+    /// Read a single bit as boolean without checking if enough data is left
+    ///
+    /// # Safety
+    ///
+    /// The stream needs to have at least one bit left, which can be ensured by calling [`check_read`] beforehand.
+    /// See the [`unchecked`] module for details.
+    ///
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
     #[inline]
     pub unsafe fn read_bool_unchecked(&mut self) -> bool {
-        let result = self.buffer.read_bool_unchecked(self.pos);
+        let result = self.buffer.read_bool_unchecked(self.pos.get());
         self.pos += 1;
+        self.spend(1);
         result
     }
 
-    /// Read a sequence of bits from the stream as integer
+    /// Read up to 64 bits as booleans in one go
+    ///
+    /// Bit `i` of the returned mask contains the `i`th boolean read, regardless of the endianness of the stream,
+    /// so `mask & (1 << i) != 0` gives the same result as the `i`th call to [`read_bool`] would.
     ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
-    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
+    /// - [`ReadError::TooManyBits`]: more than 64 bits requested
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
     /// # let bytes = vec![
     /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
     /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
     /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let buffer = BitReadBuffer::new(bytes, BigEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// assert_eq!(stream.read_int::<u16>(3)?, 0b101);
-    /// assert_eq!(stream.read_int::<u16>(3)?, 0b110);
-    /// assert_eq!(stream.pos(), 6);
+    /// let flags = stream.read_bools(5)?;
+    /// assert_eq!(flags, 0b0_1101);
+    /// assert!(flags & 1 != 0); // the first bit read
+    /// assert_eq!(stream.pos(), 5);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`read_bool`]: #method.read_bool
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
     /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
     #[inline]
-    pub fn read_int<T>(&mut self, count: usize) -> Result<T>
-    where
-        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
-    {
-        let result = self.buffer.read_int(self.pos, count);
-        if result.is_ok() {
-            self.pos += count;
+    pub fn read_bools(&mut self, count: usize) -> Result<u64> {
+        let value: u64 = self.read_int(count)?;
+        // big endian streams put the first bit read in the most significant position
+        if E::is_le() || count == 0 {
+            Ok(value)
+        } else {
+            Ok(value.reverse_bits() >> (64 - count))
         }
-        result
-    }
-
-    #[doc(hidden)]
-    #[inline]
-    pub unsafe fn read_int_unchecked<T>(&mut self, count: usize) -> T
-    where
-        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
-    {
-        let result = self.buffer.read_int_unchecked(self.pos, count);
-        self.pos += count;
-        result
     }
 
-    /// Read a sequence of bits from the stream as float
+    /// Read a sequence of bits from the stream as integer
+    ///
+    /// Any number of bits up to the size of the integer type can be read, including up to 128 bits for `u128` and `i128`.
     ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
     ///
     /// # Examples
     ///
@@ -177,92 +224,97 @@ where
     /// # ];
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// let result = stream.read_float::<f32>()?;
-    /// assert_eq!(stream.pos(), 32);
+    /// assert_eq!(stream.read_int::<u16>(3)?, 0b101);
+    /// assert_eq!(stream.read_int::<u16>(3)?, 0b110);
+    /// assert_eq!(stream.pos(), 6);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
     #[inline]
-    pub fn read_float<T>(&mut self) -> Result<T>
+    pub fn read_int<T>(&mut self, count: usize) -> Result<T>
     where
-        T: Float + UncheckedPrimitiveFloat,
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
     {
-        let count = size_of::<T>() * 8;
-        let result = self.buffer.read_float(self.pos);
+        self.check_limits(count)?;
+        let result = self.buffer.read_int(self.pos.get(), count);
         if result.is_ok() {
             self.pos += count;
+            self.spend(count);
         }
         result
     }
 
-    #[doc(hidden)]
-    #[inline]
-    pub unsafe fn read_float_unchecked<T>(&mut self) -> T
-    where
-        T: Float + UncheckedPrimitiveFloat,
-    {
-        let count = size_of::<T>() * 8;
-        let result = self.buffer.read_float_unchecked(self.pos);
-        self.pos += count;
-        result
-    }
-
-    /// Read a series of bytes from the stream
+    /// Read a sequence of bits from the stream as integer, returning an error if the value doesn't fit in the integer type
+    ///
+    /// Unlike [`read_int`], the number of bits can be larger than the size of the integer type, up to 128 bits.
+    /// For signed types the bits are read as a two's complement number of `count` bits.
+    ///
+    /// The position of the stream is not changed when an error is returned.
     ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: more than 128 bits requested
+    /// - [`ReadError::IntegerOutOfRange`]: the read value doesn't fit in the integer type
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitError, BitReadBuffer, BitReadStream, LittleEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
+    /// # let bytes = vec![0b1111_1110, 0b0000_0000, 0b1000_0000];
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// assert_eq!(stream.read_bytes(3)?, &[0b1011_0101, 0b0110_1010, 0b1010_1100]);
-    /// assert_eq!(stream.pos(), 24);
+    /// assert_eq!(stream.read_int_checked::<u8>(12)?, 0b1111_1110);
+    /// assert!(matches!(
+    ///     stream.read_int_checked::<i8>(12),
+    ///     Err(BitError::IntegerOutOfRange { value: -2048, bits: 8 })
+    /// ));
+    /// assert_eq!(stream.read_int_checked::<i16>(12)?, -2048);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`read_int`]: #method.read_int
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
-    #[inline]
-    pub fn read_bytes(&mut self, byte_count: usize) -> Result<Vec<u8>> {
-        let count = byte_count * 8;
-        let result = self.buffer.read_bytes(self.pos, byte_count);
-        if result.is_ok() {
-            self.pos += count;
-        }
-        result
-    }
-
-    #[doc(hidden)]
-    #[inline]
-    pub unsafe fn read_bytes_unchecked(&mut self, byte_count: usize) -> Vec<u8> {
-        let count = byte_count * 8;
-        let result = self.buffer.read_bytes_unchecked(self.pos, byte_count);
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_int_checked<T>(&mut self, count: usize) -> Result<T>
+    where
+        T: PrimInt + IsSigned,
+    {
+        self.check_limits(count)?;
+        let out_of_range = |value: i128| BitError::IntegerOutOfRange {
+            value,
+            bits: size_of::<T>() * 8,
+        };
+        let result = if T::is_signed() {
+            let value: i128 = self.buffer.read_int(self.pos.get(), count)?;
+            T::from(value).ok_or_else(|| out_of_range(value))?
+        } else {
+            let value: u128 = self.buffer.read_int(self.pos.get(), count)?;
+            T::from(value)
+                .ok_or_else(|| out_of_range(i128::try_from(value).unwrap_or(i128::MAX)))?
+        };
         self.pos += count;
-        result
+        self.spend(count);
+        Ok(result)
     }
 
-    /// Read a series of bytes from the stream as utf8 string
+    /// Read a zigzag encoded signed integer of `count` bits
     ///
-    /// You can either read a fixed number of bytes, or a dynamic length null-terminated string
+    /// See [`ZigZag`] for details about the encoding.
     ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
-    /// - [`ReadError::Utf8Error`]: the read bytes are not valid utf8
+    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
     ///
     /// # Examples
     ///
@@ -270,238 +322,2470 @@ where
     /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0x48, 0x65, 0x6c, 0x6c,
-    /// #     0x6f, 0x20, 0x77, 0x6f,
-    /// #     0x72, 0x6c, 0x64, 0,
-    /// #     0,    0,    0,    0
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let buffer = BitReadBuffer::new(vec![0b0100_0101], LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// // Fixed length string
-    /// stream.set_pos(0);
-    /// assert_eq!(stream.read_string(Some(11))?, "Hello world".to_owned());
-    /// assert_eq!(11 * 8, stream.pos());
-    /// // fixed length with null padding
-    /// stream.set_pos(0);
-    /// assert_eq!(stream.read_string(Some(16))?, "Hello world".to_owned());
-    /// assert_eq!(16 * 8, stream.pos());
-    /// // null terminated
-    /// stream.set_pos(0);
-    /// assert_eq!(stream.read_string(None)?, "Hello world".to_owned());
-    /// assert_eq!(12 * 8, stream.pos()); // 1 more for the terminating null byte
+    /// assert_eq!(stream.read_zigzag::<i32>(4)?, -3);
+    /// assert_eq!(stream.read_zigzag::<i8>(4)?, 2);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`ZigZag`]: struct.ZigZag.html
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
-    /// [`ReadError::Utf8Error`]: enum.ReadError.html#variant.Utf8Error
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
     #[inline]
-    pub fn read_string(&mut self, byte_len: Option<usize>) -> Result<String> {
-        let max_length = self.bits_left() / 8;
-
-        let result = self.buffer.read_string(self.pos, byte_len).map_err(|err| {
-            // still advance the stream on malformed utf8
-            if let BitError::Utf8Error(err) = &err {
-                self.pos += match byte_len {
-                    Some(len) => len * 8,
-                    None => min((err.as_bytes().len() + 1) * 8, max_length),
-                };
-            }
-            err
-        })?;
-        let read = match byte_len {
-            Some(len) => len * 8,
-            None => (result.len() + 1) * 8,
-        };
-
-        // due to how sub buffer/streams work, the result string can be longer than the current stream
-        // (but not the top level buffer)
-        // thus we trim the resulting string to make sure it fits in the source stream
-        if read > self.bits_left() {
-            // find the maximum well-formed utf8 string that fits in max_len
-            let mut acc = String::new();
-            for c in result.chars() {
-                if acc.len() + c.len_utf8() > max_length {
-                    break;
-                }
-                acc.push(c);
-            }
-            self.pos += acc.len() * 8;
-            return Ok(acc);
+    pub fn read_zigzag<T>(&mut self, count: usize) -> Result<T>
+    where
+        T: PrimInt + Signed,
+    {
+        if count > size_of::<T>() * 8 {
+            return Err(BitError::TooManyBits {
+                requested: count,
+                max: size_of::<T>() * 8,
+            });
         }
-        self.pos += read;
-        Ok(result)
+        let value: u128 = self.read_int(count)?;
+        zigzag::decode(value, count)
     }
 
-    /// Read a sequence of bits from the stream as a BitStream
+    /// Read a Rice coded unsigned integer with parameter `k`
+    ///
+    /// See [`Rice`] for details about the encoding.
     ///
     /// # Errors
     ///
     /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: `k` is larger than the chosen integer type
+    /// - [`ReadError::IntegerOutOfRange`]: the decoded value doesn't fit in the chosen integer type
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
-    /// # let mut stream = BitReadStream::new(buffer);
-    /// let mut bits = stream.read_bits(3)?;
-    /// assert_eq!(stream.pos(), 3);
-    /// assert_eq!(bits.pos(), 0);
-    /// assert_eq!(bits.bit_len(), 3);
-    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
-    /// assert_eq!(bits.read_int::<u8>(3)?, 0b101);
-    /// assert_eq!(true, bits.read_int::<u8>(1).is_err());
+    /// let buffer = BitReadBuffer::new(vec![0b0010_1110], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_rice::<u32>(2)?, 9);
+    /// assert_eq!(stream.read_rice::<u8>(1)?, 1);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`Rice`]: struct.Rice.html
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
-    pub fn read_bits(&mut self, count: usize) -> Result<Self> {
-        let result = BitReadStream {
-            buffer: self.buffer.get_sub_buffer(self.pos + count)?,
-            start_pos: self.pos,
-            pos: self.pos,
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_rice<T>(&mut self, k: usize) -> Result<T>
+    where
+        T: PrimInt + Unsigned,
+    {
+        if k > size_of::<T>() * 8 {
+            return Err(BitError::TooManyBits {
+                requested: k,
+                max: size_of::<T>() * 8,
+            });
+        }
+        let quotient = self.read_unary_quotient()?;
+        let count = quotient.saturating_add(1 + k);
+        self.check_read(count)?;
+        let remainder: u128 = if k > 0 {
+            self.buffer.read_int(self.offset_pos(count - k)?, k)?
+        } else {
+            0
         };
+        let value = match quotient {
+            0 => Some(remainder),
+            quotient => 1u128
+                .checked_shl(k as u32)
+                .and_then(|factor| (quotient as u128).checked_mul(factor))
+                .map(|value| value | remainder),
+        };
+        let result = golomb::to_int(value)?;
         self.pos += count;
+        self.spend(count);
         Ok(result)
     }
 
-    /// Skip a number of bits in the stream
+    /// Read a Golomb coded unsigned integer with parameter `m`
+    ///
+    /// See [`Golomb`] for details about the encoding.
     ///
     /// # Errors
     ///
-    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream to skip
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidGolombParameter`]: `m` is 0
+    /// - [`ReadError::IntegerOutOfRange`]: the decoded value doesn't fit in the chosen integer type
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
-    /// # let mut stream = BitReadStream::new(buffer);
-    /// stream.skip_bits(3)?;
-    /// assert_eq!(stream.pos(), 3);
-    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
+    /// let buffer = BitReadBuffer::new(vec![0b0111_1000], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_golomb::<u32>(3)?, 5);
+    /// assert_eq!(stream.read_golomb::<u32>(3)?, 0);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`Golomb`]: struct.Golomb.html
     /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
-    pub fn skip_bits(&mut self, count: usize) -> Result<()> {
-        if count <= self.bits_left() {
-            self.pos += count;
-            Ok(())
-        } else {
-            Err(BitError::NotEnoughData {
-                requested: count,
-                bits_left: self.bits_left(),
-            })
+    /// [`ReadError::InvalidGolombParameter`]: enum.ReadError.html#variant.InvalidGolombParameter
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_golomb<T>(&mut self, m: usize) -> Result<T>
+    where
+        T: PrimInt + Unsigned,
+    {
+        if m == 0 {
+            return Err(BitError::InvalidGolombParameter);
+        }
+        let quotient = self.read_unary_quotient()?;
+        let (bits, cutoff) = golomb::remainder_bits(m);
+        let mut count = quotient.saturating_add(1);
+        let mut remainder: u128 = 0;
+        if bits > 1 {
+            self.check_read(count.saturating_add(bits - 1))?;
+            remainder = self.buffer.read_int(self.offset_pos(count)?, bits - 1)?;
+            count += bits - 1;
+        }
+        if bits > 0 && remainder >= cutoff {
+            self.check_read(count.saturating_add(1))?;
+            let last = self.buffer.read_bool(self.offset_pos(count)?)?;
+            remainder = ((remainder << 1) | u128::from(last)) - cutoff;
+            count += 1;
         }
+        self.check_read(count)?;
+        let result = golomb::to_int(Some(quotient as u128 * m as u128 + remainder))?;
+        self.pos += count;
+        self.spend(count);
+        Ok(result)
     }
 
-    /// Set the position of the stream
+    /// Read a unary coded integer, the number of `0` bits before the next `1` bit
+    ///
+    /// See [`Unary`] for details about the encoding.
     ///
     /// # Errors
     ///
-    /// - [`ReadError::IndexOutOfBounds`]: new position is outside the bounds of the stream
+    /// - [`ReadError::NotEnoughData`]: the stream ends before a `1` bit is found
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
-    /// # let mut stream = BitReadStream::new(buffer);
-    /// stream.set_pos(3)?;
-    /// assert_eq!(stream.pos(), 3);
-    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
+    /// let buffer = BitReadBuffer::new(vec![0b0001_1000], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_unary()?, 3);
+    /// assert_eq!(stream.read_unary()?, 0);
+    /// assert!(stream.read_unary().is_err());
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
-    pub fn set_pos(&mut self, pos: usize) -> Result<()> {
-        if pos > self.bit_len() {
-            return Err(BitError::IndexOutOfBounds {
-                pos,
-                size: self.bit_len(),
-            });
-        }
-        self.pos = pos + self.start_pos;
-        Ok(())
+    /// [`Unary`]: struct.Unary.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_unary(&mut self) -> Result<usize> {
+        let value = self.read_unary_quotient()?;
+        let count = value.saturating_add(1);
+        self.check_read(count)?;
+        self.pos += count;
+        self.spend(count);
+        Ok(value)
     }
 
-    /// Get the length of the stream in bits
+    /// Read an Elias gamma coded positive integer
+    ///
+    /// See [`EliasGamma`] for details about the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::IntegerOutOfRange`]: the decoded value doesn't fit in the chosen integer type
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
-    /// # let mut stream = BitReadStream::new(buffer);
-    /// assert_eq!(stream.bit_len(), 64);
+    /// let buffer = BitReadBuffer::new(vec![0b0010_1100], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_elias_gamma::<u32>()?, 5);
+    /// assert_eq!(stream.read_elias_gamma::<u32>()?, 1);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn bit_len(&self) -> usize {
-        self.buffer.bit_len() - self.start_pos
+    ///
+    /// [`EliasGamma`]: struct.EliasGamma.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_elias_gamma<T>(&mut self) -> Result<T>
+    where
+        T: PrimInt + Unsigned,
+    {
+        let zeros = self.read_unary_quotient()?;
+        let count = zeros.saturating_mul(2).saturating_add(1);
+        self.check_read(count)?;
+        let result = golomb::to_int(self.read_elias_value(self.offset_pos(zeros + 1)?, zeros)?)?;
+        self.pos += count;
+        self.spend(count);
+        Ok(result)
     }
 
-    /// Get the current position in the stream
+    /// Read an Elias delta coded positive integer
+    ///
+    /// See [`EliasDelta`] for details about the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::IntegerOutOfRange`]: the decoded value doesn't fit in the chosen integer type
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
-    /// # let mut stream = BitReadStream::new(buffer);
-    /// assert_eq!(stream.pos(), 0);
-    /// stream.skip_bits(5)?;
-    /// assert_eq!(stream.pos(), 5);
+    /// let buffer = BitReadBuffer::new(vec![0b0010_0001, 0b1000_0000], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_elias_delta::<u32>()?, 9);
+    /// assert_eq!(stream.read_elias_delta::<u32>()?, 1);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn pos(&self) -> usize {
-        self.pos - self.start_pos
+    ///
+    /// [`EliasDelta`]: struct.EliasDelta.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_elias_delta<T>(&mut self) -> Result<T>
+    where
+        T: PrimInt + Unsigned,
+    {
+        let zeros = self.read_unary_quotient()?;
+        let length_count = zeros.saturating_mul(2).saturating_add(1);
+        self.check_read(length_count)?;
+        let bits = match self.read_elias_value(self.offset_pos(zeros + 1)?, zeros)? {
+            Some(bits) if bits <= 128 => bits as usize,
+            _ => return golomb::to_int(None),
+        };
+        let count = length_count.saturating_add(bits - 1);
+        self.check_read(count)?;
+        let result =
+            golomb::to_int(self.read_elias_value(self.offset_pos(length_count)?, bits - 1)?)?;
+        self.pos += count;
+        self.spend(count);
+        Ok(result)
     }
 
-    /// Get the number of bits left in the stream
+    /// Read a 4 bit nibble
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![0xa5], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_nibble()?, 0xa);
+    /// assert_eq!(stream.read_nibble()?, 0x5);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_nibble(&mut self) -> Result<u8> {
+        self.read_int(4)
+    }
+
+    /// Read a binary coded decimal number of `digits` digits
+    ///
+    /// Every digit is stored as a 4 bit nibble, starting with the most significant digit.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidBcdDigit`]: a nibble is larger than 9
+    /// - [`ReadError::IntegerOutOfRange`]: the decoded value doesn't fit in the chosen integer type
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![0x12, 0x34, 0x5f], BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_bcd::<u16>(4)?, 1234);
+    /// assert!(stream.read_bcd::<u8>(2).is_err());
+    /// assert_eq!(stream.read_nibble()?, 5);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidBcdDigit`]: enum.ReadError.html#variant.InvalidBcdDigit
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn read_bcd<T>(&mut self, digits: usize) -> Result<T>
+    where
+        T: PrimInt + Unsigned,
+    {
+        let count = digits.saturating_mul(4);
+        self.check_read(count)?;
+        let mut value = Some(0u128);
+        for digit_pos in (self.pos.get()..self.offset_pos(count)?).step_by(4) {
+            let digit: u8 = self.buffer.read_int(digit_pos, 4)?;
+            if digit > 9 {
+                return Err(BitError::InvalidBcdDigit {
+                    digit,
+                    pos: digit_pos,
+                });
+            }
+            value = value
+                .and_then(|value| value.checked_mul(10))
+                .and_then(|value| value.checked_add(digit as u128));
+        }
+        let result = golomb::to_int(value)?;
+        self.pos += count;
+        self.spend(count);
+        Ok(result)
+    }
+
+    /// Read `bits` bits at `pos` with an implicit leading `1` bit, returns `None` if the value doesn't fit
+    fn read_elias_value(&self, pos: usize, bits: usize) -> Result<Option<u128>> {
+        if bits >= 128 {
+            return Ok(None);
+        }
+        let value: u128 = if bits > 0 {
+            self.buffer.read_int(pos, bits)?
+        } else {
+            0
+        };
+        Ok(Some((1 << bits) | value))
+    }
+
+    /// Count the number of `0` bits before the next `1` bit, without advancing the stream
+    fn read_unary_quotient(&self) -> Result<usize> {
+        // scan a word at a time, the first bit read is the lowest bit for little endian and the highest for big endian
+        let start = self.pos.get();
+        let end = self.offset_pos(self.bits_left())?;
+        let mut pos = start;
+        while pos < end {
+            let count = (end - pos).min(64);
+            let word: u64 = self.buffer.read_int(pos, count)?;
+            if word != 0 {
+                let zeros = if E::is_le() {
+                    word.trailing_zeros()
+                } else {
+                    word.leading_zeros() - (64 - count as u32)
+                };
+                return Ok(pos - start + zeros as usize);
+            }
+            pos += count;
+        }
+        Err(BitError::NotEnoughData {
+            requested: self.bits_left() + 1,
+            bits_left: self.bits_left(),
+        })
+    }
+
+    /// Read a sequence of bits from the stream as integer without checking if enough data is left
+    ///
+    /// # Safety
+    ///
+    /// The stream needs to have at least `count` bits left, which can be ensured by calling [`check_read`] beforehand.
+    /// `count` should be at most the size of the integer type, larger counts don't result in undefined behavior
+    /// but give meaningless results. See the [`unchecked`] module for details.
+    ///
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
+    #[inline]
+    pub unsafe fn read_int_unchecked<T>(&mut self, count: usize) -> T
+    where
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
+    {
+        let result = self.buffer.read_int_unchecked(self.pos.get(), count);
+        self.pos += count;
+        self.spend(count);
+        result
+    }
+
+    /// Read `count` bits a word at a time, calling `f` for every bit in stream order
+    pub(crate) fn read_bits_with<F: FnMut(bool)>(&mut self, count: usize, mut f: F) -> Result<()> {
+        self.check_read(count)?;
+        let mut left = count;
+        while left > 0 {
+            let chunk = min(left, 64);
+            // safety: the bounds have been checked for all chunks at once
+            let word: u64 = unsafe { self.read_int_unchecked(chunk) };
+            for i in 0..chunk {
+                let shift = if E::is_le() { i } else { chunk - 1 - i };
+                f((word >> shift) & 1 == 1);
+            }
+            left -= chunk;
+        }
+        Ok(())
+    }
+
+    /// Read a sequence of bits from the stream as float
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let result = stream.read_float::<f32>()?;
+    /// assert_eq!(stream.pos(), 32);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_float<T>(&mut self) -> Result<T>
+    where
+        T: Float + UncheckedPrimitiveFloat,
+    {
+        let count = size_of::<T>() * 8;
+        self.check_limits(count)?;
+        let result = self.buffer.read_float(self.pos.get());
+        if result.is_ok() {
+            self.pos += count;
+            self.spend(count);
+        }
+        result
+    }
+
+    /// Read a float quantized to a `bits` bit integer spread evenly between `min` and `max`
+    ///
+    /// The read integer is mapped to `min + int / (2^bits - 1) * (max - min)`, so `0` is read as `min`
+    /// and the largest `bits` bit integer as `max`. `bits` can be at most 32.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: more than 32 bits requested
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1111_1111, 0b1100_0000];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_quantized_float(8, -1.0, 1.0)?, 1.0);
+    /// assert_eq!(stream.read_quantized_float(2, 0.0, 360.0)?, 360.0);
+    /// assert_eq!(stream.read_quantized_float(6, 0.0, 63.0)?, 0.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    #[inline]
+    pub fn read_quantized_float(&mut self, bits: usize, min: f32, max: f32) -> Result<f32> {
+        let value: u32 = self.read_int(bits)?;
+        if bits == 0 {
+            return Ok(min);
+        }
+        let steps = ((1u64 << bits) - 1) as f32;
+        Ok(min + value as f32 / steps * (max - min))
+    }
+
+    /// Read an angle in degrees stored as a `bits` bit binary angular measurement
+    ///
+    /// The full circle is divided into `2^bits` steps, the read integer is mapped to `int * 360 / 2^bits`,
+    /// resulting in an angle between `0` and `360` degrees. `bits` can be at most 32.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: more than 32 bits requested
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b0100_0000, 0b1110_0000];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_bit_angle(8)?, 90.0);
+    /// assert_eq!(stream.read_bit_angle(3)?, 315.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    #[inline]
+    pub fn read_bit_angle(&mut self, bits: usize) -> Result<f32> {
+        let value: u32 = self.read_int(bits)?;
+        Ok((f64::from(value) * 360.0 / (1u64 << bits) as f64) as f32)
+    }
+
+    /// Read a sequence of bits from the stream as float without checking if enough data is left
+    ///
+    /// # Safety
+    ///
+    /// The stream needs to have at least 32 bits left for an `f32` or 64 bits for an `f64`, which can be ensured by calling
+    /// [`check_read`] beforehand. See the [`unchecked`] module for details.
+    ///
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
+    #[inline]
+    pub unsafe fn read_float_unchecked<T>(&mut self) -> T
+    where
+        T: Float + UncheckedPrimitiveFloat,
+    {
+        let count = size_of::<T>() * 8;
+        let result = self.buffer.read_float_unchecked(self.pos.get());
+        self.pos += count;
+        self.spend(count);
+        result
+    }
+
+    /// Read a series of bytes from the stream
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_bytes(3)?, &[0b1011_0101, 0b0110_1010, 0b1010_1100]);
+    /// assert_eq!(stream.pos(), 24);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_bytes(&mut self, byte_count: usize) -> Result<Vec<u8>> {
+        let count = byte_count.saturating_mul(8);
+        self.check_limits(count)?;
+        let result = self.buffer.read_bytes(self.pos.get(), byte_count);
+        if result.is_ok() {
+            self.pos += count;
+            self.spend(count);
+        }
+        result
+    }
+
+    /// Read the bytes before the next `terminator` byte, the terminator is consumed but not included in the result
+    ///
+    /// At most `max` bytes are read before the terminator, the bytes don't have to be byte aligned.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::TerminatorNotFound`]: the terminator doesn't occur within the first `max + 1` bytes
+    /// - [`ReadError::NotEnoughData`]: the stream ends before the terminator
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(b"GET /\r\n".to_vec(), LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_bytes_until(b' ', 16)?, b"GET");
+    /// assert_eq!(stream.read_bytes_until(b'\r', 16)?, b"/");
+    /// assert_eq!(stream.pos(), 6 * 8);
+    /// assert!(stream.read_bytes_until(b'!', 16).is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::TerminatorNotFound`]: enum.ReadError.html#variant.TerminatorNotFound
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bytes_until(&mut self, terminator: u8, max: usize) -> Result<Vec<u8>> {
+        let bytes = match self.aligned_bytes() {
+            Some(data) => {
+                let search = &data[0..data.len().min(max.saturating_add(1))];
+                match memchr::memchr(terminator, search) {
+                    Some(len) => search[0..len].to_vec(),
+                    None if data.len() > max => {
+                        return Err(BitError::TerminatorNotFound { terminator, max })
+                    }
+                    None => {
+                        return Err(BitError::NotEnoughData {
+                            requested: (data.len() + 1) * 8,
+                            bits_left: self.bits_left(),
+                        })
+                    }
+                }
+            }
+            None => self.read_bytes_until_unaligned(terminator, max)?,
+        };
+        let read = (bytes.len() + 1) * 8;
+        self.check_limits(read)?;
+        self.pos += read;
+        self.spend(read);
+        Ok(bytes)
+    }
+
+    /// The bytes from the current position until the end of the stream, if the position is byte aligned
+    /// and the stream reads directly from the underlying bytes
+    fn aligned_bytes(&self) -> Option<&[u8]> {
+        if self.pos.get() & 7 != 0 {
+            return None;
+        }
+        self.buffer
+            .as_bytes()
+            .and_then(|bytes| bytes.get(self.pos.get() / 8..))
+    }
+
+    /// Find the bytes before the next `terminator` one byte at a time, without advancing the stream
+    fn read_bytes_until_unaligned(&self, terminator: u8, max: usize) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        loop {
+            let offset = bytes.len() * 8;
+            if self.bits_left() - offset < 8 {
+                return Err(BitError::NotEnoughData {
+                    requested: offset + 8,
+                    bits_left: self.bits_left(),
+                });
+            }
+            let byte: u8 = self.buffer.read_int(self.offset_pos(offset)?, 8)?;
+            if byte == terminator {
+                break;
+            }
+            if bytes.len() == max {
+                return Err(BitError::TerminatorNotFound { terminator, max });
+            }
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+
+    /// Read a series of bytes from the stream without checking if enough data is left
+    ///
+    /// # Safety
+    ///
+    /// The stream needs to have at least `byte_count * 8` bits left, which can be ensured by calling [`check_read`]
+    /// beforehand. See the [`unchecked`] module for details.
+    ///
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
+    #[inline]
+    pub unsafe fn read_bytes_unchecked(&mut self, byte_count: usize) -> Vec<u8> {
+        let count = byte_count * 8;
+        let result = self.buffer.read_bytes_unchecked(self.pos.get(), byte_count);
+        self.pos += count;
+        self.spend(count);
+        result
+    }
+
+    /// Read a series of bytes from the stream as utf8 string
+    ///
+    /// You can either read a fixed number of bytes, or a dynamic length null-terminated string
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::Utf8Error`]: the read bytes are not valid utf8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0x48, 0x65, 0x6c, 0x6c,
+    /// #     0x6f, 0x20, 0x77, 0x6f,
+    /// #     0x72, 0x6c, 0x64, 0,
+    /// #     0,    0,    0,    0
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// // Fixed length string
+    /// stream.set_pos(0);
+    /// assert_eq!(stream.read_string(Some(11))?, "Hello world".to_owned());
+    /// assert_eq!(11 * 8, stream.pos());
+    /// // fixed length with null padding
+    /// stream.set_pos(0);
+    /// assert_eq!(stream.read_string(Some(16))?, "Hello world".to_owned());
+    /// assert_eq!(16 * 8, stream.pos());
+    /// // null terminated
+    /// stream.set_pos(0);
+    /// assert_eq!(stream.read_string(None)?, "Hello world".to_owned());
+    /// assert_eq!(12 * 8, stream.pos()); // 1 more for the terminating null byte
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Utf8Error`]: enum.ReadError.html#variant.Utf8Error
+    #[inline]
+    pub fn read_string(&mut self, byte_len: Option<usize>) -> Result<String> {
+        match byte_len {
+            Some(len) => self.read_string_mode(len, StringMode::FixedTrimTrailingNulls),
+            None => {
+                let mut bytes = Vec::with_capacity(32);
+                self.read_string_into(None, &mut bytes)?;
+                // read_string_into already validated the string
+                Ok(unsafe { String::from_utf8_unchecked(bytes) })
+            }
+        }
+    }
+
+    /// Read a series of bytes from the stream as string, replacing invalid utf8 with `U+FFFD REPLACEMENT CHARACTER`
+    ///
+    /// This behaves the same as [`read_string`], except that malformed strings don't result in an error.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![b'a', 0xff, b'b', 0, b'c', 0], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_string_lossy(None)?, "a\u{FFFD}b");
+    /// assert_eq!(stream.read_string_lossy(Some(2))?, "c");
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read_string`]: #method.read_string
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_string_lossy(&mut self, byte_len: Option<usize>) -> Result<String> {
+        match self.read_string(byte_len) {
+            // the stream is advanced past malformed strings
+            Err(BitError::Utf8Error(err)) => {
+                Ok(String::from_utf8_lossy(err.as_bytes()).into_owned())
+            }
+            result => result,
+        }
+    }
+
+    /// Read a string of `byte_len` bytes, handling null bytes according to `mode`
+    ///
+    /// The stream is always advanced by `byte_len` bytes, even if the string ends at an earlier null byte.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::Utf8Error`]: the bytes that are part of the string are not valid utf8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result, StringMode};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(b"ab\0cd\0\0".to_vec(), LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_string_mode(7, StringMode::FixedKeepNulls)?, "ab\0cd\0\0");
+    /// assert_eq!(7 * 8, stream.pos());
+    /// stream.set_pos(0)?;
+    /// assert_eq!(stream.read_string_mode(7, StringMode::NullTerminated)?, "ab");
+    /// assert_eq!(7 * 8, stream.pos());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Utf8Error`]: enum.ReadError.html#variant.Utf8Error
+    pub fn read_string_mode(&mut self, byte_len: usize, mode: StringMode) -> Result<String> {
+        let read = byte_len.saturating_mul(8);
+        self.check_limits(read)?;
+        let result = self
+            .buffer
+            .read_string_mode(self.pos.get(), byte_len, mode)
+            .inspect_err(|err| {
+                // still advance the stream on malformed utf8
+                if let BitError::Utf8Error(_) = err {
+                    self.pos += read;
+                    self.spend(read);
+                }
+            })?;
+        self.pos += read;
+        self.spend(read);
+        Ok(result)
+    }
+
+    /// Read a string of `chars` 7 bit ascii characters
+    ///
+    /// The characters are packed without padding, as used by SMS messages and many telemetry formats. When reading
+    /// from a little endian stream this matches the packing of GSM 03.38, note that the GSM default alphabet maps
+    /// some of the values to different characters than ascii, those can be read using [`read_int`] instead.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // "hello" packed as 5 septets
+    /// let buffer = BitReadBuffer::new(vec![0xe8, 0x32, 0x9b, 0xfd, 0x06], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_packed_ascii(5)?, "hello");
+    /// assert_eq!(stream.pos(), 35);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read_int`]: #method.read_int
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_packed_ascii(&mut self, chars: usize) -> Result<String> {
+        let count = chars.saturating_mul(7);
+        self.check_read(count)?;
+        let string = (self.pos.get()..self.offset_pos(count)?)
+            .step_by(7)
+            .map(|pos| Ok(char::from(self.buffer.read_int::<u8>(pos, 7)?)))
+            .collect::<Result<String>>()?;
+        self.pos += count;
+        self.spend(count);
+        Ok(string)
+    }
+
+    /// Read a series of bytes from the stream, appending them to `output`
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let mut output = Vec::new();
+    /// stream.read_bytes_into(2, &mut output)?;
+    /// stream.read_bytes_into(1, &mut output)?;
+    /// assert_eq!(output, &[0b1011_0101, 0b0110_1010, 0b1010_1100]);
+    /// assert_eq!(stream.pos(), 24);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_bytes_into<O: ByteOutput>(
+        &mut self,
+        byte_count: usize,
+        output: &mut O,
+    ) -> Result<()> {
+        let count = byte_count.saturating_mul(8);
+        self.check_limits(count)?;
+        self.buffer
+            .read_bytes_into(self.pos.get(), byte_count, output)?;
+        self.pos += count;
+        self.spend(count);
+        Ok(())
+    }
+
+    /// Copy `count` bits from the stream to `writer`
+    ///
+    /// When the stream is positioned at a byte boundary the bytes are written directly from the
+    /// underlying data, otherwise they are shifted in fixed size chunks, no intermediate buffer
+    /// for the full range is allocated.
+    ///
+    /// When `count` isn't a multiple of 8, the remaining bits are written as a final byte, padded
+    /// with `0` bits the same way as [`BitWriteStream`] does.
+    ///
+    /// The position of the stream is only advanced when all bits have been written.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::Io`]: writing to `writer` failed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b0101_0011, 0b0000_0001, 0b0000_0010];
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// stream.skip_bits(4)?;
+    ///
+    /// let mut output = Vec::new();
+    /// stream.copy_to(&mut output, 16)?;
+    /// assert_eq!(output, &[0b0001_0101, 0b0010_0000]);
+    /// assert_eq!(stream.pos(), 20);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitWriteStream`]: struct.BitWriteStream.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Io`]: enum.ReadError.html#variant.Io
+    pub fn copy_to<W: io::Write>(&mut self, writer: &mut W, count: usize) -> Result<()> {
+        self.check_read(count)?;
+        let rest = count & 7;
+        unsafe {
+            self.buffer
+                .copy_bytes_to(self.pos.get(), count / 8, writer)?;
+        }
+        if rest > 0 {
+            let pos = self.offset_pos(count - rest)?;
+            let last: u8 = unsafe { self.buffer.read_int_unchecked(pos, rest) };
+            let last = if E::is_le() { last } else { last << (8 - rest) };
+            writer.write_all(&[last])?;
+        }
+        self.pos += count;
+        self.spend(count);
+        Ok(())
+    }
+
+    /// Read a series of bytes from the stream as utf8 string, appending it to `output`
+    ///
+    /// This behaves the same as [`read_string`], except that the string is appended to `output` instead of
+    /// being returned. When the read string is not valid utf8, the invalid bytes are still appended to the output.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::Utf8Error`]: the read bytes are not valid utf8
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0x48, 0x65, 0x6c, 0x6c,
+    /// #     0x6f, 0x20, 0x77, 0x6f,
+    /// #     0x72, 0x6c, 0x64, 0,
+    /// #     0,    0,    0,    0
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let mut output = Vec::new();
+    /// stream.read_string_into(None, &mut output)?;
+    /// assert_eq!(output, b"Hello world");
+    /// assert_eq!(12 * 8, stream.pos());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read_string`]: #method.read_string
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::Utf8Error`]: enum.ReadError.html#variant.Utf8Error
+    pub fn read_string_into<O: ByteOutput>(
+        &mut self,
+        byte_len: Option<usize>,
+        output: &mut O,
+    ) -> Result<()> {
+        let start = output.as_slice().len();
+        match byte_len {
+            Some(byte_len) => {
+                self.read_bytes_into(byte_len, output)?;
+                let end = output.as_slice()[start..]
+                    .iter()
+                    .rposition(|byte| *byte != 0)
+                    .map_or(start, |index| start + index + 1);
+                output.truncate(end);
+                validate_utf8(&output.as_slice()[start..])
+            }
+            None => {
+                if self.pos() > self.bit_len() {
+                    return Err(BitError::IndexOutOfBounds {
+                        pos: self.pos(),
+                        size: self.bit_len(),
+                    });
+                }
+                self.buffer.read_string_bytes_into(self.pos.get(), output);
+                let string_len = output.as_slice().len() - start;
+
+                // the null terminator might be past the end of the stream when reading from a sub stream,
+                // in that case the bytes past the end of the stream are not part of the string
+                let max_length = self.bits_left() / 8;
+                let terminated = string_len < max_length;
+                if !terminated {
+                    output.truncate(start + max_length);
+                }
+                if let Err(err) = std::str::from_utf8(&output.as_slice()[start..]) {
+                    if !terminated && err.error_len().is_none() {
+                        // a character cut off by the end of the stream
+                        output.truncate(start + err.valid_up_to());
+                    } else {
+                        let err = validate_utf8(&output.as_slice()[start..]).unwrap_err();
+                        // still advance the stream on malformed utf8
+                        let read = min((string_len + 1) * 8, self.bits_left());
+                        self.pos += read;
+                        self.spend(read);
+                        return Err(err);
+                    }
+                }
+
+                let read = if terminated {
+                    (string_len + 1) * 8
+                } else {
+                    (output.as_slice().len() - start) * 8
+                };
+                self.check_limits(read)?;
+                self.pos += read;
+                self.spend(read);
+                Ok(())
+            }
+        }
+    }
+
+    /// Read a series of bytes from the stream into an arena
+    ///
+    /// See [`read_bytes`](#method.read_bytes) for details, requires the `arena` feature.
+    #[cfg(feature = "arena")]
+    #[inline]
+    pub fn read_bytes_in<'bump>(
+        &mut self,
+        byte_count: usize,
+        bump: &'bump bumpalo::Bump,
+    ) -> Result<bumpalo::collections::Vec<'bump, u8>> {
+        let mut output = bumpalo::collections::Vec::new_in(bump);
+        self.read_bytes_into(byte_count, &mut output)?;
+        Ok(output)
+    }
+
+    /// Read a series of bytes from the stream as utf8 string into an arena
+    ///
+    /// See [`read_string`](#method.read_string) for details, requires the `arena` feature.
+    #[cfg(feature = "arena")]
+    pub fn read_string_in<'bump>(
+        &mut self,
+        byte_len: Option<usize>,
+        bump: &'bump bumpalo::Bump,
+    ) -> Result<bumpalo::collections::String<'bump>> {
+        let mut output = bumpalo::collections::Vec::new_in(bump);
+        self.read_string_into(byte_len, &mut output)?;
+        // read_string_into already validated the string
+        Ok(unsafe { bumpalo::collections::String::from_utf8_unchecked(output) })
+    }
+
+    /// Read `count` values into a vector allocated in an arena
+    ///
+    /// Allocating the values of a packet in an arena allows freeing all of them at once by resetting the arena,
+    /// requires the `arena` feature.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - any error from reading the elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut bump = bumpalo::Bump::new();
+    /// let mut stream = BitReadStream::new(BitReadBuffer::new(vec![1, 0, 2, 0, 3, 0, 4, 0], LittleEndian));
+    /// for expected in [[1u16, 2], [3, 4]] {
+    ///     let values = stream.read_vec_in::<u16>(2, &bump)?;
+    ///     assert_eq!(values.as_slice(), expected);
+    ///     drop(values);
+    ///     // free all values of the packet at once
+    ///     bump.reset();
+    /// }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[cfg(feature = "arena")]
+    pub fn read_vec_in<'bump, T: BitRead<E>>(
+        &mut self,
+        count: usize,
+        bump: &'bump bumpalo::Bump,
+    ) -> Result<bumpalo::collections::Vec<'bump, T>> {
+        check_element_count(self, count, T::min_bit_size())?;
+        let mut values = bumpalo::collections::Vec::with_capacity_in(min(count, 128), bump);
+        for _ in 0..count {
+            values.push(self.read()?);
+        }
+        Ok(values)
+    }
+
+    /// Read `count` values that each take the provided size into a vector allocated in an arena
+    ///
+    /// See [`read_vec_in`](#method.read_vec_in) and [`read_sized_vec`](#method.read_sized_vec) for details,
+    /// requires the `arena` feature.
+    #[cfg(feature = "arena")]
+    pub fn read_sized_vec_in<'bump, T: BitReadSized<E>>(
+        &mut self,
+        count: usize,
+        size: usize,
+        bump: &'bump bumpalo::Bump,
+    ) -> Result<bumpalo::collections::Vec<'bump, T>> {
+        check_element_count(self, count, T::bit_size_sized(size).unwrap_or(0))?;
+        let mut values = bumpalo::collections::Vec::with_capacity_in(min(count, 128), bump);
+        for _ in 0..count {
+            values.push(self.read_sized(size)?);
+        }
+        Ok(values)
+    }
+
+    /// Read a sequence of bits from the stream as a BitStream
+    ///
+    /// The returned stream is isolated from this stream, all positions used by the returned stream
+    /// (including [`pos`], [`set_pos`], [`bit_len`] and positions reported in errors)
+    /// are relative to the start of the sequence, and reads can never go past the end of the sequence.
+    /// The same holds for streams created from the returned stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_bits(3)?;
+    /// assert_eq!(stream.pos(), 3);
+    /// assert_eq!(bits.pos(), 0);
+    /// assert_eq!(bits.bit_len(), 3);
+    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
+    /// assert_eq!(bits.read_int::<u8>(3)?, 0b101);
+    /// assert_eq!(true, bits.read_int::<u8>(1).is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`pos`]: #method.pos
+    /// [`set_pos`]: #method.set_pos
+    /// [`bit_len`]: #method.bit_len
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bits(&mut self, count: usize) -> Result<Self> {
+        if count > self.bits_left() {
+            return Err(BitError::NotEnoughData {
+                requested: count,
+                bits_left: self.bits_left(),
+            });
+        }
+        self.check_marker(count)?;
+        let result = self.derive(
+            self.buffer.get_sub_buffer(self.offset_pos(count)?)?,
+            self.pos.get(),
+        );
+        self.pos += count;
+        Ok(result)
+    }
+
+    /// Read a sequence of bits from the stream, starting at the next byte boundary
+    ///
+    /// Any bits up to the next byte boundary of the underlying data are skipped before reading,
+    /// the returned stream always starts on a byte boundary so its bytes can be accessed directly
+    /// using [`as_bytes`] when `count` is a multiple of 8.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b0000_0101, 1, 2, 3];
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let header: u8 = stream.read_int(3)?;
+    /// let payload = stream.read_bits_aligned(16)?;
+    /// assert_eq!(header, 0b101);
+    /// assert_eq!(stream.pos(), 24);
+    /// assert_eq!(payload.as_bytes(), Some(&[1, 2][..]));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`as_bytes`]: #method.as_bytes
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bits_aligned(&mut self, count: usize) -> Result<Self> {
+        let start = self.pos.get();
+        self.skip_bits((8 - (self.pos.get() & 7)) & 7)?;
+        self.read_bits(count)
+            .inspect_err(|_| self.pos = BitPos::new(start))
+    }
+
+    /// Get the bytes of the stream, if the stream both starts and ends on a byte boundary
+    ///
+    /// The full stream is returned regardless of the current position, `None` is returned
+    /// for streams that don't start or end on a byte boundary of the underlying data and for
+    /// streams that read from a view created by [`BitReadBuffer::reverse_bits`] or similar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![1, 2, 3, 4], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_bits(16)?.as_bytes(), Some(&[1, 2][..]));
+    /// assert_eq!(stream.read_bits(4)?.as_bytes(), None);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitReadBuffer::reverse_bits`]: struct.BitReadBuffer.html#method.reverse_bits
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        if self.start_pos & 7 != 0 {
+            return None;
+        }
+        self.buffer
+            .as_bytes()
+            .map(|bytes| &bytes[self.start_pos / 8..])
+    }
+
+    /// Read a sequence of bits from the stream while removing HDLC style bit stuffing
+    ///
+    /// Whenever five consecutive `1` bits are read, the `0` bit that was inserted after them is
+    /// dropped. The returned stream contains `count` de-stuffed bits, the source stream is advanced
+    /// past all bits that were consumed, including the stuffed ones. When the last returned bit
+    /// completes a run of five `1` bits, the stuffed bit following it is consumed too.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidBitStuffing`]: six consecutive `1` bits were found (a flag or abort sequence)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // 0b11111 followed by a stuffed 0, then 0b01
+    /// let bytes = vec![0b1001_1111];
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_unstuffed(7)?;
+    /// assert_eq!(stream.pos(), 8);
+    /// assert_eq!(bits.bit_len(), 7);
+    /// assert_eq!(bits.read_int::<u8>(7)?, 0b10_11111);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidBitStuffing`]: enum.ReadError.html#variant.InvalidBitStuffing
+    pub fn read_unstuffed(&mut self, count: usize) -> Result<Self> {
+        let mut bits = BitCollector::with_capacity(min(count, self.bits_left()));
+        let mut pos = self.pos.get();
+        let mut ones = 0;
+        for _ in 0..count {
+            let bit = self.read_bool_for(pos, count)?;
+            pos += 1;
+            bits.push(bit);
+            ones = if bit { ones + 1 } else { 0 };
+            if ones == 5 {
+                // skip the stuffed bit, also after the last bit so reading in chunks gives the same result
+                if self.read_bool_for(pos, count)? {
+                    return Err(BitError::InvalidBitStuffing {
+                        pos: pos - self.start_pos,
+                    });
+                }
+                pos += 1;
+                ones = 0;
+            }
+        }
+        self.check_limits(pos - self.pos.get())?;
+        self.spend(pos - self.pos.get());
+        self.pos = BitPos::new(pos);
+
+        Ok(self.derive_decoded(bits.finish()?))
+    }
+
+    /// Decode a sequence of Manchester encoded bits from the stream
+    ///
+    /// Every logical bit is read as a pair of line bits, the returned stream contains `count`
+    /// decoded bits, the source stream is advanced by `count * 2` bits.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::InvalidLineCode`]: a pair of line bits without a transition was found
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, ManchesterEncoding, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1001_0110];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_manchester(4, ManchesterEncoding::Ieee)?;
+    /// assert_eq!(stream.pos(), 8);
+    /// assert_eq!(bits.read_int::<u8>(4)?, 0b0110);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidLineCode`]: enum.ReadError.html#variant.InvalidLineCode
+    pub fn read_manchester(&mut self, count: usize, encoding: ManchesterEncoding) -> Result<Self> {
+        self.check_read(count.saturating_mul(2))?;
+        let mut bits = BitCollector::with_capacity(count);
+        for i in 0..count {
+            let pos = self.offset_pos(i * 2)?;
+            let first = self.buffer.read_bool(pos)?;
+            let second = self.buffer.read_bool(pos + 1)?;
+            match encoding.decode(first, second) {
+                Some(bit) => bits.push(bit),
+                None => {
+                    return Err(BitError::InvalidLineCode {
+                        pos: pos - self.start_pos,
+                    })
+                }
+            }
+        }
+        self.pos += count * 2;
+        self.spend(count * 2);
+
+        Ok(self.derive_decoded(bits.finish()?))
+    }
+
+    /// Decode a sequence of NRZI encoded bits from the stream
+    ///
+    /// `initial_level` is the line level preceding the first read bit, the returned stream contains
+    /// `count` decoded bits.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, NrziEncoding, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1101_0000];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_nrzi(4, NrziEncoding::Space, false)?;
+    /// assert_eq!(stream.pos(), 4);
+    /// assert_eq!(bits.read_int::<u8>(4)?, 0b0100);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_nrzi(
+        &mut self,
+        count: usize,
+        encoding: NrziEncoding,
+        initial_level: bool,
+    ) -> Result<Self> {
+        self.check_read(count)?;
+        let mut bits = BitCollector::with_capacity(count);
+        let mut previous = initial_level;
+        for pos in self.pos.get()..self.offset_pos(count)? {
+            let level = self.buffer.read_bool(pos)?;
+            bits.push(encoding.decode(previous, level));
+            previous = level;
+        }
+        self.pos += count;
+        self.spend(count);
+
+        Ok(self.derive_decoded(bits.finish()?))
+    }
+
+    /// Read a huffman encoded symbol from the stream
+    ///
+    /// The stream is advanced by the length of the matched code.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: the stream ends before a full code could be read
+    /// - [`ReadError::InvalidHuffmanCode`]: the read bits don't match any code in the table
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, HuffmanTable, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let table = HuffmanTable::new(vec![(0b0, 1, 10u8), (0b10, 2, 20), (0b11, 2, 30)])?;
+    ///
+    /// // little endian streams read the bits from least to most significant
+    /// let bytes = vec![0b0000_1101];
+    /// let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_huffman(&table)?, 20);
+    /// assert_eq!(stream.read_huffman(&table)?, 30);
+    /// assert_eq!(stream.read_huffman(&table)?, 10);
+    /// assert_eq!(stream.pos(), 5);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::InvalidHuffmanCode`]: enum.ReadError.html#variant.InvalidHuffmanCode
+    pub fn read_huffman<T: Clone>(&mut self, table: &HuffmanTable<T>) -> Result<T> {
+        let bits_left = self.bits_left();
+        match table.decode(&self.buffer, self.pos.get(), bits_left)? {
+            Some((length, symbol)) => {
+                self.check_limits(length)?;
+                self.pos += length;
+                self.spend(length);
+                Ok(symbol.clone())
+            }
+            None if bits_left < table.max_length() => Err(BitError::NotEnoughData {
+                requested: table.max_length(),
+                bits_left,
+            }),
+            None => Err(BitError::InvalidHuffmanCode { pos: self.pos() }),
+        }
+    }
+
+    /// Read a block of `rows * columns` bits from the stream and undo block interleaving
+    ///
+    /// The block is expected to be transmitted column by column, the returned stream contains
+    /// the bits in their original row by row order.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // 2 rows, 4 columns: 1111 and 0000 transmitted column by column
+    /// let bytes = vec![0b1010_1010];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_deinterleaved(2, 4)?;
+    /// assert_eq!(stream.pos(), 8);
+    /// assert_eq!(bits.read_int::<u8>(8)?, 0b1111_0000);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_deinterleaved(&mut self, rows: usize, columns: usize) -> Result<Self> {
+        let count = rows.saturating_mul(columns);
+        self.check_read(count)?;
+        let mut bits = BitCollector::with_capacity(count);
+        for row in 0..rows {
+            for column in 0..columns {
+                bits.push(
+                    self.buffer
+                        .read_bool(self.offset_pos(column * rows + row)?)?,
+                );
+            }
+        }
+        self.pos += count;
+        self.spend(count);
+
+        Ok(self.derive_decoded(bits.finish()?))
+    }
+
+    /// Read a block of `rows * columns` bits from the stream and apply block interleaving
+    ///
+    /// The bits are read as `rows` rows of `columns` bits, the returned stream contains
+    /// the bits column by column. This is the inverse of [`read_deinterleaved`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1111_0000];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let mut bits = stream.read_interleaved(2, 4)?;
+    /// assert_eq!(bits.read_int::<u8>(8)?, 0b1010_1010);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`read_deinterleaved`]: #method.read_deinterleaved
+    pub fn read_interleaved(&mut self, rows: usize, columns: usize) -> Result<Self> {
+        let count = rows.saturating_mul(columns);
+        self.check_read(count)?;
+        let mut bits = BitCollector::with_capacity(count);
+        for column in 0..columns {
+            for row in 0..rows {
+                bits.push(
+                    self.buffer
+                        .read_bool(self.offset_pos(row * columns + column)?)?,
+                );
+            }
+        }
+        self.pos += count;
+        self.spend(count);
+
+        Ok(self.derive_decoded(bits.finish()?))
+    }
+
+    /// Read a sequence of bits from the stream and check its parity
+    ///
+    /// The `count` bits read include the parity bit, returns `true` if the number of set bits
+    /// matches the expected parity.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Parity, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1011_0101, 0b0110_1010];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_parity(8, Parity::Odd)?, true);
+    /// assert_eq!(stream.read_parity(8, Parity::Odd)?, false);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_parity(&mut self, count: usize, parity: Parity) -> Result<bool> {
+        self.check_read(count)?;
+        let mut ones = 0;
+        for pos in self.pos.get()..self.offset_pos(count)? {
+            ones += self.buffer.read_bool(pos)? as u32;
+        }
+        self.pos += count;
+        self.spend(count);
+
+        Ok(parity.matches(ones))
+    }
+
+    /// Decode a number of Hamming code blocks from the stream
+    ///
+    /// Every block consists of [`HammingCode::block_len`] bits and decodes to 4 data bits,
+    /// the returned stream contains the corrected data bits together with the status of every block.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, FecStatus, HammingCode, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // the code word for data 1011 with the last bit flipped
+    /// let bytes = vec![0b0110_0100];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let (mut bits, status) = stream.read_hamming(1, HammingCode::Hamming74)?;
+    /// assert_eq!(bits.read_int::<u8>(4)?, 0b1011);
+    /// assert_eq!(status, vec![FecStatus::Corrected]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`HammingCode::block_len`]: enum.HammingCode.html#method.block_len
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_hamming(
+        &mut self,
+        blocks: usize,
+        code: HammingCode,
+    ) -> Result<(Self, Vec<FecStatus>)> {
+        let block_len = code.block_len();
+        let count = blocks.saturating_mul(block_len);
+        self.check_read(count)?;
+        let mut bits = BitCollector::with_capacity(blocks * 4);
+        let mut status = Vec::with_capacity(blocks);
+        for block in 0..blocks {
+            let start = self.offset_pos(block * block_len)?;
+            let mut word = [false; 8];
+            for (i, bit) in word.iter_mut().take(block_len).enumerate() {
+                *bit = self.buffer.read_bool(start + i)?;
+            }
+            let (data, block_status) = code.decode(word);
+            data.iter().for_each(|bit| bits.push(*bit));
+            status.push(block_status);
+        }
+        self.pos += count;
+        self.spend(count);
+
+        Ok((self.derive_decoded(bits.finish()?), status))
+    }
+
+    /// Read an integer of `count` bits followed by a parity bit covering it
+    ///
+    /// The stream will be advanced by `count + 1` bits if the parity matches, on error the position
+    /// of the stream is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
+    /// - [`ReadError::ParityError`]: the number of set bits doesn't match the expected parity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitError, BitReadBuffer, BitReadStream, BigEndian, Parity, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let bytes = vec![0b1011_0101, 0b0110_1010];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_with_parity::<u8>(7, Parity::Odd)?, 0b101_1010);
+    /// assert!(matches!(
+    ///     stream.read_with_parity::<u8>(7, Parity::Odd),
+    ///     Err(BitError::ParityError { pos: 8 })
+    /// ));
+    /// assert_eq!(stream.pos(), 8);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    /// [`ReadError::ParityError`]: enum.ReadError.html#variant.ParityError
+    pub fn read_with_parity<T>(&mut self, count: usize, parity: Parity) -> Result<T>
+    where
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
+    {
+        self.check_read(count.saturating_add(1))?;
+        let value: T = self.buffer.read_int(self.pos.get(), count)?;
+        let ones = value.count_ones() + self.buffer.read_bool(self.offset_pos(count)?)? as u32;
+        // sign extension sets the bits above `count`
+        let ones = if T::is_signed() && value < T::zero() {
+            ones - (size_of::<T>() * 8 - count) as u32
+        } else {
+            ones
+        };
+        if !parity.matches(ones) {
+            return Err(BitError::ParityError {
+                pos: self.pos.get() - self.start_pos,
+            });
+        }
+        self.pos += count + 1;
+        self.spend(count + 1);
+
+        Ok(value)
+    }
+
+    /// Decode a single Hamming code block, returning the 4 data bits with `d1` as the most significant bit
+    ///
+    /// Single bit errors are corrected, the stream will be advanced by [`HammingCode::block_len`] bits
+    /// if the block could be decoded, on error the position of the stream is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::ParityError`]: the block contains an error that can't be corrected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitError, BitReadBuffer, BitReadStream, BigEndian, HammingCode, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // the SECDED code word for data 1011 with one and two bits flipped
+    /// let bytes = vec![0b0110_0111, 0b0110_1111];
+    /// let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_hamming_block(HammingCode::Secded)?, 0b1011);
+    /// assert!(matches!(
+    ///     stream.read_hamming_block(HammingCode::Secded),
+    ///     Err(BitError::ParityError { pos: 8 })
+    /// ));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`HammingCode::block_len`]: enum.HammingCode.html#method.block_len
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::ParityError`]: enum.ReadError.html#variant.ParityError
+    pub fn read_hamming_block(&mut self, code: HammingCode) -> Result<u8> {
+        let block_len = code.block_len();
+        self.check_read(block_len)?;
+        let mut word = [false; 8];
+        for (i, bit) in word.iter_mut().take(block_len).enumerate() {
+            *bit = self.buffer.read_bool(self.offset_pos(i)?)?;
+        }
+        match code.decode(word) {
+            (_, FecStatus::Uncorrectable) => Err(BitError::ParityError {
+                pos: self.pos.get() - self.start_pos,
+            }),
+            (data, _) => {
+                self.pos += block_len;
+                self.spend(block_len);
+                Ok(data
+                    .iter()
+                    .fold(0, |nibble, bit| (nibble << 1) | *bit as u8))
+            }
+        }
+    }
+
+    /// Read a single bit at an absolute position as part of a larger read of `count` bits
+    #[inline]
+    fn read_bool_for(&self, pos: usize, count: usize) -> Result<bool> {
+        self.buffer
+            .read_bool(pos)
+            .map_err(|_| BitError::NotEnoughData {
+                requested: count,
+                bits_left: self.bits_left(),
+            })
+    }
+
+    /// Skip a number of bits in the stream
+    ///
+    /// Skipped bits count towards the read limit the same way as read bits.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream to skip
+    /// - [`ReadError::ReadLimitReached`]: skipping would exceed the limit set with [`set_read_limit`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// stream.skip_bits(3)?;
+    /// assert_eq!(stream.pos(), 3);
+    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::ReadLimitReached`]: enum.ReadError.html#variant.ReadLimitReached
+    /// [`set_read_limit`]: #method.set_read_limit
+    pub fn skip_bits(&mut self, count: usize) -> Result<()> {
+        let pos = self.pos.advance(count, self.buffer.bit_len())?;
+        self.check_budget(count)?;
+        self.pos = pos;
+        self.spend(count);
+        Ok(())
+    }
+
+    /// Skip ahead to the next position that is a multiple of `alignment` bits
+    ///
+    /// The alignment is relative to the start of the stream. If the stream is already aligned no bits are skipped.
+    /// Returns the number of skipped bits.
+    ///
+    /// This can be used to recover from an error when reading formats that consist of aligned frames.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream to skip
+    /// - [`ReadError::ReadLimitReached`]: skipping would exceed the limit set with [`set_read_limit`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// stream.skip_bits(3)?;
+    /// assert_eq!(stream.align_to(8)?, 5);
+    /// assert_eq!(stream.align_to(8)?, 0);
+    /// assert_eq!(stream.pos(), 8);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::ReadLimitReached`]: enum.ReadError.html#variant.ReadLimitReached
+    /// [`set_read_limit`]: #method.set_read_limit
+    pub fn align_to(&mut self, alignment: usize) -> Result<usize> {
+        let alignment = alignment.max(1);
+        let count = (alignment - self.pos() % alignment) % alignment;
+        self.skip_bits(count)?;
+        Ok(count)
+    }
+
+    /// Skip ahead to the next occurrence of a sync pattern
+    ///
+    /// Searches for the `bits` bit `pattern`, as it would be read by [`read_int`], starting at the current position
+    /// and moves the stream to the start of the pattern. Returns the number of skipped bits.
+    ///
+    /// This can be used to recover from an error by skipping to the start of the next frame, note that
+    /// the stream has to be moved past the sync pattern of the corrupt frame first.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::TooManyBits`]: `bits` is `0` or more than 64
+    /// - [`ReadError::PatternNotFound`]: the pattern doesn't occur in the remaining bits
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![0x00, 0x13, 0xa5, 0x42], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// stream.skip_bits(3)?;
+    /// assert_eq!(stream.resync_to(0xa5, 8)?, 13);
+    /// assert_eq!(stream.pos(), 16);
+    /// assert_eq!(stream.read_int::<u8>(8)?, 0xa5);
+    /// assert!(stream.resync_to(0xa5, 8).is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read_int`]: #method.read_int
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    /// [`ReadError::PatternNotFound`]: enum.ReadError.html#variant.PatternNotFound
+    pub fn resync_to(&mut self, pattern: u64, bits: usize) -> Result<usize> {
+        if bits == 0 || bits > 64 {
+            return Err(BitError::TooManyBits {
+                requested: bits,
+                max: 64,
+            });
+        }
+        let pattern = pattern & (u64::MAX >> (64 - bits));
+        let end = self.start_pos + self.bit_len();
+        let mut pos = self.pos.get();
+        while pos + bits <= end {
+            if self.buffer.read_int::<u64>(pos, bits)? == pattern {
+                let count = pos - self.pos.get();
+                self.pos = BitPos::new(pos);
+                return Ok(count);
+            }
+            pos += 1;
+        }
+        Err(BitError::PatternNotFound { pattern, bits })
+    }
+
+    /// Read the first of a list of magic byte strings that matches the next bytes in the stream
+    ///
+    /// The magic byte strings are compared against the stream in order, and can have different lengths.
+    /// If one matches the stream is moved past it and its index is returned, otherwise the stream is left unchanged
+    /// and `None` is returned. When one magic is a prefix of another, the longer one should come first.
+    ///
+    /// This is used by the derived [`BitRead`] implementation for enums with a `magic` attribute on their variants.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::ReadLimitReached`]: the matched magic exceeds the read limit of the stream
+    /// - [`ReadError::ReadPastMarker`]: the matched magic extends past the read marker of the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(b"LISTINFO".to_vec(), LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.read_magic(&[b"RIFF", b"LIST"])?, Some(1));
+    /// assert_eq!(stream.read_magic(&[b"RIFF", b"LIST"])?, None);
+    /// assert_eq!(stream.pos(), 32);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitRead`]: trait.BitRead.html
+    /// [`ReadError::ReadLimitReached`]: enum.ReadError.html#variant.ReadLimitReached
+    /// [`ReadError::ReadPastMarker`]: enum.ReadError.html#variant.ReadPastMarker
+    pub fn read_magic(&mut self, magics: &[&[u8]]) -> Result<Option<usize>> {
+        'magics: for (index, magic) in magics.iter().enumerate() {
+            let count = magic.len().saturating_mul(8);
+            if count > self.bits_left() {
+                continue;
+            }
+            for (offset, byte) in magic.iter().enumerate() {
+                if self
+                    .buffer
+                    .read_int::<u8>(self.offset_pos(offset * 8)?, 8)?
+                    != *byte
+                {
+                    continue 'magics;
+                }
+            }
+            self.check_limits(count)?;
+            self.pos += count;
+            self.spend(count);
+            return Ok(Some(index));
+        }
+        Ok(None)
+    }
+
+    /// Set the position of the stream
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: new position is outside the bounds of the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// stream.set_pos(3)?;
+    /// assert_eq!(stream.pos(), 3);
+    /// assert_eq!(stream.read_int::<u8>(3)?, 0b110);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn set_pos(&mut self, pos: usize) -> Result<()> {
+        if pos > self.bit_len() {
+            return Err(BitError::IndexOutOfBounds {
+                pos,
+                size: self.bit_len(),
+            });
+        }
+        self.pos = BitPos::new(pos + self.start_pos);
+        Ok(())
+    }
+
+    /// Save the current position and jump to a new position
+    ///
+    /// The saved position can be restored using [`pop_pos`], calls can be nested to follow
+    /// offsets in the data and return afterwards.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: new position is outside the bounds of the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![3, 0, 0, 42];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let offset: u8 = stream.read()?;
+    /// stream.push_pos(offset as usize * 8)?;
+    /// assert_eq!(stream.read::<u8>()?, 42);
+    /// assert_eq!(stream.pop_pos(), Some(8));
+    /// assert_eq!(stream.pos(), 8);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`pop_pos`]: #method.pop_pos
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn push_pos(&mut self, pos: usize) -> Result<()> {
+        let saved = self.pos.get();
+        self.set_pos(pos)?;
+        self.saved_positions.push(saved);
+        Ok(())
+    }
+
+    /// Restore the position saved by the last call to [`push_pos`]
+    ///
+    /// Returns the restored position, or `None` without changing the position if no position is saved.
+    /// Saved positions are not shared with clones or sub streams of this stream.
+    ///
+    /// [`push_pos`]: #method.push_pos
+    pub fn pop_pos(&mut self) -> Option<usize> {
+        self.pos = BitPos::new(self.saved_positions.pop()?);
+        Some(self.pos())
+    }
+
+    /// Read a value at a position in the stream without changing the current position
+    ///
+    /// This is useful for resolving offsets in the data, the position is relative to the start of the stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: position is outside the bounds of the stream
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream after the position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![3, 0, 0, 42];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let offset: u8 = stream.read()?;
+    /// assert_eq!(stream.read_at::<u8>(offset as usize * 8)?, 42);
+    /// assert_eq!(stream.pos(), 8);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_at<T: BitRead<E>>(&self, pos: usize) -> Result<T> {
+        let mut stream = self.derive(self.buffer.clone(), self.start_pos);
+        stream.set_pos(pos)?;
+        stream.read()
+    }
+
+    /// Get the length of the stream in bits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.bit_len(), 64);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn bit_len(&self) -> usize {
+        self.buffer.bit_len() - self.start_pos
+    }
+
+    /// Get the current position in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.pos(), 0);
+    /// stream.skip_bits(5)?;
+    /// assert_eq!(stream.pos(), 5);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn pos(&self) -> usize {
+        self.pos.get() - self.start_pos
+    }
+
+    /// Get the number of bits left in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// assert_eq!(stream.bits_left(), 64);
+    /// stream.skip_bits(5)?;
+    /// assert_eq!(stream.bits_left(), 59);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn bits_left(&self) -> usize {
+        self.bit_len() - self.pos()
+    }
+
+    /// Shorten the stream to `bit_len` bits
+    ///
+    /// Unlike [`read_bits`] no new stream is created, the stream itself is limited in place.
+    /// This can be used to enforce the length of a length-prefixed frame without allocating.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: `bit_len` is larger than the length of the stream
+    /// - [`ReadError::IndexOutOfBounds`]: the current position is past `bit_len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![1, 2, 3, 4], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// stream.truncate(16)?;
+    /// assert_eq!(stream.bit_len(), 16);
+    /// assert_eq!(stream.read_int::<u16>(16)?, 0x0201);
+    /// assert!(stream.read_bool().is_err());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read_bits`]: #method.read_bits
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn truncate(&mut self, bit_len: usize) -> Result<()> {
+        if bit_len > self.bit_len() {
+            return Err(BitError::NotEnoughData {
+                requested: bit_len,
+                bits_left: self.bit_len(),
+            });
+        }
+        if self.pos() > bit_len {
+            return Err(BitError::IndexOutOfBounds {
+                pos: self.pos(),
+                size: bit_len,
+            });
+        }
+        self.buffer.truncate(self.start_pos + bit_len);
+        Ok(())
+    }
+
+    /// Limit the number of bits left in the stream to `count`
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: less than `count` bits are left in the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let buffer = BitReadBuffer::new(vec![2, b'h', b'i', b'!', 0], LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let frame_len: u8 = stream.read()?;
+    /// stream.take(frame_len as usize * 8)?;
+    /// assert_eq!(stream.read_string(None)?, "hi");
+    /// assert_eq!(stream.bits_left(), 0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn take(&mut self, count: usize) -> Result<()> {
+        if count > self.bits_left() {
+            return Err(BitError::NotEnoughData {
+                requested: count,
+                bits_left: self.bits_left(),
+            });
+        }
+        self.truncate(self.pos() + count)
+    }
+
+    /// Read a value based on the provided type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let int: u8 = stream.read()?;
+    /// assert_eq!(int, 0b1011_0101);
+    /// let boolean: bool = stream.read()?;
+    /// assert_eq!(false, boolean);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// use bitbuffer::BitRead;
+    /// #
+    /// #[derive(BitRead, Debug, PartialEq)]
+    /// struct ComplexType {
+    ///     first: u8,
+    ///     #[size = 15]
+    ///     second: u16,
+    ///     third: bool,
+    /// }
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let data: ComplexType = stream.read()?;
+    /// assert_eq!(data, ComplexType {
+    ///     first: 0b1011_0101,
+    ///     second: 0b010_1100_0110_1010,
+    ///     third: true,
+    /// });
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn read<T: BitRead<E>>(&mut self) -> Result<T> {
+        self.nested(T::read)
+    }
+
+    /// Read a value based on the provided type without checking if enough data is left
+    ///
+    /// Only the bounds are unchecked, the value is validated the same way as by [`read`].
+    ///
+    /// # Safety
+    ///
+    /// `T` needs to have a fixed size and the stream needs to have at least [`BitRead::bit_size`] bits left,
+    /// which can be ensured by calling [`check_read`] beforehand. See the [`unchecked`] module for details.
+    ///
+    /// [`read`]: #method.read
+    /// [`BitRead::bit_size`]: trait.BitRead.html#method.bit_size
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
+    #[inline]
+    pub unsafe fn read_unchecked<T: BitRead<E>>(&mut self) -> Result<T> {
+        self.nested(|stream| T::read_unchecked(stream))
+    }
+
+    /// Read a value based on the provided type and size
+    ///
+    /// The meaning of the size parameter differs depending on the type that is being read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let int: u8 = stream.read_sized(7)?;
+    /// assert_eq!(int, 0b011_0101);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![
+    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
+    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
+    /// # ];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let data: Vec<u16> = stream.read_sized(3)?;
+    /// assert_eq!(data, vec![0b0110_1010_1011_0101, 0b1001_1001_1010_1100, 0b1001_1001_1001_1001]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn read_sized<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
+        self.nested(|stream| T::read(stream, size))
+    }
+
+    /// Read a value into an existing value, reusing its allocations
+    ///
+    /// See [`BitReadInto`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - Any error returned by the [`BitReadInto`] implementation of the type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitReadStream::new(BitReadBuffer::new(b"foo\0bar\0".to_vec(), LittleEndian));
+    /// let mut name = String::with_capacity(16);
+    /// for expected in ["foo", "bar"] {
+    ///     stream.read_into(&mut name)?;
+    ///     assert_eq!(name, expected);
+    /// }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitReadInto`]: trait.BitReadInto.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_into<T: BitReadInto<E> + ?Sized>(&mut self, value: &mut T) -> Result<()> {
+        self.nested(|stream| value.read_into(stream))
+    }
+
+    /// Read a value into an existing value with the provided size, reusing its allocations
+    ///
+    /// See [`BitReadInto`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - Any error returned by the [`BitReadIntoSized`] implementation of the type
+    ///
+    /// [`BitReadInto`]: trait.BitReadInto.html
+    /// [`BitReadIntoSized`]: trait.BitReadIntoSized.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_into_sized<T: BitReadIntoSized<E> + ?Sized>(
+        &mut self,
+        value: &mut T,
+        size: usize,
+    ) -> Result<()> {
+        self.nested(|stream| value.read_into_sized(stream, size))
+    }
+
+    /// Read `count` values that each take the provided size
+    ///
+    /// Where reading a `Vec<T>` with [`read_sized`] uses the size as the number of elements, this reads
+    /// `count` elements using [`BitReadSized`] with `size` for every element.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - any error from reading the elements
     ///
     /// # Examples
     ///
@@ -509,156 +2793,582 @@ where
     /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
+    /// # let bytes = b"abcdef".to_vec();
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// assert_eq!(stream.bits_left(), 64);
-    /// stream.skip_bits(5)?;
-    /// assert_eq!(stream.bits_left(), 59);
+    /// let names: Vec<String> = stream.read_sized_vec(3, 2)?;
+    /// assert_eq!(names, ["ab", "cd", "ef"]);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
-    pub fn bits_left(&self) -> usize {
-        self.bit_len() - self.pos()
+    ///
+    /// [`read_sized`]: #method.read_sized
+    /// [`BitReadSized`]: trait.BitReadSized.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_sized_vec<T: BitReadSized<E>>(
+        &mut self,
+        count: usize,
+        size: usize,
+    ) -> Result<Vec<T>> {
+        check_element_count(self, count, T::bit_size_sized(size).unwrap_or(0))?;
+        let mut vec = Vec::with_capacity(min(count, 128));
+        for _ in 0..count {
+            vec.push(self.read_sized(size)?);
+        }
+        Ok(vec)
     }
 
-    /// Read a value based on the provided type
+    /// Read a value that depends on the provided context
+    ///
+    /// See [`BitReadWithContext`] for details.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitReadWithContext, LittleEndian, Result};
     /// #
+    /// #[derive(BitReadWithContext, Debug, PartialEq)]
+    /// #[context = "usize"]
+    /// struct Sample {
+    ///     #[size = "*ctx"]
+    ///     value: u16,
+    /// }
+    ///
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
+    /// # let bytes = vec![0b1011_0101, 0b0110_1010];
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// let int: u8 = stream.read()?;
-    /// assert_eq!(int, 0b1011_0101);
-    /// let boolean: bool = stream.read()?;
-    /// assert_eq!(false, boolean);
+    /// let sample: Sample = stream.read_with_context(&4)?;
+    /// assert_eq!(sample, Sample { value: 0b0101 });
+    /// assert_eq!(stream.pos(), 4);
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`BitReadWithContext`]: trait.BitReadWithContext.html
+    #[inline]
+    pub fn read_with_context<T: BitReadWithContext<E, Ctx>, Ctx: ?Sized>(
+        &mut self,
+        ctx: &Ctx,
+    ) -> Result<T> {
+        self.nested(|stream| T::read_with_context(stream, ctx))
+    }
+
+    /// Read a value based on the provided type and size without checking if enough data is left
+    ///
+    /// Only the bounds are unchecked, the value is validated the same way as by [`read_sized`].
+    ///
+    /// # Safety
+    ///
+    /// `T` needs to have a fixed size for `size` and the stream needs to have at least [`BitReadSized::bit_size`] bits
+    /// left, which can be ensured by calling [`check_read`] beforehand. See the [`unchecked`] module for details.
+    ///
+    /// [`read_sized`]: #method.read_sized
+    /// [`BitReadSized::bit_size`]: trait.BitReadSized.html#method.bit_size
+    /// [`check_read`]: #method.check_read
+    /// [`unchecked`]: unchecked/index.html
+    #[inline]
+    pub unsafe fn read_sized_unchecked<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
+        self.nested(|stream| T::read_unchecked(stream, size))
+    }
+
+    /// Set the maximum nesting depth for [`read`] and [`read_sized`]
+    ///
+    /// Every call to [`read`] or [`read_sized`] made while reading another value increases the
+    /// nesting depth by one, once the depth would exceed `max_depth` the read fails with
+    /// [`ReadError::DepthLimitReached`]. This protects parsers for recursive types from overflowing
+    /// the stack on hostile input.
+    ///
+    /// By default the depth is not limited, the limit is inherited by sub streams created from this stream.
+    ///
+    /// # Examples
+    ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
-    /// use bitbuffer::BitRead;
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitError, LittleEndian, Result};
     /// #
-    /// #[derive(BitRead, Debug, PartialEq)]
-    /// struct ComplexType {
-    ///     first: u8,
-    ///     #[size = 15]
-    ///     second: u16,
-    ///     third: bool,
-    /// }
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![0b1111_1111, 0b1111_1111];
+    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// stream.set_max_depth(2);
+    /// assert_eq!(stream.read::<Option<bool>>()?, Some(true));
+    /// assert!(matches!(
+    ///     stream.read::<Option<Option<bool>>>(),
+    ///     Err(BitError::DepthLimitReached { max_depth: 2 })
+    /// ));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`read`]: #method.read
+    /// [`read_sized`]: #method.read_sized
+    /// [`ReadError::DepthLimitReached`]: enum.ReadError.html#variant.DepthLimitReached
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    #[inline]
+    pub(crate) fn nested<T, F: FnOnce(&mut Self) -> Result<T>>(&mut self, read: F) -> Result<T> {
+        if self.depth >= self.max_depth {
+            return Err(BitError::DepthLimitReached {
+                max_depth: self.max_depth,
+            });
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Limit the total number of bits that can be read from the stream
+    ///
+    /// The limit is shared with every stream created from this stream after setting the limit,
+    /// such as streams returned by [`read_bits`] or clones of the stream, and every bit read by any of those
+    /// streams counts towards the limit. Once the limit would be exceeded the read fails with
+    /// [`ReadError::ReadLimitReached`].
+    ///
+    /// This allows safely parsing untrusted input where the same data might be read multiple times
+    /// or where size fields in the data control how much is read and allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitError, LittleEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
+    /// # let bytes = vec![0, 0, 0, 0];
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// let data: ComplexType = stream.read()?;
-    /// assert_eq!(data, ComplexType {
-    ///     first: 0b1011_0101,
-    ///     second: 0b010_1100_0110_1010,
-    ///     third: true,
-    /// });
+    /// stream.set_read_limit(16);
+    /// let mut copy = stream.clone();
+    /// copy.read::<u8>()?;
+    /// stream.read::<u8>()?;
+    /// assert_eq!(stream.read_limit_left(), Some(0));
+    /// assert!(matches!(
+    ///     stream.read::<u8>(),
+    ///     Err(BitError::ReadLimitReached { requested: 8, remaining: 0 })
+    /// ));
     /// #
     /// #     Ok(())
     /// # }
     /// ```
+    ///
+    /// [`read_bits`]: #method.read_bits
+    /// [`ReadError::ReadLimitReached`]: enum.ReadError.html#variant.ReadLimitReached
+    pub fn set_read_limit(&mut self, bits: usize) {
+        self.budget = Some(Rc::new(Cell::new(bits)));
+    }
+
+    /// The number of bits that can still be read before reaching the limit set with [`set_read_limit`]
+    ///
+    /// [`set_read_limit`]: #method.set_read_limit
+    pub fn read_limit_left(&self) -> Option<usize> {
+        self.budget.as_ref().map(|budget| budget.get())
+    }
+
     #[inline]
-    pub fn read<T: BitRead<E>>(&mut self) -> Result<T> {
-        T::read(self)
+    fn check_budget(&self, count: usize) -> Result<()> {
+        match &self.budget {
+            Some(budget) if budget.get() < count => Err(BitError::ReadLimitReached {
+                requested: count,
+                remaining: budget.get(),
+            }),
+            _ => Ok(()),
+        }
     }
 
-    #[doc(hidden)]
     #[inline]
-    pub unsafe fn read_unchecked<T: BitRead<E>>(&mut self) -> Result<T> {
-        T::read_unchecked(self)
+    fn spend(&self, count: usize) {
+        if let Some(budget) = &self.budget {
+            budget.set(budget.get().saturating_sub(count));
+        }
     }
 
-    /// Read a value based on the provided type and size
+    /// Read a value and record which bits every field was read from
     ///
-    /// The meaning of the size parameter differs depending on the type that is being read
+    /// Returns the value together with a [`DecodeNode`] tree describing the read, every field read by
+    /// a derived [`BitRead`] or [`BitReadSized`] implementation results in a node containing the name of the field,
+    /// the range of bits it was read from and the `Debug` representation of the read value.
+    ///
+    /// Values read by manual implementations are recorded as a single node for the field they are read into.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitRead, LittleEndian, Result};
     /// #
+    /// #[derive(BitRead, Debug)]
+    /// struct Header {
+    ///     #[size = 3]
+    ///     version: u8,
+    ///     flag: bool,
+    /// }
+    ///
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
-    /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    /// # let buffer = BitReadBuffer::new(vec![0b1101], LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// let int: u8 = stream.read_sized(7)?;
-    /// assert_eq!(int, 0b011_0101);
+    /// let (header, tree) = stream.read_instrumented::<Header>()?;
+    /// assert_eq!(tree.range, 0..4);
+    /// assert_eq!(tree.children[0].name, "version");
+    /// assert_eq!(tree.children[0].value, "5");
+    /// assert_eq!(tree.find(3).unwrap().name, "flag");
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
+    /// [`DecodeNode`]: struct.DecodeNode.html
+    /// [`BitRead`]: trait.BitRead.html
+    /// [`BitReadSized`]: trait.BitReadSized.html
+    pub fn read_instrumented<T: BitRead<E> + Debug>(&mut self) -> Result<(T, DecodeNode)> {
+        let start = self.pos.get();
+        let recorder = Rc::new(RefCell::new(DecodeRecorder::new(self.start_pos)));
+        let outer = self.recorder.replace(Rc::clone(&recorder));
+        let result = self.read::<T>();
+        self.recorder = outer;
+        let value = result?;
+
+        // sub streams that are part of the value might still hold on to the recorder
+        let recorder = recorder.replace(DecodeRecorder::new(self.start_pos));
+        let node = recorder.finish(std::any::type_name::<T>(), start, self.pos.get(), &value);
+        Ok((value, node))
+    }
+
+    /// Start collecting statistics of the bits consumed by every derived type, requires the `stats` feature
+    ///
+    /// Any previously collected statistics are discarded, streams read from this stream afterwards
+    /// record into the same statistics. See [`ReadStats`] for details.
+    ///
+    /// [`ReadStats`]: struct.ReadStats.html
+    #[cfg(feature = "stats")]
+    pub fn collect_stats(&mut self) {
+        self.stats = Some(Rc::new(RefCell::new(ReadStats::default())));
+    }
+
+    /// Get the statistics collected since calling [`collect_stats`], requires the `stats` feature
+    ///
+    /// Returns `None` if collecting statistics hasn't been enabled.
+    ///
+    /// [`collect_stats`]: #method.collect_stats
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Option<ReadStats> {
+        self.stats.as_ref().map(|stats| stats.borrow().clone())
+    }
+
+    #[cfg(feature = "stats")]
+    #[doc(hidden)]
+    #[inline]
+    pub fn __begin_read(&self) -> usize {
+        self.pos.get()
+    }
+
+    #[cfg(feature = "stats")]
+    #[doc(hidden)]
+    #[inline]
+    pub fn __end_read(&self, type_name: &'static str, start: usize) {
+        if let Some(stats) = &self.stats {
+            stats
+                .borrow_mut()
+                .record(type_name, self.pos.get().saturating_sub(start));
+        }
+    }
+
+    /// The underlying buffer and the absolute position in it
+    pub(crate) fn buffer_pos(&self) -> (&BitReadBuffer<E>, usize) {
+        (&self.buffer, self.pos.get())
+    }
+
+    /// Used by `bit_layout!` and `read_all!` to accept both streams and mutable references to streams
+    #[doc(hidden)]
+    #[inline]
+    pub fn __layout_stream(&mut self) -> &mut Self {
+        self
+    }
+
+    /// Read all fields of a derived struct with only small integer fields as a single integer, without advancing
+    ///
+    /// # Safety
+    ///
+    /// The stream needs to have at least `count` bits left and `count` can be at most 64.
+    #[doc(hidden)]
+    #[inline]
+    pub unsafe fn __read_packed(&self, count: usize) -> u64 {
+        self.buffer.read_int_unchecked(self.pos.get(), count)
+    }
+
+    /// Take the `width` bits at `offset` from an integer read by `__read_packed` and advance past them
+    #[doc(hidden)]
+    #[inline]
+    pub fn __packed_field(
+        &mut self,
+        packed: u64,
+        count: usize,
+        offset: usize,
+        width: usize,
+    ) -> u64 {
+        let shift = if E::is_le() {
+            offset
+        } else {
+            count - offset - width
+        };
+        self.pos += width;
+        self.spend(width);
+        (packed >> shift) & (u64::MAX >> (64 - width))
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn __begin_field(&mut self) -> usize {
+        if let Some(recorder) = &self.recorder {
+            recorder.borrow_mut().begin_field();
+        }
+        self.pos()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn __end_field(&mut self, name: &str, start: usize, value: &dyn Debug) {
+        if let Some(recorder) = &self.recorder {
+            recorder
+                .borrow_mut()
+                .end_field(name, start + self.start_pos, self.pos.get(), value);
+        }
+    }
+
+    /// Create a stream for `buffer` that shares the limits of this stream
+    fn derive(&self, buffer: BitReadBuffer<E>, start_pos: usize) -> Self {
+        BitReadStream {
+            buffer,
+            start_pos,
+            pos: BitPos::new(start_pos),
+            depth: self.depth,
+            max_depth: self.max_depth,
+            budget: self.budget.clone(),
+            marker: self.marker,
+            saved_positions: Vec::new(),
+            recorder: self.recorder.clone(),
+            #[cfg(feature = "stats")]
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Create a stream for newly decoded data that shares the limits of this stream
+    ///
+    /// The read marker and decode recorder are not carried over since they refer to positions in this stream
+    fn derive_decoded(&self, buffer: BitReadBuffer<E>) -> Self {
+        BitReadStream {
+            marker: None,
+            recorder: None,
+            ..self.derive(buffer, 0)
+        }
+    }
+
+    /// Set a marker that reads are not allowed to cross
+    ///
+    /// Any read that would read past the marker fails with [`ReadError::ReadPastMarker`] instead of
+    /// reading data from beyond the marker, skipping or seeking past the marker is still allowed.
+    /// This can be used to verify that parsing a section of the data doesn't read into the following section.
+    ///
+    /// The marker is also applied to clones and sub streams of this stream.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: the marker is outside the bounds of the stream
+    ///
+    /// # Examples
+    ///
     /// ```
-    /// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitError, LittleEndian, Result};
     /// #
     /// # fn main() -> Result<()> {
-    /// # let bytes = vec![
-    /// #     0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001,
-    /// #     0b1001_1001, 0b1001_1001, 0b1001_1001, 0b1110_0111
-    /// # ];
+    /// # let bytes = vec![0, 0, 0, 0];
     /// # let buffer = BitReadBuffer::new(bytes, LittleEndian);
     /// # let mut stream = BitReadStream::new(buffer);
-    /// let data: Vec<u16> = stream.read_sized(3)?;
-    /// assert_eq!(data, vec![0b0110_1010_1011_0101, 0b1001_1001_1010_1100, 0b1001_1001_1001_1001]);
+    /// stream.set_read_marker(12)?;
+    /// stream.read::<u8>()?;
+    /// assert!(matches!(
+    ///     stream.read::<u8>(),
+    ///     Err(BitError::ReadPastMarker { marker: 12, pos: 8, requested: 8 })
+    /// ));
+    /// stream.read_int::<u8>(4)?;
+    /// stream.clear_read_marker();
+    /// stream.read::<u8>()?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
+    ///
+    /// [`ReadError::ReadPastMarker`]: enum.ReadError.html#variant.ReadPastMarker
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn set_read_marker(&mut self, pos: usize) -> Result<()> {
+        if pos > self.bit_len() {
+            return Err(BitError::IndexOutOfBounds {
+                pos,
+                size: self.bit_len(),
+            });
+        }
+        self.marker = Some(pos + self.start_pos);
+        Ok(())
+    }
+
+    /// Remove the marker set by [`set_read_marker`]
+    ///
+    /// [`set_read_marker`]: #method.set_read_marker
+    pub fn clear_read_marker(&mut self) {
+        self.marker = None;
+    }
+
     #[inline]
-    pub fn read_sized<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
-        T::read(self, size)
+    fn check_marker(&self, count: usize) -> Result<()> {
+        match self.marker {
+            Some(marker)
+                if self.pos.get() < marker && self.pos.get().saturating_add(count) > marker =>
+            {
+                Err(BitError::ReadPastMarker {
+                    marker: marker - self.start_pos,
+                    pos: self.pos(),
+                    requested: count,
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
-    #[doc(hidden)]
     #[inline]
-    pub unsafe fn read_sized_unchecked<T: BitReadSized<E>>(&mut self, size: usize) -> Result<T> {
-        T::read_unchecked(self, size)
+    fn check_limits(&self, count: usize) -> Result<()> {
+        self.check_marker(count)?;
+        self.check_budget(count)
+    }
+
+    /// The position `offset` bits after the current position, which has to be within the stream
+    #[inline]
+    fn offset_pos(&self, offset: usize) -> Result<usize> {
+        Ok(self.pos.advance(offset, self.buffer.bit_len())?.get())
     }
 
     /// Check if we can read a number of bits from the stream
     pub fn check_read(&self, count: usize) -> Result<()> {
-        if self.bits_left() < count {
-            Err(BitError::NotEnoughData {
-                requested: count,
-                bits_left: self.bits_left(),
-            })
-        } else {
-            Ok(())
+        self.pos.advance(count, self.buffer.bit_len())?;
+        self.check_limits(count)
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Format a range of bits from the stream as a binary and hex dump
+    ///
+    /// Every line shows the position of the first bit, 32 bits grouped per byte and the hex value
+    /// of every group. The current position of the stream is marked with a `^` below the line.
+    /// The range is relative to the start of the stream and clamped to the length of the stream,
+    /// the dump doesn't count towards the read limit of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let bytes = vec![0b1011_0101, 0b0110_1010, 0b1010_1100];
+    /// # let buffer = BitReadBuffer::new(bytes, BigEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// stream.skip_bits(10)?;
+    /// println!("{}", stream.dump(0..20));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Prints
+    ///
+    /// ```text
+    ///      0: 10110101 01101010 1010               b5 6a 0a
+    ///                    ^
+    /// ```
+    pub fn dump(&self, range: Range<usize>) -> String {
+        const LINE_BITS: usize = 32;
+
+        let end = min(range.end, self.bit_len());
+        let start = min(range.start, end);
+        let pos = self.pos();
+        let mut dump = String::new();
+
+        let mut line_start = start;
+        loop {
+            let line_end = min(line_start + LINE_BITS, end);
+            let mut bits = String::new();
+            let mut hex = Vec::new();
+            for group_start in (line_start..line_end).step_by(8) {
+                let group_end = min(group_start + 8, line_end);
+                for bit_pos in group_start..group_end {
+                    let bit = self
+                        .buffer
+                        .read_bool(self.start_pos + bit_pos)
+                        .unwrap_or(false);
+                    bits.push(if bit { '1' } else { '0' });
+                }
+                bits.push(' ');
+                let value: u8 = self
+                    .buffer
+                    .read_int(self.start_pos + group_start, group_end - group_start)
+                    .unwrap_or(0);
+                hex.push(format!("{:02x}", value));
+            }
+            let _ = writeln!(
+                dump,
+                "{:>6}: {:<width$} {}",
+                line_start,
+                bits,
+                hex.join(" "),
+                width = LINE_BITS + LINE_BITS / 8
+            );
+
+            let is_last = line_end == end;
+            if pos >= line_start && (pos < line_end || (is_last && pos == line_end)) {
+                let offset = pos - line_start;
+                let _ = writeln!(dump, "{:>width$}", '^', width = 9 + offset + offset / 8);
+            }
+
+            if is_last {
+                break;
+            }
+            line_start = line_end;
+        }
+        dump
+    }
+}
+
+/// Shows the position and length of the stream, the alternate form also includes a [`dump`] around the current position
+///
+/// [`dump`]: #method.dump
+impl<E: Endianness> fmt::Debug for BitReadStream<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitReadStream")
+            .field("pos", &self.pos())
+            .field("bit_len", &self.bit_len())
+            .field("endianness", &format_args!("{}", E::as_string()))
+            .finish()?;
+        if f.alternate() {
+            write!(f, "\n{}", self)?;
         }
+        Ok(())
+    }
+}
+
+/// Shows a [`dump`] of the 64 bits before and after the current position
+///
+/// [`dump`]: #method.dump
+impl<E: Endianness> fmt::Display for BitReadStream<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_start = self.pos() - self.pos() % 32;
+        f.write_str(&self.dump(line_start.saturating_sub(64)..line_start + 96))
     }
 }
 
 impl<E: Endianness> Clone for BitReadStream<E> {
     fn clone(&self) -> Self {
-        BitReadStream {
-            buffer: self.buffer.clone(),
-            start_pos: self.pos,
-            pos: self.pos,
-        }
+        self.derive(self.buffer.clone(), self.pos.get())
     }
 }
 
@@ -673,3 +3383,11 @@ impl<E: Endianness> From<Vec<u8>> for BitReadStream<E> {
         BitReadStream::new(BitReadBuffer::from(bytes))
     }
 }
+
+/// Validate utf8, reporting errors the same way as `String::from_utf8`
+fn validate_utf8(bytes: &[u8]) -> Result<()> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(String::from_utf8(bytes.to_vec()).unwrap_err().into()),
+    }
+}
@@ -0,0 +1,227 @@
+use std::mem::size_of;
+use std::ops::BitOrAssign;
+
+use num_traits::{Float, PrimInt};
+
+use crate::num_traits::{IsSigned, UncheckedPrimitiveFloat, UncheckedPrimitiveInt};
+use crate::{BitError, BitReadBuffer, Endianness, Result};
+
+/// Stream that reads bits from the end of a buffer towards the start
+///
+/// Some codecs, like the entropy coders used by zstd, write a bitstream forwards and read it back starting from
+/// the last written value. Every read takes the `count` bits directly before the current position, so reading a
+/// value returns the same value as reading those bits from a regular stream would, only the order in which the
+/// values are read is reversed.
+///
+/// The stream starts at the end of the buffer, [`pos`] is the number of bits that are left to read.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadBuffer, BitReadStreamRev, BitWriteStream, LittleEndian, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut write = BitWriteStream::new(LittleEndian);
+/// write.write_int(5u8, 3)?;
+/// write.write_int(300u16, 12)?;
+/// write.write_bool(true)?;
+///
+/// let buffer = BitReadBuffer::new(write.finish(), LittleEndian);
+/// let mut stream = BitReadStreamRev::new(buffer);
+/// assert_eq!(stream.read_bool()?, true);
+/// assert_eq!(stream.read_int::<u16>(12)?, 300);
+/// assert_eq!(stream.read_int::<u8>(3)?, 5);
+/// assert_eq!(stream.bits_left(), 0);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`pos`]: #method.pos
+#[derive(Debug, Clone)]
+pub struct BitReadStreamRev<E: Endianness> {
+    buffer: BitReadBuffer<E>,
+    pos: usize,
+    /// position that reads can't move past
+    start: usize,
+}
+
+impl<E: Endianness> BitReadStreamRev<E> {
+    /// Create a stream that reads the buffer backwards, starting at the end of the buffer
+    pub fn new(buffer: BitReadBuffer<E>) -> Self {
+        BitReadStreamRev {
+            pos: buffer.bit_len(),
+            buffer,
+            start: 0,
+        }
+    }
+
+    /// Limit reads to the bits after `start`, the position needs to be at least `start`
+    pub(crate) fn set_start(&mut self, start: usize) {
+        debug_assert!(start <= self.pos);
+        self.start = start;
+    }
+
+    /// Move the position back by `count` bits and return the new position
+    fn take(&mut self, count: usize) -> Result<usize> {
+        if count > self.bits_left() {
+            return Err(BitError::NotEnoughData {
+                requested: count,
+                bits_left: self.bits_left(),
+            });
+        }
+        Ok(self.pos - count)
+    }
+
+    /// Read the bit directly before the current position as boolean
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: the stream is at the start of the buffer
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_bool(&mut self) -> Result<bool> {
+        let pos = self.take(1)?;
+        let result = self.buffer.read_bool(pos)?;
+        self.pos = pos;
+        Ok(result)
+    }
+
+    /// Read the `count` bits directly before the current position as integer
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: less than `count` bits are left before the current position
+    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    #[inline]
+    pub fn read_int<T>(&mut self, count: usize) -> Result<T>
+    where
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
+    {
+        let pos = self.take(count)?;
+        let result = self.buffer.read_int(pos, count)?;
+        self.pos = pos;
+        Ok(result)
+    }
+
+    /// Read the float directly before the current position
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits are left before the current position
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    #[inline]
+    pub fn read_float<T>(&mut self) -> Result<T>
+    where
+        T: Float + UncheckedPrimitiveFloat,
+    {
+        let pos = self.take(size_of::<T>() * 8)?;
+        let result = self.buffer.read_float(pos)?;
+        self.pos = pos;
+        Ok(result)
+    }
+
+    /// Skip the `count` bits directly before the current position
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: less than `count` bits are left before the current position
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn skip_bits(&mut self, count: usize) -> Result<()> {
+        self.pos = self.take(count)?;
+        Ok(())
+    }
+
+    /// Skip the zero bits at the end of the buffer and the set bit marking the end of the data
+    ///
+    /// Backwards bitstreams are often padded to a whole number of bytes by writing a single set bit followed by
+    /// zero bits, this skips over that padding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: no set bit was found before the start of the buffer
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStreamRev, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// // 4 bits of data followed by the end marker and 3 bits of padding
+    /// let buffer = BitReadBuffer::new(vec![0b0001_1010], LittleEndian);
+    /// let mut stream = BitReadStreamRev::new(buffer);
+    /// stream.skip_padding()?;
+    /// assert_eq!(stream.read_int::<u8>(4)?, 0b1010);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn skip_padding(&mut self) -> Result<()> {
+        let start = self.pos;
+        loop {
+            match self.read_bool() {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => {
+                    self.pos = start;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// The current position in the buffer
+    ///
+    /// This is also the number of bits left to read, unless the stream is part of a [`DualReadStream`].
+    ///
+    /// [`DualReadStream`]: struct.DualReadStream.html
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Set the position in the buffer
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: new position is outside the bounds of the buffer, or before the position
+    ///   of the forward stream when part of a [`DualReadStream`]
+    ///
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    /// [`DualReadStream`]: struct.DualReadStream.html
+    pub fn set_pos(&mut self, pos: usize) -> Result<()> {
+        if pos > self.buffer.bit_len() || pos < self.start {
+            return Err(BitError::IndexOutOfBounds {
+                pos,
+                size: self.buffer.bit_len(),
+            });
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// The number of bits left to read
+    pub fn bits_left(&self) -> usize {
+        self.pos - self.start
+    }
+
+    /// The length of the underlying buffer in bits
+    pub fn bit_len(&self) -> usize {
+        self.buffer.bit_len()
+    }
+}
+
+impl<E: Endianness> From<BitReadBuffer<E>> for BitReadStreamRev<E> {
+    fn from(buffer: BitReadBuffer<E>) -> Self {
+        BitReadStreamRev::new(buffer)
+    }
+}
@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{BitError, BitRead, BitReadStream, Endianness, Result};
+
+type Parser<E> = Box<dyn Fn(&mut BitReadStream<E>) -> Result<Box<dyn Any>>>;
+
+/// Runtime map from message id to parser
+///
+/// Allows reading message types that aren't known at compile time, for example types provided by plugins,
+/// without having to list all of them in a single enum.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, ParserRegistry, Result};
+///
+/// #[derive(BitRead, Debug, PartialEq)]
+/// struct Ping {
+///     sequence: u16,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// let mut registry = ParserRegistry::new();
+/// registry.register_type::<Ping>(1);
+/// registry.register(2, |stream: &mut BitReadStream<LittleEndian>| {
+///     let len: u8 = stream.read()?;
+///     stream.read_string(Some(len as usize))
+/// });
+///
+/// let buffer = BitReadBuffer::new(vec![1, 0, 2, b'h', b'i'], LittleEndian);
+/// let mut stream = BitReadStream::new(buffer);
+///
+/// let ping = registry.read_dynamic(&mut stream, 1)?;
+/// assert_eq!(ping.downcast_ref::<Ping>(), Some(&Ping { sequence: 1 }));
+/// let text = registry.read_dynamic(&mut stream, 2)?;
+/// assert_eq!(text.downcast_ref::<String>().map(String::as_str), Some("hi"));
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub struct ParserRegistry<E: Endianness> {
+    parsers: HashMap<u64, Parser<E>>,
+}
+
+impl<E: Endianness> ParserRegistry<E> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        ParserRegistry {
+            parsers: HashMap::new(),
+        }
+    }
+
+    /// Register a parser for `id`, replacing any previous parser for the id
+    pub fn register<T, F>(&mut self, id: u64, parser: F)
+    where
+        T: Any,
+        F: Fn(&mut BitReadStream<E>) -> Result<T> + 'static,
+    {
+        self.parsers.insert(
+            id,
+            Box::new(move |stream| parser(stream).map(|value| Box::new(value) as Box<dyn Any>)),
+        );
+    }
+
+    /// Register the [`BitRead`] implementation of `T` as parser for `id`, replacing any previous parser for the id
+    ///
+    /// [`BitRead`]: trait.BitRead.html
+    pub fn register_type<T: BitRead<E> + Any>(&mut self, id: u64) {
+        self.register(id, |stream| stream.read::<T>());
+    }
+
+    /// Check whether a parser is registered for `id`
+    pub fn contains(&self, id: u64) -> bool {
+        self.parsers.contains_key(&id)
+    }
+
+    /// Remove the parser for `id`, returns whether a parser was registered
+    pub fn unregister(&mut self, id: u64) -> bool {
+        self.parsers.remove(&id).is_some()
+    }
+
+    /// Read a value using the parser registered for `id`
+    ///
+    /// The result can be converted back to the concrete type using [`Box::downcast`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::UnknownParser`]: no parser is registered for `id`
+    /// - any error returned by the parser
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// [`Box::downcast`]: https://doc.rust-lang.org/std/boxed/struct.Box.html#method.downcast
+    /// [`ReadError::UnknownParser`]: enum.ReadError.html#variant.UnknownParser
+    pub fn read_dynamic(&self, stream: &mut BitReadStream<E>, id: u64) -> Result<Box<dyn Any>> {
+        let parser = self
+            .parsers
+            .get(&id)
+            .ok_or(BitError::UnknownParser { id })?;
+        let start = stream.pos();
+        let result = parser(stream);
+        if result.is_err() {
+            stream.set_pos(start)?;
+        }
+        result
+    }
+}
+
+impl<E: Endianness> Default for ParserRegistry<E> {
+    fn default() -> Self {
+        ParserRegistry::new()
+    }
+}
+
+impl<E: Endianness> fmt::Debug for ParserRegistry<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ids: Vec<_> = self.parsers.keys().collect();
+        ids.sort();
+        f.debug_struct("ParserRegistry").field("ids", &ids).finish()
+    }
+}
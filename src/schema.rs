@@ -0,0 +1,244 @@
+use crate::{
+    bit_size_of, BitAngle, BitReadStream, EliasDelta, EliasGamma, Endianness, Golomb, Int, Micros,
+    Millis, Nanos, Quantized, Rice, Seconds, UInt, Unary, ZigZag,
+};
+use std::any::type_name;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Types that can describe their wire layout
+///
+/// The description can be used by tooling to generate documentation or dissectors for a format defined
+/// in rust. The trait can be derived for any struct or enum that derives [`BitRead`] or [`BitReadSized`],
+/// as long as all fields implement `BitSchema`.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitRead, BitSchema, FieldSchema, Schema, SizeSchema};
+///
+/// #[derive(BitRead, BitSchema)]
+/// struct Header {
+///     #[size = 3]
+///     version: u8,
+///     flag: bool,
+/// }
+///
+/// match Header::schema() {
+///     Schema::Struct { fields, .. } => {
+///         assert_eq!(fields[0].name, "version");
+///         assert_eq!(fields[0].size, SizeSchema::Fixed(3));
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// [`BitRead`]: trait.BitRead.html
+/// [`BitReadSized`]: trait.BitReadSized.html
+pub trait BitSchema {
+    /// Describe the wire layout of the type
+    fn schema() -> Schema;
+}
+
+/// Description of the wire layout of a type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schema {
+    /// A value that is read as a whole, such as an integer or string
+    Value {
+        /// The name of the rust type
+        type_name: &'static str,
+        /// The number of bits the value takes when read without size, if that number is fixed
+        bit_size: Option<usize>,
+    },
+    /// A `bool` flag, followed by the value if the flag is set
+    Optional(Box<Schema>),
+    /// A number of values, the number is set by the size of the field
+    List(Box<Schema>),
+    /// A struct that is read field by field
+    Struct {
+        /// The name of the rust type
+        type_name: &'static str,
+        /// The fields in the order they are read
+        fields: Vec<FieldSchema>,
+    },
+    /// An enum that is read as a discriminant, followed by the field of the variant
+    Enum {
+        /// The name of the rust type
+        type_name: &'static str,
+        /// The number of bits of the discriminant, `0` if the discriminant is selected by a previously read value
+        /// or the variants are selected by magic bytes
+        discriminant_bits: usize,
+        /// The variants of the enum
+        variants: Vec<VariantSchema>,
+    },
+}
+
+/// Description of a field in a struct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// The name of the field, or the index for tuple structs
+    pub name: &'static str,
+    /// The size used when reading the field
+    pub size: SizeSchema,
+    /// The layout of the field type
+    pub schema: Schema,
+}
+
+/// Description of a variant of an enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSchema {
+    /// The name of the variant
+    pub name: &'static str,
+    /// The discriminant of the variant, `None` for the variant matching any discriminant
+    pub discriminant: Option<usize>,
+    /// The magic bytes selecting the variant, for enums using the `magic` attribute instead of a discriminant
+    pub magic: Option<&'static [u8]>,
+    /// The size used when reading the field of the variant
+    pub size: SizeSchema,
+    /// The layout of the field of the variant, `None` for variants without field
+    pub field: Option<Schema>,
+}
+
+/// The size used when reading a field
+///
+/// The meaning of the size depends on the type of the field, see [`BitReadSized`].
+///
+/// [`BitReadSized`]: trait.BitReadSized.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SizeSchema {
+    /// The field is read without size
+    None,
+    /// A fixed size
+    Fixed(usize),
+    /// The size is the result of an expression, such as the name of a previous field or `input_size`
+    Expression(&'static str),
+    /// The size is read as an integer with the given number of bits before the field
+    Prefixed(usize),
+}
+
+macro_rules! impl_schema_value {
+    ($($type:ty),*) => {
+        $(
+            impl BitSchema for $type {
+                fn schema() -> Schema {
+                    Schema::Value {
+                        type_name: type_name::<$type>(),
+                        bit_size: bit_size_of::<$type>(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_schema_value!(
+    u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64, bool, char, String
+);
+
+impl<const BITS: usize> BitSchema for UInt<BITS> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: Some(BITS),
+        }
+    }
+}
+
+impl<const BITS: usize> BitSchema for Int<BITS> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: Some(BITS),
+        }
+    }
+}
+
+impl<const BITS: usize, const MIN: i32, const MAX: i32> BitSchema for Quantized<BITS, MIN, MAX> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: Some(BITS),
+        }
+    }
+}
+
+impl<const BITS: usize> BitSchema for BitAngle<BITS> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: Some(BITS),
+        }
+    }
+}
+
+impl<T> BitSchema for ZigZag<T> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: Some(size_of::<T>() * 8),
+        }
+    }
+}
+
+macro_rules! impl_schema_variable_length {
+    ($($type:ident),*) => {
+        $(
+            impl<T> BitSchema for $type<T> {
+                fn schema() -> Schema {
+                    Schema::Value {
+                        type_name: type_name::<Self>(),
+                        bit_size: None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_schema_variable_length!(Rice, Golomb, Unary, EliasGamma, EliasDelta);
+
+impl<E: Endianness> BitSchema for BitReadStream<E> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: None,
+        }
+    }
+}
+
+impl<K, T> BitSchema for HashMap<K, T> {
+    fn schema() -> Schema {
+        Schema::Value {
+            type_name: type_name::<Self>(),
+            bit_size: None,
+        }
+    }
+}
+
+impl<T: BitSchema> BitSchema for Option<T> {
+    fn schema() -> Schema {
+        Schema::Optional(Box::new(T::schema()))
+    }
+}
+
+impl<T: BitSchema> BitSchema for Vec<T> {
+    fn schema() -> Schema {
+        Schema::List(Box::new(T::schema()))
+    }
+}
+
+macro_rules! impl_schema_wrapper {
+    ($($type:ident),*) => {
+        $(
+            impl<T: BitSchema> BitSchema for $type<T> {
+                fn schema() -> Schema {
+                    T::schema()
+                }
+            }
+        )*
+    };
+}
+
+impl_schema_wrapper!(Box, Rc, Arc, Seconds, Millis, Micros, Nanos);
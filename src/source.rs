@@ -0,0 +1,231 @@
+//! Source engine coordinate and normal encodings, requires the `source` feature
+//!
+//! Adds methods to [`BitReadStream`] and [`BitWriteStream`] for the composite encodings the Source engine uses
+//! for coordinates and normal vectors in its network and demo formats, matching `ReadBitCoord`, `ReadBitNormal`,
+//! `ReadBitVec3Coord` and `ReadBitVec3Normal` from the Source SDK.
+//!
+//! # Examples
+//!
+//! ```
+//! # use bitbuffer::{BitReadBuffer, BitReadStream, BitWriteStream, LittleEndian, Result};
+//! #
+//! # fn main() -> Result<()> {
+//! let mut stream = BitWriteStream::new(LittleEndian);
+//! stream.write_bit_vec3_coord([12.5, 0.0, -1024.25])?;
+//! stream.write_bit_normal(-0.5)?;
+//!
+//! let buffer = BitReadBuffer::new(stream.finish(), LittleEndian);
+//! let mut stream = BitReadStream::new(buffer);
+//! assert_eq!(stream.read_bit_vec3_coord()?, [12.5, 0.0, -1024.25]);
+//! assert!((stream.read_bit_normal()? + 0.5).abs() < 0.001);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`BitReadStream`]: ../struct.BitReadStream.html
+//! [`BitWriteStream`]: ../struct.BitWriteStream.html
+
+use crate::{BitError, BitReadStream, BitWriteStream, Endianness, Result};
+
+/// The number of bits used for the integer part of a coordinate
+pub const COORD_INTEGER_BITS: usize = 14;
+/// The number of bits used for the fractional part of a coordinate
+pub const COORD_FRACTIONAL_BITS: usize = 5;
+/// The denominator of the fractional part of a coordinate
+pub const COORD_DENOMINATOR: u32 = 1 << COORD_FRACTIONAL_BITS;
+/// The smallest non-zero coordinate
+pub const COORD_RESOLUTION: f32 = 1.0 / COORD_DENOMINATOR as f32;
+
+/// The number of bits used for the fractional part of a normal
+pub const NORMAL_FRACTIONAL_BITS: usize = 11;
+/// The denominator of the fractional part of a normal
+pub const NORMAL_DENOMINATOR: u32 = (1 << NORMAL_FRACTIONAL_BITS) - 1;
+/// The smallest non-zero normal component
+pub const NORMAL_RESOLUTION: f32 = 1.0 / NORMAL_DENOMINATOR as f32;
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Read a coordinate as encoded by the Source engine's `WriteBitCoord`
+    ///
+    /// A coordinate consists of flags for the presence of the integer and fractional parts, followed by
+    /// a sign bit, a 14 bit integer part and a 5 bit fractional part for the parts that are present.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bit_coord(&mut self) -> Result<f32> {
+        let has_int = self.read_bool()?;
+        let has_fract = self.read_bool()?;
+        if !has_int && !has_fract {
+            return Ok(0.0);
+        }
+        let negative = self.read_bool()?;
+        let int = if has_int {
+            self.read_int::<u32>(COORD_INTEGER_BITS)? + 1
+        } else {
+            0
+        };
+        let fract = if has_fract {
+            self.read_int::<u32>(COORD_FRACTIONAL_BITS)?
+        } else {
+            0
+        };
+        let value = int as f32 + fract as f32 * COORD_RESOLUTION;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Read a normal vector component as encoded by the Source engine's `WriteBitNormal`
+    ///
+    /// A normal component consists of a sign bit followed by an 11 bit fraction.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bit_normal(&mut self) -> Result<f32> {
+        let negative = self.read_bool()?;
+        let value = self.read_int::<u32>(NORMAL_FRACTIONAL_BITS)? as f32 * NORMAL_RESOLUTION;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Read a vector of coordinates as encoded by the Source engine's `WriteBitVec3Coord`
+    ///
+    /// The vector starts with a flag for every component, only the components with their flag set are stored
+    /// using [`read_bit_coord`], the others are `0`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`read_bit_coord`]: #method.read_bit_coord
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bit_vec3_coord(&mut self) -> Result<[f32; 3]> {
+        let flags = [self.read_bool()?, self.read_bool()?, self.read_bool()?];
+        let mut vector = [0.0; 3];
+        for (component, flag) in vector.iter_mut().zip(flags.iter()) {
+            if *flag {
+                *component = self.read_bit_coord()?;
+            }
+        }
+        Ok(vector)
+    }
+
+    /// Read a normal vector as encoded by the Source engine's `WriteBitVec3Normal`
+    ///
+    /// Only the `x` and `y` components are stored using [`read_bit_normal`] if their flag is set,
+    /// the `z` component is calculated from them with a stored sign bit.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    ///
+    /// [`read_bit_normal`]: #method.read_bit_normal
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn read_bit_vec3_normal(&mut self) -> Result<[f32; 3]> {
+        let has_x = self.read_bool()?;
+        let has_y = self.read_bool()?;
+        let x = if has_x { self.read_bit_normal()? } else { 0.0 };
+        let y = if has_y { self.read_bit_normal()? } else { 0.0 };
+        let z_negative = self.read_bool()?;
+        let length_squared = x * x + y * y;
+        let z = if length_squared < 1.0 {
+            (1.0 - length_squared).sqrt()
+        } else {
+            0.0
+        };
+        Ok([x, y, if z_negative { -z } else { z }])
+    }
+}
+
+impl<E: Endianness> BitWriteStream<E> {
+    /// Write a coordinate using the Source engine's coordinate encoding
+    ///
+    /// The fractional part is truncated to a multiple of `1/32`, see [`read_bit_coord`] for details on the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IntegerOutOfRange`]: the integer part of the coordinate is larger than `16384`
+    ///
+    /// [`read_bit_coord`]: struct.BitReadStream.html#method.read_bit_coord
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn write_bit_coord(&mut self, value: f32) -> Result<()> {
+        let int = value.abs() as u32;
+        let fract = (value * COORD_DENOMINATOR as f32).abs() as u32 & (COORD_DENOMINATOR - 1);
+        if int > 1 << COORD_INTEGER_BITS {
+            return Err(BitError::IntegerOutOfRange {
+                value: int as i128,
+                bits: COORD_INTEGER_BITS,
+            });
+        }
+        self.write_bool(int != 0)?;
+        self.write_bool(fract != 0)?;
+        if int != 0 || fract != 0 {
+            self.write_bool(value <= -COORD_RESOLUTION)?;
+            if int != 0 {
+                self.write_int(int - 1, COORD_INTEGER_BITS)?;
+            }
+            if fract != 0 {
+                self.write_int(fract, COORD_FRACTIONAL_BITS)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a normal vector component using the Source engine's normal encoding
+    ///
+    /// The value is clamped to `-1..1`, see [`read_bit_normal`] for details on the encoding.
+    ///
+    /// [`read_bit_normal`]: struct.BitReadStream.html#method.read_bit_normal
+    pub fn write_bit_normal(&mut self, value: f32) -> Result<()> {
+        let fract = ((value * NORMAL_DENOMINATOR as f32).abs() as u32).min(NORMAL_DENOMINATOR);
+        self.write_bool(value <= -NORMAL_RESOLUTION)?;
+        self.write_int(fract, NORMAL_FRACTIONAL_BITS)
+    }
+
+    /// Write a vector of coordinates using the Source engine's coordinate vector encoding
+    ///
+    /// Components smaller than the coordinate resolution are written as `0`, see [`read_bit_vec3_coord`]
+    /// for details on the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IntegerOutOfRange`]: the integer part of a component is larger than `16384`
+    ///
+    /// [`read_bit_vec3_coord`]: struct.BitReadStream.html#method.read_bit_vec3_coord
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn write_bit_vec3_coord(&mut self, vector: [f32; 3]) -> Result<()> {
+        let flags = vector.map(|component| component.abs() >= COORD_RESOLUTION);
+        for flag in flags.iter() {
+            self.write_bool(*flag)?;
+        }
+        for (component, flag) in vector.iter().zip(flags.iter()) {
+            if *flag {
+                self.write_bit_coord(*component)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a normal vector using the Source engine's normal vector encoding
+    ///
+    /// Only the sign of the `z` component is stored, see [`read_bit_vec3_normal`] for details on the encoding.
+    ///
+    /// [`read_bit_vec3_normal`]: struct.BitReadStream.html#method.read_bit_vec3_normal
+    pub fn write_bit_vec3_normal(&mut self, vector: [f32; 3]) -> Result<()> {
+        let [x, y, z] = vector;
+        let has_x = x.abs() >= NORMAL_RESOLUTION;
+        let has_y = y.abs() >= NORMAL_RESOLUTION;
+        self.write_bool(has_x)?;
+        self.write_bool(has_y)?;
+        if has_x {
+            self.write_bit_normal(x)?;
+        }
+        if has_y {
+            self.write_bit_normal(y)?;
+        }
+        self.write_bool(z <= -NORMAL_RESOLUTION)
+    }
+}
@@ -0,0 +1,141 @@
+//! Support for the `stats` feature, recording the bits consumed by every derived type
+//!
+//! The `__record_read` macro is used by the code generated by the derive macros and only records when the feature is enabled
+
+#[cfg(feature = "stats")]
+use std::collections::HashMap;
+#[cfg(feature = "stats")]
+use std::fmt;
+
+/// Statistics for reads of a single type
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeStats {
+    /// The number of values of the type that were read
+    pub reads: usize,
+    /// The total number of bits consumed by the reads
+    pub bits: usize,
+}
+
+/// Statistics of the types read from a stream, requires the `stats` feature
+///
+/// Created by [`stats`] after enabling collection with [`collect_stats`], every value read by a derived
+/// [`BitRead`], [`BitReadSized`] or [`BitReadWithContext`] implementation is recorded under its type name.
+/// The bits of nested derived types are counted for both the inner and outer type.
+///
+/// The [`Display`] implementation formats a report of all types, ordered by the number of bits consumed.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, LittleEndian, Result};
+/// #
+/// #[derive(BitRead)]
+/// struct Entity {
+///     #[size = 6]
+///     kind: u8,
+///     alive: bool,
+/// }
+///
+/// # fn main() -> Result<()> {
+/// # let buffer = BitReadBuffer::new(vec![0; 8], LittleEndian);
+/// # let mut stream = BitReadStream::new(buffer);
+/// stream.collect_stats();
+/// let entities: Vec<Entity> = stream.read_sized(3)?;
+/// let stats = stream.stats().unwrap();
+/// let entity = stats.of::<Entity>().unwrap();
+/// assert_eq!(entity.reads, 3);
+/// assert_eq!(entity.bits, 21);
+/// println!("{}", stats);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`stats`]: struct.BitReadStream.html#method.stats
+/// [`collect_stats`]: struct.BitReadStream.html#method.collect_stats
+/// [`BitRead`]: trait.BitRead.html
+/// [`BitReadSized`]: trait.BitReadSized.html
+/// [`BitReadWithContext`]: trait.BitReadWithContext.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadStats {
+    types: HashMap<&'static str, TypeStats>,
+}
+
+#[cfg(feature = "stats")]
+impl ReadStats {
+    /// Get the statistics for a type by its name, as returned by [`std::any::type_name`]
+    ///
+    /// [`std::any::type_name`]: https://doc.rust-lang.org/std/any/fn.type_name.html
+    pub fn get(&self, type_name: &str) -> Option<TypeStats> {
+        self.types.get(type_name).copied()
+    }
+
+    /// Get the statistics for a type
+    pub fn of<T: ?Sized>(&self) -> Option<TypeStats> {
+        self.get(std::any::type_name::<T>())
+    }
+
+    /// Iterate over the statistics of all read types, ordered by the number of bits consumed
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, TypeStats)> {
+        let mut types: Vec<_> = self
+            .types
+            .iter()
+            .map(|(name, stats)| (*name, *stats))
+            .collect();
+        types.sort_by(|a, b| b.1.bits.cmp(&a.1.bits).then(a.0.cmp(b.0)));
+        types.into_iter()
+    }
+
+    pub(crate) fn record(&mut self, type_name: &'static str, bits: usize) {
+        let stats = self.types.entry(type_name).or_default();
+        stats.reads += 1;
+        stats.bits += bits;
+    }
+}
+
+#[cfg(feature = "stats")]
+impl fmt::Display for ReadStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, stats) in self.iter() {
+            writeln!(
+                f,
+                "{}: {} bits in {} reads ({:.1} bits per read)",
+                name,
+                stats.bits,
+                stats.reads,
+                stats.bits as f64 / stats.reads as f64
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Record the bits consumed by a read in a derived implementation when collecting stats
+#[cfg(feature = "stats")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __record_read {
+    ($stream:ident, $body:expr) => {{
+        let __bitbuffer_stats_start = $stream.__begin_read();
+        #[allow(clippy::redundant_closure_call)]
+        let __bitbuffer_stats_result: $crate::Result<Self> =
+            (|| -> $crate::Result<Self> { $body })();
+        if __bitbuffer_stats_result.is_ok() {
+            $stream.__end_read(::std::any::type_name::<Self>(), __bitbuffer_stats_start);
+        }
+        __bitbuffer_stats_result
+    }};
+}
+
+/// Record the bits consumed by a read in a derived implementation when collecting stats
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __record_read {
+    ($stream:ident, $body:expr) => {
+        $body
+    };
+}
@@ -0,0 +1,24 @@
+/// The bytes backing a [`BitReadBuffer`]
+///
+/// Implemented for every type that can be borrowed as a byte slice, like `Vec<u8>`, `Box<[u8]>`, `Arc<[u8]>`,
+/// `&'static [u8]`, or the memory maps and byte containers of other crates, so the owner of the data can be chosen
+/// by the caller. The storage is shared by all clones and sub buffers of a buffer, and never copied or modified.
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadBuffer, LittleEndian, Result};
+///
+/// # fn main() -> Result<()> {
+/// static DATA: [u8; 2] = [0b1011_0101, 0b0110_1010];
+/// let buffer = BitReadBuffer::new(&DATA[..], LittleEndian);
+/// assert_eq!(buffer.read_int::<u16>(0, 16)?, 0b0110_1010_1011_0101);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitReadBuffer`]: struct.BitReadBuffer.html
+pub trait Storage: AsRef<[u8]> + 'static {}
+
+impl<T: AsRef<[u8]> + 'static> Storage for T {}
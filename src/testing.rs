@@ -0,0 +1,153 @@
+//! Helpers for testing `BitRead` and `BitWrite` implementations, requires the `testing` feature
+//!
+//! [`assert_roundtrip`] writes a value and reads it back in both endiannesses, checking that the same value
+//! is read and that the read consumes exactly the written bits. The [`padding`] and [`padded`] strategies
+//! can be used with [`proptest`] to check a type at random bit alignments.
+//!
+//! # Examples
+//!
+//! ```
+//! use bitbuffer::testing::{assert_roundtrip, assert_roundtrip_padded, padded};
+//! use bitbuffer::testing::proptest::prelude::*;
+//! use bitbuffer::{BitRead, BitWrite};
+//!
+//! #[derive(Debug, PartialEq, BitRead, BitWrite)]
+//! struct Packet {
+//!     flag: bool,
+//!     #[size = 5]
+//!     kind: u8,
+//!     payload: u32,
+//! }
+//!
+//! assert_roundtrip(Packet { flag: true, kind: 3, payload: 1234 });
+//!
+//! proptest!(|((payload, padding) in padded(any::<u32>()))| {
+//!     assert_roundtrip_padded(Packet { flag: false, kind: 31, payload }, padding);
+//! });
+//! ```
+//!
+//! [`assert_roundtrip`]: fn.assert_roundtrip.html
+//! [`padding`]: fn.padding.html
+//! [`padded`]: fn.padded.html
+//! [`proptest`]: https://docs.rs/proptest
+
+use crate::{
+    BigEndian, BitRead, BitReadBuffer, BitReadStream, BitWrite, BitWriteStream, Endianness,
+    LittleEndian,
+};
+use proptest::strategy::Strategy;
+use std::cmp::min;
+use std::fmt::Debug;
+
+pub use proptest;
+
+/// The largest padding generated by [`padding`]
+///
+/// [`padding`]: fn.padding.html
+pub const MAX_PADDING: usize = 128;
+
+/// Assert that a value reads back unchanged after being written, in both endiannesses
+///
+/// # Panics
+///
+/// Panics if writing or reading the value fails, if the read value doesn't equal the written one
+/// or if reading doesn't consume exactly the bits written.
+pub fn assert_roundtrip<T>(value: T)
+where
+    T: BitRead<LittleEndian> + BitWrite<LittleEndian>,
+    T: BitRead<BigEndian> + BitWrite<BigEndian>,
+    T: Debug + PartialEq,
+{
+    assert_roundtrip_padded(value, 0)
+}
+
+/// Assert that a value reads back unchanged after being written after `padding` bits, in both endiannesses
+///
+/// # Panics
+///
+/// Panics if writing or reading the value fails, if the read value doesn't equal the written one
+/// or if reading doesn't consume exactly the bits written.
+pub fn assert_roundtrip_padded<T>(value: T, padding: usize)
+where
+    T: BitRead<LittleEndian> + BitWrite<LittleEndian>,
+    T: BitRead<BigEndian> + BitWrite<BigEndian>,
+    T: Debug + PartialEq,
+{
+    roundtrip(&value, padding, LittleEndian, LittleEndian);
+    roundtrip(&value, padding, BigEndian, BigEndian);
+}
+
+fn roundtrip<E, T>(value: &T, padding: usize, write_endianness: E, read_endianness: E)
+where
+    E: Endianness,
+    T: BitRead<E> + BitWrite<E> + Debug + PartialEq,
+{
+    let mut stream = BitWriteStream::new(write_endianness);
+    write_padding(&mut stream, padding);
+    stream.write(value).unwrap_or_else(|e| {
+        panic!(
+            "failed to write {:?} at offset {} as {}: {}",
+            value,
+            padding,
+            E::as_string(),
+            e
+        )
+    });
+    let bit_len = stream.bit_len() - padding;
+    // trailing padding so reading past the written bits is reported by the position check
+    write_padding(&mut stream, 8);
+
+    let buffer = BitReadBuffer::new(stream.finish(), read_endianness);
+    let mut read = BitReadStream::new(buffer);
+    read.skip_bits(padding).unwrap();
+    let result: T = read.read().unwrap_or_else(|e| {
+        panic!(
+            "failed to read {:?} at offset {} as {}: {}",
+            value,
+            padding,
+            E::as_string(),
+            e
+        )
+    });
+    assert_eq!(
+        value,
+        &result,
+        "value read at offset {} as {} differs from the written value",
+        padding,
+        E::as_string()
+    );
+    assert_eq!(
+        bit_len,
+        read.pos() - padding,
+        "number of bits read at offset {} as {} differs from the number of bits written for {:?}",
+        padding,
+        E::as_string(),
+        value
+    );
+}
+
+/// Write `count` bits with alternating values
+fn write_padding<E: Endianness>(stream: &mut BitWriteStream<E>, mut count: usize) {
+    while count > 0 {
+        let chunk = min(count, 64);
+        stream.write_int(0x5555_5555_5555_5555u64, chunk).unwrap();
+        count -= chunk;
+    }
+}
+
+/// Strategy generating a number of padding bits, between `0` and [`MAX_PADDING`]
+///
+/// [`MAX_PADDING`]: constant.MAX_PADDING.html
+pub fn padding() -> impl Strategy<Value = usize> {
+    0..=MAX_PADDING
+}
+
+/// Strategy generating a bit alignment, between `0` and `7`
+pub fn alignment() -> impl Strategy<Value = usize> {
+    0..8usize
+}
+
+/// Strategy pairing the values generated by `values` with a number of padding bits
+pub fn padded<S: Strategy>(values: S) -> impl Strategy<Value = (S::Value, usize)> {
+    (values, padding())
+}
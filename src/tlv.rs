@@ -0,0 +1,227 @@
+//! Reading type-length-value chunks
+//!
+//! Many container formats are a sequence of chunks, each starting with a tag identifying the chunk, followed by the
+//! length of the chunk data in bytes and the data itself. [`TlvFormat`] describes the width of the tag and length,
+//! [`TlvFormat::chunks`] iterates over the chunks in a stream and [`TlvHandlers`] dispatches chunks to a handler
+//! registered for their tag, skipping the chunks without handler.
+//!
+//! Every chunk is read from its own sub-stream, so a handler can't read past the end of its chunk and any data it
+//! leaves unread is skipped.
+//!
+//! ```
+//! use bitbuffer::tlv::{TlvFormat, TlvHandlers};
+//! # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Item {
+//!     Name(String),
+//!     Size(u16),
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! // 8 bit tag, 8 bit length: name "hi", an unknown chunk and a size of 258
+//! let bytes = vec![1, 2, b'h', b'i', 9, 1, 0xff, 2, 2, 0x02, 0x01];
+//! let buffer = BitReadBuffer::new(bytes, LittleEndian);
+//! let mut stream = BitReadStream::new(buffer);
+//!
+//! let handlers = TlvHandlers::new(TlvFormat::new(8, 8))
+//!     .on(1, |data| Ok(Item::Name(data.read_string(None)?)))
+//!     .on(2, |data| Ok(Item::Size(data.read()?)));
+//!
+//! let items = handlers.read_all(&mut stream)?;
+//! assert_eq!(items, vec![Item::Name("hi".into()), Item::Size(258)]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TlvFormat`]: struct.TlvFormat.html
+//! [`TlvFormat::chunks`]: struct.TlvFormat.html#method.chunks
+//! [`TlvHandlers`]: struct.TlvHandlers.html
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use crate::{BitReadStream, Endianness, Result};
+
+/// The layout of the header of a chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TlvFormat {
+    tag_bits: usize,
+    length_bits: usize,
+}
+
+impl TlvFormat {
+    /// Create a format with a tag of `tag_bits` bits followed by a length of `length_bits` bits
+    ///
+    /// The length is the number of bytes of chunk data following the header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `tag_bits` or `length_bits` is larger than 64.
+    pub fn new(tag_bits: usize, length_bits: usize) -> Self {
+        assert!(tag_bits <= 64, "tags can be at most 64 bits");
+        assert!(length_bits <= 64, "lengths can be at most 64 bits");
+        TlvFormat {
+            tag_bits,
+            length_bits,
+        }
+    }
+
+    /// The number of bits in the tag
+    pub fn tag_bits(&self) -> usize {
+        self.tag_bits
+    }
+
+    /// The number of bits in the length
+    pub fn length_bits(&self) -> usize {
+        self.length_bits
+    }
+
+    /// Read a single chunk from the stream
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream for the header or chunk data
+    ///
+    /// On error the position of the stream is unchanged.
+    ///
+    /// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+    pub fn read_chunk<E: Endianness>(&self, stream: &mut BitReadStream<E>) -> Result<Chunk<E>> {
+        let start = stream.pos();
+        let result = self.read_chunk_inner(stream);
+        if result.is_err() {
+            stream.set_pos(start)?;
+        }
+        result
+    }
+
+    fn read_chunk_inner<E: Endianness>(&self, stream: &mut BitReadStream<E>) -> Result<Chunk<E>> {
+        let tag = stream.read_int(self.tag_bits)?;
+        let length: u64 = stream.read_int(self.length_bits)?;
+        let count = usize::try_from(length)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(8);
+        let data = stream.read_bits(count)?;
+        Ok(Chunk { tag, data })
+    }
+
+    /// Iterate over the chunks in the stream until the end of the stream
+    ///
+    /// The iterator stops after the first error.
+    pub fn chunks<E: Endianness>(self, stream: &mut BitReadStream<E>) -> Chunks<'_, E> {
+        Chunks {
+            format: self,
+            stream,
+            done: false,
+        }
+    }
+}
+
+/// A single chunk read from a stream
+#[derive(Debug, Clone)]
+pub struct Chunk<E: Endianness> {
+    /// The tag of the chunk
+    pub tag: u64,
+    /// The data of the chunk
+    pub data: BitReadStream<E>,
+}
+
+/// Iterator over the chunks in a stream, created by [`TlvFormat::chunks`]
+///
+/// [`TlvFormat::chunks`]: struct.TlvFormat.html#method.chunks
+pub struct Chunks<'a, E: Endianness> {
+    format: TlvFormat,
+    stream: &'a mut BitReadStream<E>,
+    done: bool,
+}
+
+impl<E: Endianness> Iterator for Chunks<'_, E> {
+    type Item = Result<Chunk<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.stream.bits_left() == 0 {
+            return None;
+        }
+        let result = self.format.read_chunk(self.stream);
+        self.done = result.is_err();
+        Some(result)
+    }
+}
+
+type Handler<'h, E, T> = Box<dyn Fn(&mut BitReadStream<E>) -> Result<T> + 'h>;
+
+/// Dispatch chunks to handlers registered by tag
+///
+/// Chunks with a tag that has no registered handler are skipped.
+pub struct TlvHandlers<'h, E: Endianness, T> {
+    format: TlvFormat,
+    handlers: HashMap<u64, Handler<'h, E, T>>,
+}
+
+impl<'h, E: Endianness, T> TlvHandlers<'h, E, T> {
+    /// Create a dispatcher for chunks in `format` without any handlers
+    pub fn new(format: TlvFormat) -> Self {
+        TlvHandlers {
+            format,
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Register the handler for chunks with `tag`, replacing any previous handler for the tag
+    ///
+    /// The handler receives a stream containing only the data of the chunk.
+    pub fn on<F>(mut self, tag: u64, handler: F) -> Self
+    where
+        F: Fn(&mut BitReadStream<E>) -> Result<T> + 'h,
+    {
+        self.handlers.insert(tag, Box::new(handler));
+        self
+    }
+
+    /// Pass a chunk to the handler for its tag, returns `None` if no handler is registered for the tag
+    ///
+    /// # Errors
+    ///
+    /// - any error returned by the handler
+    pub fn dispatch(&self, mut chunk: Chunk<E>) -> Result<Option<T>> {
+        match self.handlers.get(&chunk.tag) {
+            Some(handler) => handler(&mut chunk.data).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read chunks until a chunk with a registered handler is found and return the result of its handler
+    ///
+    /// Returns `None` when the end of the stream is reached.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream for a chunk
+    /// - any error returned by the handler
+    ///
+    /// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+    pub fn read_next(&self, stream: &mut BitReadStream<E>) -> Result<Option<T>> {
+        for chunk in self.format.chunks(stream) {
+            if let Some(value) = self.dispatch(chunk?)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read all chunks until the end of the stream and collect the results of the handlers
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream for a chunk
+    /// - any error returned by a handler
+    ///
+    /// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+    pub fn read_all(&self, stream: &mut BitReadStream<E>) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+        while let Some(value) = self.read_next(stream)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
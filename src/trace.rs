@@ -0,0 +1,75 @@
+//! Support for the `trace` feature, used by the code generated by the derive macros
+
+use std::fmt::Debug;
+
+/// Wrapper used to format field values that might not implement `Debug`
+///
+/// Method resolution prefers [`TraceDebug`] when the value implements `Debug` and falls back to
+/// [`TraceFallback`] otherwise.
+pub struct TraceValue<'a, T>(pub &'a T);
+
+/// Format the value using its `Debug` implementation
+pub trait TraceDebug {
+    /// Get the value to format
+    fn trace_value(&self) -> &dyn Debug;
+}
+
+impl<T: Debug> TraceDebug for TraceValue<'_, T> {
+    fn trace_value(&self) -> &dyn Debug {
+        self.0
+    }
+}
+
+/// Format a placeholder for values that don't implement `Debug`
+pub trait TraceFallback {
+    /// Get the value to format
+    fn trace_value(&self) -> &dyn Debug;
+}
+
+impl<T> TraceFallback for &TraceValue<'_, T> {
+    fn trace_value(&self) -> &dyn Debug {
+        &"<no Debug implementation>"
+    }
+}
+
+/// Emit a trace event for a field read by a derived `BitRead` or `BitReadSized` implementation
+#[cfg(feature = "trace")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($type_name:expr, $field:expr, $pos:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{TraceDebug, TraceFallback};
+        $crate::__private::tracing::trace!(
+            type_name = $type_name,
+            field = $field,
+            pos = $pos,
+            value = ?(&$crate::__private::TraceValue(&$value)).trace_value(),
+        );
+    }};
+}
+
+/// Emit a trace event for a field read by a derived `BitRead` or `BitReadSized` implementation
+#[cfg(not(feature = "trace"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($type_name:expr, $field:expr, $pos:expr, $value:expr) => {{
+        let _ = $pos;
+    }};
+}
+
+/// Record a field read by a derived `BitRead` or `BitReadSized` implementation for `read_instrumented`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __record_field {
+    ($stream:expr, $field:expr, $pos:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{TraceDebug, TraceFallback};
+        $stream.__end_field(
+            $field,
+            $pos,
+            (&$crate::__private::TraceValue(&$value)).trace_value(),
+        );
+    }};
+}
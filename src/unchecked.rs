@@ -0,0 +1,222 @@
+//! Reading without bounds checks
+//!
+//! Every read from a [`BitReadStream`] checks that enough bits are left, for hot loops reading many small values these
+//! checks can make up a significant part of the time spent. The unchecked methods skip the bounds checks, and are
+//! sound as long as the caller guarantees the bits being read are available:
+//!
+//! - [`BitReadStream::read_bool_unchecked`], [`read_int_unchecked`], [`read_float_unchecked`] and
+//!   [`read_bytes_unchecked`] require that the stream has at least the number of bits being read left,
+//!   and that no more bits are read than the read limit and read marker allow.
+//! - [`BitReadStream::read_unchecked`] and [`BitRead::read_unchecked`] require that the stream has at least
+//!   [`BitRead::bit_size`] bits left, they can only be used for types with a fixed size.
+//! - The matching methods on [`BitReadBuffer`] require that `position` plus the number of bits read is at most the
+//!   length of the buffer.
+//!
+//! Only the bounds are unchecked, any other validation (e.g. checking for valid utf8 or enum discriminants) is still
+//! done and can still return errors.
+//!
+//! The easiest way to uphold these contracts is by doing a single [`check_read`] for all following reads,
+//! [`ReadGuard`] does this while keeping track of the bits that are left, so it can offer the unchecked reads
+//! without requiring any `unsafe` code from the caller.
+//!
+//! ```
+//! use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result};
+//! use bitbuffer::unchecked::ReadGuard;
+//!
+//! # fn main() -> Result<()> {
+//! let buffer = BitReadBuffer::new(vec![0b1011_0101, 0b0110_1010, 0b1010_1100], LittleEndian);
+//! let mut stream = BitReadStream::new(buffer);
+//!
+//! let mut guard = ReadGuard::new(&mut stream, 20)?;
+//! let mut values = Vec::new();
+//! for _ in 0..5 {
+//!     values.push(guard.read_int::<u8>(4));
+//! }
+//! assert_eq!(values, vec![0b0101, 0b1011, 0b1010, 0b0110, 0b1100]);
+//! assert_eq!(guard.remaining(), 0);
+//! assert_eq!(stream.pos(), 20);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`BitReadStream`]: ../struct.BitReadStream.html
+//! [`BitReadStream::read_bool_unchecked`]: ../struct.BitReadStream.html#method.read_bool_unchecked
+//! [`read_int_unchecked`]: ../struct.BitReadStream.html#method.read_int_unchecked
+//! [`read_float_unchecked`]: ../struct.BitReadStream.html#method.read_float_unchecked
+//! [`read_bytes_unchecked`]: ../struct.BitReadStream.html#method.read_bytes_unchecked
+//! [`BitReadStream::read_unchecked`]: ../struct.BitReadStream.html#method.read_unchecked
+//! [`BitRead::read_unchecked`]: ../trait.BitRead.html#method.read_unchecked
+//! [`BitRead::bit_size`]: ../trait.BitRead.html#method.bit_size
+//! [`BitReadBuffer`]: ../struct.BitReadBuffer.html
+//! [`check_read`]: ../struct.BitReadStream.html#method.check_read
+//! [`ReadGuard`]: struct.ReadGuard.html
+
+use std::mem::size_of;
+use std::ops::BitOrAssign;
+
+use num_traits::{Float, PrimInt};
+
+use crate::num_traits::{IsSigned, UncheckedPrimitiveFloat, UncheckedPrimitiveInt};
+use crate::{BitRead, BitReadStream, Endianness, Result};
+
+/// Proof that a number of bits can be read from a stream
+///
+/// Created by checking that `count` bits can be read from a stream, the reads from the guard are then done without
+/// bounds checks. Reading more bits than were checked is a bug in the caller and panics, the same way indexing a
+/// slice out of bounds does.
+///
+/// The guard borrows the stream, so the stream can't be moved or modified while the guard exists.
+pub struct ReadGuard<'a, E: Endianness> {
+    stream: &'a mut BitReadStream<E>,
+    remaining: usize,
+}
+
+impl<'a, E: Endianness> ReadGuard<'a, E> {
+    /// Check that `count` bits can be read from `stream`
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::ReadLimitReached`]: reading `count` bits would exceed the read limit of the stream
+    /// - [`ReadError::ReadPastMarker`]: reading `count` bits would read past the read marker of the stream
+    ///
+    /// [`ReadError::NotEnoughData`]: ../enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::ReadLimitReached`]: ../enum.ReadError.html#variant.ReadLimitReached
+    /// [`ReadError::ReadPastMarker`]: ../enum.ReadError.html#variant.ReadPastMarker
+    pub fn new(stream: &'a mut BitReadStream<E>, count: usize) -> Result<Self> {
+        stream.check_read(count)?;
+        Ok(ReadGuard {
+            stream,
+            remaining: count,
+        })
+    }
+
+    /// The number of checked bits that haven't been read yet
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    #[inline]
+    fn take(&mut self, count: usize) {
+        assert!(
+            count <= self.remaining,
+            "reading {} bits from a guard with only {} bits remaining",
+            count,
+            self.remaining
+        );
+        self.remaining -= count;
+    }
+
+    /// Read a single bit as boolean
+    ///
+    /// # Panics
+    ///
+    /// Panics if no checked bits are remaining.
+    #[inline]
+    pub fn read_bool(&mut self) -> bool {
+        self.take(1);
+        unsafe { self.stream.read_bool_unchecked() }
+    }
+
+    /// Read `count` bits as an integer
+    ///
+    /// # Panics
+    ///
+    /// Panics if less than `count` checked bits are remaining or `count` is larger than the integer type.
+    #[inline]
+    pub fn read_int<T>(&mut self, count: usize) -> T
+    where
+        T: PrimInt + BitOrAssign + IsSigned + UncheckedPrimitiveInt,
+    {
+        assert!(
+            count <= size_of::<T>() * 8,
+            "reading {} bits into a {} bit integer",
+            count,
+            size_of::<T>() * 8
+        );
+        self.take(count);
+        unsafe { self.stream.read_int_unchecked(count) }
+    }
+
+    /// Read a float
+    ///
+    /// # Panics
+    ///
+    /// Panics if less than the size of the float checked bits are remaining.
+    #[inline]
+    pub fn read_float<T>(&mut self) -> T
+    where
+        T: Float + UncheckedPrimitiveFloat,
+    {
+        self.take(size_of::<T>() * 8);
+        unsafe { self.stream.read_float_unchecked() }
+    }
+
+    /// Read `byte_count` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if less than `byte_count * 8` checked bits are remaining.
+    #[inline]
+    pub fn read_bytes(&mut self, byte_count: usize) -> Vec<u8> {
+        self.take(byte_count.saturating_mul(8));
+        unsafe { self.stream.read_bytes_unchecked(byte_count) }
+    }
+
+    /// Read a type with a fixed size
+    ///
+    /// # Errors
+    ///
+    /// - any error from validating the value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type doesn't have a fixed size or less than [`bit_size`] checked bits are remaining.
+    ///
+    /// [`bit_size`]: ../trait.BitRead.html#method.bit_size
+    #[inline]
+    pub fn read<T: BitRead<E>>(&mut self) -> Result<T> {
+        let size = T::bit_size().expect("only types with a fixed size can be read from a guard");
+        self.take(size);
+        unsafe { self.stream.read_unchecked() }
+    }
+}
+
+impl<E: Endianness> BitReadStream<E> {
+    /// Check that `count` bits can be read and return a guard for reading them without bounds checks
+    ///
+    /// See [`ReadGuard`] for details.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::NotEnoughData`]: not enough bits available in the stream
+    /// - [`ReadError::ReadLimitReached`]: reading `count` bits would exceed the read limit of the stream
+    /// - [`ReadError::ReadPastMarker`]: reading `count` bits would read past the read marker of the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BigEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// # let buffer = BitReadBuffer::new(vec![0x12, 0x34, 0x56, 0x78, 0x9a], BigEndian);
+    /// # let mut stream = BitReadStream::new(buffer);
+    /// let mut guard = stream.guard(40)?;
+    /// let header = guard.read_int::<u8>(8);
+    /// let payload = guard.read::<u32>()?;
+    /// assert_eq!(header, 0x12);
+    /// assert_eq!(payload, 0x3456789a);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadGuard`]: unchecked/struct.ReadGuard.html
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::ReadLimitReached`]: enum.ReadError.html#variant.ReadLimitReached
+    /// [`ReadError::ReadPastMarker`]: enum.ReadError.html#variant.ReadPastMarker
+    pub fn guard(&mut self, count: usize) -> Result<ReadGuard<'_, E>> {
+        ReadGuard::new(self, count)
+    }
+}
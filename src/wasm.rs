@@ -0,0 +1,30 @@
+use crate::{BitReadBuffer, Endianness};
+use js_sys::Uint8Array;
+
+impl<E: Endianness> BitReadBuffer<E> {
+    /// Create a new BitBuffer from a javascript `Uint8Array`
+    ///
+    /// The bytes are copied out of the javascript memory in one go, requires the `wasm` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian};
+    /// use js_sys::Uint8Array;
+    ///
+    /// fn parse(data: &Uint8Array) -> bitbuffer::Result<u32> {
+    ///     let buffer = BitReadBuffer::from_uint8_array(data, LittleEndian);
+    ///     let mut stream = BitReadStream::new(buffer);
+    ///     stream.read()
+    /// }
+    /// ```
+    pub fn from_uint8_array(array: &Uint8Array, _endianness: E) -> Self {
+        BitReadBuffer::from(array)
+    }
+}
+
+impl<E: Endianness> From<&Uint8Array> for BitReadBuffer<E> {
+    fn from(array: &Uint8Array) -> Self {
+        BitReadBuffer::from(array.to_vec())
+    }
+}
@@ -15,10 +15,9 @@ use std::sync::Arc;
 /// The struct is written field by field in the order they are defined in, if the size for a field is set [`stream.write_sized()`][write_sized]
 /// will be used, otherwise [`stream_write()`][write] will be used.
 ///
-/// The size for a field can be set using 3 different methods
+/// The size for a field can be set using 2 different methods
 ///  - set the size as an integer using the `size` attribute,
 ///  - use a previously defined field as the size using the `size` attribute
-///  - read a set number of bits as an integer, using the resulting value as size using the `size_bits` attribute
 ///
 /// ## Examples
 ///
@@ -29,14 +28,12 @@ use std::sync::Arc;
 /// struct TestStruct {
 ///     foo: u8,
 ///     str: String,
-///     #[size = 2] // when `size` is set, the attributed will be read using `read_sized`
+///     #[size = 2] // when `size` is set, the attributed will be written using `write_sized`
 ///     truncated: String,
 ///     bar: u16,
 ///     float: f32,
 ///     #[size = 3]
 ///     asd: u8,
-///     #[size_bits = 2] // first read 2 bits as unsigned integer, then use the resulting value as size for the read
-///     dynamic_length: u8,
 ///     #[size = "asd"] // use a previously defined field as size
 ///     previous_field: u8,
 /// }
@@ -135,6 +132,30 @@ impl_write_int_nonzero!(std::num::NonZeroU32);
 impl_write_int_nonzero!(std::num::NonZeroU64);
 impl_write_int_nonzero!(std::num::NonZeroU128);
 
+/// `char` is written as a 32 bit unicode scalar value
+impl<E: Endianness> BitWrite<E> for char {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(*self as u32, 32)
+    }
+}
+
+/// `usize` is always written as 64 bits, independent of the platform
+impl<E: Endianness> BitWrite<E> for usize {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(*self as u64, 64)
+    }
+}
+
+/// `isize` is always written as 64 bits, independent of the platform
+impl<E: Endianness> BitWrite<E> for isize {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(*self as i64, 64)
+    }
+}
+
 impl<E: Endianness> BitWrite<E> for f32 {
     #[inline]
     fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
@@ -215,11 +236,10 @@ impl_write_tuple!(T1, T2, T3, T4);
 /// The struct is written field by field in the order they are defined in, if the size for a field is set [`stream.write_sized()`][write_sized]
 /// will be used, otherwise [`stream_write()`][write] will be used.
 ///
-/// The size for a field can be set using 4 different methods
+/// The size for a field can be set using 3 different methods
 ///  - set the size as an integer using the `size` attribute,
 ///  - use a previously defined field as the size using the `size` attribute
 ///  - based on the input size by setting `size` attribute to `"input_size"`
-///  - read a set number of bits as an integer, using the resulting value as size using the `size_bits` attribute
 ///
 /// ## Examples
 ///
@@ -294,6 +314,30 @@ impl_write_int_sized!(i32);
 impl_write_int_sized!(i64);
 impl_write_int_sized!(i128);
 
+/// Writes the unicode scalar value as `size` bits
+impl<E: Endianness> BitWriteSized<E> for char {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        stream.write_int(*self as u32, size)
+    }
+}
+
+/// Writes `size` bits, up to 64, independent of the platform
+impl<E: Endianness> BitWriteSized<E> for usize {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        stream.write_int(*self as u64, size)
+    }
+}
+
+/// Writes `size` bits, up to 64, independent of the platform
+impl<E: Endianness> BitWriteSized<E> for isize {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        stream.write_int(*self as i64, size)
+    }
+}
+
 impl<E: Endianness> BitWriteSized<E> for String {
     #[inline]
     fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
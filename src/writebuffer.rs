@@ -0,0 +1,201 @@
+use num_traits::PrimInt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use crate::endianness::Endianness;
+use crate::num_traits::IntoBytes;
+use crate::pos::BitPos;
+use crate::{BitError, Result};
+
+/// Buffer that allows modifying bits of existing data in place
+///
+/// Where a [`BitWriteStream`] can only append, a `BitWriteBuffer` overwrites the bits at a given position,
+/// which allows changing a single field in a large buffer without decoding and encoding all data.
+/// The positions of fields can be found by reading the data with a [`BitReadStream`].
+///
+/// # Examples
+///
+/// ```
+/// use bitbuffer::{BitReadBuffer, BitWriteBuffer, LittleEndian, Result};
+///
+/// # fn main() -> Result<()> {
+/// let mut buffer = BitWriteBuffer::new(vec![0b1011_0101, 0b0110_1010], LittleEndian);
+/// // change the 7 bit field starting at bit 3
+/// buffer.patch_int(3, 7, 0b101_0101u8)?;
+/// assert_eq!(buffer.bytes(), &[0b1010_1101, 0b0110_1010]);
+///
+/// let read = BitReadBuffer::new(buffer.into_bytes(), LittleEndian);
+/// assert_eq!(read.read_int::<u8>(3, 7)?, 0b101_0101);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`BitWriteStream`]: struct.BitWriteStream.html
+/// [`BitReadStream`]: struct.BitReadStream.html
+pub struct BitWriteBuffer<E>
+where
+    E: Endianness,
+{
+    bytes: Vec<u8>,
+    endianness: PhantomData<E>,
+}
+
+impl<E> BitWriteBuffer<E>
+where
+    E: Endianness,
+{
+    /// Create a new write buffer from existing bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitbuffer::{BitWriteBuffer, LittleEndian};
+    ///
+    /// let bytes = vec![10, 255, 30];
+    /// let buffer = BitWriteBuffer::new(bytes, LittleEndian);
+    /// ```
+    pub fn new(bytes: Vec<u8>, _endianness: E) -> Self {
+        BitWriteBuffer {
+            bytes,
+            endianness: PhantomData,
+        }
+    }
+
+    /// Wrap the bytes of a write stream with the same endianness
+    pub(crate) fn from_stream_bytes(bytes: Vec<u8>) -> Self {
+        BitWriteBuffer {
+            bytes,
+            endianness: PhantomData,
+        }
+    }
+
+    /// The number of bits in the buffer
+    pub fn bit_len(&self) -> usize {
+        self.bytes.len().saturating_mul(8)
+    }
+
+    /// The number of bytes in the buffer
+    pub fn byte_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The bytes of the buffer, including all patches
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Get the bytes of the buffer, including all patches
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    #[inline]
+    fn set_bit(&mut self, position: usize, value: bool) {
+        let bit = if E::is_le() {
+            position & 7
+        } else {
+            7 - (position & 7)
+        };
+        let byte = &mut self.bytes[position / 8];
+        *byte = (*byte & !(1 << bit)) | ((value as u8) << bit);
+    }
+
+    /// Overwrite the bit at `position` with a boolean
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: `position` is past the end of the buffer
+    ///
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    pub fn patch_bool(&mut self, position: usize, value: bool) -> Result<()> {
+        if position >= self.bit_len() {
+            return Err(BitError::IndexOutOfBounds {
+                pos: position,
+                size: self.bit_len(),
+            });
+        }
+        self.set_bit(position, value);
+        Ok(())
+    }
+
+    /// Overwrite `count` bits starting at `position` with an integer
+    ///
+    /// The integer is written the same way as [`BitWriteStream::write_int`], only the lowest `count` bits of
+    /// the value are written and the bits before and after the patched range are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: `position` is past the end of the buffer
+    /// - [`ReadError::NotEnoughData`]: the patched range extends past the end of the buffer
+    /// - [`ReadError::TooManyBits`]: to many bits requested for the chosen integer type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bitbuffer::{BitReadBuffer, BitWriteBuffer, BigEndian, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut buffer = BitWriteBuffer::new(vec![0; 4], BigEndian);
+    /// buffer.patch_int(5, 20, 123_456u32)?;
+    ///
+    /// let read = BitReadBuffer::new(buffer.into_bytes(), BigEndian);
+    /// assert_eq!(read.read_int::<u32>(5, 20)?, 123_456);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitWriteStream::write_int`]: struct.BitWriteStream.html#method.write_int
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    pub fn patch_int<T>(&mut self, position: usize, count: usize, value: T) -> Result<()>
+    where
+        T: PrimInt + IntoBytes,
+    {
+        let type_bit_size = size_of::<T>() * 8;
+        if type_bit_size < count {
+            return Err(BitError::TooManyBits {
+                requested: count,
+                max: type_bit_size,
+            });
+        }
+        BitPos::new(position).advance(count, self.bit_len())?;
+
+        // the little endian bytes of the value, bit `n` of the value is bit `n % 8` of byte `n / 8`
+        let bytes: Vec<u8> = value.into_bytes().collect();
+        for n in 0..count {
+            let bit = bytes[n / 8] >> (n & 7) & 1 == 1;
+            // little endian streams store the least significant bit first, big endian the most significant
+            let target = if E::is_le() {
+                position + n
+            } else {
+                position + count - 1 - n
+            };
+            self.set_bit(target, bit);
+        }
+        Ok(())
+    }
+
+    /// Overwrite the bytes starting at bit `position`
+    ///
+    /// The bytes are written the same way as [`BitWriteStream::write_bytes`], `position` doesn't need to be
+    /// byte aligned.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::IndexOutOfBounds`]: `position` is past the end of the buffer
+    /// - [`ReadError::NotEnoughData`]: the patched range extends past the end of the buffer
+    ///
+    /// [`BitWriteStream::write_bytes`]: struct.BitWriteStream.html#method.write_bytes
+    /// [`ReadError::IndexOutOfBounds`]: enum.ReadError.html#variant.IndexOutOfBounds
+    /// [`ReadError::NotEnoughData`]: enum.ReadError.html#variant.NotEnoughData
+    pub fn patch_bytes(&mut self, position: usize, bytes: &[u8]) -> Result<()> {
+        BitPos::new(position).advance(bytes.len().saturating_mul(8), self.bit_len())?;
+        for (index, byte) in bytes.iter().enumerate() {
+            self.patch_int(position + index * 8, 8, *byte)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use num_traits::{Float, PrimInt};
-use std::iter::{once, repeat};
+use std::iter::{once, repeat_n};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::ops::{BitOrAssign, BitXor};
@@ -7,13 +7,27 @@ use std::ops::{BitOrAssign, BitXor};
 use crate::endianness::Endianness;
 use crate::num_traits::{IntoBytes, IsSigned, UncheckedPrimitiveFloat, UncheckedPrimitiveInt};
 use crate::write::{BitWrite, BitWriteSized};
-use crate::{BitError, Result};
+use crate::{BitError, BitReadBuffer, BitWriteBuffer, Result};
+use std::cmp::min;
 
 const USIZE_SIZE: usize = size_of::<usize>();
 const USIZE_BITS: usize = USIZE_SIZE * 8;
 
+/// Mask for the lowest `count` bits
+#[inline]
+fn mask(count: usize) -> usize {
+    if count == 0 {
+        0
+    } else {
+        usize::MAX >> (USIZE_BITS - count)
+    }
+}
+
 /// Stream that provides an a way to write non bit aligned adata
 ///
+/// The written data is limited to [`BitReadBuffer::MAX_BYTE_LEN`] bytes so it can always be read back,
+/// writes that would grow the stream past this limit fail with [`ReadError::BufferTooLarge`].
+///
 /// # Examples
 ///
 /// ```
@@ -30,6 +44,8 @@ const USIZE_BITS: usize = USIZE_SIZE * 8;
 /// ```
 ///
 /// [`BitBuffer`]: struct.BitBuffer.html
+/// [`BitReadBuffer::MAX_BYTE_LEN`]: struct.BitReadBuffer.html#associatedconstant.MAX_BYTE_LEN
+/// [`ReadError::BufferTooLarge`]: enum.ReadError.html#variant.BufferTooLarge
 pub struct BitWriteStream<E>
 where
     E: Endianness,
@@ -75,21 +91,40 @@ where
         self.bytes.len()
     }
 
+    /// Check that `count` more bits can be written without the stream growing larger than a readable buffer
+    #[inline]
+    fn check_write(&self, count: usize) -> Result<()> {
+        let max = BitReadBuffer::<E>::MAX_BYTE_LEN * 8;
+        if count > max - self.bit_len {
+            Err(BitError::BufferTooLarge {
+                byte_len: self.bytes.len().saturating_add(count.div_ceil(8)),
+                max: BitReadBuffer::<E>::MAX_BYTE_LEN,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Push the lowest `count` bits from the little endian `bits`
     fn push_non_fit_bits<I>(&mut self, bits: I, count: usize)
     where
         I: ExactSizeIterator,
         I: DoubleEndedIterator<Item = u8>,
     {
-        let counts = repeat(8)
-            .take(bits.len() - 1)
-            .chain(once(count - (bits.len() - 1) * 8));
+        let full_bytes = count / 8;
+        let rest = count & 7;
+        let bytes = bits.take(full_bytes + (rest > 0) as usize);
+        let counts = repeat_n(8, full_bytes).chain(once(rest).filter(|rest| *rest > 0));
         if E::is_le() {
-            bits.zip(counts)
-                .for_each(|(chunk, count)| self.push_bits(chunk as usize, count))
-        } else {
-            bits.rev()
+            bytes
                 .zip(counts)
-                .for_each(|(chunk, count)| self.push_bits(chunk as usize, count))
+                .for_each(|(chunk, count)| self.push_bits(chunk as usize & mask(count), count))
+        } else {
+            // for big endian the partial most significant byte needs to be written first
+            bytes
+                .rev()
+                .zip(counts.rev())
+                .for_each(|(chunk, count)| self.push_bits(chunk as usize & mask(count), count))
         }
     }
 
@@ -98,8 +133,13 @@ where
         debug_assert!(count < USIZE_BITS - 8);
 
         let bit_offset = self.bit_len & 7;
-        let last_written_byte = self.bytes.pop().unwrap_or(0);
-        let merged_byte_count = (count + bit_offset + 7) / 8;
+        // when byte aligned the last byte is already full and can't be merged with
+        let last_written_byte = if bit_offset > 0 {
+            self.bytes.pop().unwrap_or(0)
+        } else {
+            0
+        };
+        let merged_byte_count = (count + bit_offset).div_ceil(8);
 
         if E::is_le() {
             let merged = last_written_byte as usize | bits << bit_offset;
@@ -130,6 +170,7 @@ where
     /// ```
     #[inline]
     pub fn write_bool(&mut self, value: bool) -> Result<()> {
+        self.check_write(1)?;
         self.push_bits(value as usize, 1);
         Ok(())
     }
@@ -161,13 +202,10 @@ where
                 max: type_bit_size,
             });
         }
+        self.check_write(count)?;
 
-        if type_bit_size < USIZE_BITS {
-            if T::is_signed() {
-                todo!()
-            } else {
-                self.push_bits(value.into_usize_unchecked(), count);
-            }
+        if count < USIZE_BITS - 8 {
+            self.push_bits(value.into_usize_unchecked() & mask(count), count);
         } else {
             self.push_non_fit_bits(value.into_bytes(), count)
         }
@@ -194,6 +232,7 @@ where
     where
         T: Float + UncheckedPrimitiveFloat,
     {
+        self.check_write(size_of::<T>() * 8)?;
         if size_of::<T>() == 4 {
             if size_of::<T>() < USIZE_SIZE {
                 self.push_bits(value.to_f32().unwrap().to_bits() as usize, 32);
@@ -207,6 +246,90 @@ where
         Ok(())
     }
 
+    /// Write a float quantized to a `bits` bit integer spread evenly between `min` and `max`
+    ///
+    /// The value is clamped to the range and rounded to the nearest representable value,
+    /// see [`BitReadStream::read_quantized_float`] for details on the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::TooManyBits`]: more than 32 bits requested
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitWriteStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitWriteStream::new(LittleEndian);
+    /// stream.write_quantized_float(90.0, 10, 0.0, 360.0)?;
+    /// assert_eq!(stream.bit_len(), 10);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitReadStream::read_quantized_float`]: struct.BitReadStream.html#method.read_quantized_float
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    #[inline]
+    pub fn write_quantized_float(
+        &mut self,
+        value: f32,
+        bits: usize,
+        min: f32,
+        max: f32,
+    ) -> Result<()> {
+        if bits > 32 {
+            return Err(BitError::TooManyBits {
+                requested: bits,
+                max: 32,
+            });
+        }
+        let steps = ((1u64 << bits) - 1) as f32;
+        // NaN is written as `min`, since casting NaN to an integer results in 0
+        let normalized = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        self.write_int((normalized * steps).round() as u32, bits)
+    }
+
+    /// Write an angle in degrees as a `bits` bit binary angular measurement
+    ///
+    /// The angle is wrapped to the range between `0` and `360` degrees and rounded to the nearest step,
+    /// see [`BitReadStream::read_bit_angle`] for details on the encoding.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::TooManyBits`]: more than 32 bits requested
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitWriteStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitWriteStream::new(LittleEndian);
+    /// stream.write_bit_angle(-90.0, 16)?;
+    /// assert_eq!(stream.bit_len(), 16);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`BitReadStream::read_bit_angle`]: struct.BitReadStream.html#method.read_bit_angle
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    #[inline]
+    pub fn write_bit_angle(&mut self, degrees: f32, bits: usize) -> Result<()> {
+        if bits > 32 {
+            return Err(BitError::TooManyBits {
+                requested: bits,
+                max: 32,
+            });
+        }
+        let steps = (1u64 << bits) as f64;
+        let step = (f64::from(degrees).rem_euclid(360.0) / 360.0 * steps).round() as u64;
+        // a full circle wraps around to 0
+        self.write_int((step % (1u64 << bits)) as u32, bits)
+    }
+
     /// Write a number of bytes into the buffer
     ///
     /// # Examples
@@ -223,10 +346,49 @@ where
     /// ```
     #[inline]
     pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
-        self.push_non_fit_bits(bytes.iter().copied(), bytes.len() * 8);
+        self.check_write(bytes.len().saturating_mul(8))?;
+        if self.bit_len & 7 == 0 {
+            self.bytes.extend_from_slice(bytes);
+            self.bit_len += bytes.len() * 8;
+        } else {
+            // bytes are written in order regardless of endianness
+            bytes
+                .iter()
+                .for_each(|byte| self.push_bits(*byte as usize, 8));
+        }
         Ok(())
     }
 
+    /// Write `count` bits from `buffer` starting at `position`
+    ///
+    /// The caller is responsible for checking that the bits are within the buffer
+    ///
+    /// Panics if the written bits would grow larger than a readable buffer
+    pub(crate) fn write_buffer_bits(
+        &mut self,
+        buffer: &BitReadBuffer<E>,
+        mut position: usize,
+        count: usize,
+    ) {
+        if let Err(e) = self.check_write(count) {
+            panic!("{}", e);
+        }
+        let end = position + count;
+        if self.bit_len & 7 == 0 {
+            let byte_count = count / 8;
+            unsafe { buffer.copy_bytes_to(position, byte_count, &mut self.bytes) }
+                .expect("writing to a Vec can't fail");
+            self.bit_len += byte_count * 8;
+            position += byte_count * 8;
+        }
+        while position < end {
+            let chunk = min(end - position, USIZE_BITS - 16);
+            let bits: usize = unsafe { buffer.read_int_unchecked(position, chunk) };
+            self.push_bits(bits, chunk);
+            position += chunk;
+        }
+    }
+
     /// Add a number of padding bytes
     fn zero_pad(&mut self, count: usize) {
         // since partly written bytes are already 0 padded, we don't need to go trough all the hoop
@@ -252,6 +414,8 @@ where
     /// # }
     /// ```
     pub fn write_string(&mut self, string: &str, length: Option<usize>) -> Result<()> {
+        let byte_len = length.unwrap_or_else(|| string.len().saturating_add(1));
+        self.check_write(byte_len.saturating_mul(8))?;
         match length {
             Some(length) => {
                 if length < string.len() {
@@ -260,17 +424,97 @@ where
                         requested_length: length,
                     });
                 }
-                self.write_bytes(&string.as_bytes())?;
+                self.write_bytes(string.as_bytes())?;
                 self.zero_pad(length - string.len());
             }
             None => {
-                self.write_bytes(&string.as_bytes())?;
+                self.write_bytes(string.as_bytes())?;
                 self.zero_pad(1);
             }
         }
         Ok(())
     }
 
+    /// Remove all bits written after the first `bit_len` bits
+    fn rollback(&mut self, bit_len: usize) {
+        self.bytes.truncate(bit_len.div_ceil(8));
+        let rest = bit_len & 7;
+        if let (Some(last), true) = (self.bytes.last_mut(), rest > 0) {
+            // partly written bytes need to stay 0 padded
+            if E::is_le() {
+                *last &= (1 << rest) - 1;
+            } else {
+                *last &= !(0xff >> rest);
+            }
+        }
+        self.bit_len = bit_len;
+    }
+
+    /// Write a length field of `bits` bits followed by the data written by `body`
+    ///
+    /// The length field is reserved before calling `body` and filled in with the number of bits written by
+    /// `body` afterwards, this matches reading the frame with `read_int(bits)` followed by `read_bits(length)`.
+    ///
+    /// If `body` returns an error or the length doesn't fit in the length field, everything written by this
+    /// call is removed from the stream and the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`ReadError::TooManyBits`]: `bits` is larger than 64
+    /// - [`ReadError::IntegerOutOfRange`]: the number of bits written by `body` doesn't fit in `bits` bits
+    /// - any error returned by `body`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bitbuffer::{BitReadBuffer, BitReadStream, BitWriteStream, LittleEndian, Result};
+    /// #
+    /// # fn main() -> Result<()> {
+    /// let mut stream = BitWriteStream::new(LittleEndian);
+    /// stream.write_length_prefixed(6, |stream| {
+    ///     stream.write_int(3u8, 5)?;
+    ///     stream.write_string("hi", None)
+    /// })?;
+    ///
+    /// let buffer = BitReadBuffer::new(stream.finish(), LittleEndian);
+    /// let mut stream = BitReadStream::new(buffer);
+    /// let length = stream.read_int(6)?;
+    /// assert_eq!(length, 29);
+    /// let mut frame = stream.read_bits(length)?;
+    /// assert_eq!(frame.read_int::<u8>(5)?, 3);
+    /// assert_eq!(frame.read_string(None)?, "hi");
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ReadError::TooManyBits`]: enum.ReadError.html#variant.TooManyBits
+    /// [`ReadError::IntegerOutOfRange`]: enum.ReadError.html#variant.IntegerOutOfRange
+    pub fn write_length_prefixed<F>(&mut self, bits: usize, body: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()>,
+    {
+        let start = self.bit_len;
+        self.write_int(0u64, bits)?;
+        if let Err(e) = body(self) {
+            self.rollback(start);
+            return Err(e);
+        }
+        let length = (self.bit_len - start - bits) as u64;
+        if bits < 64 && length >> bits != 0 {
+            self.rollback(start);
+            return Err(BitError::IntegerOutOfRange {
+                value: length as i128,
+                bits,
+            });
+        }
+
+        let mut buffer = BitWriteBuffer::<E>::from_stream_bytes(std::mem::take(&mut self.bytes));
+        let result = buffer.patch_int(start, bits, length);
+        self.bytes = buffer.into_bytes();
+        result
+    }
+
     /// Convert the write buffer into the written bytes
     pub fn finish(self) -> Vec<u8> {
         self.bytes
@@ -285,15 +529,15 @@ where
     /// #
     /// # fn main() -> Result<()> {
     /// # let mut stream = BitWriteStream::new(LittleEndian);
-    /// stream.write(&53)?;
-    /// stream.write("fixed size text")?;
+    /// stream.write(&53u8)?;
+    /// stream.write(&String::from("null terminated text"))?;
     /// #
     /// #     Ok(())
     /// # }
     /// ```
     ///
     /// ```
-    /// # use bitbuffer::{BitWriteBuffer, BitWriteStream, LittleEndian, Result};
+    /// # use bitbuffer::{BitWriteStream, LittleEndian, Result};
     /// use bitbuffer::BitWrite;
     /// #
     /// #[derive(BitWrite, Debug, PartialEq)]
@@ -331,8 +575,8 @@ where
     /// #
     /// # fn main() -> Result<()> {
     /// # let mut stream = BitWriteStream::new(LittleEndian);
-    /// stream.write_sized(&53, 12)?;
-    /// stream.write_sized("text", 8)?;
+    /// stream.write_sized(&53u16, 12)?;
+    /// stream.write_sized(&String::from("text"), 8)?;
     /// #
     /// #     Ok(())
     /// # }
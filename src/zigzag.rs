@@ -0,0 +1,91 @@
+use crate::{
+    BitError, BitRead, BitReadSized, BitReadStream, BitWrite, BitWriteSized, BitWriteStream,
+    Endianness, Result,
+};
+use num_traits::{PrimInt, Signed};
+use std::mem::size_of;
+
+/// A signed integer stored using zigzag encoding
+///
+/// Zigzag encoding maps signed integers to unsigned integers so that numbers with a small absolute
+/// value use a small number of bits, `0, -1, 1, -2, 2` are encoded as `0, 1, 2, 3, 4`.
+///
+/// Without size, the full width of the integer type is used, with size, the value is stored in `size` bits.
+///
+/// # Examples
+///
+/// ```
+/// # use bitbuffer::{BitReadBuffer, BitReadStream, LittleEndian, Result, ZigZag};
+/// #
+/// # fn main() -> Result<()> {
+/// let buffer = BitReadBuffer::new(vec![0b0100_0101], LittleEndian);
+/// let mut stream = BitReadStream::new(buffer);
+/// let first: ZigZag<i32> = stream.read_sized(4)?;
+/// let second: ZigZag<i32> = stream.read_sized(4)?;
+/// assert_eq!(first.0, -3);
+/// assert_eq!(second.0, 2);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ZigZag<T>(pub T);
+
+/// Decode a zigzag encoded value of `count` bits into `T`
+pub(crate) fn decode<T: PrimInt + Signed>(value: u128, count: usize) -> Result<T> {
+    let decoded = (value >> 1) as i128 ^ -((value & 1) as i128);
+    T::from(decoded).ok_or(BitError::TooManyBits {
+        requested: count,
+        max: size_of::<T>() * 8,
+    })
+}
+
+/// Zigzag encode the value
+fn encode<T: PrimInt + Signed>(value: T) -> u128 {
+    let value = value.to_i128().unwrap();
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+impl<E: Endianness, T: PrimInt + Signed> BitRead<E> for ZigZag<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+        stream.read_zigzag(size_of::<T>() * 8).map(ZigZag)
+    }
+
+    #[inline]
+    fn bit_size() -> Option<usize> {
+        Some(size_of::<T>() * 8)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Signed> BitReadSized<E> for ZigZag<T> {
+    #[inline]
+    fn read(stream: &mut BitReadStream<E>, size: usize) -> Result<Self> {
+        stream.read_zigzag(size).map(ZigZag)
+    }
+
+    #[inline]
+    fn bit_size_sized(size: usize) -> Option<usize> {
+        Some(size)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Signed> BitWrite<E> for ZigZag<T> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+        stream.write_int(encode(self.0), size_of::<T>() * 8)
+    }
+}
+
+impl<E: Endianness, T: PrimInt + Signed> BitWriteSized<E> for ZigZag<T> {
+    #[inline]
+    fn write(&self, stream: &mut BitWriteStream<E>, size: usize) -> Result<()> {
+        if size > size_of::<T>() * 8 {
+            return Err(BitError::TooManyBits {
+                requested: size,
+                max: size_of::<T>() * 8,
+            });
+        }
+        stream.write_int(encode(self.0), size)
+    }
+}
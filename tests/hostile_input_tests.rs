@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use bitbuffer::{BigEndian, BitError, BitRead, BitReadBuffer, BitReadStream, LittleEndian};
+
+fn stream(bytes: &[u8]) -> BitReadStream<LittleEndian> {
+    BitReadStream::new(BitReadBuffer::new(bytes.to_vec(), LittleEndian))
+}
+
+#[test]
+fn huge_vec_length() {
+    let mut stream = stream(&[1, 2, 3, 4]);
+    assert!(matches!(
+        stream.read_sized::<Vec<u32>>(usize::MAX),
+        Err(BitError::NotEnoughData {
+            requested: usize::MAX,
+            bits_left: 32
+        })
+    ));
+    assert_eq!(stream.pos(), 0);
+    assert!(stream.read_sized::<Vec<u32>>(2).is_err());
+    assert_eq!(stream.pos(), 0);
+    assert_eq!(stream.read_sized::<Vec<u32>>(1).unwrap(), vec![0x04030201]);
+}
+
+#[test]
+fn huge_vec_length_dynamic_elements() {
+    let mut stream = stream(&[1, 2, 3, 4]);
+    assert!(stream.read_sized::<Vec<String>>(5).is_err());
+    assert_eq!(stream.pos(), 0);
+    assert!(stream.read_sized::<Vec<Option<u8>>>(33).is_err());
+    assert_eq!(stream.pos(), 0);
+    assert!(stream.read_sized::<Vec<(bool, String)>>(4).is_err());
+    assert_eq!(stream.pos(), 0);
+    assert!(stream
+        .read_sized::<HashMap<u8, String>>(usize::MAX)
+        .is_err());
+    assert_eq!(stream.pos(), 0);
+}
+
+#[test]
+fn huge_byte_length() {
+    let mut stream = stream(&[1, 2, 3, 4]);
+    assert!(stream.read_bytes(usize::MAX).is_err());
+    assert!(stream.read_bytes(usize::MAX / 8 + 1).is_err());
+    assert!(stream.read_string(Some(usize::MAX)).is_err());
+    assert!(stream.read_sized::<String>(usize::MAX / 4).is_err());
+    assert!(stream.read_bits(usize::MAX).is_err());
+    assert_eq!(stream.pos(), 0);
+    stream.skip_bits(3).unwrap();
+    assert!(stream.read_bits(usize::MAX).is_err());
+    assert!(stream.read_bytes(usize::MAX).is_err());
+    assert_eq!(stream.pos(), 3);
+}
+
+#[derive(BitRead, Debug, PartialEq)]
+struct LengthPrefixed {
+    length: u32,
+    #[size = "length"]
+    items: Vec<u16>,
+}
+
+#[test]
+fn hostile_length_prefix() {
+    let bytes = vec![0xff, 0xff, 0xff, 0xff, 1, 2];
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, BigEndian));
+    assert!(matches!(
+        stream.read::<LengthPrefixed>(),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    let bytes = vec![0, 0, 0, 1, 1, 2];
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, BigEndian));
+    assert_eq!(
+        LengthPrefixed {
+            length: 1,
+            items: vec![0x0102]
+        },
+        stream.read().unwrap()
+    );
+}
+
+#[test]
+fn huge_positions_and_counts() {
+    use bitbuffer::{HammingCode, ManchesterEncoding, NrziEncoding, Parity};
+
+    let buffer = BitReadBuffer::new(vec![1, 2, 3, 4], LittleEndian);
+    for pos in [usize::MAX, usize::MAX - 7, usize::MAX / 8 + 1] {
+        assert!(buffer.read_bool(pos).is_err());
+        assert!(buffer.read_int::<u8>(pos, 8).is_err());
+        assert!(buffer.read_float::<f64>(pos).is_err());
+        assert!(buffer.read_bytes(pos, 1).is_err());
+        assert!(buffer.read_bytes(0, pos).is_err());
+        assert!(buffer.read_string(pos, Some(1)).is_err());
+        assert!(buffer.read_string(0, Some(pos)).is_err());
+        assert!(buffer.sub_buffer(pos..pos).is_err());
+        assert!(buffer.sub_buffer(8..pos).is_err());
+    }
+
+    for count in [
+        usize::MAX,
+        usize::MAX - 7,
+        usize::MAX / 2 + 1,
+        usize::MAX / 8 + 1,
+    ] {
+        let mut stream = stream(&[1, 2, 3, 4]);
+        stream.skip_bits(3).unwrap();
+        assert!(stream.set_pos(count).is_err());
+        assert!(stream.skip_bits(count).is_err());
+        assert!(stream.read_bits(count).is_err());
+        assert!(stream.read_bits_aligned(count).is_err());
+        assert!(stream.read_bytes(count).is_err());
+        assert!(stream.read_string(Some(count)).is_err());
+        assert!(stream.read_unstuffed(count).is_err());
+        assert!(stream
+            .read_manchester(count, ManchesterEncoding::Ieee)
+            .is_err());
+        assert!(stream.read_nrzi(count, NrziEncoding::Mark, false).is_err());
+        assert!(stream.read_interleaved(count, 2).is_err());
+        assert!(stream.read_deinterleaved(2, count).is_err());
+        assert!(stream.read_parity(count, Parity::Even).is_err());
+        assert!(stream.read_with_parity::<u8>(count, Parity::Even).is_err());
+        assert!(stream.read_hamming(count, HammingCode::Hamming74).is_err());
+        assert!(stream.read_rice::<u32>(count).is_err());
+        assert!(stream.read_golomb::<u32>(count).is_err());
+        assert!(stream.set_read_marker(count).is_err());
+        assert!(stream.truncate(count).is_err());
+        assert!(stream.take(count).is_err());
+        assert!(stream.align_to(count).is_err());
+        assert!(stream.resync_to(0, count).is_err());
+        assert!(stream.copy_to(&mut Vec::new(), count).is_err());
+        assert_eq!(stream.pos(), 3);
+        assert_eq!(stream.read_int::<u8>(5).unwrap(), 0);
+    }
+}
+
+#[test]
+fn huge_positions_exact_errors() {
+    use bitbuffer::BitWriteBuffer;
+
+    let buffer = BitReadBuffer::new(vec![1, 2, 3, 4], LittleEndian);
+    assert!(matches!(
+        buffer.read_int::<u8>(usize::MAX, 8),
+        Err(BitError::IndexOutOfBounds {
+            pos: usize::MAX,
+            size: 32
+        })
+    ));
+    assert!(matches!(
+        buffer.read_bytes(8, usize::MAX / 8 + 1),
+        Err(BitError::NotEnoughData {
+            requested: usize::MAX,
+            bits_left: 24
+        })
+    ));
+    assert!(matches!(
+        buffer.read_float::<f32>(usize::MAX - 31),
+        Err(BitError::IndexOutOfBounds { .. })
+    ));
+
+    let mut stream = stream(&[1, 2, 3, 4]);
+    stream.skip_bits(3).unwrap();
+    assert!(matches!(
+        stream.check_read(usize::MAX - 2),
+        Err(BitError::NotEnoughData {
+            requested,
+            bits_left: 29
+        }) if requested == usize::MAX - 2
+    ));
+    assert!(matches!(
+        stream.skip_bits(usize::MAX),
+        Err(BitError::NotEnoughData {
+            requested: usize::MAX,
+            bits_left: 29
+        })
+    ));
+    assert_eq!(stream.pos(), 3);
+
+    let mut patch = BitWriteBuffer::new(vec![0; 4], LittleEndian);
+    assert!(matches!(
+        patch.patch_int(usize::MAX, 8, 1u8),
+        Err(BitError::IndexOutOfBounds {
+            pos: usize::MAX,
+            size: 32
+        })
+    ));
+    assert!(matches!(
+        patch.patch_bytes(8, &[1; 4]),
+        Err(BitError::NotEnoughData {
+            requested: 32,
+            bits_left: 24
+        })
+    ));
+    assert!(matches!(
+        patch.patch_int(usize::MAX - 3, 8, 1u8),
+        Err(BitError::IndexOutOfBounds { .. })
+    ));
+    assert_eq!(patch.into_bytes(), vec![0; 4]);
+}
@@ -0,0 +1,179 @@
+#![allow(dead_code)]
+
+//! Reading arbitrary input should never panic, see the "Untrusted input" section of the crate docs
+//!
+//! These mirror the fuzz targets in the `fuzz` directory using a fixed set of pseudo random inputs
+
+use std::collections::HashMap;
+
+use bitbuffer::{
+    BigEndian, BitRead, BitReadBuffer, BitReadSized, BitReadStream, Endianness, HammingCode,
+    LittleEndian, ManchesterEncoding, NrziEncoding, Parity,
+};
+
+/// xorshift, so the inputs are the same for every run
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.next() as usize % max_len;
+        let mode = self.next() % 3;
+        (0..len)
+            .map(|_| match mode {
+                // mostly zeros or ones to trigger the more interesting edge cases
+                0 => self.next().is_multiple_of(7) as u8,
+                1 => !(self.next().is_multiple_of(7) as u8),
+                _ => self.next() as u8,
+            })
+            .collect()
+    }
+}
+
+fn run_ops<E: Endianness>(ops: &[u8], bytes: &[u8], endianness: E) {
+    let buffer = BitReadBuffer::new(bytes.to_vec(), endianness);
+    let buffer = match ops.first() {
+        Some(1) => buffer.reverse_bits(),
+        Some(2) => buffer.reverse_bytes(),
+        _ => buffer,
+    };
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_max_depth(64);
+
+    for op in ops.chunks_exact(2) {
+        let param = op[1] as usize;
+        let _ = match op[0] % 20 {
+            0 => stream.read_bool().map(drop),
+            1 => stream.read_int::<u64>(param % 65).map(drop),
+            2 => stream.read_int::<i32>(param % 33).map(drop),
+            3 => stream.read_float::<f32>().map(drop),
+            4 => stream.read_float::<f64>().map(drop),
+            5 => stream.read_bytes(param).map(drop),
+            6 => stream.read_string(None).map(drop),
+            7 => stream.read_string(Some(param)).map(drop),
+            8 => stream
+                .read_bits(param)
+                .and_then(|mut bits| bits.read::<Option<String>>())
+                .map(drop),
+            9 => stream.skip_bits(param),
+            10 => stream.set_pos(param),
+            11 => stream.read_unstuffed(param).map(drop),
+            12 => stream
+                .read_manchester(param, ManchesterEncoding::Ieee)
+                .map(drop),
+            13 => stream
+                .read_nrzi(param, NrziEncoding::Space, param & 1 == 1)
+                .map(drop),
+            14 => stream.read_interleaved(param & 7, param >> 3).map(drop),
+            15 => stream
+                .read_hamming(param & 3, HammingCode::Secded)
+                .map(drop),
+            16 => stream.read_parity(param, Parity::Even).map(drop),
+            17 => stream.read_sized::<Vec<u16>>(param).map(drop),
+            18 => stream.read_sized::<HashMap<u8, String>>(param).map(drop),
+            _ => stream.read_sized::<String>(param).map(drop),
+        };
+    }
+}
+
+#[test]
+fn stream_reads_dont_panic() {
+    let mut random = Random(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let ops = random.bytes(64);
+        let bytes = random.bytes(64);
+        run_ops(&ops, &bytes, LittleEndian);
+        run_ops(&ops, &bytes, BigEndian);
+    }
+}
+
+#[test]
+fn buffer_reads_dont_panic() {
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..2000 {
+        let buffer = BitReadBuffer::new(random.bytes(32), LittleEndian);
+        let position = match random.next() % 3 {
+            0 => random.next() as usize,
+            _ => random.next() as usize % 300,
+        };
+        let count = random.next() as usize % 130;
+        let _ = buffer.read_bool(position);
+        let _ = buffer.read_int::<u128>(position, count);
+        let _ = buffer.read_int::<i8>(position, count);
+        let _ = buffer.read_float::<f64>(position);
+        let _ = buffer.read_bytes(position, count);
+        let _ = buffer.read_string(position, None);
+        let _ = buffer.read_string(position, Some(count));
+    }
+}
+
+#[derive(BitRead, Debug)]
+struct Header {
+    version: u8,
+    #[size = 3]
+    kind: u8,
+    flag: bool,
+    length: u16,
+    #[size = "length"]
+    name: String,
+    values: Option<Box<Header>>,
+}
+
+#[derive(BitRead, Debug)]
+#[discriminant_bits = 2]
+enum Packet {
+    Empty,
+    Header(Header),
+    #[size = 12]
+    Short(i16),
+    Text(String),
+}
+
+#[derive(BitReadSized, Debug)]
+struct Sized {
+    #[size = "input_size"]
+    items: Vec<u16>,
+    tail: f64,
+}
+
+#[test]
+fn derived_reads_dont_panic() {
+    let mut random = Random(0xdead_beef_cafe_f00d);
+    for _ in 0..2000 {
+        let data = random.bytes(128);
+        let mut stream = BitReadStream::new(BitReadBuffer::new(data.clone(), LittleEndian));
+        stream.set_max_depth(64);
+        while stream.read::<Packet>().is_ok() {}
+
+        let mut stream = BitReadStream::new(BitReadBuffer::new(data, BigEndian));
+        stream.set_max_depth(64);
+        let _ = stream.read_sized::<Sized>(random.next() as usize);
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_buffers_dont_panic() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut random = Random(0x0123_4567_89ab_cdef);
+    for _ in 0..2000 {
+        let data = random.bytes(128);
+        let mut input = Unstructured::new(&data);
+        let buffer = BitReadBuffer::<LittleEndian>::arbitrary(&mut input).unwrap();
+        assert!(buffer.bit_len() <= data.len() * 8);
+        let mut stream = BitReadStream::new(buffer);
+        stream.set_max_depth(64);
+        while stream.read::<Packet>().is_ok() {}
+
+        let buffer = BitReadBuffer::<BigEndian>::arbitrary_take_rest(input).unwrap();
+        let mut stream = BitReadStream::new(buffer);
+        let _ = stream.read_sized::<Sized>(random.next() as usize % 64);
+    }
+}
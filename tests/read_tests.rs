@@ -1,11 +1,23 @@
-use std::collections::HashMap;
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::char_lit_as_u8,
+    clippy::disallowed_names,
+    clippy::unusual_byte_groupings
+)]
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU16;
 
 use maplit::hashmap;
 
-use bitbuffer::{BigEndian, BitRead, BitReadBuffer, BitReadStream, LittleEndian};
+use bitbuffer::{
+    num_traits, read_all, BigEndian, BitBufferBuilder, BitError, BitRead, BitReadBuffer,
+    BitReadStream, BitReadStreamRev, BitWriteStream, DualReadStream, Endianness, FecStatus,
+    HammingCode, HuffmanTable, Int, LittleEndian, ManchesterEncoding, NrziEncoding, Parity,
+    StringMode, UInt,
+};
+use std::convert::TryFrom;
 
-const BYTES: &'static [u8] = &[
+const BYTES: &[u8] = &[
     0b1011_0101,
     0b0110_1010,
     0b1010_1100,
@@ -193,6 +205,111 @@ fn read_i64_be() {
     assert_eq!(buffer.read_int::<i64>(7, 64).unwrap(), -5380028262354455604);
 }
 
+#[test]
+fn read_u128_le() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+
+    assert_eq!(
+        buffer.read_int::<u128>(0, 96).unwrap(),
+        0xe7999999_e7999999_99ac6ab5
+    );
+    assert_eq!(
+        buffer.read_int::<u128>(5, 91).unwrap(),
+        0x73ccccc_cf3ccccc_cccd6355
+    );
+    assert_eq!(
+        buffer.read_int::<u128>(3, 65).unwrap(),
+        0x1_3cf33333_33358d56
+    );
+    assert!(buffer.read_int::<u128>(1, 96).is_err());
+}
+
+#[test]
+fn read_u128_be() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+
+    assert_eq!(
+        buffer.read_int::<u128>(0, 96).unwrap(),
+        0xb56aac99_999999e7_999999e7
+    );
+    assert_eq!(
+        buffer.read_int::<u128>(5, 91).unwrap(),
+        0x56aac99_999999e7_999999e7
+    );
+    assert_eq!(
+        buffer.read_int::<u128>(3, 65).unwrap(),
+        0x1_56aac999_99999e79
+    );
+    assert!(buffer.read_int::<u128>(1, 96).is_err());
+}
+
+#[test]
+fn read_i128_le() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+
+    assert_eq!(
+        buffer.read_int::<i128>(0, 96).unwrap(),
+        -7551434234020327341925045579
+    );
+    assert_eq!(
+        buffer.read_int::<i128>(7, 80).unwrap(),
+        241785207833022201813205
+    );
+}
+
+#[test]
+fn read_i128_be() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+
+    assert_eq!(
+        buffer.read_int::<i128>(0, 96).unwrap(),
+        -23082414519266785826068391449
+    );
+    assert_eq!(
+        buffer.read_int::<i128>(7, 80).unwrap(),
+        -352585532201661602411316
+    );
+}
+
+/// Read bit by bit, to compare the optimized reads against
+fn read_reference<E: Endianness>(buffer: &BitReadBuffer<E>, position: usize, count: usize) -> u128 {
+    (position..position + count).fold(0, |acc, pos| {
+        let bit = buffer.read_bool(pos).unwrap() as u128;
+        if E::is_le() {
+            acc | bit << (pos - position)
+        } else {
+            acc << 1 | bit
+        }
+    })
+}
+
+fn read_u128_all_offsets<E: Endianness>(endianness: E) {
+    let bytes: Vec<u8> = (0..40u8).map(|i| i.wrapping_mul(0x9d) ^ 0x5a).collect();
+    let buffer = BitReadBuffer::new(bytes, endianness);
+    for count in 57..=128 {
+        // include the reads ending at the very end of the buffer
+        for position in 0..=(buffer.bit_len() - count) {
+            assert_eq!(
+                read_reference(&buffer, position, count),
+                buffer.read_int::<u128>(position, count).unwrap(),
+                "reading {} bits at {}",
+                count,
+                position
+            );
+        }
+    }
+}
+
+#[test]
+fn read_u128_all_offsets_le() {
+    read_u128_all_offsets(LittleEndian);
+}
+
+#[test]
+fn read_u128_all_offsets_be() {
+    read_u128_all_offsets(BigEndian);
+}
+
 #[test]
 fn read_f32_le() {
     let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
@@ -234,6 +351,24 @@ fn test_read_str_be() {
     );
 }
 
+#[test]
+fn test_read_str_unaligned_be() {
+    // "Hello world\0" prefixed with 3 zero bits
+    let bytes = vec![
+        0x09, 0x0c, 0xad, 0x8d, 0x8d, 0xe4, 0x0e, 0xed, 0xee, 0x4d, 0x8c, 0x80, 0, 0,
+    ];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    assert_eq!(buffer.read_bytes(3, 5).unwrap(), b"Hello");
+    assert_eq!(
+        buffer.read_string(3, Some(11)).unwrap(),
+        "Hello world".to_owned()
+    );
+    assert_eq!(
+        buffer.read_string(3, None).unwrap(),
+        "Hello world".to_owned()
+    );
+}
+
 #[test]
 fn test_read_str_no_null_termination_le() {
     let bytes = vec![
@@ -276,6 +411,84 @@ fn test_read_str_le() {
     );
 }
 
+#[test]
+fn test_read_into_output() {
+    // "foo\0" followed by "hello world\0", prefixed with 3 zero bits
+    let bytes = vec![
+        0x09, 0x0c, 0xad, 0x8d, 0x8d, 0xe4, 0x0e, 0xed, 0xee, 0x4d, 0x8c, 0x80, 0, 0,
+    ];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.skip_bits(3).unwrap();
+    let mut output = b"prefix ".to_vec();
+    stream.read_bytes_into(5, &mut output).unwrap();
+    assert_eq!(output, b"prefix Hello");
+    stream.read_string_into(None, &mut output).unwrap();
+    assert_eq!(output, b"prefix Hello world");
+    assert_eq!(3 + 12 * 8, stream.pos());
+
+    stream.set_pos(3).unwrap();
+    output.clear();
+    stream.read_string_into(Some(13), &mut output).unwrap();
+    assert_eq!(output, b"Hello world");
+    assert_eq!(3 + 13 * 8, stream.pos());
+
+    // string trimmed to the end of the sub stream
+    stream.set_pos(3).unwrap();
+    let mut sub_stream = stream.read_bits(5 * 8).unwrap();
+    output.clear();
+    sub_stream.read_string_into(None, &mut output).unwrap();
+    assert_eq!(output, b"Hello");
+    assert_eq!(0, sub_stream.bits_left());
+
+    let buffer = BitReadBuffer::new(vec![b'a', 0xff, b'b', 0, 0], LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    output.clear();
+    assert!(matches!(
+        stream.read_string_into(None, &mut output),
+        Err(BitError::Utf8Error(_))
+    ));
+    assert_eq!(output, &[b'a', 0xff, b'b']);
+    assert_eq!(4 * 8, stream.pos());
+}
+
+#[cfg(feature = "arena")]
+#[test]
+fn test_read_into_arena() {
+    let bump = bumpalo::Bump::new();
+    let bytes = vec![b'f', b'o', b'o', 0, 1, 2, 3];
+    let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let string = stream.read_string_in(None, &bump).unwrap();
+    let bytes = stream.read_bytes_in(3, &bump).unwrap();
+    assert_eq!("foo", string.as_str());
+    assert_eq!(&[1, 2, 3], bytes.as_slice());
+}
+
+#[cfg(feature = "arena")]
+#[test]
+fn test_read_vec_in_arena() {
+    let mut bump = bumpalo::Bump::new();
+    let bytes = vec![1, 0, 2, 0, b'a', b'b', b'c', 0, 3];
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+    {
+        let values = stream.read_vec_in::<u16>(2, &bump).unwrap();
+        assert_eq!(&[1, 2], values.as_slice());
+        let strings = stream.read_sized_vec_in::<String>(2, 2, &bump).unwrap();
+        assert_eq!(&["ab", "c"], strings.as_slice());
+    }
+    bump.reset();
+    assert!(matches!(
+        stream.read_vec_in::<u16>(1, &bump),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert!(matches!(
+        stream.read_sized_vec_in::<u8>(2, 8, &bump),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(3u8, stream.read::<u8>().unwrap());
+}
+
 #[test]
 fn read_trait() {
     let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
@@ -343,7 +556,7 @@ fn read_sized_trait() {
     );
     stream.set_pos(0).unwrap();
     let mut result: BitReadStream<BigEndian> = stream.read_sized(4).unwrap();
-    assert_eq!(0b10u8, result.read_int(2).unwrap());
+    assert_eq!(0b10u8, result.read_int::<u8>(2).unwrap());
 }
 
 #[test]
@@ -374,7 +587,7 @@ fn read_sized_trait_unchecked() {
         );
         stream.set_pos(0).unwrap();
         let mut result: BitReadStream<BigEndian> = stream.read_sized_unchecked(4).unwrap();
-        assert_eq!(0b10u8, result.read_int(2).unwrap());
+        assert_eq!(0b10u8, result.read_int::<u8>(2).unwrap());
     }
 }
 
@@ -454,3 +667,3412 @@ fn read_read_signed() {
     let buffer = BitReadBuffer::new(byte_vec, LittleEndian);
     assert_eq!(buffer.read_int::<i32>(0, 32).unwrap(), -10);
 }
+
+#[test]
+fn read_unstuffed() {
+    // a stuffed 0 follows every run of five 1's
+    let bytes = vec![0b0111_1100, 0b1111_1001, 0b0000_0000];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut bits = stream.read_unstuffed(16).unwrap();
+    assert_eq!(18, stream.pos());
+    assert_eq!(16, bits.bit_len());
+    assert_eq!(0b0111_1101_1111_0100, bits.read_int::<u16>(16).unwrap());
+
+    let buffer = BitReadBuffer::new(vec![0b1111_1110], LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!(
+        0b1110,
+        stream.read_unstuffed(4).unwrap().read_int::<u8>(4).unwrap()
+    );
+
+    // the stuffed 0 after the last bit of a chunk is consumed with the chunk
+    let bytes = vec![0b1111_1010];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer.clone());
+    let mut bits = stream.read_unstuffed(7).unwrap();
+    assert_eq!(0b111_1110, bits.read_int::<u8>(7).unwrap());
+    let mut stream = BitReadStream::new(buffer);
+    let mut first = stream.read_unstuffed(5).unwrap();
+    assert_eq!(6, stream.pos());
+    let mut second = stream.read_unstuffed(2).unwrap();
+    assert_eq!(0b1_1111, first.read_int::<u8>(5).unwrap());
+    assert_eq!(0b10, second.read_int::<u8>(2).unwrap());
+
+    // flag sequence
+    let buffer = BitReadBuffer::new(vec![0b0111_1110], BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert!(matches!(
+        stream.read_unstuffed(8),
+        Err(BitError::InvalidBitStuffing { pos: 6 })
+    ));
+    assert_eq!(0, stream.pos());
+
+    let buffer = BitReadBuffer::new(vec![0b0111_1100], BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert!(matches!(
+        stream.read_unstuffed(8),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(0, stream.pos());
+}
+
+#[test]
+fn read_manchester() {
+    let bytes = vec![0b1001_0110, 0b0101_1010];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut bits = stream.read_manchester(8, ManchesterEncoding::Ieee).unwrap();
+    assert_eq!(16, stream.pos());
+    assert_eq!(8, bits.bit_len());
+    assert_eq!(0b0110_1100, bits.read_int::<u8>(8).unwrap());
+
+    stream.set_pos(0).unwrap();
+    let mut bits = stream
+        .read_manchester(8, ManchesterEncoding::Thomas)
+        .unwrap();
+    assert_eq!(0b1001_0011, bits.read_int::<u8>(8).unwrap());
+
+    let buffer = BitReadBuffer::new(vec![0b1011_0000], BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert!(matches!(
+        stream.read_manchester(4, ManchesterEncoding::Ieee),
+        Err(BitError::InvalidLineCode { pos: 2 })
+    ));
+    assert_eq!(0, stream.pos());
+    assert!(matches!(
+        stream.read_manchester(5, ManchesterEncoding::Ieee),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn read_nrzi() {
+    let bytes = vec![0b1101_0011];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut bits = stream.read_nrzi(8, NrziEncoding::Space, true).unwrap();
+    assert_eq!(8, stream.pos());
+    assert_eq!(0b1100_0101, bits.read_int::<u8>(8).unwrap());
+
+    stream.set_pos(0).unwrap();
+    let mut bits = stream.read_nrzi(8, NrziEncoding::Mark, true).unwrap();
+    assert_eq!(0b0011_1010, bits.read_int::<u8>(8).unwrap());
+}
+
+#[test]
+fn read_nrzi_unstuffed() {
+    // NRZ-S encoded HDLC data with two stuffed bits
+    let bytes = vec![0b0000_0111, 0b1110_0000];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut line = stream.read_nrzi(16, NrziEncoding::Space, false).unwrap();
+    let mut bits = line.read_unstuffed(14).unwrap();
+    assert_eq!(16, line.pos());
+    assert_eq!(0b11_1111_1111_1111, bits.read_int::<u16>(14).unwrap());
+}
+
+fn read_huffman<E: Endianness>(write_endianness: E, read_endianness: E) {
+    // symbol n has a code of n ones followed by a zero, the last symbol is all ones
+    let table = HuffmanTable::canonical((1..=12).chain(Some(12)).zip(0u8..)).unwrap();
+    assert_eq!(13, table.len());
+    assert_eq!(12, table.max_length());
+
+    let symbols = [3, 0, 11, 12, 1, 10, 0];
+    let mut stream = BitWriteStream::new(write_endianness);
+    for &symbol in symbols.iter() {
+        for _ in 0..symbol {
+            stream.write_bool(true).unwrap();
+        }
+        if symbol < 12 {
+            stream.write_bool(false).unwrap();
+        }
+    }
+    stream.write_bool(true).unwrap();
+    let bit_len = stream.bit_len();
+
+    let buffer = BitReadBuffer::new(stream.finish(), read_endianness);
+    let mut stream = BitReadStream::new(buffer).read_bits(bit_len).unwrap();
+    for &symbol in symbols.iter() {
+        assert_eq!(symbol, stream.read_huffman(&table).unwrap());
+    }
+    assert_eq!(bit_len - 1, stream.pos());
+    assert!(matches!(
+        stream.read_huffman(&table),
+        Err(BitError::NotEnoughData {
+            requested: 12,
+            bits_left: 1
+        })
+    ));
+    assert_eq!(bit_len - 1, stream.pos());
+
+    // incomplete table, 0b11 doesn't match any code
+    let table = HuffmanTable::new(vec![(0b0, 1, 'a'), (0b10, 2, 'b')]).unwrap();
+    stream.set_pos(0).unwrap();
+    assert!(matches!(
+        stream.read_huffman(&table),
+        Err(BitError::InvalidHuffmanCode { pos: 0 })
+    ));
+    assert_eq!(0, stream.pos());
+}
+
+#[test]
+fn test_read_huffman_le() {
+    read_huffman(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_huffman_be() {
+    read_huffman(BigEndian, BigEndian);
+}
+
+#[test]
+fn huffman_table_invalid() {
+    assert!(matches!(
+        HuffmanTable::new(vec![(0b10, 2, 'a'), (0b1, 1, 'b')]),
+        Err(BitError::InvalidHuffmanTable {
+            code: 0b10,
+            length: 2
+        })
+    ));
+    assert!(matches!(
+        HuffmanTable::new(vec![(0b0, 1, 'a'), (0b0, 1, 'b')]),
+        Err(BitError::InvalidHuffmanTable { code: 0, length: 1 })
+    ));
+    assert!(matches!(
+        HuffmanTable::new(vec![(0b100, 2, 'a')]),
+        Err(BitError::InvalidHuffmanTable {
+            code: 0b100,
+            length: 2
+        })
+    ));
+    assert!(matches!(
+        HuffmanTable::canonical(vec![(1, 'a'), (1, 'b'), (1, 'c')]),
+        Err(BitError::InvalidHuffmanTable {
+            code: 0b10,
+            length: 1
+        })
+    ));
+}
+
+#[test]
+fn read_range_coded() {
+    use bitbuffer::range_coder::{Probability, RangeDecoder};
+
+    let bytes = vec![
+        0x00, 0x92, 0x34, 0x16, 0x0f, 0x87, 0x6b, 0x59, 0x35, 0x0a, 0x31, 0xe2, 0x17, 0x0c, 0xe7,
+        0x94, 0x74, 0x80, 0xff,
+    ];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut decoder = RangeDecoder::new(&mut stream).unwrap();
+
+    let mut flags = [Probability::default(); 2];
+    for i in 0..64 {
+        assert_eq!(i % 3 == 0, decoder.decode_bit(&mut flags[i % 2]).unwrap());
+    }
+    let mut tree = [Probability::default(); 16];
+    for &value in [3, 15, 0, 9].iter() {
+        assert_eq!(value, decoder.decode_tree(&mut tree, 4).unwrap());
+    }
+    let mut reverse = [Probability::default(); 8];
+    for &value in [5, 1, 6].iter() {
+        assert_eq!(value, decoder.decode_tree_reverse(&mut reverse, 3).unwrap());
+    }
+    assert_eq!(0xabcde, decoder.decode_direct(20).unwrap());
+    assert!(decoder.is_finished());
+
+    assert_eq!(18 * 8, stream.pos());
+    assert_eq!(0xff, stream.read_int::<u8>(8).unwrap());
+}
+
+#[test]
+fn test_const_read() {
+    use bitbuffer::const_read::{
+        read_bool_be, read_bool_le, read_int_be, read_int_le, read_signed_be, read_signed_le,
+    };
+
+    const LE: (bool, u64, i64) = (
+        read_bool_le(BYTES, 0),
+        read_int_le(BYTES, 3, 13),
+        read_signed_le(BYTES, 1, 7),
+    );
+    assert_eq!((true, 0b0_1101_0101_0110, -38), LE);
+
+    let le = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+    let be = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+    for pos in 0..le.bit_len() {
+        assert_eq!(le.read_bool(pos).unwrap(), read_bool_le(BYTES, pos));
+        assert_eq!(be.read_bool(pos).unwrap(), read_bool_be(BYTES, pos));
+        for count in 0..=64.min(le.bit_len() - pos) {
+            assert_eq!(
+                le.read_int::<u64>(pos, count).unwrap(),
+                read_int_le(BYTES, pos, count)
+            );
+            assert_eq!(
+                be.read_int::<u64>(pos, count).unwrap(),
+                read_int_be(BYTES, pos, count)
+            );
+            assert_eq!(
+                le.read_int::<i64>(pos, count).unwrap(),
+                read_signed_le(BYTES, pos, count)
+            );
+            assert_eq!(
+                be.read_int::<i64>(pos, count).unwrap(),
+                read_signed_be(BYTES, pos, count)
+            );
+        }
+    }
+}
+
+#[test]
+fn read_interleaved() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut interleaved = stream.read_interleaved(3, 7).unwrap();
+    assert_eq!(21, stream.pos());
+    assert_eq!(21, interleaved.bit_len());
+    let mut restored = interleaved.read_deinterleaved(3, 7).unwrap();
+    stream.set_pos(0).unwrap();
+    assert_eq!(
+        stream.read_int::<u32>(21).unwrap(),
+        restored.read_int::<u32>(21).unwrap()
+    );
+
+    let buffer = BitReadBuffer::new(vec![0b1100_1001, 0b0000_0000], BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let mut bits = stream.read_deinterleaved(3, 3).unwrap();
+    assert_eq!(0b100_111_000, bits.read_int::<u16>(9).unwrap());
+    assert!(matches!(
+        stream.read_deinterleaved(4, 2),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(9, stream.pos());
+}
+
+#[test]
+fn read_limit() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_read_limit(24);
+    let mut bits = stream.read_bits(16).unwrap();
+    assert_eq!(stream.read_limit_left(), Some(24));
+    assert_eq!(bits.read_int::<u16>(16).unwrap(), 0b1011_0101_0110_1010);
+    assert_eq!(stream.read_limit_left(), Some(8));
+    assert_eq!(stream.read::<u8>().unwrap(), 0b1010_1100);
+    assert!(matches!(
+        stream.read_sized::<Vec<u8>>(2),
+        Err(BitError::ReadLimitReached {
+            requested: 8,
+            remaining: 0
+        })
+    ));
+    assert_eq!(stream.pos(), 24);
+    assert!(matches!(
+        stream.read_string(Some(2)),
+        Err(BitError::ReadLimitReached {
+            requested: 16,
+            remaining: 0
+        })
+    ));
+    assert_eq!(stream.pos(), 24);
+}
+
+#[test]
+fn read_limit_skip() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_read_limit(20);
+    stream.skip_bits(12).unwrap();
+    assert_eq!(stream.read_limit_left(), Some(8));
+    assert_eq!(stream.align_to(16).unwrap(), 4);
+    assert_eq!(stream.read_limit_left(), Some(4));
+    assert!(matches!(
+        stream.skip_bits(8),
+        Err(BitError::ReadLimitReached {
+            requested: 8,
+            remaining: 4
+        })
+    ));
+    assert_eq!(stream.pos(), 16);
+    assert!(matches!(
+        <u8 as BitRead<BigEndian>>::skip(&mut stream),
+        Err(BitError::ReadLimitReached { .. })
+    ));
+    assert_eq!(stream.read_int::<u8>(4).unwrap(), 0b1010);
+}
+
+#[test]
+fn read_parity() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert!(stream.read_parity(3, Parity::Even).unwrap());
+    assert!(!stream.read_parity(5, Parity::Even).unwrap());
+    assert!(stream.read_parity(9, Parity::Odd).unwrap());
+    assert_eq!(stream.pos(), 17);
+}
+
+#[test]
+fn read_hamming() {
+    let bytes = vec![0b0110_0110, 0b1110_0110, 0b0100_0100, 0b0110_0111];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let (mut bits, status) = stream.read_hamming(4, HammingCode::Secded).unwrap();
+    assert_eq!(stream.pos(), 32);
+    assert_eq!(bits.read_int::<u16>(16).unwrap(), 0b1011_1011_0010_1011);
+    assert_eq!(
+        vec![
+            FecStatus::Ok,
+            FecStatus::Corrected,
+            FecStatus::Uncorrectable,
+            FecStatus::Corrected
+        ],
+        status
+    );
+
+    let bytes = vec![0b0110_0111, 0b1100_1100];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let (mut bits, status) = stream.read_hamming(2, HammingCode::Hamming74).unwrap();
+    assert_eq!(stream.pos(), 14);
+    assert_eq!(bits.read_int::<u8>(8).unwrap(), 0b1011_1011);
+    assert_eq!(vec![FecStatus::Ok, FecStatus::Corrected], status);
+    assert!(stream.read_hamming(1, HammingCode::Hamming74).is_err());
+}
+
+fn read_with_parity<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut write = BitWriteStream::new(write_endianness);
+    write.write_int(0b101_1010u8, 7).unwrap();
+    write.write_bool(false).unwrap();
+    write.write_int(-3i8, 5).unwrap();
+    write.write_bool(false).unwrap();
+    write.write_int(0b101_1010u8, 7).unwrap();
+    write.write_bool(true).unwrap();
+    let buffer = BitReadBuffer::new(write.finish(), read_endianness);
+    let mut stream = BitReadStream::new(buffer);
+
+    assert_eq!(
+        stream.read_with_parity::<u8>(7, Parity::Even).unwrap(),
+        0b101_1010
+    );
+    assert_eq!(stream.read_with_parity::<i8>(5, Parity::Even).unwrap(), -3);
+    assert!(matches!(
+        stream.read_with_parity::<u8>(7, Parity::Even),
+        Err(BitError::ParityError { pos: 14 })
+    ));
+    assert_eq!(stream.pos(), 14);
+    assert_eq!(
+        stream.read_with_parity::<u8>(7, Parity::Odd).unwrap(),
+        0b101_1010
+    );
+    assert!(matches!(
+        stream.read_with_parity::<u8>(7, Parity::Odd),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(stream.pos(), 22);
+}
+
+#[test]
+fn read_with_parity_le() {
+    read_with_parity(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn read_with_parity_be() {
+    read_with_parity(BigEndian, BigEndian);
+}
+
+#[test]
+fn read_hamming_block() {
+    let bytes = vec![0b0110_0110, 0b1110_0110, 0b0100_0100, 0b0110_0111];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!(
+        stream.read_hamming_block(HammingCode::Secded).unwrap(),
+        0b1011
+    );
+    assert_eq!(
+        stream.read_hamming_block(HammingCode::Secded).unwrap(),
+        0b1011
+    );
+    assert!(matches!(
+        stream.read_hamming_block(HammingCode::Secded),
+        Err(BitError::ParityError { pos: 16 })
+    ));
+    assert_eq!(stream.pos(), 16);
+    stream.skip_bits(8).unwrap();
+    assert_eq!(
+        stream.read_hamming_block(HammingCode::Secded).unwrap(),
+        0b1011
+    );
+    assert!(matches!(
+        stream.read_hamming_block(HammingCode::Secded),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    let bytes = vec![0b0110_0111, 0b1100_1100];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.skip_bits(7).unwrap();
+    let mut block = stream.read_bits(7).unwrap();
+    assert_eq!(
+        block.read_hamming_block(HammingCode::Hamming74).unwrap(),
+        0b1011
+    );
+    assert_eq!(block.bits_left(), 0);
+}
+
+#[test]
+fn reverse_bits_view() {
+    let bytes: Vec<u8> = BYTES.iter().rev().map(|byte| byte.reverse_bits()).collect();
+    let reversed = BitReadBuffer::new(bytes, LittleEndian).reverse_bits();
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+    assert_eq!(reversed.bit_len(), buffer.bit_len());
+    for (pos, count) in [(0, 8), (3, 13), (5, 32), (17, 47), (60, 4)] {
+        assert_eq!(
+            buffer.read_int::<u64>(pos, count).unwrap(),
+            reversed.read_int::<u64>(pos, count).unwrap()
+        );
+    }
+    for pos in 0..buffer.bit_len() {
+        assert_eq!(
+            buffer.read_bool(pos).unwrap(),
+            reversed.read_bool(pos).unwrap()
+        );
+    }
+    assert_eq!(
+        buffer.read_bytes(3, 5).unwrap(),
+        reversed.read_bytes(3, 5).unwrap()
+    );
+    assert_eq!(
+        buffer.read_float::<f32>(7).unwrap().to_bits(),
+        reversed.read_float::<f32>(7).unwrap().to_bits()
+    );
+    assert!(reversed.read_bool(buffer.bit_len()).is_err());
+
+    let mut stream = BitReadStream::new(reversed);
+    stream.skip_bits(5).unwrap();
+    assert_eq!(
+        buffer.read_int::<u16>(5, 16).unwrap(),
+        stream.read::<u16>().unwrap()
+    );
+}
+
+#[test]
+fn reverse_bytes_view() {
+    let buffer = BitReadBuffer::new(vec![b'o', b'l', b'l', b'e', b'h'], BigEndian);
+    let reversed = buffer.reverse_bytes();
+    assert_eq!(reversed.read_string(0, None).unwrap(), "hello");
+    assert_eq!(reversed.read_string(8, Some(3)).unwrap(), "ell");
+    assert_eq!(reversed.read_string(8, None).unwrap(), "ello");
+    assert_eq!(
+        reversed.read_int::<u16>(4, 12).unwrap(),
+        (u16::from(b'h') & 0xf) << 8 | u16::from(b'e')
+    );
+
+    let twice = reversed.reverse_bytes();
+    assert_eq!(twice.read_string(0, Some(5)).unwrap(), "olleh");
+    let bits_twice = buffer.reverse_bits().reverse_bits();
+    assert_eq!(bits_twice.read_string(0, Some(5)).unwrap(), "olleh");
+    let bits_and_bytes = buffer.reverse_bits().reverse_bytes();
+    assert_eq!(
+        bits_and_bytes.read_int::<u8>(0, 8).unwrap(),
+        b'o'.reverse_bits()
+    );
+}
+
+#[test]
+fn read_marker() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.set_read_marker(20).unwrap();
+    assert!(stream.set_read_marker(10_000).is_err());
+    stream.skip_bits(4).unwrap();
+
+    let mut bits = stream.read_bits(16).unwrap();
+    assert!(bits.read_int::<u16>(16).is_ok());
+    stream.set_pos(19).unwrap();
+    assert!(matches!(
+        stream.read_bits(2),
+        Err(BitError::ReadPastMarker {
+            marker: 20,
+            pos: 19,
+            requested: 2
+        })
+    ));
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(BYTES.to_vec(), BigEndian));
+    stream.skip_bits(4).unwrap();
+    stream.set_read_marker(16).unwrap();
+    let mut clone = stream.clone();
+    // clones start counting positions from the position they were cloned at
+    assert!(matches!(
+        clone.read_int::<u16>(13),
+        Err(BitError::ReadPastMarker {
+            marker: 12,
+            pos: 0,
+            requested: 13
+        })
+    ));
+    assert!(matches!(
+        stream.read_string(Some(2)),
+        Err(BitError::ReadPastMarker { .. })
+    ));
+    assert!(matches!(
+        stream.read_bits(13),
+        Err(BitError::ReadPastMarker { .. })
+    ));
+    assert!(matches!(
+        stream.read::<f32>(),
+        Err(BitError::ReadPastMarker { .. })
+    ));
+    assert_eq!(stream.pos(), 4);
+    assert_eq!(stream.read_int::<u16>(12).unwrap(), 0b0101_0110_1010);
+    // reads starting at the marker are allowed
+    assert!(stream.read::<u8>().is_ok());
+}
+
+#[test]
+fn test_read_const_size_ints() {
+    let bytes = vec![0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!(UInt::<3>::new(0b101).unwrap(), stream.read().unwrap());
+    assert_eq!(Int::<5>::new(-11).unwrap(), stream.read().unwrap());
+    assert_eq!(
+        UInt::<16>::new(0b0110_1010_1010_1100).unwrap(),
+        stream.read().unwrap()
+    );
+    assert!(matches!(
+        stream.read::<UInt<9>>(),
+        Err(BitError::NotEnoughData {
+            requested: 9,
+            bits_left: 8
+        })
+    ));
+    assert_eq!(Some(12), bitbuffer::bit_size_of::<UInt<12>>());
+}
+
+#[test]
+fn test_const_size_int_arithmetic() {
+    assert_eq!(7, UInt::<3>::MAX.get());
+    assert_eq!(-16, Int::<5>::MIN.get());
+    assert_eq!(15, Int::<5>::MAX.get());
+    assert_eq!(u64::MAX, UInt::<64>::MAX.get());
+    assert_eq!(None, UInt::<3>::new(8));
+    assert_eq!(None, Int::<5>::new(16));
+
+    let a = UInt::<3>::new(6).unwrap();
+    let b = UInt::<3>::new(3).unwrap();
+    assert_eq!(1, (a + b).get());
+    assert_eq!(5, (b - a).get());
+    assert_eq!(None, a.checked_add(b));
+    assert_eq!(1, (!a).get());
+
+    let c = Int::<5>::new(14).unwrap();
+    assert_eq!(-4, (c + c).get());
+    assert_eq!(None, c.checked_mul(c));
+    assert_eq!(-15, (!c).get());
+
+    assert_eq!(6u64, u64::from(a));
+    assert!(UInt::<3>::try_from(7u64).is_ok());
+    assert!(matches!(
+        Int::<5>::try_from(-17i64),
+        Err(BitError::IntegerOutOfRange {
+            value: -17,
+            bits: 5
+        })
+    ));
+}
+
+fn read_generic<T, E: Endianness>(stream: &mut BitReadStream<E>, count: usize) -> T
+where
+    T: num_traits::PrimInt
+        + std::ops::BitOrAssign
+        + std::ops::BitXor
+        + num_traits::IsSigned
+        + num_traits::UncheckedPrimitiveInt,
+{
+    stream.read_int(count).unwrap()
+}
+
+#[test]
+fn test_read_int_generic() {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!(0b101u8, read_generic::<u8, _>(&mut stream, 3));
+    assert_eq!(-10i16, read_generic::<i16, _>(&mut stream, 5));
+}
+
+#[test]
+fn test_read_char() {
+    let bytes = vec![
+        0, 0, 0, b'a', 0, 0x01, 0xf6, 0x00, 0, 0, 0xd8, 0x00, 0, 0x11, 0, 0,
+    ];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!('a', stream.read().unwrap());
+    assert_eq!('😀', stream.read().unwrap());
+    assert!(matches!(
+        stream.read::<char>(),
+        Err(BitError::InvalidChar { value: 0xd800 })
+    ));
+    assert!(matches!(
+        stream.read::<char>(),
+        Err(BitError::InvalidChar { value: 0x110000 })
+    ));
+
+    // 7 bit ascii
+    let buffer = BitReadBuffer::new(vec![0b1100_0011, 0b1000_1000], BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    assert_eq!('a', stream.read_sized(7).unwrap());
+    assert_eq!('b', stream.read_sized(7).unwrap());
+}
+
+#[test]
+fn test_read_duration() {
+    use bitbuffer::{Millis, Seconds};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[derive(BitRead)]
+    struct Telemetry {
+        timestamp: Seconds<u32>,
+        #[size = 12]
+        latency: Millis<u16>,
+    }
+
+    let bytes = vec![0x65, 0x53, 0xf1, 0x00, 0x7d, 0x00];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let telemetry: Telemetry = stream.read().unwrap();
+    assert_eq!(44, stream.pos());
+    assert_eq!(Seconds(1_700_000_000), telemetry.timestamp);
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        telemetry.timestamp.since_unix_epoch()
+    );
+    assert_eq!(Duration::from_millis(2000), telemetry.latency.duration());
+    assert_eq!(Some(32), bitbuffer::bit_size_of::<Seconds<u32>>());
+
+    #[cfg(feature = "chrono")]
+    assert_eq!(
+        1_700_000_000,
+        telemetry.timestamp.to_chrono().unwrap().timestamp()
+    );
+    #[cfg(feature = "time")]
+    assert_eq!(
+        1_700_000_000,
+        telemetry
+            .timestamp
+            .to_offset_date_time()
+            .unwrap()
+            .unix_timestamp()
+    );
+}
+
+#[test]
+fn test_read_one_shot() {
+    let bytes = [0b1011_0101, 0b0110_1010, 0b1010_1100];
+
+    assert_eq!(
+        (0b0110_1010_1011_0101u16, 16),
+        bitbuffer::read_le(&bytes).unwrap()
+    );
+    assert_eq!(
+        (0b1011_0101_0110_1010u16, 16),
+        bitbuffer::read_be(&bytes).unwrap()
+    );
+    assert_eq!(
+        (String::from("abc"), 32),
+        bitbuffer::read_from::<String, _>(b"abc\0def", LittleEndian).unwrap()
+    );
+    assert!(matches!(
+        bitbuffer::read_le::<u32>(&bytes),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_read_net() {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+
+    #[derive(BitRead)]
+    struct Packet {
+        #[size = 4]
+        version: u8,
+        #[size = 4]
+        flags: u8,
+        source: SocketAddrV4,
+        destination: Ipv6Addr,
+    }
+
+    let mut bytes = vec![0x41, 192, 168, 1, 10, 0x1f, 0x90];
+    bytes.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+    let (packet, bits) = bitbuffer::read_be::<Packet>(&bytes).unwrap();
+    assert_eq!(4, packet.version);
+    assert_eq!(1, packet.flags);
+    assert_eq!(
+        SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 10), 8080),
+        packet.source
+    );
+    assert_eq!(Ipv6Addr::LOCALHOST, packet.destination);
+    assert_eq!(8 + 48 + 128, bits);
+    assert_eq!(Some(8 + 48 + 128), bitbuffer::bit_size_of::<Packet>());
+
+    // the octets are read in order for little endian streams
+    let (address, _) = bitbuffer::read_le::<Ipv4Addr>(&[127, 0, 0, 1]).unwrap();
+    assert_eq!(Ipv4Addr::LOCALHOST, address);
+}
+
+#[test]
+fn test_position_stack() {
+    // offset table pointing to two length prefixed strings
+    let bytes = vec![2, 5, 2, b'h', b'i', 3, b'f', b'o', b'o'];
+    let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+
+    let mut strings = Vec::new();
+    for index in 1..=2 {
+        let offset: u8 = stream.read().unwrap();
+        stream.push_pos(offset as usize * 8).unwrap();
+        let length: u8 = stream.read().unwrap();
+        strings.push(stream.read_string(Some(length as usize)).unwrap());
+        assert_eq!(Some(index * 8), stream.pop_pos());
+    }
+    assert_eq!(vec!["hi", "foo"], strings);
+    assert_eq!(16, stream.pos());
+
+    stream.push_pos(0).unwrap();
+    stream.push_pos(64).unwrap();
+    assert!(matches!(
+        stream.push_pos(100),
+        Err(BitError::IndexOutOfBounds { pos: 100, size: 72 })
+    ));
+    assert_eq!(64, stream.pos());
+    assert_eq!(Some(0), stream.pop_pos());
+    assert_eq!(Some(16), stream.pop_pos());
+    assert_eq!(None, stream.pop_pos());
+    assert_eq!(16, stream.pos());
+}
+
+#[derive(BitRead, PartialEq, Debug)]
+struct Entry {
+    #[size = 4]
+    kind: u8,
+    #[size = 12]
+    value: u16,
+}
+
+fn read_at<E: Endianness>(endianness: E) -> Entry {
+    let bytes = vec![2, 0xff, 0b0101_0011, 0b1100_1010];
+    let buffer = BitReadBuffer::new(bytes, endianness);
+    let mut stream = BitReadStream::new(buffer);
+    let offset: u8 = stream.read().unwrap();
+
+    let entry: Entry = stream.read_at(offset as usize * 8).unwrap();
+    assert_eq!(8, stream.pos());
+
+    // positions are relative to the start of the stream
+    let sub = stream.read_bits(24).unwrap();
+    assert_eq!(entry, sub.read_at(8).unwrap());
+    assert!(matches!(
+        sub.read_at::<u8>(25),
+        Err(BitError::IndexOutOfBounds { pos: 25, size: 24 })
+    ));
+    assert!(matches!(
+        sub.read_at::<Entry>(12),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    entry
+}
+
+#[test]
+fn test_read_at_le() {
+    assert_eq!(
+        Entry {
+            kind: 0b0011,
+            value: 0b1100_1010_0101
+        },
+        read_at(LittleEndian)
+    );
+}
+
+#[test]
+fn test_read_at_be() {
+    assert_eq!(
+        Entry {
+            kind: 0b0101,
+            value: 0b0011_1100_1010
+        },
+        read_at(BigEndian)
+    );
+}
+
+fn sub_buffer<E: Endianness>(endianness: E) {
+    let bytes = vec![
+        0b1011_0101,
+        0b0110_1010,
+        0b1010_1100,
+        0b1001_1001,
+        0b1110_0111,
+    ];
+    let buffer = BitReadBuffer::new(bytes, endianness);
+
+    for (start, end) in [
+        (0, 40),
+        (0, 13),
+        (8, 32),
+        (16, 21),
+        (3, 40),
+        (5, 30),
+        (9, 9),
+    ] {
+        let sub = buffer.sub_buffer(start..end).unwrap();
+        assert_eq!(end - start, sub.bit_len());
+        for pos in 0..sub.bit_len() {
+            assert_eq!(
+                buffer.read_bool(start + pos).unwrap(),
+                sub.read_bool(pos).unwrap()
+            );
+        }
+    }
+
+    // the same bits compare and hash equal regardless of how they are stored
+    let copied = buffer.sub_buffer(3..19).unwrap();
+    let shifted: BitReadBuffer<E> = BitReadBuffer::from(copied.read_bytes(0, 2).unwrap());
+    let shared = shifted.sub_buffer(0..16).unwrap();
+    assert_eq!(copied, shifted);
+    assert_eq!(copied, shared);
+    assert_ne!(copied, buffer.sub_buffer(4..20).unwrap());
+    assert_ne!(copied, shifted.sub_buffer(0..15).unwrap());
+    assert_eq!(
+        buffer.sub_buffer(8..16).unwrap(),
+        buffer.reverse_bytes().sub_buffer(24..32).unwrap()
+    );
+
+    let set: HashSet<_> = vec![copied, shifted, shared, buffer.sub_buffer(1..17).unwrap()]
+        .into_iter()
+        .collect();
+    assert_eq!(2, set.len());
+
+    assert_eq!(
+        Some(&[0b1011_0101, 0b0110_1010][..]),
+        buffer.sub_buffer(0..16).unwrap().as_bytes()
+    );
+    assert_eq!(None, buffer.sub_buffer(8..16).unwrap().as_bytes());
+    assert_eq!(None, buffer.sub_buffer(0..15).unwrap().as_bytes());
+
+    assert!(matches!(
+        buffer.sub_buffer(8..41),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = buffer.sub_buffer(9..8);
+    assert!(matches!(
+        reversed,
+        Err(BitError::IndexOutOfBounds { pos: 9, size: 8 })
+    ));
+}
+
+#[test]
+fn test_sub_buffer_le() {
+    sub_buffer(LittleEndian);
+}
+
+#[test]
+fn test_sub_buffer_be() {
+    sub_buffer(BigEndian);
+}
+
+#[test]
+fn test_dump() {
+    let bytes = vec![0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001, 0xff];
+    let buffer = BitReadBuffer::new(bytes, BigEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.skip_bits(34).unwrap();
+    assert_eq!(
+        concat!(
+            "     2: 11010101 10101010 10110010 01100111  d5 aa b2 67\n",
+            "    34: 111111                               3f\n",
+            "        ^\n",
+        ),
+        stream.dump(2..100)
+    );
+    assert_eq!(
+        "    10: 1010                                 0a\n",
+        stream.dump(10..14)
+    );
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(vec![1, 2, 3], LittleEndian));
+    stream.skip_bits(24).unwrap();
+    let dump = concat!(
+        "     0: 10000000 01000000 11000000           01 02 03\n",
+        "                                   ^\n",
+    );
+    assert_eq!(dump, stream.to_string());
+    assert_eq!(
+        "BitReadStream { pos: 24, bit_len: 24, endianness: LittleEndian }",
+        format!("{:?}", stream)
+    );
+    assert!(format!("{:#?}", stream).ends_with(dump));
+}
+
+#[cfg(feature = "trace")]
+mod trace {
+    use bitbuffer::{BigEndian, BitRead, BitReadBuffer, BitReadStream};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber that records the fields of every event
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    struct EventVisitor(Vec<String>);
+
+    impl Visit for EventVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = EventVisitor(Vec::new());
+            event.record(&mut visitor);
+            self.0.lock().unwrap().push(visitor.0.join(" "));
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    struct NoDebug(u8);
+
+    impl<E: bitbuffer::Endianness> BitRead<E> for NoDebug {
+        fn read(stream: &mut BitReadStream<E>) -> bitbuffer::Result<Self> {
+            Ok(NoDebug(stream.read()?))
+        }
+    }
+
+    #[derive(BitRead)]
+    struct Header {
+        #[size = 4]
+        version: u8,
+        flag: bool,
+        extra: NoDebug,
+    }
+
+    #[derive(BitRead)]
+    struct Pair(#[size = 3] u8, Header);
+
+    #[test]
+    fn test_trace_fields() {
+        let recorder = Recorder::default();
+        let buffer = BitReadBuffer::new(vec![0b1011_0101, 0b0110_1010, 0xff], BigEndian);
+        let mut stream = BitReadStream::new(buffer);
+        let pair: Pair =
+            tracing::subscriber::with_default(recorder.clone(), || stream.read().unwrap());
+        assert_eq!(0b101, pair.0);
+        assert_eq!(0b1010, pair.1.version);
+        assert!(pair.1.flag);
+        assert_eq!(0b0110_1010, pair.1.extra.0);
+
+        assert_eq!(
+            vec![
+                "type_name=\"Pair\" field=\"0\" pos=0 value=5",
+                "type_name=\"Header\" field=\"version\" pos=3 value=10",
+                "type_name=\"Header\" field=\"flag\" pos=7 value=true",
+                "type_name=\"Header\" field=\"extra\" pos=8 value=\"<no Debug implementation>\"",
+                "type_name=\"Pair\" field=\"1\" pos=3 value=\"<no Debug implementation>\"",
+            ],
+            *recorder.0.lock().unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_instrumented() {
+    use bitbuffer::DecodeNode;
+
+    #[derive(BitRead, Debug)]
+    struct Flags {
+        first: bool,
+        #[size = 2]
+        rest: u8,
+    }
+
+    #[derive(BitRead, Debug)]
+    #[endianness = "LittleEndian"]
+    struct Packet {
+        #[size = 4]
+        version: u8,
+        flags: Flags,
+        length: u8,
+        #[size = "length"]
+        payload: BitReadStream<LittleEndian>,
+        trailer: UInt<5>,
+    }
+
+    fn node(name: &str, range: std::ops::Range<usize>, value: &str) -> DecodeNode {
+        DecodeNode {
+            name: name.to_string(),
+            range,
+            value: value.to_string(),
+            children: Vec::new(),
+        }
+    }
+
+    let bytes = vec![0b1011_0101, 6, 0b1010_1100, 0xff];
+    let buffer = BitReadBuffer::new(bytes, LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    stream.skip_bits(1).unwrap();
+    let mut sub = stream.read_bits(31).unwrap();
+
+    let (packet, tree) = sub.read_instrumented::<Packet>().unwrap();
+    assert_eq!(0b1010, packet.version);
+    assert_eq!(6, packet.length);
+    assert!(packet.flags.first);
+    assert_eq!(2, packet.flags.rest);
+    assert_eq!(6, packet.payload.bit_len());
+    assert_eq!(30, packet.trailer.get());
+    assert_eq!(0..26, tree.range);
+    assert_eq!(std::any::type_name::<Packet>(), tree.name);
+
+    let names: Vec<_> = tree.children.iter().map(|child| &child.name).collect();
+    assert_eq!(
+        vec!["version", "flags", "length", "payload", "trailer"],
+        names
+    );
+    assert_eq!(node("version", 0..4, "10"), tree.children[0]);
+    assert_eq!(
+        vec![node("first", 4..5, "true"), node("rest", 5..7, "2")],
+        tree.children[1].children
+    );
+    assert_eq!(4..7, tree.children[1].range);
+    assert_eq!(node("length", 7..15, "6"), tree.children[2]);
+    assert_eq!(15..21, tree.children[3].range);
+    assert_eq!(node("trailer", 21..26, "UInt(30)"), tree.children[4]);
+    assert_eq!("rest", tree.find(6).unwrap().name);
+    assert_eq!("payload", tree.find(15).unwrap().name);
+    assert_eq!(None, tree.find(26));
+
+    let (trailer, tree) = sub.read_instrumented::<UInt<5>>().unwrap();
+    assert_eq!(UInt::<5>::MAX, trailer);
+    assert_eq!(
+        node(std::any::type_name::<UInt<5>>(), 26..31, "UInt(31)"),
+        tree
+    );
+    assert!(sub.read_instrumented::<Packet>().is_err());
+}
+
+#[test]
+fn test_diff() {
+    use bitbuffer::{diff, BitWrite, FieldDiff};
+
+    #[derive(BitRead, BitWrite, Debug, Clone)]
+    struct Flags {
+        first: bool,
+        #[size = 2]
+        rest: u8,
+    }
+
+    #[derive(BitRead, BitWrite, Debug, Clone)]
+    #[discriminant_bits = 1]
+    enum Body {
+        Empty,
+        Value(u8),
+    }
+
+    #[derive(BitRead, BitWrite, Debug, Clone)]
+    struct Packet {
+        #[size = 4]
+        version: u8,
+        flags: Flags,
+        body: Body,
+        trailer: UInt<5>,
+    }
+
+    fn write(packet: &Packet) -> BitReadBuffer<BigEndian> {
+        let mut stream = BitWriteStream::new(BigEndian);
+        stream.write(packet).unwrap();
+        BitReadBuffer::new(stream.finish(), BigEndian)
+    }
+
+    let original = Packet {
+        version: 3,
+        flags: Flags {
+            first: true,
+            rest: 2,
+        },
+        body: Body::Value(12),
+        trailer: UInt::new(7).unwrap(),
+    };
+    assert_eq!(
+        Vec::<FieldDiff>::new(),
+        diff::<Packet, _>(&write(&original), &write(&original)).unwrap()
+    );
+
+    let mut changed = original.clone();
+    changed.flags.rest = 1;
+    changed.body = Body::Empty;
+    let diffs = diff::<Packet, _>(&write(&original), &write(&changed)).unwrap();
+    assert_eq!(
+        vec![
+            FieldDiff {
+                path: "flags.rest".to_string(),
+                left_range: 5..7,
+                left_value: "2".to_string(),
+                right_range: 5..7,
+                right_value: "1".to_string(),
+            },
+            FieldDiff {
+                path: "body".to_string(),
+                left_range: 7..16,
+                left_value: "Value(12)".to_string(),
+                right_range: 7..8,
+                right_value: "Empty".to_string(),
+            },
+        ],
+        diffs
+    );
+    assert_eq!(
+        "flags.rest: 2 at bits 5..7 != 1 at bits 5..7",
+        diffs[0].to_string()
+    );
+
+    let short = BitReadBuffer::new(vec![0], BigEndian);
+    assert!(diff::<Packet, _>(&write(&original), &short).is_err());
+
+    let diffs = diff::<u8, _>(&short, &write(&original)).unwrap();
+    assert_eq!(1, diffs.len());
+    assert_eq!("u8", diffs[0].path);
+}
+
+fn read_layout<E: Endianness>(stream: &mut BitReadStream<E>) -> bitbuffer::Result<(u8, bool, u16)> {
+    bitbuffer::bit_layout!(stream, (u8[3], bool, u16[12]))
+}
+
+#[test]
+fn test_bit_layout() {
+    let bytes = vec![0b1011_0101, 0b0110_1010, 0b1010_1100, 0b1001_1001];
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes.clone(), BigEndian));
+    let header = bitbuffer::bit_layout!(stream, {
+        version: u8 [3],
+        flag: bool,
+        length: u8 [4],
+        values: Vec<u8> [length / 2],
+    })
+    .unwrap();
+    assert_eq!(0b101, header.version);
+    assert!(header.flag);
+    assert_eq!(0b0101, header.length);
+    assert_eq!(vec![0b0110_1010, 0b1010_1100], header.values);
+    assert_eq!(24, stream.pos());
+
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+    assert_eq!(
+        (0b101, false, 0b0110_1010_1011),
+        read_layout(&mut stream).unwrap()
+    );
+    read_layout(&mut stream).unwrap();
+    assert!(matches!(
+        read_layout(&mut stream),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+fn read_int_checked<E: Endianness>(endianness: E) {
+    let bytes = vec![0xff, 0xff, 0x01, 0x01, 0x00, 0x00];
+    let buffer = BitReadBuffer::new(bytes, endianness);
+    let mut stream = BitReadStream::new(buffer);
+
+    assert_eq!(-1i8, stream.read_int_checked::<i8>(16).unwrap());
+    let pos = stream.pos();
+    assert!(matches!(
+        stream.read_int_checked::<u8>(16),
+        Err(BitError::IntegerOutOfRange {
+            value: 257,
+            bits: 8
+        })
+    ));
+    assert_eq!(pos, stream.pos());
+    assert_eq!(257u32, stream.read_int_checked::<u32>(16).unwrap());
+    assert_eq!(0u128, stream.read_int_checked::<u128>(9).unwrap());
+    assert!(matches!(
+        stream.read_int_checked::<u64>(129),
+        Err(BitError::TooManyBits { requested: 129, .. })
+    ));
+    assert!(matches!(
+        stream.read_int_checked::<u64>(17),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_int_checked_le() {
+    read_int_checked(LittleEndian);
+    let buffer = BitReadBuffer::new(vec![0x80, 0xff], LittleEndian);
+    assert_eq!(
+        i8::MIN,
+        BitReadStream::new(buffer)
+            .read_int_checked::<i8>(16)
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_read_int_checked_be() {
+    read_int_checked(BigEndian);
+    let buffer = BitReadBuffer::new(vec![0xff, 0x7f], BigEndian);
+    assert!(matches!(
+        BitReadStream::new(buffer).read_int_checked::<i8>(16),
+        Err(BitError::IntegerOutOfRange {
+            value: -129,
+            bits: 8
+        })
+    ));
+}
+
+#[cfg(feature = "python")]
+mod python {
+    use pyo3::prelude::*;
+    use pyo3::types::IntoPyDict;
+
+    #[test]
+    fn test_python_bindings() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "bitbuffer").unwrap();
+            bitbuffer::python::register(&module).unwrap();
+            let locals = [("bitbuffer", module)].into_py_dict_bound(py);
+            py.run_bound(
+                r#"
+buffer = bitbuffer.BitReadBufferLE(bytes([0b1011_0101, 0b0110_1010, 0xff, 0x68, 0x69]))
+assert buffer.bit_len() == 40
+assert buffer.read_bool(0)
+assert buffer.read_int(0, 3) == 0b101
+assert buffer.read_int(0, 3, signed=True) == -3
+assert buffer.read_bytes(24, 2) == b"hi"
+
+stream = bitbuffer.BitReadStreamBE(bytes([0b1011_0101, 0b0110_1010, 0xff, 0x68, 0x69]))
+assert stream.read_bool()
+assert stream.read_int(7) == 0b011_0101
+assert stream.pos == 8
+bits = stream.read_bits(16)
+assert bits.read_int(16) == 0x6aff
+assert stream.read_string(2) == "hi"
+try:
+    stream.read_bool()
+    assert False
+except ValueError as e:
+    assert "Not enough data" in str(e)
+
+stream = buffer.stream()
+stream.skip_bits(24)
+assert stream.read_string() == "hi"
+"#,
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
+
+#[cfg(feature = "capi")]
+mod capi {
+    use bitbuffer::capi::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_capi() {
+        let bytes = [0b1011_0101, 0b0110_1010, b'h', b'i'];
+        unsafe {
+            let stream =
+                bitbuffer_stream_new(bytes.as_ptr(), bytes.len(), BitbufferEndianness::BigEndian);
+            assert!(!stream.is_null());
+            assert_eq!(32, bitbuffer_stream_bits_left(stream));
+
+            let mut value = 0u64;
+            assert_eq!(
+                BitbufferStatus::Ok,
+                bitbuffer_stream_read_int(stream, 3, &mut value)
+            );
+            assert_eq!(0b101, value);
+            let mut signed = 0i64;
+            assert_eq!(
+                BitbufferStatus::Ok,
+                bitbuffer_stream_read_signed(stream, 5, &mut signed)
+            );
+            assert_eq!(-11, signed);
+            assert_eq!(8, bitbuffer_stream_pos(stream));
+
+            assert_eq!(
+                BitbufferStatus::TooManyBits,
+                bitbuffer_stream_read_int(stream, 65, &mut value)
+            );
+            assert_eq!(
+                BitbufferStatus::InvalidArgument,
+                bitbuffer_stream_read_int(stream, 8, null_mut())
+            );
+
+            let mut out = [0u8; 3];
+            assert_eq!(
+                BitbufferStatus::Ok,
+                bitbuffer_stream_read_bytes(stream, out.as_mut_ptr(), 3)
+            );
+            assert_eq!([0b0110_1010, b'h', b'i'], out);
+            assert_eq!(
+                BitbufferStatus::NotEnoughData,
+                bitbuffer_stream_read_int(stream, 1, &mut value)
+            );
+            assert_eq!(32, bitbuffer_stream_pos(stream));
+
+            assert_eq!(BitbufferStatus::Ok, bitbuffer_stream_set_pos(stream, 16));
+            assert_eq!(
+                BitbufferStatus::IndexOutOfBounds,
+                bitbuffer_stream_set_pos(stream, 33)
+            );
+            assert_eq!(16, bitbuffer_stream_pos(stream));
+            bitbuffer_stream_free(stream);
+
+            assert!(
+                bitbuffer_stream_new(std::ptr::null(), 1, BitbufferEndianness::LittleEndian)
+                    .is_null()
+            );
+            assert_eq!(0, bitbuffer_stream_pos(std::ptr::null()));
+            bitbuffer_stream_free(null_mut());
+        }
+    }
+}
+
+fn read_bits_aligned<E: Endianness>(endianness: E) {
+    let bytes = vec![0b1010_0101, 1, 2, 3, 4, 5];
+    let buffer = BitReadBuffer::new(bytes, endianness);
+    let mut stream = BitReadStream::new(buffer);
+
+    stream.skip_bits(3).unwrap();
+    let mut payload = stream.read_bits_aligned(16).unwrap();
+    assert_eq!(24, stream.pos());
+    assert_eq!(Some(&[1, 2][..]), payload.as_bytes());
+    assert_eq!(1, payload.read_int::<u8>(8).unwrap());
+    assert_eq!(Some(&[1, 2][..]), payload.as_bytes());
+
+    // already aligned
+    let payload = stream.read_bits_aligned(8).unwrap();
+    assert_eq!(Some(&[3][..]), payload.as_bytes());
+    assert_eq!(32, stream.pos());
+
+    let mut unaligned_end = stream.read_bits_aligned(12).unwrap();
+    assert_eq!(None, unaligned_end.as_bytes());
+    assert_eq!(
+        Some(&[4][..]),
+        unaligned_end.read_bits(8).unwrap().as_bytes()
+    );
+
+    // nested sub streams are aligned to the underlying data
+    stream.set_pos(5).unwrap();
+    let mut outer = stream.read_bits(40).unwrap();
+    assert_eq!(None, outer.as_bytes());
+    let inner = outer.read_bits_aligned(16).unwrap();
+    assert_eq!(Some(&[1, 2][..]), inner.as_bytes());
+    assert_eq!(19, outer.pos());
+
+    // position is unchanged on error
+    stream.set_pos(41).unwrap();
+    assert!(matches!(
+        stream.read_bits_aligned(8),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(41, stream.pos());
+    stream.set_pos(33).unwrap();
+    assert!(matches!(
+        stream.read_bits_aligned(16),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(33, stream.pos());
+}
+
+#[test]
+fn test_read_bits_aligned_le() {
+    read_bits_aligned(LittleEndian);
+}
+
+#[test]
+fn test_read_bits_aligned_be() {
+    read_bits_aligned(BigEndian);
+}
+
+fn nested_sub_streams<E: Endianness>(endianness: E) {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let mut stream = BitReadStream::new(buffer);
+    let mut parent = stream.clone();
+    stream.skip_bits(5).unwrap();
+
+    let mut sub = stream.read_bits(40).unwrap();
+    assert_eq!(45, stream.pos());
+    assert_eq!(0, sub.pos());
+    assert_eq!(40, sub.bit_len());
+    sub.skip_bits(3).unwrap();
+
+    let mut sub_sub = sub.read_bits(20).unwrap();
+    assert_eq!(23, sub.pos());
+    assert_eq!(0, sub_sub.pos());
+    assert_eq!(20, sub_sub.bit_len());
+    assert_eq!(20, sub_sub.bits_left());
+
+    // reads match the same bits read from the parent
+    parent.set_pos(8 + 2).unwrap();
+    let expected: u16 = parent.read_int(12).unwrap();
+    sub_sub.set_pos(2).unwrap();
+    assert_eq!(2, sub_sub.pos());
+    assert_eq!(expected, sub_sub.read_int::<u16>(12).unwrap());
+    assert_eq!(14, sub_sub.pos());
+    assert_eq!(
+        parent.read_at::<u16>(10).unwrap(),
+        sub_sub.read_at::<u16>(2).unwrap()
+    );
+    sub_sub.push_pos(2).unwrap();
+    assert_eq!(expected, sub_sub.read_int::<u16>(12).unwrap());
+    assert_eq!(Some(14), sub_sub.pop_pos());
+
+    // positions in errors are relative to the sub stream
+    assert!(matches!(
+        sub_sub.set_pos(21),
+        Err(BitError::IndexOutOfBounds { pos: 21, size: 20 })
+    ));
+    assert!(matches!(
+        sub_sub.read_int::<u8>(7),
+        Err(BitError::NotEnoughData {
+            requested: 7,
+            bits_left: 6
+        })
+    ));
+    assert!(matches!(
+        sub_sub.read_bits(7),
+        Err(BitError::NotEnoughData {
+            requested: 7,
+            bits_left: 6
+        })
+    ));
+    assert!(matches!(
+        sub.read_bits(18),
+        Err(BitError::NotEnoughData {
+            requested: 18,
+            bits_left: 17
+        })
+    ));
+    assert!(matches!(
+        sub_sub.read_at::<u8>(30),
+        Err(BitError::IndexOutOfBounds { pos: 30, size: 20 })
+    ));
+
+    // reads can't escape the sub range, even when the underlying data continues
+    sub_sub.set_pos(20).unwrap();
+    assert!(sub_sub.read_bool().is_err());
+    assert!(sub_sub.read_string(None).unwrap().is_empty());
+    assert_eq!(20, sub_sub.pos());
+    sub_sub.set_pos(0).unwrap();
+    let mut sub_sub_sub = sub_sub.read_bits(4).unwrap();
+    sub_sub_sub.set_pos(4).unwrap();
+    assert!(sub_sub_sub.read_bool().is_err());
+    assert!(sub_sub_sub.set_pos(5).is_err());
+
+    // the parent streams are unaffected
+    assert_eq!(23, sub.pos());
+    assert_eq!(45, stream.pos());
+}
+
+#[test]
+fn test_nested_sub_streams_le() {
+    nested_sub_streams(LittleEndian);
+}
+
+#[test]
+fn test_nested_sub_streams_be() {
+    nested_sub_streams(BigEndian);
+}
+
+fn truncate_stream<E: Endianness>(endianness: E) {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let mut stream = BitReadStream::new(buffer);
+    let mut reference = stream.clone();
+    let mut parent = stream.clone();
+
+    stream.skip_bits(3).unwrap();
+    stream.truncate(40).unwrap();
+    assert_eq!(40, stream.bit_len());
+    assert_eq!(37, stream.bits_left());
+    assert!(matches!(
+        stream.truncate(41),
+        Err(BitError::NotEnoughData {
+            requested: 41,
+            bits_left: 40
+        })
+    ));
+    assert!(matches!(
+        stream.truncate(2),
+        Err(BitError::IndexOutOfBounds { pos: 3, size: 2 })
+    ));
+
+    stream.take(20).unwrap();
+    assert_eq!(23, stream.bit_len());
+    reference.skip_bits(3).unwrap();
+    assert_eq!(
+        reference.read_int::<u32>(20).unwrap(),
+        stream.read_int::<u32>(20).unwrap()
+    );
+    assert!(matches!(
+        stream.read_bool(),
+        Err(BitError::NotEnoughData {
+            requested: 1,
+            bits_left: 0
+        })
+    ));
+    assert!(matches!(
+        stream.take(1),
+        Err(BitError::NotEnoughData {
+            requested: 1,
+            bits_left: 0
+        })
+    ));
+    assert!(stream.set_pos(24).is_err());
+
+    // truncating a sub stream doesn't affect the parent
+    let mut sub = parent.read_bits(32).unwrap();
+    sub.take(8).unwrap();
+    assert_eq!(8, sub.bits_left());
+    assert_eq!(BYTES.len() * 8 - 32, parent.bits_left());
+    assert_eq!(BYTES[4] as u32, parent.read_int::<u32>(8).unwrap());
+}
+
+#[test]
+fn test_truncate_stream_le() {
+    truncate_stream(LittleEndian);
+}
+
+#[test]
+fn test_truncate_stream_be() {
+    truncate_stream(BigEndian);
+}
+
+#[derive(BitRead, Debug, PartialEq)]
+struct ReadAllPoint {
+    #[size = 4]
+    x: u8,
+    #[size = 4]
+    y: u8,
+}
+
+fn read_all_values<E: Endianness>(
+    stream: &mut BitReadStream<E>,
+) -> bitbuffer::Result<(u8, u16, bool)>
+where
+    ReadAllPoint: BitRead<E>,
+{
+    let (first, second, third, point) =
+        read_all!(stream => first: u8, second: #[size = 12] u16, third: bool, point: ReadAllPoint)?;
+    assert_eq!(point, stream.read_at::<ReadAllPoint>(stream.pos() - 8)?);
+    Ok((first, second, third))
+}
+
+fn test_read_all<E: Endianness>(endianness: E) {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let mut stream = BitReadStream::new(buffer);
+    let mut reference = stream.clone();
+
+    let (first, second, third) = read_all_values(&mut stream).unwrap();
+    assert_eq!(first, reference.read::<u8>().unwrap());
+    assert_eq!(second, reference.read_sized::<u16>(12).unwrap());
+    assert_eq!(third, reference.read::<bool>().unwrap());
+    assert_eq!(29, stream.pos());
+
+    // nothing is read when the values don't fit
+    stream.set_pos(stream.bit_len() - 10).unwrap();
+    assert!(matches!(
+        read_all!(stream => a: u8, b: u8),
+        Err(BitError::NotEnoughData {
+            requested: 16,
+            bits_left: 10
+        })
+    ));
+    assert_eq!(stream.bit_len() - 10, stream.pos());
+    let sized_fail: bitbuffer::Result<(u8, String)> =
+        read_all!(stream => a: u8, b: #[size = 1] String);
+    assert!(sized_fail.is_err());
+}
+
+#[test]
+fn test_read_all_le() {
+    test_read_all(LittleEndian);
+
+    // types without a fixed size are read one by one
+    let buffer = BitReadBuffer::new(b"hi\x05foo\0bar".to_vec(), LittleEndian);
+    let mut stream = BitReadStream::new(buffer);
+    let size = 2;
+    let (fixed, number, terminated) =
+        read_all!(stream => fixed: #[size = size] String, number: u8, terminated: String).unwrap();
+    assert_eq!("hi", fixed);
+    assert_eq!(5, number);
+    assert_eq!("foo", terminated);
+    assert_eq!(7 * 8, stream.pos());
+    assert!(matches!(
+        read_all!(stream => a: String, b: u8),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_all_be() {
+    test_read_all(BigEndian);
+}
+
+struct FailingWriter;
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("write failed"))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn copy_to<E: Endianness>(endianness: E) {
+    let bytes: Vec<u8> = (0..2000).map(|i| (i * 7 + i / 3) as u8).collect();
+    let buffer = BitReadBuffer::new(bytes, endianness);
+    let reversed = buffer.reverse_bytes();
+
+    for buffer in [buffer.clone(), reversed] {
+        let stream = BitReadStream::new(buffer);
+        for offset in [0, 3, 8, 13] {
+            for count in [0, 5, 8, 64, 100, 1000 * 8 + 3] {
+                let mut stream = stream.clone();
+                stream.skip_bits(offset).unwrap();
+                let mut expected = stream.clone();
+                let mut output = Vec::new();
+                stream.copy_to(&mut output, count).unwrap();
+                assert_eq!(offset + count, stream.pos());
+
+                let mut expected_bytes = expected.read_bytes(count / 8).unwrap();
+                let rest = count % 8;
+                if rest > 0 {
+                    let last: u8 = expected.read_int(rest).unwrap();
+                    expected_bytes.push(if E::is_le() { last } else { last << (8 - rest) });
+                }
+                assert_eq!(expected_bytes, output, "offset {} count {}", offset, count);
+            }
+        }
+    }
+
+    let mut stream = BitReadStream::new(buffer);
+    stream.skip_bits(3).unwrap();
+    assert!(matches!(
+        stream.copy_to(&mut Vec::new(), 2000 * 8),
+        Err(BitError::NotEnoughData {
+            requested: 16000,
+            bits_left: 15997
+        })
+    ));
+    assert!(matches!(
+        stream.copy_to(&mut FailingWriter, 16),
+        Err(BitError::Io(_))
+    ));
+    assert_eq!(3, stream.pos());
+}
+
+#[test]
+fn test_copy_to_le() {
+    copy_to(LittleEndian);
+}
+
+#[test]
+fn test_copy_to_be() {
+    copy_to(BigEndian);
+}
+
+fn buffer_builder<E: Endianness>(endianness: E, builder_endianness: E) {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let full = BitReadStream::new(buffer.clone());
+    let mut builder = BitBufferBuilder::new(builder_endianness);
+
+    // split the data in fragments of odd lengths and reassemble them
+    for fragment_len in [1, 3, 8, 13, 40, 64] {
+        let mut stream = full.clone();
+        let start = builder.bit_len();
+        while stream.bits_left() > 0 {
+            let len = fragment_len.min(stream.bits_left());
+            builder.push_bits(&mut stream, len).unwrap();
+        }
+        assert_eq!(start + buffer.bit_len(), builder.bit_len());
+        // fragments can also be added from other sources
+        builder.push_buffer(&buffer.sub_buffer(3..5).unwrap());
+        builder.push_stream(&full);
+        builder.push_buffer(&buffer.reverse_bytes());
+    }
+    let rebuilt = BitReadStream::new(builder.finish());
+    assert_eq!(6 * (3 * BYTES.len() * 8 + 2), rebuilt.bit_len());
+
+    let mut rebuilt = rebuilt;
+    let mut reversed = BYTES.to_vec();
+    reversed.reverse();
+    for _ in 0..6 {
+        assert_eq!(BYTES.to_vec(), rebuilt.read_bytes(BYTES.len()).unwrap());
+        assert_eq!(
+            buffer.read_int::<u8>(3, 2).unwrap(),
+            rebuilt.read_int::<u8>(2).unwrap()
+        );
+        assert_eq!(BYTES.to_vec(), rebuilt.read_bytes(BYTES.len()).unwrap());
+        assert_eq!(reversed, rebuilt.read_bytes(BYTES.len()).unwrap());
+    }
+    assert_eq!(0, rebuilt.bits_left());
+}
+
+#[test]
+fn test_buffer_builder_le() {
+    buffer_builder(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_buffer_builder_be() {
+    buffer_builder(BigEndian, BigEndian);
+}
+
+fn map_bytes<E: Endianness + 'static>(endianness: E) {
+    let key = [0x12u8, 0x34, 0x56];
+    let scrambled: Vec<u8> = BYTES
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| byte ^ key[index % 3])
+        .collect();
+    let plain = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let buffer = BitReadBuffer::<E>::from(scrambled);
+    let descrambled = buffer.map_bytes(move |index, byte| byte ^ key[index % 3]);
+
+    assert_eq!(plain.bit_len(), descrambled.bit_len());
+    assert_eq!(plain, descrambled);
+    assert_ne!(plain, buffer);
+    // transforms can be stacked
+    assert_eq!(
+        buffer,
+        descrambled.map_bytes(move |index, byte| byte ^ key[index % 3])
+    );
+    for pos in [0, 3, 8, 13, 40] {
+        for count in [1, 7, 8, 17, 32, 64] {
+            if pos + count > plain.bit_len() {
+                continue;
+            }
+            assert_eq!(
+                plain.read_int::<u64>(pos, count).unwrap(),
+                descrambled.read_int::<u64>(pos, count).unwrap()
+            );
+        }
+    }
+    assert_eq!(
+        plain.read_bytes(3, 10).unwrap(),
+        descrambled.read_bytes(3, 10).unwrap()
+    );
+
+    // views on top of transformed buffers
+    assert_eq!(plain.reverse_bits(), descrambled.reverse_bits());
+    assert_eq!(
+        plain.sub_buffer(16..60).unwrap(),
+        descrambled.sub_buffer(16..60).unwrap()
+    );
+    let sub = plain.sub_buffer(8..64).unwrap();
+    assert_eq!(
+        sub.map_bytes(|index, byte| byte.wrapping_add(index as u8))
+            .read_bytes(0, 2)
+            .unwrap(),
+        vec![BYTES[1], BYTES[2].wrapping_add(1)]
+    );
+
+    // transform is only applied to bytes in the buffer
+    let strings = BitReadBuffer::<E>::from(b"ab".to_vec()).map_bytes(|_, byte| byte + 1);
+    let mut stream = BitReadStream::new(strings);
+    assert_eq!("bc", stream.read_string(None).unwrap());
+}
+
+#[test]
+fn test_map_bytes_le() {
+    map_bytes(LittleEndian);
+}
+
+#[test]
+fn test_map_bytes_be() {
+    map_bytes(BigEndian);
+}
+
+fn read_bool_vec<E: Endianness>(endianness: E) {
+    let buffer = BitReadBuffer::new(BYTES.to_vec(), endianness);
+    let mut stream = BitReadStream::new(buffer);
+    let mut single = stream.clone();
+    stream.skip_bits(3).unwrap();
+    single.skip_bits(3).unwrap();
+
+    let bits: Vec<bool> = stream.read_sized(90).unwrap();
+    let expected: Vec<bool> = (0..90).map(|_| single.read_bool().unwrap()).collect();
+    assert_eq!(expected, bits);
+    assert_eq!(93, stream.pos());
+
+    let bits: Vec<bool> = stream.read_sized(0).unwrap();
+    assert!(bits.is_empty());
+    assert!(matches!(
+        stream.read_sized::<Vec<bool>>(4),
+        Err(BitError::NotEnoughData {
+            requested: 4,
+            bits_left: 3
+        })
+    ));
+    assert_eq!(93, stream.pos());
+}
+
+#[test]
+fn test_read_bool_vec_le() {
+    read_bool_vec(LittleEndian);
+}
+
+#[test]
+fn test_read_bool_vec_be() {
+    read_bool_vec(BigEndian);
+}
+
+#[cfg(feature = "bitvec")]
+mod bitvec {
+    use super::BYTES;
+    use bitbuffer::{
+        BigEndian, BitReadBuffer, BitReadStream, BitWriteStream, Endianness, LittleEndian,
+    };
+    use bitvec::prelude::*;
+
+    fn roundtrip<E: Endianness>(write_endianness: E, read_endianness: E) {
+        let buffer = BitReadBuffer::new(BYTES.to_vec(), read_endianness);
+        let mut stream = BitReadStream::new(buffer);
+        stream.skip_bits(5).unwrap();
+        let mut single = stream.clone();
+
+        let bits: BitVec<u8, Msb0> = stream.read_sized(80).unwrap();
+        let rest: BitVec = stream.read_sized(11).unwrap();
+        assert_eq!(80, bits.len());
+        for bit in bits.iter().by_vals().chain(rest.iter().by_vals()) {
+            assert_eq!(single.read_bool().unwrap(), bit);
+        }
+        assert_eq!(96, stream.pos());
+
+        let mut write = BitWriteStream::new(write_endianness);
+        write.write_int(0b101u8, 3).unwrap();
+        write.write(&bits).unwrap();
+        write.write(&rest).unwrap();
+        assert_eq!(94, write.bit_len());
+        let mut read = BitReadStream::new(BitReadBuffer::<E>::from(write.finish()));
+        assert_eq!(0b101u8, read.read_int::<u8>(3).unwrap());
+        assert_eq!(bits, read.read_sized::<BitVec<u8, Msb0>>(80).unwrap());
+        assert_eq!(rest, read.read_sized::<BitVec>(11).unwrap());
+    }
+
+    #[test]
+    fn test_bitvec_le() {
+        roundtrip(LittleEndian, LittleEndian);
+    }
+
+    #[test]
+    fn test_bitvec_be() {
+        roundtrip(BigEndian, BigEndian);
+    }
+}
+
+#[cfg(any(feature = "json", feature = "cbor"))]
+mod payload {
+    use bitbuffer::{
+        BigEndian, BitError, BitReadBuffer, BitReadStream, BitWriteStream, Endianness, LittleEndian,
+    };
+    use std::collections::BTreeMap;
+
+    fn payload_values() -> BTreeMap<String, Vec<u32>> {
+        let mut values = BTreeMap::new();
+        values.insert("ticks".to_string(), vec![1, 2, 300]);
+        values.insert("empty".to_string(), vec![]);
+        values
+    }
+
+    #[cfg(feature = "json")]
+    fn read_json<E: Endianness>(write_endianness: E, read_endianness: E) {
+        let mut stream = BitWriteStream::new(write_endianness);
+        stream.write_int(0b101u8, 3).unwrap();
+        let length = stream.write_json(&payload_values()).unwrap();
+        stream.write_bool(true).unwrap();
+
+        let buffer = BitReadBuffer::new(stream.finish(), read_endianness);
+        let mut stream = BitReadStream::new(buffer);
+        assert_eq!(0b101, stream.read_int::<u8>(3).unwrap());
+        let values: BTreeMap<String, Vec<u32>> = stream.read_json(length).unwrap();
+        assert_eq!(payload_values(), values);
+        assert_eq!(true, stream.read_bool().unwrap());
+
+        stream.set_pos(3).unwrap();
+        assert!(matches!(
+            stream.read_json::<Vec<u32>>(length),
+            Err(BitError::InvalidPayload { pos: 3, .. })
+        ));
+        assert_eq!(3, stream.pos());
+        assert!(matches!(
+            stream.read_json::<Vec<u32>>(length + 1),
+            Err(BitError::NotEnoughData { .. })
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_read_json_le() {
+        read_json(LittleEndian, LittleEndian);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_read_json_be() {
+        read_json(BigEndian, BigEndian);
+    }
+
+    #[cfg(feature = "cbor")]
+    fn read_cbor<E: Endianness>(write_endianness: E, read_endianness: E) {
+        let mut stream = BitWriteStream::new(write_endianness);
+        stream.write_int(0b101u8, 3).unwrap();
+        let length = stream.write_cbor(&payload_values()).unwrap();
+        stream.write_bool(true).unwrap();
+
+        let buffer = BitReadBuffer::new(stream.finish(), read_endianness);
+        let mut stream = BitReadStream::new(buffer);
+        assert_eq!(0b101, stream.read_int::<u8>(3).unwrap());
+        let values: BTreeMap<String, Vec<u32>> = stream.read_cbor(length).unwrap();
+        assert_eq!(payload_values(), values);
+        assert_eq!(true, stream.read_bool().unwrap());
+
+        stream.set_pos(3).unwrap();
+        assert!(matches!(
+            stream.read_cbor::<String>(length),
+            Err(BitError::InvalidPayload { pos: 3, .. })
+        ));
+        assert_eq!(3, stream.pos());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_read_cbor_le() {
+        read_cbor(LittleEndian, LittleEndian);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_read_cbor_be() {
+        read_cbor(BigEndian, BigEndian);
+    }
+}
+
+fn read_protobuf<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::protobuf::{read_pb_tag, read_pb_varint, skip_pb_field, Tag, WireType};
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_int(0b101u8, 3).unwrap();
+    stream
+        .write_bytes(&[
+            // field 1: varint 150
+            0x08, 0x96, 0x01, // field 2: fixed64
+            0x11, 1, 2, 3, 4, 5, 6, 7, 8, // field 3: group containing field 4: fixed32
+            0x1b, 0x25, 1, 2, 3, 4, 0x1c, // field 5: bytes
+            0x2a, 0x02, b'h', b'i', // field 6: max varint
+            0x30, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+        ])
+        .unwrap();
+    let bit_len = stream.bit_len();
+    let data = stream.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(0b101, stream.read_int::<u8>(3).unwrap());
+    let tag = read_pb_tag(&mut stream).unwrap();
+    assert_eq!(
+        Tag {
+            field: 1,
+            wire_type: WireType::Varint
+        },
+        tag
+    );
+    assert_eq!(150, read_pb_varint(&mut stream).unwrap());
+    for (field, wire_type) in [
+        (2, WireType::Fixed64),
+        (3, WireType::StartGroup),
+        (5, WireType::LengthDelimited),
+    ]
+    .iter()
+    {
+        let tag = read_pb_tag(&mut stream).unwrap();
+        assert_eq!((*field, *wire_type), (tag.field, tag.wire_type));
+        skip_pb_field(&mut stream, tag).unwrap();
+    }
+    assert_eq!(6, read_pb_tag(&mut stream).unwrap().field);
+    assert_eq!(u64::MAX, read_pb_varint(&mut stream).unwrap());
+    assert_eq!(bit_len, stream.pos());
+}
+
+#[test]
+fn test_read_protobuf_le() {
+    read_protobuf(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_protobuf_be() {
+    read_protobuf(BigEndian, BigEndian);
+}
+
+#[test]
+fn test_read_protobuf_invalid() {
+    use bitbuffer::protobuf::{read_pb_tag, read_pb_varint, skip_pb_field, Tag, WireType};
+
+    let read = |bytes: Vec<u8>| BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+
+    let mut stream = read(vec![0xff; 10]);
+    assert!(matches!(
+        read_pb_varint(&mut stream),
+        Err(BitError::InvalidVarint { pos: 0 })
+    ));
+    assert_eq!(0, stream.pos());
+
+    let mut stream = read(vec![
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02,
+    ]);
+    assert!(matches!(
+        read_pb_varint(&mut stream),
+        Err(BitError::InvalidVarint { pos: 0 })
+    ));
+
+    let mut stream = read(vec![0x96]);
+    assert!(matches!(
+        read_pb_varint(&mut stream),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(0, stream.pos());
+
+    let mut stream = read(vec![0x0e]);
+    assert!(matches!(
+        read_pb_tag(&mut stream),
+        Err(BitError::InvalidWireType {
+            wire_type: 6,
+            pos: 0
+        })
+    ));
+    assert_eq!(0, stream.pos());
+
+    // group 1 closed by the end tag of group 2
+    let mut stream = read(vec![0x0b, 0x14]);
+    let tag = read_pb_tag(&mut stream).unwrap();
+    assert!(matches!(
+        skip_pb_field(&mut stream, tag),
+        Err(BitError::InvalidWireType {
+            wire_type: 4,
+            pos: 8
+        })
+    ));
+
+    let mut stream = read(vec![0x12, 0x05, 0]);
+    let tag = read_pb_tag(&mut stream).unwrap();
+    assert!(matches!(
+        skip_pb_field(&mut stream, tag),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    let mut stream = read(vec![0x0b; 64]);
+    stream.set_max_depth(16);
+    assert!(matches!(
+        skip_pb_field(
+            &mut stream,
+            Tag {
+                field: 1,
+                wire_type: WireType::StartGroup
+            }
+        ),
+        Err(BitError::DepthLimitReached { max_depth: 16 })
+    ));
+}
+
+fn read_resync<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_int(0u8, 5).unwrap();
+    stream.write_int(0b1_0110_1101u16, 9).unwrap();
+    stream.write_int(0u8, 2).unwrap();
+    stream.write_int(0b1_0110_1101u16, 9).unwrap();
+    stream.write_int(0b11u8, 2).unwrap();
+    let data = stream.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(5, stream.resync_to(0b1_0110_1101, 9).unwrap());
+    assert_eq!(5, stream.pos());
+    assert_eq!(0, stream.resync_to(0b1_0110_1101, 9).unwrap());
+    stream.skip_bits(1).unwrap();
+    assert_eq!(10, stream.resync_to(0b1_0110_1101, 9).unwrap());
+    assert_eq!(16, stream.pos());
+    assert_eq!(0b1_0110_1101u16, stream.read_int::<u16>(9).unwrap());
+    assert!(matches!(
+        stream.resync_to(0b1_0110_1101, 9),
+        Err(BitError::PatternNotFound { bits: 9, .. })
+    ));
+    assert_eq!(25, stream.pos());
+    assert_eq!(0, stream.resync_to(0b11, 2).unwrap());
+    assert!(matches!(
+        stream.resync_to(0, 65),
+        Err(BitError::TooManyBits { .. })
+    ));
+
+    stream.set_pos(3).unwrap();
+    assert_eq!(5, stream.align_to(8).unwrap());
+    assert_eq!(0, stream.align_to(8).unwrap());
+    assert_eq!(24, stream.align_to(32).unwrap());
+    assert_eq!(32, stream.pos());
+    assert!(matches!(
+        stream.align_to(64),
+        Err(BitError::NotEnoughData { .. })
+    ));
+
+    stream.set_pos(4).unwrap();
+    let mut sub = stream.read_bits(20).unwrap();
+    assert_eq!(1, sub.resync_to(0b1_0110_1101, 9).unwrap());
+    assert_eq!(3, sub.align_to(4).unwrap());
+}
+
+#[test]
+fn test_read_resync_le() {
+    read_resync(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_resync_be() {
+    read_resync(BigEndian, BigEndian);
+}
+
+#[test]
+fn test_buffer_try_new() {
+    let buffer = BitReadBuffer::try_new(vec![0b1011_0101, 0b0110_1010], BigEndian).unwrap();
+    assert_eq!(16, buffer.bit_len());
+    assert_eq!(0b1011, buffer.read_int::<u8>(0, 4).unwrap());
+    assert_eq!(
+        buffer,
+        BitReadBuffer::new(vec![0b1011_0101, 0b0110_1010], BigEndian)
+    );
+
+    let buffer = BitReadBuffer::try_new(Vec::new(), LittleEndian).unwrap();
+    assert_eq!(0, buffer.bit_len());
+
+    let max = BitReadBuffer::<LittleEndian>::MAX_BYTE_LEN;
+    assert!(max.checked_mul(8).is_some());
+}
+
+#[cfg(feature = "stats")]
+#[allow(dead_code)]
+mod stats {
+    use bitbuffer::{BitRead, BitReadBuffer, BitReadStream, BitReadWithContext, LittleEndian};
+
+    #[derive(BitRead)]
+    struct Position {
+        #[size = 10]
+        x: u16,
+        #[size = 10]
+        y: u16,
+    }
+
+    #[derive(BitRead)]
+    #[discriminant_bits = 2]
+    enum Event {
+        Move(Position),
+        #[size = 5]
+        Chat(String),
+        Quit,
+    }
+
+    #[derive(BitRead)]
+    struct Frame {
+        #[size = 3]
+        count: u8,
+        #[size = "count"]
+        events: Vec<Event>,
+    }
+
+    #[derive(BitReadWithContext)]
+    #[context = "usize"]
+    struct Padded {
+        #[size = "*ctx"]
+        padding: u32,
+    }
+
+    #[test]
+    fn test_read_stats() {
+        let buffer = BitReadBuffer::new(vec![0xff; 32], LittleEndian);
+        let mut stream = BitReadStream::new(buffer);
+        let _: Position = stream.read().unwrap();
+        assert!(stream.stats().is_none());
+
+        stream.collect_stats();
+        let _: Position = stream.read().unwrap();
+        let _: Position = stream.read().unwrap();
+        let _: Padded = stream.read_with_context(&7).unwrap();
+        let _: Padded = stream.read_with_context(&3).unwrap();
+        let stats = stream.stats().unwrap();
+        assert_eq!(2, stats.of::<Position>().unwrap().reads);
+        assert_eq!(40, stats.of::<Position>().unwrap().bits);
+        assert_eq!(2, stats.of::<Padded>().unwrap().reads);
+        assert_eq!(10, stats.of::<Padded>().unwrap().bits);
+        assert!(stats.of::<Frame>().is_none());
+
+        // count 3: quit, move, chat
+        let mut data = bitbuffer::BitWriteStream::new(LittleEndian);
+        data.write_int(3u8, 3).unwrap();
+        data.write_int(2u8, 2).unwrap();
+        data.write_int(0u8, 2).unwrap();
+        data.write_int(0u32, 20).unwrap();
+        data.write_int(1u8, 2).unwrap();
+        data.write_string("hello", Some(5)).unwrap();
+        let buffer = BitReadBuffer::new(data.finish(), LittleEndian);
+        let mut stream = BitReadStream::new(buffer);
+        stream.collect_stats();
+        let _: Frame = stream.read().unwrap();
+        let stats = stream.stats().unwrap();
+        assert_eq!(1, stats.of::<Frame>().unwrap().reads);
+        assert_eq!(3 + 2 + 22 + 42, stats.of::<Frame>().unwrap().bits);
+        assert_eq!(3, stats.of::<Event>().unwrap().reads);
+        assert_eq!(2 + 22 + 42, stats.of::<Event>().unwrap().bits);
+        assert_eq!(20, stats.of::<Position>().unwrap().bits);
+        assert!(stats.get("u8").is_none());
+
+        let names: Vec<_> = stats.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            vec![
+                std::any::type_name::<Frame>(),
+                std::any::type_name::<Event>(),
+                std::any::type_name::<Position>()
+            ],
+            names
+        );
+        let report = stats.to_string();
+        assert!(report.starts_with(&format!(
+            "{}: 69 bits in 1 reads (69.0 bits per read)\n",
+            std::any::type_name::<Frame>()
+        )));
+        assert_eq!(3, report.lines().count());
+    }
+}
+
+fn read_bufread<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::BufReadStream;
+    use std::io::{BufReader, Cursor};
+
+    #[derive(BitRead, Debug, PartialEq)]
+    struct Record {
+        #[size = 5]
+        kind: u8,
+        value: u16,
+    }
+
+    let mut write = BitWriteStream::new(write_endianness);
+    for index in 0..100u16 {
+        write.write_int(index as u8 & 31, 5).unwrap();
+        write.write_int(index * 3, 16).unwrap();
+    }
+    write.write_bytes(&[0xff; 40]).unwrap();
+    write.write_int(0b101u8, 3).unwrap();
+    let bytes = write.finish();
+
+    // a small reader buffer to force many partial reads
+    let reader = BufReader::with_capacity(3, Cursor::new(bytes));
+    let mut stream = BitReadStream::from_bufread(reader, 8, read_endianness);
+    assert_eq!(8, stream.window_bytes());
+    for index in 0..100u16 {
+        assert_eq!(index as u8 & 31, stream.peek_int::<u8>(5).unwrap());
+        let record: Record = stream.read().unwrap();
+        assert_eq!(index as u8 & 31, record.kind);
+        assert_eq!(index * 3, record.value);
+    }
+    assert_eq!(2100, stream.pos());
+
+    // values larger than the window can't be read, but can be skipped
+    assert!(matches!(
+        stream.read_sized::<Vec<u8>>(10),
+        Err(BitError::WindowExceeded {
+            pos: 2100,
+            window_bytes: 8
+        })
+    ));
+    assert_eq!(2100, stream.pos());
+    // the window needs to include the partially read byte
+    assert!(stream.peek_sized::<Vec<u8>>(8).is_err());
+    assert_eq!(vec![0xff; 7], stream.read_sized::<Vec<u8>>(7).unwrap());
+    stream.skip_bits(32 * 8).unwrap();
+    assert!(!stream.at_end().unwrap());
+    assert!(stream.read_bool().unwrap());
+    stream.skip_bits(7).unwrap();
+    assert_eq!(0b101, stream.read_int::<u8>(3).unwrap());
+    assert!(matches!(
+        stream.read_int::<u8>(5),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    // padding of the last byte
+    assert!(!stream.read_bool().unwrap());
+    assert!(stream.at_end().unwrap());
+    assert!(stream.skip_bits(1).is_err());
+
+    let mut stream = BufReadStream::new(Cursor::new(vec![1, 2, 3]), 2, LittleEndian);
+    assert!(stream.skip_bits(25).is_err());
+    assert_eq!(24, stream.pos());
+    assert!(stream.at_end().unwrap());
+}
+
+#[test]
+fn test_read_bufread_le() {
+    read_bufread(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_bufread_be() {
+    read_bufread(BigEndian, BigEndian);
+}
+
+#[test]
+fn test_read_bufread_io_error() {
+    use std::io::{self, BufReader, Read};
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disconnected"))
+        }
+    }
+
+    let mut stream = BitReadStream::from_bufread(BufReader::new(FailingReader), 8, LittleEndian);
+    assert!(matches!(stream.read::<u8>(), Err(BitError::Io(_))));
+
+    struct FailingAfter(u8);
+
+    impl Read for FailingAfter {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0 == 6 {
+                return Err(io::Error::other("disconnected"));
+            }
+            self.0 += 1;
+            buf[0] = self.0;
+            Ok(1)
+        }
+    }
+
+    // the bytes read before the error are still returned, even when the window is refilled early
+    let reader = BufReader::with_capacity(1, FailingAfter(0));
+    let mut stream = BitReadStream::from_bufread(reader, 8, LittleEndian);
+    for expected in 1..=6u8 {
+        assert_eq!(expected, stream.read::<u8>().unwrap());
+    }
+    assert!(matches!(stream.read::<u8>(), Err(BitError::Io(_))));
+}
+
+#[cfg(feature = "prefetch")]
+mod prefetch {
+    use super::BYTES;
+    use bitbuffer::{
+        BigEndian, BitError, BitReadBuffer, BitReadStream, LittleEndian, PrefetchReader,
+    };
+    use std::io::{self, BufRead, Cursor, Read};
+
+    #[test]
+    fn test_read_prefetch() {
+        let buffer = BitReadBuffer::new(BYTES.to_vec(), BigEndian);
+        let mut expected = BitReadStream::new(buffer);
+        let mut stream =
+            BitReadStream::from_read_prefetch(Cursor::new(BYTES.to_vec()), 8, BigEndian);
+        for size in [1, 7, 13, 2, 20, 5, 16].iter() {
+            assert_eq!(
+                expected.read_int::<u32>(*size).unwrap(),
+                stream.read_int::<u32>(*size).unwrap()
+            );
+        }
+        assert_eq!(expected.pos(), stream.pos());
+    }
+
+    #[test]
+    fn test_prefetch_reader() {
+        let mut reader = PrefetchReader::new(Cursor::new((0..100).collect::<Vec<u8>>()), 7, 2);
+        let mut first = [0; 10];
+        reader.read_exact(&mut first).unwrap();
+        assert_eq!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9], first);
+        // chunks are only as large as the chunk size
+        assert_eq!(4, reader.fill_buf().unwrap().len());
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!((10..100).collect::<Vec<u8>>(), rest);
+        assert!(reader.fill_buf().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prefetch_io_error() {
+        struct FailingReader(usize);
+
+        impl Read for FailingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(io::Error::other("disconnected"));
+                }
+                self.0 -= 1;
+                buf[0] = 0xff;
+                Ok(1)
+            }
+        }
+
+        let mut stream = BitReadStream::from_read_prefetch(FailingReader(2), 4, LittleEndian);
+        assert_eq!(0xffff, stream.read_int::<u16>(16).unwrap());
+        assert!(matches!(stream.read::<u8>(), Err(BitError::Io(_))));
+    }
+
+    #[test]
+    fn test_prefetch_reader_panic() {
+        struct PanickingReader(usize);
+
+        impl Read for PanickingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0 == 0 {
+                    panic!("reader failed");
+                }
+                self.0 -= 1;
+                buf[0] = 0xff;
+                Ok(1)
+            }
+        }
+
+        let mut reader = PrefetchReader::new(PanickingReader(2), 4, 2);
+        let mut data = Vec::new();
+        // the panic is reported as an error instead of the end of the data
+        assert!(reader.read_to_end(&mut data).is_err());
+        assert_eq!(vec![0xff, 0xff], data);
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compress {
+    use bitbuffer::{BigEndian, BitError, BitReadBuffer, BitReadStream, LittleEndian};
+    use std::io::Cursor;
+
+    fn data() -> Vec<u8> {
+        (0..2000u32).map(|i| (i * 7 % 251) as u8).collect()
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn test_read_gzip() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&data()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let buffer =
+            BitReadBuffer::from_gzip(Cursor::new(compressed.clone()), LittleEndian).unwrap();
+        let expected = BitReadBuffer::new(data(), LittleEndian);
+        assert_eq!(expected, buffer);
+
+        let mut stream = BitReadStream::from_gzip(Cursor::new(compressed), 16, LittleEndian);
+        let mut expected = BitReadStream::new(expected);
+        while expected.bits_left() >= 13 {
+            assert_eq!(
+                expected.read_int::<u16>(13).unwrap(),
+                stream.read_int::<u16>(13).unwrap()
+            );
+        }
+        stream.skip_bits(expected.bits_left()).unwrap();
+        assert!(stream.at_end().unwrap());
+
+        assert!(matches!(
+            BitReadBuffer::from_gzip(Cursor::new(vec![1, 2, 3]), BigEndian),
+            Err(BitError::Io(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn test_read_zstd() {
+        let compressed = zstd::encode_all(Cursor::new(data()), 0).unwrap();
+
+        let buffer = BitReadBuffer::from_zstd(Cursor::new(compressed.clone()), BigEndian).unwrap();
+        let expected = BitReadBuffer::new(data(), BigEndian);
+        assert_eq!(expected, buffer);
+
+        let mut stream = BitReadStream::from_zstd(Cursor::new(compressed), 16, BigEndian).unwrap();
+        let mut expected = BitReadStream::new(expected);
+        while expected.bits_left() >= 13 {
+            assert_eq!(
+                expected.read_int::<u16>(13).unwrap(),
+                stream.read_int::<u16>(13).unwrap()
+            );
+        }
+
+        assert!(matches!(
+            BitReadBuffer::from_zstd(Cursor::new(vec![1, 2, 3]), LittleEndian),
+            Err(BitError::Io(_))
+        ));
+    }
+}
+
+fn read_guard<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut write = BitWriteStream::new(write_endianness);
+    write.write_bool(true).unwrap();
+    write.write_int(-5i8, 5).unwrap();
+    write.write_int(1234u16, 13).unwrap();
+    write.write(&1.5f32).unwrap();
+    write.write_bytes(&[1, 2, 3]).unwrap();
+    write.write(&0x1234_5678u32).unwrap();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(write.finish(), read_endianness));
+
+    let mut guard = stream.guard(1 + 5 + 13 + 32 + 24 + 32).unwrap();
+    assert!(guard.read_bool());
+    assert_eq!(-5i8, guard.read_int::<i8>(5));
+    assert_eq!(1234u16, guard.read_int::<u16>(13));
+    assert_eq!(1.5f32, guard.read_float::<f32>());
+    assert_eq!(vec![1, 2, 3], guard.read_bytes(3));
+    assert_eq!(32, guard.remaining());
+    assert_eq!(0x1234_5678u32, guard.read::<u32>().unwrap());
+    assert_eq!(0, guard.remaining());
+    assert_eq!(107, stream.pos());
+
+    assert!(matches!(
+        stream.guard(stream.bits_left() + 1),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    stream.set_pos(0).unwrap();
+    stream.set_read_limit(8);
+    assert!(matches!(
+        stream.guard(9),
+        Err(BitError::ReadLimitReached { .. })
+    ));
+    let mut guard = stream.guard(8).unwrap();
+    guard.read_int::<u8>(8);
+    assert_eq!(Some(0), stream.read_limit_left());
+}
+
+#[test]
+fn test_read_guard_le() {
+    read_guard(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_guard_be() {
+    read_guard(BigEndian, BigEndian);
+}
+
+#[test]
+#[should_panic(expected = "reading 9 bits from a guard with only 8 bits remaining")]
+fn test_read_guard_overread() {
+    let mut stream = BitReadStream::new(BitReadBuffer::new(vec![0; 4], LittleEndian));
+    let mut guard = stream.guard(8).unwrap();
+    guard.read_int::<u16>(9);
+}
+
+fn read_bools<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let flags: Vec<bool> = (0..100).map(|i| i % 3 == 0 || i % 7 == 1).collect();
+    let mut write = BitWriteStream::new(write_endianness);
+    write.write_bool(true).unwrap();
+    for flag in &flags {
+        write.write_bool(*flag).unwrap();
+    }
+    let mut stream = BitReadStream::new(BitReadBuffer::new(write.finish(), read_endianness));
+    stream.skip_bits(1).unwrap();
+
+    let mut read = Vec::new();
+    for count in [0, 1, 7, 64, 28] {
+        let mask = stream.read_bools(count).unwrap();
+        if count < 64 {
+            assert_eq!(0, mask >> count);
+        }
+        read.extend((0..count).map(|i| mask & (1 << i) != 0));
+    }
+    assert_eq!(flags, read);
+
+    stream.set_pos(0).unwrap();
+    assert!(matches!(
+        stream.read_bools(65),
+        Err(BitError::TooManyBits {
+            requested: 65,
+            max: 64
+        })
+    ));
+    stream.set_pos(100).unwrap();
+    assert!(matches!(
+        stream.read_bools(5),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(100, stream.pos());
+}
+
+#[test]
+fn test_read_bools_le() {
+    read_bools(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_bools_be() {
+    read_bools(BigEndian, BigEndian);
+}
+
+fn read_tlv<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::tlv::{TlvFormat, TlvHandlers};
+
+    #[derive(Debug, PartialEq)]
+    enum Chunk {
+        Flags(u8),
+        Point(i16, i16),
+    }
+
+    let mut write = BitWriteStream::new(write_endianness);
+    // 4 bit tag, 12 bit length
+    write.write_int(1u8, 4).unwrap();
+    write.write_int(1u16, 12).unwrap();
+    write.write_int(0b1010_0101u8, 8).unwrap();
+    write.write_int(7u8, 4).unwrap();
+    write.write_int(3u16, 12).unwrap();
+    write.write_bytes(&[1, 2, 3]).unwrap();
+    write.write_int(2u8, 4).unwrap();
+    write.write_int(6u16, 12).unwrap();
+    write.write_int(-5i16, 16).unwrap();
+    write.write_int(300i16, 16).unwrap();
+    // trailing data in the chunk that the handler doesn't read
+    write.write_int(0xffffu16, 16).unwrap();
+    write.write_int(1u8, 4).unwrap();
+    write.write_int(1u16, 12).unwrap();
+    write.write_int(3u8, 8).unwrap();
+    let full = BitReadStream::new(BitReadBuffer::new(write.finish(), read_endianness));
+
+    let format = TlvFormat::new(4, 12);
+    let mut stream = full.clone();
+    let tags = format
+        .chunks(&mut stream)
+        .map(|chunk| chunk.map(|chunk| (chunk.tag, chunk.data.bit_len())))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(tags, vec![(1, 8), (7, 24), (2, 48), (1, 8)]);
+    assert_eq!(stream.bits_left(), 0);
+
+    let handlers = TlvHandlers::new(format)
+        .on(1, |data| Ok(Chunk::Flags(data.read()?)))
+        .on(2, |data| Ok(Chunk::Point(data.read()?, data.read()?)));
+    let mut stream = full.clone();
+    assert_eq!(
+        handlers.read_all(&mut stream).unwrap(),
+        vec![
+            Chunk::Flags(0b1010_0101),
+            Chunk::Point(-5, 300),
+            Chunk::Flags(3)
+        ]
+    );
+
+    // a length past the end of the stream leaves the position at the start of the chunk
+    let mut stream = full.clone().read_bits(full.bit_len() - 8).unwrap();
+    let mut chunks = format.chunks(&mut stream);
+    assert_eq!(chunks.by_ref().take(3).filter(Result::is_ok).count(), 3);
+    assert!(matches!(
+        chunks.next(),
+        Some(Err(BitError::NotEnoughData { .. }))
+    ));
+    assert!(chunks.next().is_none());
+    assert_eq!(stream.pos(), 16 + 8 + 16 + 24 + 16 + 48);
+}
+
+#[test]
+fn test_read_tlv_le() {
+    read_tlv(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_tlv_be() {
+    read_tlv(BigEndian, BigEndian);
+}
+
+fn parser_registry<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::ParserRegistry;
+
+    let mut write = BitWriteStream::new(write_endianness);
+    write.write_int(0x1234u16, 16).unwrap();
+    write.write_int(5u8, 3).unwrap();
+    write.write_bool(true).unwrap();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(write.finish(), read_endianness));
+
+    let mut registry = ParserRegistry::new();
+    registry.register_type::<u16>(1);
+    registry.register(2, |stream| {
+        Ok((stream.read_int::<u8>(3)?, stream.read_bool()?))
+    });
+    assert!(registry.contains(1));
+    assert!(!registry.contains(3));
+
+    assert!(matches!(
+        registry.read_dynamic(&mut stream, 3),
+        Err(BitError::UnknownParser { id: 3 })
+    ));
+    let value = registry.read_dynamic(&mut stream, 1).unwrap();
+    assert_eq!(value.downcast_ref::<u16>(), Some(&0x1234));
+
+    // a failing parser doesn't move the stream
+    assert!(registry.read_dynamic(&mut stream, 1).is_err());
+    assert_eq!(stream.pos(), 16);
+
+    let value = registry.read_dynamic(&mut stream, 2).unwrap();
+    assert_eq!(value.downcast::<(u8, bool)>().unwrap(), Box::new((5, true)));
+
+    assert!(registry.unregister(2));
+    assert!(!registry.unregister(2));
+    assert!(!registry.contains(2));
+}
+
+#[test]
+fn test_parser_registry_le() {
+    parser_registry(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_parser_registry_be() {
+    parser_registry(BigEndian, BigEndian);
+}
+
+fn read_rev<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut write = BitWriteStream::new(write_endianness);
+    for i in 0..20u32 {
+        write.write_int(i * 7, (i % 13 + 8) as usize).unwrap();
+    }
+    write.write_float(12.5f32).unwrap();
+    write.write_bool(false).unwrap();
+    // end marker and padding
+    write.write_bool(true).unwrap();
+    let padding = (8 - write.bit_len() % 8) % 8;
+    write.write_int(0u8, padding).unwrap();
+
+    let mut stream = BitReadStreamRev::new(BitReadBuffer::new(write.finish(), read_endianness));
+    stream.skip_padding().unwrap();
+    assert!(!stream.read_bool().unwrap());
+    assert_eq!(stream.read_float::<f32>().unwrap(), 12.5);
+    for i in (0..20u32).rev() {
+        assert_eq!(
+            stream.read_int::<u32>((i % 13 + 8) as usize).unwrap(),
+            i * 7
+        );
+    }
+    assert_eq!(stream.pos(), 0);
+    assert!(matches!(
+        stream.read_int::<u8>(1),
+        Err(BitError::NotEnoughData {
+            requested: 1,
+            bits_left: 0
+        })
+    ));
+
+    stream.set_pos(8).unwrap();
+    assert!(matches!(
+        stream.read_int::<u16>(9),
+        Err(BitError::NotEnoughData {
+            requested: 9,
+            bits_left: 8
+        })
+    ));
+    stream.set_pos(16).unwrap();
+    assert!(matches!(
+        stream.read_int::<u8>(9),
+        Err(BitError::TooManyBits { .. })
+    ));
+    stream.set_pos(8).unwrap();
+    stream.skip_bits(3).unwrap();
+    assert_eq!(stream.bits_left(), 5);
+    assert!(stream.set_pos(stream.bit_len() + 1).is_err());
+
+    let mut empty = BitReadStreamRev::<E>::new(BitReadBuffer::from(vec![0, 0]));
+    assert!(empty.skip_padding().is_err());
+    assert_eq!(empty.pos(), 16);
+}
+
+#[test]
+fn test_read_rev_le() {
+    read_rev(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_rev_be() {
+    read_rev(BigEndian, BigEndian);
+}
+
+fn read_dual<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut write = BitWriteStream::new(write_endianness);
+    // forward values of 5 bits at the start, backward values of 3 bits from the end
+    for i in 0..4u8 {
+        write.write_int(i + 10, 5).unwrap();
+    }
+    write.write_int(0b1111u8, 4).unwrap();
+    for i in (0..4u8).rev() {
+        write.write_int(i, 3).unwrap();
+    }
+    let mut stream = DualReadStream::new(BitReadBuffer::new(write.finish(), read_endianness));
+    assert_eq!(stream.bits_left(), 40);
+    // skip the padding of the last byte
+    stream
+        .read_backward(|backward| backward.skip_bits(4))
+        .unwrap();
+
+    let pairs = stream
+        .alternate(
+            4,
+            |forward| forward.read_int::<u8>(5),
+            |backward| backward.read_int::<u8>(3),
+        )
+        .unwrap();
+    assert_eq!(pairs, vec![(10, 0), (11, 1), (12, 2), (13, 3)]);
+    assert_eq!((stream.forward_pos(), stream.backward_pos()), (20, 24));
+
+    // neither cursor can read past the other, failed reads don't move the cursors
+    assert!(matches!(
+        stream.read_forward(|forward| forward.read_int::<u8>(5)),
+        Err(BitError::NotEnoughData {
+            requested: 5,
+            bits_left: 4
+        })
+    ));
+    assert!(matches!(
+        stream.read_backward(|backward| {
+            backward.read_int::<u8>(2)?;
+            backward.read_int::<u8>(3)
+        }),
+        Err(BitError::NotEnoughData {
+            requested: 3,
+            bits_left: 2
+        })
+    ));
+    assert_eq!((stream.forward_pos(), stream.backward_pos()), (20, 24));
+    assert_eq!(
+        stream
+            .read_backward(|backward| backward.read_int::<u8>(1))
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        stream
+            .read_forward(|forward| forward.read_int::<u8>(3))
+            .unwrap(),
+        0b111
+    );
+    assert_eq!(stream.bits_left(), 0);
+
+    let (forward, mut backward) = stream.into_parts();
+    assert_eq!(forward.pos(), 23);
+    assert_eq!(backward.read_int::<u8>(3).unwrap(), 0b111);
+}
+
+#[test]
+fn test_read_dual_le() {
+    read_dual(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_read_dual_be() {
+    read_dual(BigEndian, BigEndian);
+}
+
+static STORAGE_TEXT: &[u8] = b"\x01hello";
+
+fn storage_buffers<E: Endianness>(endianness: fn() -> E) -> Vec<BitReadBuffer<E>> {
+    use std::sync::Arc;
+
+    vec![
+        BitReadBuffer::new(BYTES.to_vec(), endianness()),
+        BitReadBuffer::try_new(BYTES, endianness()).unwrap(),
+        BitReadBuffer::new(Arc::<[u8]>::from(BYTES), endianness()),
+        BitReadBuffer::new(BYTES.to_vec().into_boxed_slice(), endianness()),
+    ]
+}
+
+fn read_storage<E: Endianness>(endianness: fn() -> E) {
+    let expected = BitReadBuffer::new(BYTES.to_vec(), endianness());
+    let len = expected.bit_len();
+    for buffer in storage_buffers(endianness) {
+        assert_eq!(buffer, expected);
+        assert_eq!(buffer.byte_len(), BYTES.len());
+        // unaligned reads up to the end of the storage
+        for pos in len - 64..len {
+            assert_eq!(
+                buffer.read_int::<u64>(pos, len - pos).unwrap(),
+                expected.read_int::<u64>(pos, len - pos).unwrap()
+            );
+        }
+        assert_eq!(
+            buffer.read_bytes(len - 8 * 5 - 3, 5).unwrap(),
+            expected.read_bytes(len - 8 * 5 - 3, 5).unwrap()
+        );
+    }
+
+    // strings without null terminator up to the end of the storage
+    let buffer = BitReadBuffer::new(STORAGE_TEXT, endianness());
+    assert_eq!(buffer.read_string(8, None).unwrap(), "hello");
+    let owned = BitReadBuffer::new(STORAGE_TEXT.to_vec(), endianness());
+    for pos in 0..16 {
+        assert_eq!(
+            buffer.read_string(pos, None).ok(),
+            owned.read_string(pos, None).ok()
+        );
+        let sub = buffer.sub_buffer(0..43).unwrap();
+        let owned_sub = owned.sub_buffer(0..43).unwrap();
+        assert_eq!(
+            sub.read_string(pos, None).ok(),
+            owned_sub.read_string(pos, None).ok()
+        );
+    }
+}
+
+#[test]
+fn test_read_storage_le() {
+    read_storage(|| LittleEndian);
+}
+
+#[test]
+fn test_read_storage_be() {
+    read_storage(|| BigEndian);
+}
+
+fn read_dyn_values(reader: &mut dyn bitbuffer::DynBitRead) {
+    assert!(reader.read_bool().unwrap());
+    assert_eq!(reader.read_uint(12).unwrap(), 1234);
+    assert_eq!(reader.read_sint(7).unwrap(), -20);
+    assert_eq!(reader.read_f32().unwrap(), 1.5);
+    assert_eq!(reader.read_f64().unwrap(), -0.25);
+    assert_eq!(reader.read_bytes(3).unwrap(), vec![1, 2, 3]);
+    assert_eq!(reader.read_string(None).unwrap(), "null");
+    assert_eq!(reader.read_string(Some(5)).unwrap(), "fixed");
+    reader.skip_bits(4).unwrap();
+    assert_eq!(reader.pos(), 1 + 12 + 7 + 32 + 64 + 24 + 40 + 40 + 4);
+    assert!(!reader.at_end().unwrap());
+    assert_eq!(reader.read_uint(64).unwrap(), u64::MAX);
+    assert!(reader.read_uint(65).is_err());
+    assert!(reader.at_end().unwrap());
+    assert!(reader.read_bool().is_err());
+}
+
+fn read_dyn<E: Endianness + 'static>(endianness: fn() -> E) {
+    use bitbuffer::{BufReadStream, DynBitRead};
+
+    let is_le = E::is_le();
+    let mut write = BitWriteStream::new(endianness());
+    write.write_bool(true).unwrap();
+    write.write_int(1234u16, 12).unwrap();
+    write.write_int(-20i8, 7).unwrap();
+    write.write_float(1.5f32).unwrap();
+    write.write_float(-0.25f64).unwrap();
+    write.write_bytes(&[1, 2, 3]).unwrap();
+    write.write_string("null", None).unwrap();
+    write.write_string("fixed", Some(5)).unwrap();
+    write.write_int(0u8, 4).unwrap();
+    write.write_int(u64::MAX, 64).unwrap();
+    let bytes = write.finish();
+
+    let stream = BitReadStream::new(BitReadBuffer::new(bytes.clone(), endianness()));
+    let buffered = BufReadStream::new(std::io::Cursor::new(bytes), 16, endianness());
+    let mut readers: Vec<Box<dyn DynBitRead>> = vec![Box::new(stream), Box::new(buffered)];
+    for reader in readers.iter_mut() {
+        assert_eq!(reader.is_le(), is_le);
+        read_dyn_values(reader.as_mut());
+    }
+}
+
+#[test]
+fn test_read_dyn_le() {
+    read_dyn(|| LittleEndian);
+}
+
+#[test]
+fn test_read_dyn_be() {
+    read_dyn(|| BigEndian);
+}
+
+fn read_string_mode<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_bool(true).unwrap();
+    write.write_bytes(b"a\0b\0\0").unwrap();
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+    stream.skip_bits(1).unwrap();
+
+    let read = |stream: &mut BitReadStream<E>, len, mode| {
+        stream.set_pos(1).unwrap();
+        let result = stream.read_string_mode(len, mode).unwrap();
+        assert_eq!(1 + len * 8, stream.pos());
+        result
+    };
+    assert_eq!("a", read(&mut stream, 5, StringMode::NullTerminated));
+    assert_eq!("a\0b\0\0", read(&mut stream, 5, StringMode::FixedKeepNulls));
+    assert_eq!(
+        "a\0b",
+        read(&mut stream, 5, StringMode::FixedTrimTrailingNulls)
+    );
+    assert_eq!(
+        "a",
+        read(&mut stream, 2, StringMode::FixedTrimTrailingNulls)
+    );
+    stream.set_pos(1).unwrap();
+    assert_eq!("a\0b", stream.read_string(Some(5)).unwrap());
+
+    // only the bytes before the null byte need to be valid utf8
+    let mut stream = BitReadStream::new(BitReadBuffer::new(vec![b'a', 0, 0xff], endianness()));
+    assert_eq!(
+        "a",
+        stream
+            .read_string_mode(3, StringMode::NullTerminated)
+            .unwrap()
+    );
+    stream.set_pos(0).unwrap();
+    assert!(matches!(
+        stream.read_string_mode(3, StringMode::FixedKeepNulls),
+        Err(BitError::Utf8Error(_))
+    ));
+    assert_eq!(24, stream.pos());
+}
+
+#[test]
+fn test_read_string_mode_le() {
+    read_string_mode(|| LittleEndian);
+}
+
+#[test]
+fn test_read_string_mode_be() {
+    read_string_mode(|| BigEndian);
+}
+
+fn read_string_lossy<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_bool(true).unwrap();
+    write.write_bytes(&[b'a', 0xff, b'b', 0]).unwrap();
+    write.write_bytes(&[0xc3, b'c', 0, 0]).unwrap();
+    write.write_bytes(b"ok\0").unwrap();
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+    stream.skip_bits(1).unwrap();
+
+    assert_eq!("a\u{FFFD}b", stream.read_string_lossy(None).unwrap());
+    assert_eq!(1 + 4 * 8, stream.pos());
+    assert_eq!("\u{FFFD}c", stream.read_string_lossy(Some(4)).unwrap());
+    assert_eq!(1 + 8 * 8, stream.pos());
+    assert_eq!("ok", stream.read_string_lossy(None).unwrap());
+    assert!(matches!(
+        stream.read_string_lossy(Some(1)),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_string_lossy_le() {
+    read_string_lossy(|| LittleEndian);
+}
+
+#[test]
+fn test_read_string_lossy_be() {
+    read_string_lossy(|| BigEndian);
+}
+
+fn read_bytes_until<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(5u8, 3).unwrap();
+    write.write_bytes(&[1, 2, 0x7e, 0x7e, 3, 0x7e]).unwrap();
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+    assert_eq!(5u8, stream.read_int::<u8>(3).unwrap());
+
+    assert!(matches!(
+        stream.read_bytes_until(0x7e, 1),
+        Err(BitError::TerminatorNotFound {
+            terminator: 0x7e,
+            max: 1
+        })
+    ));
+    assert_eq!(3, stream.pos());
+    assert_eq!(vec![1, 2], stream.read_bytes_until(0x7e, 2).unwrap());
+    assert_eq!(3 + 3 * 8, stream.pos());
+    assert_eq!(Vec::<u8>::new(), stream.read_bytes_until(0x7e, 0).unwrap());
+    assert!(matches!(
+        stream.read_bytes_until(0xff, 16),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(3 + 4 * 8, stream.pos());
+    assert_eq!(vec![3], stream.read_bytes_until(0x7e, 16).unwrap());
+    assert!(matches!(
+        stream.read_bytes_until(0x7e, 16),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_bytes_until_le() {
+    read_bytes_until(|| LittleEndian);
+}
+
+#[test]
+fn test_read_bytes_until_be() {
+    read_bytes_until(|| BigEndian);
+}
+
+fn read_bytes_until_aligned<E: Endianness>(endianness: fn() -> E) {
+    let buffer = BitReadBuffer::new(vec![1, 2, 0x7e, 0x7e, 3, 0x7e, 4], endianness());
+    let mut stream = BitReadStream::new(buffer);
+
+    assert!(matches!(
+        stream.read_bytes_until(0x7e, 1),
+        Err(BitError::TerminatorNotFound {
+            terminator: 0x7e,
+            max: 1
+        })
+    ));
+    assert_eq!(0, stream.pos());
+    assert_eq!(vec![1, 2], stream.read_bytes_until(0x7e, 2).unwrap());
+    assert_eq!(3 * 8, stream.pos());
+    assert_eq!(Vec::<u8>::new(), stream.read_bytes_until(0x7e, 0).unwrap());
+    assert!(matches!(
+        stream.read_bytes_until(0xff, 16),
+        Err(BitError::NotEnoughData {
+            requested: 32,
+            bits_left: 24
+        })
+    ));
+    assert!(matches!(
+        stream.read_bytes_until(0xff, 3),
+        Err(BitError::NotEnoughData {
+            requested: 32,
+            bits_left: 24
+        })
+    ));
+    assert!(matches!(
+        stream.read_bytes_until(0xff, 2),
+        Err(BitError::TerminatorNotFound {
+            terminator: 0xff,
+            max: 2
+        })
+    ));
+    assert_eq!(4 * 8, stream.pos());
+
+    stream.set_read_limit(8);
+    assert!(matches!(
+        stream.read_bytes_until(0x7e, 16),
+        Err(BitError::ReadLimitReached {
+            requested: 16,
+            remaining: 8
+        })
+    ));
+    assert_eq!(4 * 8, stream.pos());
+}
+
+#[test]
+fn test_read_bytes_until_aligned_le() {
+    read_bytes_until_aligned(|| LittleEndian);
+}
+
+#[test]
+fn test_read_bytes_until_aligned_be() {
+    read_bytes_until_aligned(|| BigEndian);
+}
+
+fn read_bcd<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_bool(true).unwrap();
+    for digit in [1u8, 9, 0, 7, 0xc, 3] {
+        write.write_int(digit, 4).unwrap();
+    }
+    for _ in 0..20 {
+        write.write_int(9u8, 4).unwrap();
+    }
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+    assert_eq!(true, stream.read_bool().unwrap());
+
+    assert_eq!(1907u16, stream.read_bcd::<u16>(4).unwrap());
+    assert_eq!(17, stream.pos());
+    assert!(matches!(
+        stream.read_bcd::<u8>(2),
+        Err(BitError::InvalidBcdDigit {
+            digit: 0xc,
+            pos: 17
+        })
+    ));
+    assert_eq!(17, stream.pos());
+    assert_eq!(0xc, stream.read_nibble().unwrap());
+    assert_eq!(3, stream.read_nibble().unwrap());
+    assert_eq!(0u8, stream.read_bcd::<u8>(0).unwrap());
+
+    let start = stream.pos();
+    assert!(matches!(
+        stream.read_bcd::<u8>(3),
+        Err(BitError::IntegerOutOfRange {
+            value: 999,
+            bits: 8
+        })
+    ));
+    assert!(matches!(
+        stream.read_bcd::<u64>(20),
+        Err(BitError::IntegerOutOfRange { bits: 64, .. })
+    ));
+    assert_eq!(start, stream.pos());
+    assert_eq!(
+        99_999_999_999_999_999u64,
+        stream.read_bcd::<u64>(17).unwrap()
+    );
+    assert!(matches!(
+        stream.read_bcd::<u64>(5),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_bcd_le() {
+    read_bcd(|| LittleEndian);
+}
+
+#[test]
+fn test_read_bcd_be() {
+    read_bcd(|| BigEndian);
+}
+
+fn read_packed_ascii<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_int(3u8, 2).unwrap();
+    for char in "Hi 7bit!".bytes() {
+        write.write_int(char, 7).unwrap();
+    }
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+    assert_eq!(3u8, stream.read_int::<u8>(2).unwrap());
+
+    assert!(matches!(
+        stream.read_packed_ascii(9),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(2, stream.pos());
+    assert_eq!("Hi 7bit!", stream.read_packed_ascii(8).unwrap());
+    assert_eq!(2 + 8 * 7, stream.pos());
+    assert_eq!("", stream.read_packed_ascii(0).unwrap());
+}
+
+#[test]
+fn test_read_packed_ascii_le() {
+    read_packed_ascii(|| LittleEndian);
+}
+
+#[test]
+fn test_read_packed_ascii_be() {
+    read_packed_ascii(|| BigEndian);
+}
+
+#[test]
+fn test_read_packed_ascii_gsm() {
+    // "hellohello" from the GSM 03.38 specification examples
+    let bytes = vec![0xe8, 0x32, 0x9b, 0xfd, 0x46, 0x97, 0xd9, 0xec, 0x37];
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, LittleEndian));
+    assert_eq!("hellohello", stream.read_packed_ascii(10).unwrap());
+}
+
+fn read_into<E: Endianness>(endianness: fn() -> E) {
+    let mut write = BitWriteStream::new(endianness());
+    write.write_bool(false).unwrap();
+    write.write_string("first", None).unwrap();
+    write.write_int(300u16, 12).unwrap();
+    write.write_string("ab", Some(4)).unwrap();
+    for value in [1u16, 2, 3] {
+        write.write_int(value, 16).unwrap();
+    }
+    write.write_string("x", None).unwrap();
+    write.write_string("yz", None).unwrap();
+    write.write_bytes(&[0xff, 0]).unwrap();
+    let bytes = write.finish();
+    let mut stream = BitReadStream::new(BitReadBuffer::new(bytes, endianness()));
+
+    let mut flag = true;
+    stream.read_into(&mut flag).unwrap();
+    assert_eq!(false, flag);
+
+    let mut string = String::with_capacity(64);
+    let capacity = string.capacity();
+    stream.read_into(&mut string).unwrap();
+    assert_eq!("first", string);
+    assert_eq!(300u16, stream.read_int::<u16>(12).unwrap());
+    stream.read_into_sized(&mut string, 4).unwrap();
+    assert_eq!("ab", string);
+    assert_eq!(capacity, string.capacity());
+
+    let mut values = vec![9u16; 5];
+    stream.read_into_sized(&mut values, 3).unwrap();
+    assert_eq!(vec![1, 2, 3], values);
+
+    let mut strings = vec![String::with_capacity(32)];
+    stream.read_into_sized(&mut strings, 2).unwrap();
+    assert_eq!(vec!["x", "yz"], strings);
+    assert_eq!(32, strings[0].capacity());
+
+    // invalid utf8 leaves the string empty
+    assert!(matches!(
+        stream.read_into(&mut string),
+        Err(BitError::Utf8Error(_))
+    ));
+    assert_eq!("", string);
+    assert!(matches!(
+        stream.read_into_sized(&mut values, 4),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_read_into_le() {
+    read_into(|| LittleEndian);
+}
+
+#[test]
+fn test_read_into_be() {
+    read_into(|| BigEndian);
+}
+
+fn frames_sized<E: Endianness>(endianness: fn() -> E) {
+    let bytes = vec![16, 0xff, 0x0f, 0, 24, 0x01, 0x02];
+    let buffer = BitReadBuffer::new(bytes, endianness());
+    let mut stream = BitReadStream::new(buffer);
+    let mut frames = stream.frames_sized::<u8>();
+
+    let mut first = frames.next().unwrap().unwrap();
+    assert_eq!(16, first.bit_len());
+    assert_eq!(0xff, first.read::<u8>().unwrap());
+    let empty = frames.next().unwrap().unwrap();
+    assert_eq!(0, empty.bit_len());
+    // the final frame claims more data than is left in the stream
+    assert!(matches!(
+        frames.next(),
+        Some(Err(BitError::NotEnoughData { .. }))
+    ));
+    assert!(frames.next().is_none());
+    // the stream is left at the start of the short frame
+    assert_eq!(32, stream.pos());
+    assert_eq!(24, stream.read::<u8>().unwrap());
+}
+
+#[test]
+fn test_frames_sized_le() {
+    frames_sized(|| LittleEndian);
+}
+
+#[test]
+fn test_frames_sized_be() {
+    frames_sized(|| BigEndian);
+}
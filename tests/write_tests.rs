@@ -1,4 +1,13 @@
-use bitbuffer::{BigEndian, BitReadBuffer, BitReadStream, BitWriteStream, LittleEndian};
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::char_lit_as_u8,
+    clippy::disallowed_names,
+    clippy::unusual_byte_groupings
+)]
+use bitbuffer::{
+    BigEndian, BitError, BitReadBuffer, BitReadStream, BitWriteBuffer, BitWriteStream, Endianness,
+    Int, LittleEndian, UInt,
+};
 
 #[test]
 fn test_write_bool_le() {
@@ -54,8 +63,8 @@ fn test_write_bool_number_le() {
     let mut read = BitReadStream::from(BitReadBuffer::new(data, LittleEndian));
 
     assert_eq!(true, read.read_bool().unwrap());
-    assert_eq!(3253u16, read.read().unwrap());
-    assert_eq!(13253u64, read.read().unwrap());
+    assert_eq!(3253u16, read.read::<u16>().unwrap());
+    assert_eq!(13253u64, read.read::<u64>().unwrap());
 
     // 0 padded
     assert_eq!(false, read.read_bool().unwrap());
@@ -72,9 +81,9 @@ fn test_write_bool_number_be() {
     let data = stream.finish();
     let mut read = BitReadStream::from(BitReadBuffer::new(data, BigEndian));
 
-    assert_eq!(1u8, read.read_int(1).unwrap());
-    assert_eq!(3253u16, read.read().unwrap());
-    assert_eq!(13253u64, read.read().unwrap());
+    assert_eq!(1u8, read.read_int::<u8>(1).unwrap());
+    assert_eq!(3253u16, read.read::<u16>().unwrap());
+    assert_eq!(13253u64, read.read::<u64>().unwrap());
 
     // 0 padded
     assert_eq!(false, read.read_bool().unwrap());
@@ -91,7 +100,7 @@ fn test_write_float_le() {
     let mut read = BitReadStream::from(BitReadBuffer::new(data, LittleEndian));
 
     assert_eq!(true, read.read_bool().unwrap());
-    assert_eq!(3253.12f32, read.read().unwrap());
+    assert_eq!(3253.12f32, read.read::<f32>().unwrap());
 
     // 0 padded
     assert_eq!(false, read.read_bool().unwrap());
@@ -107,8 +116,8 @@ fn test_write_float_be() {
     let data = stream.finish();
     let mut read = BitReadStream::from(BitReadBuffer::new(data, BigEndian));
 
-    assert_eq!(1u8, read.read_int(1).unwrap());
-    assert_eq!(3253.12f32, read.read().unwrap());
+    assert_eq!(1u8, read.read_int::<u8>(1).unwrap());
+    assert_eq!(3253.12f32, read.read::<f32>().unwrap());
 
     // 0 padded
     assert_eq!(false, read.read_bool().unwrap());
@@ -134,3 +143,789 @@ fn test_write_string_le() {
     // 0 padded
     assert_eq!(false, read.read_bool().unwrap());
 }
+
+fn roundtrip_signed_and_bytes<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+
+    stream.write_int(-3i8, 3).unwrap();
+    stream.write_bytes(&[1, 2, 0xff]).unwrap();
+    stream.write_int(-300i16, 11).unwrap();
+    stream.write_int(5u8, 5).unwrap();
+    stream.write_bytes(&[4, 5]).unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(-3i8, read.read_int::<i8>(3).unwrap());
+    assert_eq!(vec![1, 2, 0xff], read.read_bytes(3).unwrap());
+    assert_eq!(-300i16, read.read_int::<i16>(11).unwrap());
+    assert_eq!(5u8, read.read_int::<u8>(5).unwrap());
+    assert_eq!(vec![4, 5], read.read_bytes(2).unwrap());
+}
+
+#[test]
+fn test_write_signed_and_bytes_le() {
+    roundtrip_signed_and_bytes(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_signed_and_bytes_be() {
+    roundtrip_signed_and_bytes(BigEndian, BigEndian);
+}
+
+fn roundtrip_const_size_ints<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+
+    stream.write(&UInt::<3>::new(5).unwrap()).unwrap();
+    stream.write(&Int::<5>::new(-7).unwrap()).unwrap();
+    stream.write(&UInt::<45>::MAX).unwrap();
+    stream
+        .write(&Int::<40>::new(-123_456_789).unwrap())
+        .unwrap();
+    stream
+        .write(&UInt::<64>::new(u64::MAX - 1).unwrap())
+        .unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(UInt::<3>::new(5).unwrap(), read.read().unwrap());
+    assert_eq!(Int::<5>::new(-7).unwrap(), read.read().unwrap());
+    assert_eq!(UInt::<45>::MAX, read.read().unwrap());
+    assert_eq!(Int::<40>::new(-123_456_789).unwrap(), read.read().unwrap());
+    assert_eq!(UInt::<64>::new(u64::MAX - 1).unwrap(), read.read().unwrap());
+    assert_eq!(3 + 5 + 45 + 40 + 64, read.pos());
+}
+
+#[test]
+fn test_write_const_size_ints_le() {
+    roundtrip_const_size_ints(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_const_size_ints_be() {
+    roundtrip_const_size_ints(BigEndian, BigEndian);
+}
+
+fn roundtrip_usize<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+
+    stream.write(&12345usize).unwrap();
+    stream.write(&-12345isize).unwrap();
+    stream.write_sized(&5usize, 3).unwrap();
+    stream.write_sized(&-5isize, 4).unwrap();
+    stream.write_sized(&-123_456_789isize, 40).unwrap();
+    stream.write_int(-3i8, 3).unwrap();
+    stream.write_bytes(&[1, 2, 3]).unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(12345usize, read.read::<usize>().unwrap());
+    assert_eq!(64, read.pos());
+    assert_eq!(-12345isize, read.read::<isize>().unwrap());
+    assert_eq!(5usize, read.read_sized::<usize>(3).unwrap());
+    assert_eq!(-5isize, read.read_sized::<isize>(4).unwrap());
+    assert_eq!(-123_456_789isize, read.read_sized::<isize>(40).unwrap());
+    assert_eq!(-3i8, read.read_int::<i8>(3).unwrap());
+    assert_eq!(vec![1, 2, 3], read.read_bytes(3).unwrap());
+}
+
+#[test]
+fn test_write_usize_le() {
+    roundtrip_usize(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_usize_be() {
+    roundtrip_usize(BigEndian, BigEndian);
+}
+
+fn roundtrip_partial_ints<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+
+    stream.write_bool(true).unwrap();
+    stream.write_int(0x0abc_def0_1234_5678u64, 60).unwrap();
+    stream.write_int(-2i64, 57).unwrap();
+    stream
+        .write_int(0x1_2345_6789_abcd_ef01_2345u128, 100)
+        .unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(0x0abc_def0_1234_5678u64, read.read_int::<u64>(60).unwrap());
+    assert_eq!(-2i64, read.read_int::<i64>(57).unwrap());
+    assert_eq!(
+        0x1_2345_6789_abcd_ef01_2345u128,
+        read.read_int(100).unwrap()
+    );
+}
+
+#[test]
+fn test_write_partial_ints_le() {
+    roundtrip_partial_ints(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_partial_ints_be() {
+    roundtrip_partial_ints(BigEndian, BigEndian);
+}
+
+#[test]
+fn test_write_char() {
+    let mut stream = BitWriteStream::new(LittleEndian);
+
+    stream.write(&'😀').unwrap();
+    stream.write_sized(&'z', 7).unwrap();
+    stream.write_sized(&'é', 21).unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, LittleEndian));
+
+    assert_eq!('😀', read.read().unwrap());
+    assert_eq!('z', read.read_sized(7).unwrap());
+    assert_eq!('é', read.read_sized(21).unwrap());
+}
+
+#[cfg(feature = "net")]
+fn roundtrip_net<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+
+    let socket = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 443);
+    let address = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write(&socket).unwrap();
+    stream.write(&address).unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(socket, read.read().unwrap());
+    assert_eq!(address, read.read::<Ipv6Addr>().unwrap());
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_write_net_le() {
+    roundtrip_net(LittleEndian, LittleEndian);
+}
+
+#[cfg(feature = "net")]
+#[test]
+fn test_write_net_be() {
+    roundtrip_net(BigEndian, BigEndian);
+}
+
+#[cfg(feature = "glam")]
+fn roundtrip_glam<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::bit_size_of;
+    use glam::{DVec2, IVec3, Quat, UVec2, Vec3};
+
+    let position = Vec3::new(1.5, -2.25, 1024.0);
+    let rotation = Quat::from_xyzw(0.0, 0.5, -0.5, 1.0);
+    let coord = IVec3::new(-3, 100, -100);
+    assert_eq!(Some(96), bit_size_of::<Vec3>());
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write(&position).unwrap();
+    stream.write(&rotation).unwrap();
+    stream.write(&DVec2::new(0.1, 0.2)).unwrap();
+    stream.write_sized(&coord, 9).unwrap();
+    stream.write_sized(&UVec2::new(5, 6), 3).unwrap();
+    assert_eq!(1 + 96 + 128 + 128 + 27 + 6, stream.bit_len());
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(position, read.read().unwrap());
+    assert_eq!(rotation, read.read().unwrap());
+    assert_eq!(DVec2::new(0.1, 0.2), read.read().unwrap());
+    assert_eq!(coord, read.read_sized(9).unwrap());
+    assert_eq!(UVec2::new(5, 6), read.read_sized(3).unwrap());
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_write_glam_le() {
+    roundtrip_glam(LittleEndian, LittleEndian);
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_write_glam_be() {
+    roundtrip_glam(BigEndian, BigEndian);
+}
+
+#[cfg(feature = "nalgebra")]
+fn roundtrip_nalgebra<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{bit_size_of, bit_size_of_sized};
+    use nalgebra::{SVector, Vector2, Vector3};
+
+    let position = Vector3::new(1.5f32, -2.25, 1024.0);
+    let coord = Vector2::new(-3i16, 100);
+    assert_eq!(Some(96), bit_size_of::<Vector3<f32>>());
+    assert_eq!(Some(50), bit_size_of_sized::<SVector<u8, 10>>(5));
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write(&position).unwrap();
+    stream.write_sized(&coord, 9).unwrap();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(position, read.read::<Vector3<f32>>().unwrap());
+    assert_eq!(coord, read.read_sized::<Vector2<i16>>(9).unwrap());
+    assert_eq!(1 + 96 + 18, read.pos());
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_write_nalgebra_le() {
+    roundtrip_nalgebra(LittleEndian, LittleEndian);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_write_nalgebra_be() {
+    roundtrip_nalgebra(BigEndian, BigEndian);
+}
+
+fn roundtrip_quantized<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{BitError, Quantized};
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write_quantized_float(90.0, 10, 0.0, 360.0).unwrap();
+    stream.write_quantized_float(-1.0, 11, -1.0, 1.0).unwrap();
+    stream.write_quantized_float(2.0, 11, -1.0, 1.0).unwrap();
+    stream
+        .write_quantized_float(f32::NAN, 4, -8.0, 8.0)
+        .unwrap();
+    stream.write_quantized_float(0.1, 32, 0.0, 1.0).unwrap();
+    stream
+        .write(&Quantized::<12, -4096, 4096>::new(1234.5))
+        .unwrap();
+    assert!(matches!(
+        stream.write_quantized_float(1.0, 33, 0.0, 1.0),
+        Err(BitError::TooManyBits {
+            requested: 33,
+            max: 32
+        })
+    ));
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    let angle = read.read_quantized_float(10, 0.0, 360.0).unwrap();
+    assert!((angle - 90.0).abs() <= 360.0 / 1023.0 / 2.0);
+    assert_eq!(-1.0, read.read_quantized_float(11, -1.0, 1.0).unwrap());
+    assert_eq!(1.0, read.read_quantized_float(11, -1.0, 1.0).unwrap());
+    assert_eq!(-8.0, read.read_quantized_float(4, -8.0, 8.0).unwrap());
+    assert!((read.read_quantized_float(32, 0.0, 1.0).unwrap() - 0.1).abs() < 1e-6);
+    let coord: Quantized<12, -4096, 4096> = read.read().unwrap();
+    assert!((coord.get() - 1234.5).abs() <= 8192.0 / 4095.0 / 2.0);
+    assert_eq!(1 + 10 + 11 + 11 + 4 + 32 + 12, read.pos());
+    assert!(matches!(
+        read.read_quantized_float(33, 0.0, 1.0),
+        Err(BitError::TooManyBits { .. })
+    ));
+}
+
+#[test]
+fn test_write_quantized_le() {
+    roundtrip_quantized(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_quantized_be() {
+    roundtrip_quantized(BigEndian, BigEndian);
+}
+
+fn roundtrip_bit_angle<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{BitAngle, BitError};
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write_bit_angle(90.0, 8).unwrap();
+    stream.write_bit_angle(-90.0, 16).unwrap();
+    stream.write_bit_angle(359.99, 8).unwrap();
+    stream.write_bit_angle(720.0 + 45.0, 3).unwrap();
+    stream.write_bit_angle(123.456, 32).unwrap();
+    stream
+        .write(&BitAngle::<12>::from_radians(-std::f32::consts::FRAC_PI_4))
+        .unwrap();
+    assert!(matches!(
+        stream.write_bit_angle(1.0, 33),
+        Err(BitError::TooManyBits {
+            requested: 33,
+            max: 32
+        })
+    ));
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(90.0, read.read_bit_angle(8).unwrap());
+    assert_eq!(270.0, read.read_bit_angle(16).unwrap());
+    assert_eq!(0.0, read.read_bit_angle(8).unwrap());
+    assert_eq!(45.0, read.read_bit_angle(3).unwrap());
+    assert!((read.read_bit_angle(32).unwrap() - 123.456).abs() < 1e-4);
+    let angle: BitAngle<12> = read.read().unwrap();
+    assert_eq!(315.0, angle.degrees());
+    assert_eq!(-45.0, angle.normalized());
+    assert_eq!(1 + 8 + 16 + 8 + 3 + 32 + 12, read.pos());
+    assert!(matches!(
+        read.read_bit_angle(33),
+        Err(BitError::TooManyBits { .. })
+    ));
+}
+
+#[test]
+fn test_write_bit_angle_le() {
+    roundtrip_bit_angle(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_bit_angle_be() {
+    roundtrip_bit_angle(BigEndian, BigEndian);
+}
+
+fn roundtrip_zigzag<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::ZigZag;
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_sized(&ZigZag(-3i32), 4).unwrap();
+    stream.write_sized(&ZigZag(2i8), 4).unwrap();
+    stream.write(&ZigZag(i16::MIN)).unwrap();
+    stream.write(&ZigZag(i64::MAX)).unwrap();
+    stream.write_sized(&ZigZag(-1_000_000i128), 22).unwrap();
+    assert!(matches!(
+        stream.write_sized(&ZigZag(1i8), 9),
+        Err(bitbuffer::BitError::TooManyBits {
+            requested: 9,
+            max: 8
+        })
+    ));
+
+    let data = stream.finish();
+    let first = if E::is_le() { 0b0100_0101 } else { 0b0101_0100 };
+    assert_eq!(vec![first, 0xff, 0xff], data[0..3]);
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(-3, read.read_zigzag::<i32>(4).unwrap());
+    assert_eq!(ZigZag(2i8), read.read_sized(4).unwrap());
+    assert_eq!(ZigZag(i16::MIN), read.read().unwrap());
+    assert_eq!(i64::MAX, read.read_zigzag::<i64>(64).unwrap());
+    assert_eq!(-1_000_000i128, read.read_zigzag(22).unwrap());
+    assert_eq!(4 + 4 + 16 + 64 + 22, read.pos());
+}
+
+#[test]
+fn test_write_zigzag_le() {
+    roundtrip_zigzag(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_zigzag_be() {
+    roundtrip_zigzag(BigEndian, BigEndian);
+}
+
+fn roundtrip_golomb<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{BitError, Golomb, Rice};
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_sized(&Rice(9u32), 2).unwrap();
+    stream.write_sized(&Rice(0u8), 0).unwrap();
+    stream.write_sized(&Rice(u64::MAX), 64).unwrap();
+    stream.write_sized(&Rice(200u16), 3).unwrap();
+    stream.write_sized(&Golomb(5u32), 3).unwrap();
+    stream.write_sized(&Golomb(0u32), 3).unwrap();
+    stream.write_sized(&Golomb(4u8), 1).unwrap();
+    stream.write_sized(&Golomb(1000u64), 10).unwrap();
+    stream.write_sized(&Golomb(23u32), 8).unwrap();
+    stream.write_sized(&Rice(300u32), 8).unwrap();
+    assert!(matches!(
+        stream.write_sized(&Rice(1u8), 9),
+        Err(BitError::TooManyBits {
+            requested: 9,
+            max: 8
+        })
+    ));
+    assert!(matches!(
+        stream.write_sized(&Golomb(1u8), 0),
+        Err(BitError::InvalidGolombParameter)
+    ));
+    let bit_len = stream.bit_len();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+    assert_eq!(9, read.read_rice::<u32>(2).unwrap());
+    assert_eq!(Rice(0u8), read.read_sized(0).unwrap());
+    assert_eq!(u64::MAX, read.read_rice::<u64>(64).unwrap());
+    assert_eq!(Rice(200u16), read.read_sized(3).unwrap());
+    assert_eq!(5, read.read_golomb::<u32>(3).unwrap());
+    assert_eq!(Golomb(0u32), read.read_sized(3).unwrap());
+    assert_eq!(4, read.read_golomb::<u8>(1).unwrap());
+    assert_eq!(1000, read.read_golomb::<u64>(10).unwrap());
+    assert_eq!(Golomb(23u32), read.read_sized(8).unwrap());
+
+    let pos = read.pos();
+    assert!(matches!(
+        read.read_rice::<u8>(8),
+        Err(BitError::IntegerOutOfRange {
+            value: 300,
+            bits: 8
+        })
+    ));
+    assert!(matches!(
+        read.read_golomb::<u8>(0),
+        Err(BitError::InvalidGolombParameter)
+    ));
+    assert_eq!(pos, read.pos());
+    assert_eq!(300, read.read_rice::<u16>(8).unwrap());
+    assert_eq!(bit_len, read.pos());
+
+    // only padding left, the unary quotient is never terminated
+    assert!(matches!(
+        read.read_rice::<u8>(0),
+        Err(BitError::NotEnoughData { .. })
+    ));
+}
+
+#[test]
+fn test_write_golomb_le() {
+    roundtrip_golomb(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_golomb_be() {
+    roundtrip_golomb(BigEndian, BigEndian);
+}
+
+fn roundtrip_elias<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{BitError, EliasDelta, EliasGamma, Unary};
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write(&Unary(3u8)).unwrap();
+    stream.write(&Unary(0u32)).unwrap();
+    stream.write(&Unary(70u64)).unwrap();
+    stream.write(&EliasGamma(1u8)).unwrap();
+    stream.write(&EliasGamma(5u16)).unwrap();
+    stream.write(&EliasGamma(u64::MAX)).unwrap();
+    stream.write(&EliasDelta(1u8)).unwrap();
+    stream.write(&EliasDelta(9u32)).unwrap();
+    stream.write(&EliasDelta(u128::MAX)).unwrap();
+    stream.write(&EliasGamma(300u16)).unwrap();
+    assert!(matches!(
+        stream.write(&EliasGamma(0u8)),
+        Err(BitError::IntegerOutOfRange { value: 0, bits: 8 })
+    ));
+    assert!(matches!(
+        stream.write(&EliasDelta(0u8)),
+        Err(BitError::IntegerOutOfRange { value: 0, bits: 8 })
+    ));
+    let bit_len = stream.bit_len();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+    assert_eq!(3, read.read_unary().unwrap());
+    assert_eq!(Unary(0u32), read.read().unwrap());
+    assert_eq!(Unary(70u64), read.read().unwrap());
+    assert_eq!(1, read.read_elias_gamma::<u8>().unwrap());
+    assert_eq!(EliasGamma(5u16), read.read().unwrap());
+    assert_eq!(u64::MAX, read.read_elias_gamma::<u64>().unwrap());
+    assert_eq!(EliasDelta(1u8), read.read().unwrap());
+    assert_eq!(9, read.read_elias_delta::<u32>().unwrap());
+    assert_eq!(u128::MAX, read.read_elias_delta::<u128>().unwrap());
+
+    let pos = read.pos();
+    assert!(matches!(
+        read.read_elias_gamma::<u8>(),
+        Err(BitError::IntegerOutOfRange {
+            value: 300,
+            bits: 8
+        })
+    ));
+    assert_eq!(pos, read.pos());
+    assert_eq!(EliasGamma(300u16), read.read().unwrap());
+    assert_eq!(bit_len, read.pos());
+}
+
+#[test]
+fn test_write_elias_le() {
+    roundtrip_elias(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_write_elias_be() {
+    roundtrip_elias(BigEndian, BigEndian);
+}
+
+fn roundtrip_long_unary<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::{BitError, Unary};
+
+    // runs longer than the 64 bit words the unary prefix is scanned in
+    let values = [0u64, 1, 62, 63, 64, 65, 130, 500];
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_int(0u8, 3).unwrap();
+    for value in values.iter() {
+        stream.write(&Unary(*value)).unwrap();
+    }
+    stream.write_int(0u128, 100).unwrap();
+
+    let mut read = BitReadStream::from(BitReadBuffer::new(stream.finish(), read_endianness));
+    read.skip_bits(3).unwrap();
+    for value in values.iter() {
+        assert_eq!(*value as usize, read.read_unary().unwrap());
+    }
+    let pos = read.pos();
+    assert!(matches!(
+        read.read_unary(),
+        Err(BitError::NotEnoughData { .. })
+    ));
+    assert_eq!(pos, read.pos());
+}
+
+#[test]
+fn test_long_unary_le() {
+    roundtrip_long_unary(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_long_unary_be() {
+    roundtrip_long_unary(BigEndian, BigEndian);
+}
+
+#[cfg(feature = "testing")]
+mod testing {
+    use bitbuffer::testing::proptest::prelude::*;
+    use bitbuffer::testing::{alignment, assert_roundtrip, assert_roundtrip_padded, padded};
+    use bitbuffer::{BitRead, BitReadStream, BitWrite, BitWriteStream, Endianness, Result};
+
+    #[derive(Debug, PartialEq, BitRead, BitWrite)]
+    struct Packet {
+        flag: bool,
+        #[size = 5]
+        kind: u8,
+        payload: i32,
+        name: String,
+    }
+
+    /// Writes one bit more than it reads
+    #[derive(Debug, PartialEq)]
+    struct Unbalanced(bool);
+
+    impl<E: Endianness> BitRead<E> for Unbalanced {
+        fn read(stream: &mut BitReadStream<E>) -> Result<Self> {
+            stream.read_bool().map(Unbalanced)
+        }
+    }
+
+    impl<E: Endianness> BitWrite<E> for Unbalanced {
+        fn write(&self, stream: &mut BitWriteStream<E>) -> Result<()> {
+            stream.write_bool(self.0)?;
+            stream.write_bool(false)
+        }
+    }
+
+    #[test]
+    fn test_assert_roundtrip() {
+        assert_roundtrip(Packet {
+            flag: true,
+            kind: 17,
+            payload: -12345,
+            name: "packet".into(),
+        });
+        assert_roundtrip_padded(1.5f64, 3);
+        assert_roundtrip_padded(u128::MAX - 5, 200);
+    }
+
+    #[test]
+    #[should_panic(expected = "number of bits read")]
+    fn test_assert_roundtrip_unbalanced() {
+        assert_roundtrip(Unbalanced(true));
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip_random((payload, padding) in padded(any::<i32>()), kind in 0u8..32, name in "[a-z]{0,8}") {
+            assert_roundtrip_padded(Packet { flag: payload > 0, kind, payload, name }, padding);
+        }
+
+        #[test]
+        fn test_roundtrip_aligned(value in any::<u64>(), padding in alignment()) {
+            assert_roundtrip_padded(value, padding);
+        }
+    }
+}
+
+#[cfg(feature = "source")]
+fn roundtrip_source<E: Endianness>(write_endianness: E, read_endianness: E) {
+    use bitbuffer::BitError;
+
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream.write_bit_coord(0.0).unwrap();
+    stream.write_bit_coord(-0.5).unwrap();
+    stream.write_bit_coord(1234.0).unwrap();
+    stream.write_bit_coord(-16384.5).unwrap();
+    stream.write_bit_vec3_coord([12.5, 0.01, -1.25]).unwrap();
+    stream.write_bit_normal(-1.0).unwrap();
+    stream.write_bit_normal(2.0).unwrap();
+    stream.write_bit_vec3_normal([0.6, 0.0, -0.8]).unwrap();
+    assert!(matches!(
+        stream.write_bit_coord(16385.0),
+        Err(BitError::IntegerOutOfRange { bits: 14, .. })
+    ));
+    let bit_len = stream.bit_len();
+
+    let data = stream.finish();
+    let mut read = BitReadStream::from(BitReadBuffer::new(data, read_endianness));
+
+    assert_eq!(true, read.read_bool().unwrap());
+    assert_eq!(0.0, read.read_bit_coord().unwrap());
+    assert_eq!(2, read.pos() - 1);
+    assert_eq!(-0.5, read.read_bit_coord().unwrap());
+    assert_eq!(1234.0, read.read_bit_coord().unwrap());
+    assert_eq!(-16384.5, read.read_bit_coord().unwrap());
+    assert_eq!([12.5, 0.0, -1.25], read.read_bit_vec3_coord().unwrap());
+    assert_eq!(-1.0, read.read_bit_normal().unwrap());
+    assert_eq!(1.0, read.read_bit_normal().unwrap());
+    let [x, y, z] = read.read_bit_vec3_normal().unwrap();
+    assert!((x - 0.6).abs() < 0.001);
+    assert_eq!(0.0, y);
+    assert!((z + 0.8).abs() < 0.001);
+    assert_eq!(bit_len, read.pos());
+}
+
+#[cfg(feature = "source")]
+#[test]
+fn test_write_source_le() {
+    roundtrip_source(LittleEndian, LittleEndian);
+}
+
+#[cfg(feature = "source")]
+#[test]
+fn test_write_source_be() {
+    roundtrip_source(BigEndian, BigEndian);
+}
+
+fn patch_buffer<E: Endianness>(write_endianness: E, read_endianness: E, patch_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_int(5u8, 3).unwrap();
+    stream.write_int(-1234i16, 13).unwrap();
+    stream.write_bool(true).unwrap();
+    stream.write_int(u128::MAX, 100).unwrap();
+    stream.write_bytes(b"tick").unwrap();
+    stream.write_int(3u8, 2).unwrap();
+
+    let mut buffer = BitWriteBuffer::new(stream.finish(), patch_endianness);
+    buffer.patch_int(3, 13, 2345i16).unwrap();
+    buffer.patch_bool(16, false).unwrap();
+    buffer.patch_int(17, 100, 1u128 << 99 | 12345).unwrap();
+    buffer.patch_bytes(117, b"tock").unwrap();
+    // only the lowest bits of the value are written
+    buffer.patch_int(0, 3, 0xffu8).unwrap();
+    assert_eq!(buffer.byte_len() * 8, buffer.bit_len());
+
+    let read = BitReadBuffer::new(buffer.bytes().to_vec(), read_endianness);
+    assert_eq!(7, read.read_int::<u8>(0, 3).unwrap());
+    assert_eq!(2345, read.read_int::<i16>(3, 13).unwrap());
+    assert!(!read.read_bool(16).unwrap());
+    assert_eq!(1u128 << 99 | 12345, read.read_int::<u128>(17, 100).unwrap());
+    assert_eq!(b"tock".to_vec(), read.read_bytes(117, 4).unwrap().to_vec());
+    assert_eq!(3, read.read_int::<u8>(149, 2).unwrap());
+
+    let bit_len = buffer.bit_len();
+    assert!(matches!(
+        buffer.patch_int(0, 9, 0u8),
+        Err(BitError::TooManyBits { .. })
+    ));
+    assert!(matches!(
+        buffer.patch_int(bit_len - 4, 5, 0u8),
+        Err(BitError::NotEnoughData {
+            requested: 5,
+            bits_left: 4
+        })
+    ));
+    assert!(matches!(
+        buffer.patch_bool(bit_len, true),
+        Err(BitError::IndexOutOfBounds { .. })
+    ));
+    assert!(buffer.patch_bytes(bit_len - 8, b"ab").is_err());
+    buffer.patch_int(bit_len, 0, 0u8).unwrap();
+}
+
+#[test]
+fn test_patch_buffer_le() {
+    patch_buffer(LittleEndian, LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_patch_buffer_be() {
+    patch_buffer(BigEndian, BigEndian, BigEndian);
+}
+
+fn length_prefixed<E: Endianness>(write_endianness: E, read_endianness: E) {
+    let mut stream = BitWriteStream::new(write_endianness);
+    stream.write_bool(true).unwrap();
+    stream
+        .write_length_prefixed(10, |stream| {
+            stream.write_int(0x1234u16, 13)?;
+            stream.write_length_prefixed(4, |stream| stream.write_int(5u8, 3))
+        })
+        .unwrap();
+    stream.write_length_prefixed(3, |_| Ok(())).unwrap();
+    let bit_len = stream.bit_len();
+
+    // failed bodies and lengths that don't fit are removed from the stream
+    assert!(matches!(
+        stream.write_length_prefixed(2, |stream| stream.write_int(0u8, 4)),
+        Err(BitError::IntegerOutOfRange { value: 4, bits: 2 })
+    ));
+    assert_eq!(bit_len, stream.bit_len());
+    assert!(matches!(
+        stream.write_length_prefixed(8, |stream| {
+            stream.write_int(0xffu8, 7)?;
+            stream.write_int(0u8, 9)
+        }),
+        Err(BitError::TooManyBits { .. })
+    ));
+    assert_eq!(bit_len, stream.bit_len());
+    assert!(stream.write_length_prefixed(65, |_| Ok(())).is_err());
+    assert_eq!(bit_len, stream.bit_len());
+    stream.write_int(0b101u8, 3).unwrap();
+
+    let buffer = BitReadBuffer::new(stream.finish(), read_endianness);
+    let mut stream = BitReadStream::new(buffer);
+    assert!(stream.read_bool().unwrap());
+    assert_eq!(20, stream.read_int::<u16>(10).unwrap());
+    let mut frame = stream.read_bits(20).unwrap();
+    assert_eq!(0x1234, frame.read_int::<u16>(13).unwrap());
+    assert_eq!(3, frame.read_int::<u8>(4).unwrap());
+    assert_eq!(5, frame.read_int::<u8>(3).unwrap());
+    assert_eq!(0, stream.read_int::<u8>(3).unwrap());
+    assert_eq!(0b101, stream.read_int::<u8>(3).unwrap());
+    assert_eq!(3, stream.bits_left());
+}
+
+#[test]
+fn test_length_prefixed_le() {
+    length_prefixed(LittleEndian, LittleEndian);
+}
+
+#[test]
+fn test_length_prefixed_be() {
+    length_prefixed(BigEndian, BigEndian);
+}